@@ -0,0 +1,198 @@
+//! the widgets and attributes which are recognized by the view macro.
+//!
+//! These needs to be kept in sync with the builder functions in
+//! `sauron_widget::widget`, the attribute functions in
+//! `sauron_widget::widget::attribute` and the event functions in
+//! `sauron_widget::widget::event`, which is checked in `tests/view_macro.rs`
+
+/// How the builder function of the widget is called
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum WidgetKind {
+    /// called with the attributes and the children
+    Container,
+    /// called with the attributes only
+    Leaf,
+    /// called with the text of the `value` attribute
+    Paragraph,
+    /// called with the custom widget in the `widget` attribute,
+    /// the attributes and the children
+    Custom,
+    /// called with the attributes and the closure in the `render_row`
+    /// attribute
+    ListView,
+    /// called with the text of the `label` attribute and the only child
+    Tab,
+}
+
+/// widgets which can have children
+pub(crate) const CONTAINERS: [&str; 14] = [
+    "column",
+    "row",
+    "vpane",
+    "hpane",
+    "overlay",
+    "groupbox",
+    "header_bar",
+    "menu_bar",
+    "menu",
+    "menu_item",
+    "tabs",
+    "tree_view",
+    "tree_item",
+    "dialog",
+];
+
+/// widgets which can not have children
+pub(crate) const LEAVES: [&str; 24] = [
+    "button",
+    "text_input",
+    "checkbox",
+    "radio",
+    "image",
+    "svg",
+    "textarea",
+    "text_label",
+    "search_input",
+    "link",
+    "table",
+    "dropdown",
+    "list_box",
+    "spin_button",
+    "slider",
+    "progress_bar",
+    "spinner",
+    "canvas",
+    "alert",
+    "confirm",
+    "file_chooser",
+    "check_menu_item",
+    "radio_menu_item",
+    "menu_separator",
+];
+
+/// attributes functions which are called with a value
+pub(crate) const ATTRIBUTES: [&str; 44] = [
+    "value",
+    "data",
+    "label",
+    "svg_image",
+    "editable",
+    "scrollable",
+    "width",
+    "height",
+    "resizable",
+    "monospace",
+    "selectable",
+    "preformatted",
+    "name",
+    "uri",
+    "checked",
+    "placeholder",
+    "key",
+    "id",
+    "for",
+    "columns",
+    "rows",
+    "sortable",
+    "sort_column",
+    "sort_descending",
+    "selected",
+    "item_count",
+    "row_height",
+    "scroll_offset",
+    "overscan",
+    "options",
+    "multiple",
+    "min",
+    "max",
+    "step",
+    "fraction",
+    "indeterminate",
+    "active",
+    "expanded",
+    "draw",
+    "file_mode",
+    "filters",
+    "directory",
+    "accelerator",
+    "autofocus",
+];
+
+/// event functions which are called with a closure
+pub(crate) const EVENTS: [&str; 22] = [
+    "on_click",
+    "on_mousedown",
+    "on_mouseup",
+    "on_mousemove",
+    "on_doubleclick",
+    "on_focus",
+    "on_blur",
+    "on_input",
+    "on_keypress",
+    "on_enter",
+    "on_select",
+    "on_sort",
+    "on_row_activate",
+    "on_scroll",
+    "on_visible_range",
+    "on_tab_change",
+    "on_change",
+    "on_expand",
+    "on_collapse",
+    "on_response",
+    "on_files",
+    "on_activate",
+];
+
+/// Where the attribute function is defined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AttributeKind {
+    /// a function in `widget::attribute`
+    Plain,
+    /// a function in `widget::event`
+    Event,
+}
+
+/// returns the kind of the widget with this name
+pub(crate) fn widget_kind(name: &str) -> Option<WidgetKind> {
+    if CONTAINERS.contains(&name) {
+        Some(WidgetKind::Container)
+    } else if LEAVES.contains(&name) {
+        Some(WidgetKind::Leaf)
+    } else if name == "paragraph" {
+        Some(WidgetKind::Paragraph)
+    } else if name == "custom" {
+        Some(WidgetKind::Custom)
+    } else if name == "list_view" {
+        Some(WidgetKind::ListView)
+    } else if name == "tab" {
+        Some(WidgetKind::Tab)
+    } else {
+        None
+    }
+}
+
+/// returns the kind of the attribute and the name of its function
+pub(crate) fn attribute_fn(name: &str) -> Option<(AttributeKind, String)> {
+    if name == "for" {
+        // `for` is a keyword, so the function is named `for_`
+        Some((AttributeKind::Plain, "for_".to_string()))
+    } else if ATTRIBUTES.contains(&name) {
+        Some((AttributeKind::Plain, name.to_string()))
+    } else if EVENTS.contains(&name) {
+        Some((AttributeKind::Event, name.to_string()))
+    } else {
+        None
+    }
+}
+
+/// the list of widget names used in the error message
+pub(crate) fn widget_names() -> String {
+    CONTAINERS
+        .iter()
+        .chain(LEAVES.iter())
+        .chain(["paragraph", "custom", "list_view", "tab"].iter())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,39 @@
+//! provides the `view!` macro for declaring sauron-native widget trees
+//! using a jsx-like syntax.
+//!
+//! ```rust,ignore
+//! view! {
+//!     <column>
+//!         <button label="Hi" on_click=|_| Msg::Go/>
+//!         <text_input value={self.value.to_string()}
+//!             on_input={|ie| Msg::Input(ie.value.to_string())}/>
+//!         { ..self.entries.iter().map(|entry| entry.view()) }
+//!     </column>
+//! }
+//! ```
+//!
+//! Each widget expands to the builder function of the same name in
+//! `sauron_native::widget`, and each attribute to the attribute or event
+//! function of the same name. Unknown widgets and attributes are reported
+//! at compile time.
+//!
+//! Attribute values are literals or expressions, expressions which contain a
+//! `>` or `/>` token, such as comparisons, needs to be wrapped in braces.
+//! A braced child `{expr}` is a node, while `{..expr}` adds all the nodes
+//! of an iterator.
+extern crate proc_macro;
+
+use syn::parse_macro_input;
+
+mod known;
+mod node;
+
+/// declare a widget tree with a jsx-like syntax
+#[proc_macro]
+pub fn view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let root = parse_macro_input!(input as node::Root);
+    match root.0.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
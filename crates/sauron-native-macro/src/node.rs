@@ -0,0 +1,373 @@
+//! parsing of the jsx-like syntax and its expansion into the builder functions
+use crate::known::{self, AttributeKind, WidgetKind};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token, Expr, Ident, Token,
+};
+
+/// the root widget of the view macro
+pub(crate) struct Root(pub(crate) Element);
+
+/// a widget element, `<name attr=value>children</name>` or `<name attr=value/>`
+pub(crate) struct Element {
+    name: Ident,
+    attrs: Vec<Attr>,
+    children: Vec<Child>,
+}
+
+/// an attribute of the element, `name=value`
+struct Attr {
+    name: Ident,
+    value: Expr,
+}
+
+/// the child of an element
+enum Child {
+    /// a child widget element
+    Element(Element),
+    /// `{expr}`, an expression which evaluates to a node
+    Expr(Expr),
+    /// `{..expr}`, an expression which evaluates to an iterator of nodes
+    Spread(Expr),
+}
+
+impl Parse for Root {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let root = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("view! expects a single root widget"));
+        }
+        Ok(Root(root))
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name = Ident::parse_any(input)?;
+        let mut attrs = vec![];
+        loop {
+            if input.peek(Token![/]) && input.peek2(Token![>]) {
+                input.parse::<Token![/]>()?;
+                input.parse::<Token![>]>()?;
+                return Ok(Element {
+                    name,
+                    attrs,
+                    children: vec![],
+                });
+            }
+            if input.peek(Token![>]) {
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unclosed tag `<{}>`", name),
+                ));
+            }
+            attrs.push(input.parse()?);
+        }
+        let mut children = vec![];
+        loop {
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                input.parse::<Token![<]>()?;
+                input.parse::<Token![/]>()?;
+                let closing = Ident::parse_any(input)?;
+                if closing != name {
+                    return Err(syn::Error::new(
+                        closing.span(),
+                        format!("expecting a closing tag `</{}>`", name),
+                    ));
+                }
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("missing the closing tag `</{}>`", name),
+                ));
+            }
+            children.push(input.parse()?);
+        }
+        Ok(Element {
+            name,
+            attrs,
+            children,
+        })
+    }
+}
+
+/// the value of an attribute ends at the end of the tag or at the next
+/// attribute
+fn is_value_end(input: ParseStream) -> bool {
+    input.is_empty()
+        || input.peek(Token![>])
+        || (input.peek(Token![/]) && input.peek2(Token![>]))
+        || (input.peek(Ident::peek_any)
+            && input.peek2(Token![=])
+            && !input.peek2(Token![==]))
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = Ident::parse_any(input)?;
+        input.parse::<Token![=]>()?;
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            let value = content.parse()?;
+            return Ok(Attr { name, value });
+        }
+        let mut tokens = TokenStream::new();
+        while !is_value_end(input) {
+            tokens.extend(Some(input.parse::<TokenTree>()?));
+        }
+        if tokens.is_empty() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("missing the value of the attribute `{}`", name),
+            ));
+        }
+        let value = syn::parse2(tokens)?;
+        Ok(Attr { name, value })
+    }
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            return Ok(Child::Element(input.parse()?));
+        }
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            if content.peek(Token![..]) {
+                content.parse::<Token![..]>()?;
+                return Ok(Child::Spread(content.parse()?));
+            }
+            return Ok(Child::Expr(content.parse()?));
+        }
+        if input.peek(syn::LitStr) {
+            return Err(input.error(
+                "text is not a widget, use `<text_label value=\"..\"/>` \
+                 or `<paragraph value=\"..\"/>` instead",
+            ));
+        }
+        Err(input.error("expecting a widget `<..>` or an expression `{..}`"))
+    }
+}
+
+impl Element {
+    /// expand the element into the call to its builder function
+    pub(crate) fn expand(&self) -> syn::Result<TokenStream> {
+        let name = self.name.to_string();
+        let kind = known::widget_kind(&name).ok_or_else(|| {
+            syn::Error::new(
+                self.name.span(),
+                format!(
+                    "unknown widget `{}`, expecting one of: {}",
+                    name,
+                    known::widget_names()
+                ),
+            )
+        })?;
+        if !self.children.is_empty()
+            && (kind == WidgetKind::Leaf
+                || kind == WidgetKind::Paragraph
+                || kind == WidgetKind::ListView)
+        {
+            return Err(syn::Error::new(
+                self.name.span(),
+                format!("`{}` can not have children", name),
+            ));
+        }
+        let builder = format_ident!("{}", name, span = self.name.span());
+
+        match kind {
+            WidgetKind::Paragraph => {
+                let value = self.paragraph_value()?;
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(&#value)
+                })
+            }
+            WidgetKind::Leaf => {
+                let attrs = self.expand_attrs(&[])?;
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(::std::vec![#(#attrs),*])
+                })
+            }
+            WidgetKind::Container => {
+                let attrs = self.expand_attrs(&[])?;
+                let children = self.expand_children()?;
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(
+                        ::std::vec![#(#attrs),*],
+                        #children,
+                    )
+                })
+            }
+            WidgetKind::Custom => {
+                let custom_widget = self
+                    .attrs
+                    .iter()
+                    .find(|att| att.name == "widget")
+                    .map(|att| &att.value)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            self.name.span(),
+                            "`custom` needs a `widget` attribute",
+                        )
+                    })?;
+                let attrs = self.expand_attrs(&["widget"])?;
+                let children = self.expand_children()?;
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(
+                        #custom_widget,
+                        ::std::vec![#(#attrs),*],
+                        #children,
+                    )
+                })
+            }
+            WidgetKind::Tab => {
+                let label = self.tab_label()?;
+                let child = match self.children.as_slice() {
+                    [Child::Element(element)] => element.expand()?,
+                    [Child::Expr(expr)] => quote! { #expr },
+                    _ => {
+                        return Err(syn::Error::new(
+                            self.name.span(),
+                            "`tab` needs exactly one child widget",
+                        ))
+                    }
+                };
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(&#label, #child)
+                })
+            }
+            WidgetKind::ListView => {
+                let render_row = self
+                    .attrs
+                    .iter()
+                    .find(|att| att.name == "render_row")
+                    .map(|att| &att.value)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            self.name.span(),
+                            "`list_view` needs a `render_row` attribute",
+                        )
+                    })?;
+                let attrs = self.expand_attrs(&["render_row"])?;
+                Ok(quote! {
+                    ::sauron_native::widget::#builder(
+                        ::std::vec![#(#attrs),*],
+                        #render_row,
+                    )
+                })
+            }
+        }
+    }
+
+    /// the paragraph only has the value attribute, which is its text
+    fn paragraph_value(&self) -> syn::Result<&Expr> {
+        if let Some(att) = self.attrs.iter().find(|att| att.name != "value") {
+            return Err(syn::Error::new(
+                att.name.span(),
+                "`paragraph` only accepts the `value` attribute",
+            ));
+        }
+        self.attrs
+            .iter()
+            .find(|att| att.name == "value")
+            .map(|att| &att.value)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    self.name.span(),
+                    "`paragraph` needs a `value` attribute",
+                )
+            })
+    }
+
+    /// the tab only has the label attribute, which is its text
+    fn tab_label(&self) -> syn::Result<&Expr> {
+        if let Some(att) = self.attrs.iter().find(|att| att.name != "label") {
+            return Err(syn::Error::new(
+                att.name.span(),
+                "`tab` only accepts the `label` attribute",
+            ));
+        }
+        self.attrs
+            .iter()
+            .find(|att| att.name == "label")
+            .map(|att| &att.value)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    self.name.span(),
+                    "`tab` needs a `label` attribute",
+                )
+            })
+    }
+
+    /// expand the attributes into the calls to the attribute functions,
+    /// skipping the attributes which are used as the builder arguments
+    fn expand_attrs(&self, skip: &[&str]) -> syn::Result<Vec<TokenStream>> {
+        self.attrs
+            .iter()
+            .filter(|att| !skip.iter().any(|skip| att.name == skip))
+            .map(|att| {
+                let att_name = att.name.to_string();
+                let (att_kind, fname) = known::attribute_fn(&att_name)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            att.name.span(),
+                            format!(
+                                "unknown attribute `{}` in `<{}>`",
+                                att_name, self.name
+                            ),
+                        )
+                    })?;
+                let fname = format_ident!("{}", fname, span = att.name.span());
+                let value = &att.value;
+                Ok(match att_kind {
+                    AttributeKind::Plain => quote_spanned! {value.span()=>
+                        ::sauron_native::widget::attribute::#fname(#value)
+                    },
+                    AttributeKind::Event => quote_spanned! {value.span()=>
+                        ::sauron_native::widget::event::#fname(#value)
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// expand the children into a block which builds the Vec of nodes
+    fn expand_children(&self) -> syn::Result<TokenStream> {
+        let children = Ident::new("children", Span::mixed_site());
+        let statements = self
+            .children
+            .iter()
+            .map(|child| match child {
+                Child::Element(element) => {
+                    let node = element.expand()?;
+                    Ok(quote! { #children.push(#node); })
+                }
+                Child::Expr(expr) => Ok(quote! { #children.push(#expr); }),
+                Child::Spread(expr) => Ok(quote! { #children.extend(#expr); }),
+            })
+            .collect::<syn::Result<Vec<TokenStream>>>()?;
+        Ok(quote! {
+            {
+                #[allow(unused_mut)]
+                let mut #children = ::std::vec::Vec::new();
+                #(#statements)*
+                #children
+            }
+        })
+    }
+}
@@ -1,7 +1,7 @@
 pub use widget::{
     attribute,
     attribute::{AttribKey, Value},
-    Widget,
+    CustomWidget, Widget,
 };
 pub mod widget;
 
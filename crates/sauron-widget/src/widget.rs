@@ -1,22 +1,23 @@
 //! widget module provides a unified abstract representation of the widget
 //! which has a mapping to the actual widget in the supported backend
 //!
-use crate::widget::attribute::style;
-use crate::widget::attribute::util::find_value;
-use crate::{AttribKey, Attribute, Node, Value};
 pub use builder::*;
-use expanse::geometry::Size;
-use expanse::style::Dimension;
-use expanse::style::FlexDirection;
-use expanse::style::PositionType;
-use expanse::style::Style;
-use mt_dom::{attr, element};
 use std::fmt::Debug;
+use std::rc::Rc;
+
+pub use custom_widget::CustomWidget;
+#[cfg(feature = "serde")]
+pub use custom_widget::RemoteCustomWidget;
 
 pub mod attribute;
 mod builder;
+mod custom_widget;
+pub mod draw;
 pub mod event;
 pub mod layout;
+#[cfg(feature = "serde")]
+pub mod remote;
+pub mod snapshot;
 
 /// TODO: Each widget variant will need to have more details
 ///  such as attributes, that will be converted to their
@@ -25,7 +26,8 @@ pub mod layout;
 /// Widget definitions
 /// This will have a counterparts for each of the supported
 /// different platforms
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Widget {
     /// vertical flexbox
     Vbox,
@@ -65,12 +67,66 @@ pub enum Widget {
     MenuBar,
     /// menu
     Menu,
-    /// menu item
+    /// menu item, the sub menu is added as its child
     MenuItem,
+    /// a menu item with a check mark which is toggled when it is activated
+    CheckMenuItem,
+    /// a menu item which is checked when it is activated, only one of the
+    /// radio menu items in a group is checked by the app
+    RadioMenuItem,
+    /// a line between the groups of menu items
+    MenuSeparator,
     /// Search entry
     SearchInput,
     /// Link button
     Link,
+    /// a table of rows with column headers
+    Table,
+    /// a scrollable list which only contains the nodes of the visible rows
+    ListView,
+    /// a notebook of tabs, only the content of the selected tab is shown
+    Tabs,
+    /// a tab in tabs, with a label and the content as its child
+    Tab,
+    /// a combo box where one of the options is chosen
+    Dropdown,
+    /// a list of options where one or more of them can be selected
+    ListBox,
+    /// a text entry of a number with buttons to increase and decrease it
+    SpinButton,
+    /// a horizontal bar with a knob which is dragged to change the value
+    Slider,
+    /// a bar which is filled up to the completed part of a task
+    ProgressBar,
+    /// an animation which shows that a task is ongoing
+    Spinner,
+    /// a surface which is drawn with the draw commands,
+    /// the x and y of its mouse events are local to the canvas
+    Canvas,
+    /// a tree of items, the children are created with `tree_item`
+    TreeView,
+    /// an item in a tree view, the children are the nested items
+    TreeItem,
+    /// a modal window which is shown while it is in the view,
+    /// the children are the content of the dialog
+    Dialog,
+    /// a dialog to choose files or a folder, which is shown while it is
+    /// in the view
+    FileChooser,
+    /// A widget defined outside of this crate
+    #[cfg_attr(feature = "serde", serde(with = "custom_widget::serde_custom"))]
+    Custom(Rc<dyn CustomWidget>),
+}
+
+impl PartialEq for Widget {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Widget::Custom(custom), Widget::Custom(other_custom)) => {
+                custom.is_same(other_custom.as_ref())
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
 }
 
 impl Widget {
@@ -86,7 +142,13 @@ impl Widget {
             | Widget::HeaderBar
             | Widget::MenuBar
             | Widget::Menu
-            | Widget::MenuItem => true,
+            | Widget::MenuItem
+            | Widget::ListView
+            | Widget::Tabs
+            | Widget::Tab
+            | Widget::TreeView
+            | Widget::TreeItem
+            | Widget::Dialog => true,
 
             Widget::SearchInput
             | Widget::Button
@@ -98,7 +160,45 @@ impl Widget {
             | Widget::Image
             | Widget::Svg
             | Widget::Link
+            | Widget::Table
+            | Widget::Dropdown
+            | Widget::ListBox
+            | Widget::SpinButton
+            | Widget::Slider
+            | Widget::ProgressBar
+            | Widget::Spinner
+            | Widget::Canvas
+            | Widget::FileChooser
+            | Widget::CheckMenuItem
+            | Widget::RadioMenuItem
+            | Widget::MenuSeparator
             | Widget::TextArea => false,
+
+            Widget::Custom(custom) => custom.is_container(),
+        }
+    }
+
+    /// whether or not the widget takes the keyboard focus, the focusable
+    /// widgets are traversed with Tab and BackTab in the order of the view
+    pub fn is_focusable(&self) -> bool {
+        match self {
+            Widget::Button
+            | Widget::Link
+            | Widget::TextInput
+            | Widget::SearchInput
+            | Widget::TextArea
+            | Widget::Checkbox
+            | Widget::Radio
+            | Widget::Table
+            | Widget::Dropdown
+            | Widget::ListBox
+            | Widget::SpinButton
+            | Widget::Slider
+            | Widget::ListView
+            | Widget::TreeView
+            | Widget::FileChooser => true,
+            Widget::Custom(custom) => custom.is_focusable(),
+            _ => false,
         }
     }
 }
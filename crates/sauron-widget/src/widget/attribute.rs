@@ -1,5 +1,6 @@
 //! Provides functions for attributes of sauron native widgets
 //!
+use super::draw::DrawCommand;
 pub use super::event::Event;
 use crate::Attribute;
 use expanse::style::Style;
@@ -8,6 +9,8 @@ use std::fmt;
 pub use util::{find_callback, find_value, get_style};
 pub use value::Value;
 
+#[cfg(feature = "serde")]
+mod serde_def;
 pub mod util;
 mod value;
 
@@ -34,7 +37,8 @@ macro_rules! declare_attr {
 
 /// These are attribute keys used in sauron-native, which will be translated to their
 /// corresponding backends
-#[derive(Clone, PartialEq, PartialOrd, Debug, Eq, Ord)]
+#[derive(Clone, PartialEq, PartialOrd, Debug, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttribKey {
     /// String, used in text_input
     Value,
@@ -80,6 +84,58 @@ pub enum AttribKey {
     Uri,
     /// Placeholder is used in text input, search input, and text_area
     Placeholder,
+    /// the column headers of a table
+    Columns,
+    /// the rows of a table, each row is a list of the cell values
+    Rows,
+    /// whether the columns of a table can be sorted by clicking the header
+    Sortable,
+    /// the index of the column which the table is sorted by
+    SortColumn,
+    /// whether the sorted column is in descending order
+    SortDescending,
+    /// the index of the selected item, used in table and tabs,
+    /// and whether the item is selected in tree view
+    Selected,
+    /// the total number of items in a list view
+    ItemCount,
+    /// the height of each row in a list view
+    RowHeight,
+    /// the vertical scroll offset of a list view
+    ScrollOffset,
+    /// the number of rows which are built above and below the visible rows
+    /// of a list view
+    Overscan,
+    /// the choices of a dropdown and list box
+    Options,
+    /// whether more than one option can be selected in a list box
+    Multiple,
+    /// the lowest value of a spin button and slider
+    Min,
+    /// the highest value of a spin button and slider
+    Max,
+    /// the amount the value of a spin button and slider is changed by
+    Step,
+    /// the completed part of a progress bar, from 0.0 to 1.0
+    Fraction,
+    /// whether the progress bar only shows activity instead of the fraction
+    Indeterminate,
+    /// whether the spinner is spinning
+    Active,
+    /// whether the children of a tree item are shown
+    Expanded,
+    /// the draw commands of a canvas
+    Draw,
+    /// whether the file chooser opens, saves or selects a folder
+    FileMode,
+    /// the patterns of the file names which are shown in the file chooser
+    Filters,
+    /// the directory which is shown in the file chooser
+    Directory,
+    /// the key combination which activates a menu item, such as `Ctrl+S`
+    Accelerator,
+    /// whether the widget is focused when it is shown
+    Autofocus,
 
     /// Events
     ClickEvent,
@@ -95,10 +151,37 @@ pub enum AttribKey {
     KeyEvent,
     /// doubleclick event
     DoubleClickEvent,
-    /// on blur event
+    /// the widget gained the keyboard focus
+    FocusEvent,
+    /// the widget lost the keyboard focus
     BlurEvent,
     /// activate event, used in on_enter
     Activate,
+    /// an item is selected, used in table and tree view
+    SelectEvent,
+    /// a sortable column header is clicked, used in table
+    SortEvent,
+    /// a row is activated by double click or enter, used in table and
+    /// tree view
+    RowActivateEvent,
+    /// the content is scrolled, used in list view
+    ScrollEvent,
+    /// the range of the visible rows changed, used in list view
+    VisibleRangeEvent,
+    /// another tab is selected, used in tabs
+    TabChangeEvent,
+    /// the selected options changed, used in dropdown and list box
+    ChangeEvent,
+    /// a tree item is expanded, used in tree view
+    ExpandEvent,
+    /// a tree item is collapsed, used in tree view
+    CollapseEvent,
+    /// a button of the dialog is clicked or the dialog is dismissed
+    ResponseEvent,
+    /// the files are chosen in the file chooser
+    FilesEvent,
+    /// a menu item is clicked or its accelerator is pressed
+    ActivateEvent,
     /// For
     For,
 }
@@ -143,6 +226,131 @@ declare_attr! {
     id => Id;
     /// for
     for_ => For;
+    /// sortable columns, used in table
+    sortable => Sortable;
+    /// the index of the sorted column, used in table
+    sort_column => SortColumn;
+    /// descending sort order, used in table
+    sort_descending => SortDescending;
+    /// the index of the selected item, used in table and tabs
+    selected => Selected;
+    /// the total number of items, used in list view
+    item_count => ItemCount;
+    /// the height of each row, used in list view
+    row_height => RowHeight;
+    /// the vertical scroll offset, used in list view
+    scroll_offset => ScrollOffset;
+    /// the number of extra rows built outside the visible rows,
+    /// used in list view
+    overscan => Overscan;
+    /// allow selecting more than one option, used in list box
+    multiple => Multiple;
+    /// the lowest value, used in spin button and slider
+    min => Min;
+    /// the highest value, used in spin button and slider
+    max => Max;
+    /// the increment of the value, used in spin button and slider
+    step => Step;
+    /// the completed part from 0.0 to 1.0, used in progress bar
+    fraction => Fraction;
+    /// show the activity instead of the fraction, used in progress bar
+    indeterminate => Indeterminate;
+    /// whether it is spinning, used in spinner
+    active => Active;
+    /// whether the children are shown, used in tree item
+    expanded => Expanded;
+    /// the directory which is shown first, used in file chooser
+    directory => Directory;
+    /// the key combination such as `Ctrl+S`, used in menu items
+    accelerator => Accelerator;
+    /// focus the widget when it is shown, used in the focusable widgets
+    autofocus => Autofocus;
+}
+
+/// what the file chooser is used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileMode {
+    /// choose existing files to be opened
+    Open,
+    /// choose the path where a file is saved
+    Save,
+    /// choose an existing folder
+    SelectFolder,
+}
+
+impl FileMode {
+    /// the name of the mode, which is the value of the attribute
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileMode::Open => "open",
+            FileMode::Save => "save",
+            FileMode::SelectFolder => "select_folder",
+        }
+    }
+}
+
+/// file mode attribute, used in file chooser
+pub fn file_mode<MSG>(mode: FileMode) -> Attribute<MSG> {
+    attr(AttribKey::FileMode, Value::from(mode.as_str()))
+}
+
+/// filters attribute, the patterns of the file names such as `*.png`,
+/// used in file chooser
+pub fn filters<V, MSG>(patterns: impl IntoIterator<Item = V>) -> Attribute<MSG>
+where
+    V: Into<Value>,
+{
+    attr(
+        AttribKey::Filters,
+        Value::List(patterns.into_iter().map(Into::into).collect()),
+    )
+}
+
+/// column headers attribute, used in table
+pub fn columns<V, MSG>(columns: impl IntoIterator<Item = V>) -> Attribute<MSG>
+where
+    V: Into<Value>,
+{
+    attr(
+        AttribKey::Columns,
+        Value::List(columns.into_iter().map(Into::into).collect()),
+    )
+}
+
+/// draw commands attribute, used in canvas
+pub fn draw<MSG>(
+    commands: impl IntoIterator<Item = DrawCommand>,
+) -> Attribute<MSG> {
+    attr(AttribKey::Draw, Value::Draw(commands.into_iter().collect()))
+}
+
+/// options attribute, used in dropdown and list box
+pub fn options<V, MSG>(options: impl IntoIterator<Item = V>) -> Attribute<MSG>
+where
+    V: Into<Value>,
+{
+    attr(
+        AttribKey::Options,
+        Value::List(options.into_iter().map(Into::into).collect()),
+    )
+}
+
+/// rows attribute, used in table, each row is the list of its cell values
+pub fn rows<R, V, MSG>(rows: impl IntoIterator<Item = R>) -> Attribute<MSG>
+where
+    R: IntoIterator<Item = V>,
+    V: Into<Value>,
+{
+    attr(
+        AttribKey::Rows,
+        Value::List(
+            rows.into_iter()
+                .map(|row| {
+                    Value::List(row.into_iter().map(Into::into).collect())
+                })
+                .collect(),
+        ),
+    )
 }
 
 impl fmt::Display for AttribKey {
@@ -0,0 +1,371 @@
+//! serde definitions of the values wrapped in `Value`, since the expanse
+//! types doesn't implement serde.
+use super::Value;
+use crate::widget::draw::DrawCommand;
+use expanse::geometry::{Rect, Size};
+use expanse::number::Number;
+use expanse::result::Layout;
+use expanse::style::{
+    AlignContent, AlignItems, AlignSelf, Dimension, Direction, Display,
+    FlexDirection, FlexWrap, JustifyContent, Overflow, PositionType, Style,
+};
+use expanse::Stretch;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// the serialized form of `Value`,
+/// the static str is deserialized into an owned String
+#[derive(Serialize, Deserialize)]
+enum ValueDef {
+    String(String),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    #[serde(with = "StyleDef")]
+    Style(Style),
+    #[serde(with = "layout_def")]
+    Layout(Layout),
+    #[serde(with = "PositionTypeDef")]
+    PositionType(PositionType),
+    F64(f64),
+    List(Vec<Value>),
+    Draw(Vec<DrawCommand>),
+}
+
+impl From<Value> for ValueDef {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::String(v) => ValueDef::String(v),
+            Value::Str(v) => ValueDef::String(v.to_string()),
+            Value::Bool(v) => ValueDef::Bool(v),
+            Value::Bytes(v) => ValueDef::Bytes(v),
+            Value::Style(v) => ValueDef::Style(v),
+            Value::Layout(v) => ValueDef::Layout(v),
+            Value::PositionType(v) => ValueDef::PositionType(v),
+            Value::F64(v) => ValueDef::F64(v),
+            Value::List(v) => ValueDef::List(v),
+            Value::Draw(v) => ValueDef::Draw(v),
+        }
+    }
+}
+
+impl From<ValueDef> for Value {
+    fn from(value: ValueDef) -> Self {
+        match value {
+            ValueDef::String(v) => Value::String(v),
+            ValueDef::Bool(v) => Value::Bool(v),
+            ValueDef::Bytes(v) => Value::Bytes(v),
+            ValueDef::Style(v) => Value::Style(v),
+            ValueDef::Layout(v) => Value::Layout(v),
+            ValueDef::PositionType(v) => Value::PositionType(v),
+            ValueDef::F64(v) => Value::F64(v),
+            ValueDef::List(v) => Value::List(v),
+            ValueDef::Draw(v) => Value::Draw(v),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ValueDef::from(self.clone()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        ValueDef::deserialize(deserializer).map(Value::from)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Style")]
+struct StyleDef {
+    #[serde(with = "DisplayDef")]
+    display: Display,
+    #[serde(with = "PositionTypeDef")]
+    position_type: PositionType,
+    #[serde(with = "DirectionDef")]
+    direction: Direction,
+    #[serde(with = "FlexDirectionDef")]
+    flex_direction: FlexDirection,
+    #[serde(with = "FlexWrapDef")]
+    flex_wrap: FlexWrap,
+    #[serde(with = "OverflowDef")]
+    overflow: Overflow,
+    #[serde(with = "AlignItemsDef")]
+    align_items: AlignItems,
+    #[serde(with = "AlignSelfDef")]
+    align_self: AlignSelf,
+    #[serde(with = "AlignContentDef")]
+    align_content: AlignContent,
+    #[serde(with = "JustifyContentDef")]
+    justify_content: JustifyContent,
+    #[serde(with = "rect_def")]
+    position: Rect<Dimension>,
+    #[serde(with = "rect_def")]
+    margin: Rect<Dimension>,
+    #[serde(with = "rect_def")]
+    padding: Rect<Dimension>,
+    #[serde(with = "rect_def")]
+    border: Rect<Dimension>,
+    flex_grow: f32,
+    flex_shrink: f32,
+    #[serde(with = "DimensionDef")]
+    flex_basis: Dimension,
+    #[serde(with = "size_def")]
+    size: Size<Dimension>,
+    #[serde(with = "size_def")]
+    min_size: Size<Dimension>,
+    #[serde(with = "size_def")]
+    max_size: Size<Dimension>,
+    #[serde(with = "NumberDef")]
+    aspect_ratio: Number,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Display")]
+enum DisplayDef {
+    Flex,
+    None,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PositionType")]
+enum PositionTypeDef {
+    Relative,
+    Absolute,
+}
+
+/// the variants are named the same as in expanse
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Direction")]
+enum DirectionDef {
+    Inherit,
+    LTR,
+    RTL,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "FlexDirection")]
+enum FlexDirectionDef {
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "FlexWrap")]
+enum FlexWrapDef {
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Overflow")]
+enum OverflowDef {
+    Visible,
+    Hidden,
+    Scroll,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "AlignItems")]
+enum AlignItemsDef {
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+    Stretch,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "AlignSelf")]
+enum AlignSelfDef {
+    Auto,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+    Stretch,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "AlignContent")]
+enum AlignContentDef {
+    FlexStart,
+    FlexEnd,
+    Center,
+    Stretch,
+    SpaceBetween,
+    SpaceAround,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "JustifyContent")]
+enum JustifyContentDef {
+    FlexStart,
+    FlexEnd,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Dimension")]
+enum DimensionDef {
+    Undefined,
+    Auto,
+    Points(f32),
+    Percent(f32),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Number")]
+enum NumberDef {
+    Defined(f32),
+    Undefined,
+}
+
+/// serde of the generic `Rect` with dimension values
+mod rect_def {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct RectDef {
+        #[serde(with = "DimensionDef")]
+        start: Dimension,
+        #[serde(with = "DimensionDef")]
+        end: Dimension,
+        #[serde(with = "DimensionDef")]
+        top: Dimension,
+        #[serde(with = "DimensionDef")]
+        bottom: Dimension,
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        rect: &Rect<Dimension>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RectDef {
+            start: rect.start,
+            end: rect.end,
+            top: rect.top,
+            bottom: rect.bottom,
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Rect<Dimension>, D::Error> {
+        let rect = RectDef::deserialize(deserializer)?;
+        Ok(Rect {
+            start: rect.start,
+            end: rect.end,
+            top: rect.top,
+            bottom: rect.bottom,
+        })
+    }
+}
+
+/// serde of the generic `Size` with dimension values
+mod size_def {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct SizeDef {
+        #[serde(with = "DimensionDef")]
+        width: Dimension,
+        #[serde(with = "DimensionDef")]
+        height: Dimension,
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        size: &Size<Dimension>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        SizeDef {
+            width: size.width,
+            height: size.height,
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Size<Dimension>, D::Error> {
+        let size = SizeDef::deserialize(deserializer)?;
+        Ok(Size {
+            width: size.width,
+            height: size.height,
+        })
+    }
+}
+
+/// serde of the computed `Layout`, only the location and size is serialized
+mod layout_def {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct LayoutDef {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        layout: &Layout,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        LayoutDef {
+            x: layout.location.x,
+            y: layout.location.y,
+            width: layout.size.width,
+            height: layout.size.height,
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Layout, D::Error> {
+        let layout = LayoutDef::deserialize(deserializer)?;
+        let mut new_layout = TEMPLATE.with(|template| *template);
+        new_layout.location.x = layout.x;
+        new_layout.location.y = layout.y;
+        new_layout.size.width = layout.width;
+        new_layout.size.height = layout.height;
+        Ok(new_layout)
+    }
+
+    thread_local! {
+        /// Layout can only be created by expanse, so the layout of an empty
+        /// node is computed once and its location and size are overwritten
+        static TEMPLATE: Layout = empty_layout();
+    }
+
+    fn empty_layout() -> Layout {
+        let mut stretch = Stretch::new();
+        let node = stretch
+            .new_node(Style::default(), &[])
+            .expect("must create a node");
+        stretch
+            .compute_layout(
+                node,
+                Size {
+                    width: Number::Undefined,
+                    height: Number::Undefined,
+                },
+            )
+            .expect("must compute the layout");
+        *stretch.layout(node).expect("must have a layout")
+    }
+}
@@ -1,10 +1,13 @@
 //! utility functions for manipulating attributes
 //!
 use crate::{
-    widget::attribute::AttribKey, Attribute, Callback, Element, Node, Value,
+    widget::attribute::{AttribKey, FileMode},
+    widget::event::KeyEvent,
+    Attribute, Callback, Element, Node, Value, Widget,
 };
 use expanse::result::Layout;
 use expanse::style::Style;
+use std::ops::Range;
 
 /// find the value of the attribute key from a Vec of attributes
 pub fn find_value<MSG>(
@@ -17,8 +20,7 @@ where
     attrs
         .iter()
         .find(|att| *att.name() == key)
-        .map(|att| att.get_plain().first().copied())
-        .flatten()
+        .and_then(|att| att.get_plain().first().copied())
 }
 
 /// find the callback of the attribute key from a Vec of attributes
@@ -45,8 +47,7 @@ pub fn is_scrollable<MSG: 'static>(attrs: &[Attribute<MSG>]) -> bool {
 /// return the first style attribute of this node
 pub fn get_style<MSG>(node: &Node<MSG>) -> Option<&Style> {
     node.get_attribute_value(&AttribKey::Style)
-        .map(|values| values.first().map(|value| value.as_style()))
-        .flatten()
+        .and_then(|values| values.first().map(|value| value.as_style()))
         .flatten()
 }
 
@@ -54,21 +55,329 @@ pub fn get_style<MSG>(node: &Node<MSG>) -> Option<&Style> {
 pub fn get_layout<MSG>(element: &Element<MSG>) -> Option<&Layout> {
     element
         .get_attribute_value(&AttribKey::Layout)
-        .map(|values| values.first().map(|value| value.as_layout()))
-        .flatten()
+        .and_then(|values| values.first().map(|value| value.as_layout()))
         .flatten()
 }
 
 pub fn get_key<MSG>(element: &Element<MSG>) -> Option<&Value> {
     element
         .get_attribute_value(&AttribKey::Key)
-        .map(|values| values.first().map(|v| *v))
-        .flatten()
+        .and_then(|values| values.first().copied())
 }
 
 pub fn get_id<MSG>(element: &Element<MSG>) -> Option<&Value> {
     element
         .get_attribute_value(&AttribKey::Id)
-        .map(|values| values.first().map(|v| *v))
-        .flatten()
+        .and_then(|values| values.first().copied())
+}
+
+/// return the column headers and the text of the cells in each row of a table
+pub fn get_table_data<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let columns = find_value(AttribKey::Columns, attrs)
+        .and_then(|v| v.as_list())
+        .map(|columns| columns.iter().map(|v| v.to_string()).collect())
+        .unwrap_or_default();
+    let rows = find_value(AttribKey::Rows, attrs)
+        .and_then(|v| v.as_list())
+        .map(|rows| {
+            rows.iter()
+                .map(|row| {
+                    row.as_list()
+                        .unwrap_or(&[])
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (columns, rows)
+}
+
+/// return the options of a dropdown or list box
+pub fn get_options<MSG: 'static>(attrs: &[Attribute<MSG>]) -> Vec<String> {
+    find_value(AttribKey::Options, attrs)
+        .and_then(|v| v.as_list())
+        .map(|options| options.iter().map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// return the selected options of a dropdown or list box, the value is
+/// either a single option or a list of options
+pub fn get_selected_values<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Vec<String> {
+    find_value(AttribKey::Value, attrs)
+        .map(selected_values)
+        .unwrap_or_default()
+}
+
+/// the selected options in this value
+pub fn selected_values(value: &Value) -> Vec<String> {
+    match value.as_list() {
+        Some(list) => list.iter().map(|v| v.to_string()).collect(),
+        None => vec![value.to_string()],
+    }
+}
+
+/// return the index value of the attribute key, such as the selected item
+pub fn get_index<MSG: 'static>(
+    key: AttribKey,
+    attrs: &[Attribute<MSG>],
+) -> Option<usize> {
+    find_value(key, attrs)
+        .and_then(|v| v.as_f64())
+        .filter(|v| *v >= 0.0)
+        .map(|v| v as usize)
+}
+
+/// return the min, max and step of a spin button or slider
+pub fn get_numeric_range<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> (f64, f64, f64) {
+    let min = find_value(AttribKey::Min, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_MIN);
+    let max = find_value(AttribKey::Max, attrs)
+        .and_then(|v| v.as_f64())
+        .filter(|max| *max >= min)
+        .unwrap_or_else(|| min.max(DEFAULT_MAX));
+    let step = find_value(AttribKey::Step, attrs)
+        .and_then(|v| v.as_f64())
+        .filter(|step| *step > 0.0)
+        .unwrap_or(DEFAULT_STEP);
+    (min, max, step)
+}
+
+/// return the value of a spin button or slider, clamped to its range
+pub fn get_numeric_value<MSG: 'static>(attrs: &[Attribute<MSG>]) -> f64 {
+    let (min, max, _step) = get_numeric_range(attrs);
+    find_value(AttribKey::Value, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(min)
+        .max(min)
+        .min(max)
+}
+
+/// return the id of a tree item which is used in the tree view events,
+/// this is the key of the item, or its label when it has no key
+pub fn get_tree_item_id<MSG: 'static>(attrs: &[Attribute<MSG>]) -> String {
+    find_value(AttribKey::Key, attrs)
+        .or_else(|| find_value(AttribKey::Label, attrs))
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+/// return whether the tree item is expanded or selected
+pub fn get_tree_item_state<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> (bool, bool) {
+    let is_expanded = find_value(AttribKey::Expanded, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let is_selected = find_value(AttribKey::Selected, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    (is_expanded, is_selected)
+}
+
+/// whether the node is a dialog or has a dialog in its descendants
+pub fn has_dialog<MSG>(node: &Node<MSG>) -> bool {
+    match node {
+        Node::Element(element) => {
+            *element.tag() == Widget::Dialog
+                || element.get_children().iter().any(has_dialog)
+        }
+        Node::Text(_) => false,
+    }
+}
+
+/// return the last dialog in the node, which is the one above the others
+pub fn find_dialog<MSG>(node: &Node<MSG>) -> Option<&Element<MSG>> {
+    match node {
+        Node::Element(element) => element
+            .get_children()
+            .iter()
+            .rev()
+            .find_map(find_dialog)
+            .or_else(|| Some(element).filter(|e| *e.tag() == Widget::Dialog)),
+        Node::Text(_) => None,
+    }
+}
+
+/// whether the widget is focused when it is shown
+pub fn is_autofocus<MSG: 'static>(attrs: &[Attribute<MSG>]) -> bool {
+    find_value(AttribKey::Autofocus, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// return the parsed accelerator of a menu item
+pub fn get_accelerator<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Option<KeyEvent> {
+    find_value(AttribKey::Accelerator, attrs)
+        .and_then(|v| KeyEvent::from_accelerator(&v.to_string()))
+}
+
+/// the value of the activate event of the menu item, which is the toggled
+/// checked state of a check menu item, `true` for a radio menu item and the
+/// label of the other menu items
+pub fn get_activate_value<MSG: 'static>(
+    widget: &Widget,
+    attrs: &[Attribute<MSG>],
+) -> Value {
+    let is_checked = find_value(AttribKey::Checked, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    match widget {
+        Widget::CheckMenuItem => Value::from(!is_checked),
+        Widget::RadioMenuItem => Value::from(true),
+        _ => find_value(AttribKey::Label, attrs)
+            .cloned()
+            .unwrap_or_else(|| Value::from("")),
+    }
+}
+
+/// find the menu item in the node tree which is activated by the key event
+pub fn find_accelerated<'a, MSG: 'static>(
+    node: &'a Node<MSG>,
+    key_event: &KeyEvent,
+) -> Option<&'a Element<MSG>> {
+    match node {
+        Node::Element(element) => {
+            if get_accelerator(element.get_attributes()).as_ref()
+                == Some(key_event)
+            {
+                Some(element)
+            } else {
+                element
+                    .get_children()
+                    .iter()
+                    .find_map(|child| find_accelerated(child, key_event))
+            }
+        }
+        Node::Text(_) => None,
+    }
+}
+
+/// return the mode of the file chooser, which opens files by default
+pub fn get_file_mode<MSG: 'static>(attrs: &[Attribute<MSG>]) -> FileMode {
+    match find_value(AttribKey::FileMode, attrs).and_then(|v| v.as_str()) {
+        Some("save") => FileMode::Save,
+        Some("select_folder") => FileMode::SelectFolder,
+        _ => FileMode::Open,
+    }
+}
+
+/// return the patterns of the file names in the file chooser
+pub fn get_filters<MSG: 'static>(attrs: &[Attribute<MSG>]) -> Vec<String> {
+    find_value(AttribKey::Filters, attrs)
+        .and_then(|v| v.as_list())
+        .map(|filters| filters.iter().map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// return the fraction of a progress bar, clamped from 0.0 to 1.0
+pub fn get_fraction<MSG: 'static>(attrs: &[Attribute<MSG>]) -> f64 {
+    find_value(AttribKey::Fraction, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        .clamp(0.0, 1.0)
+}
+
+/// the lowest value of a spin button or slider when it is not specified
+pub const DEFAULT_MIN: f64 = 0.0;
+/// the highest value of a spin button or slider when it is not specified
+pub const DEFAULT_MAX: f64 = 100.0;
+/// the increment of a spin button or slider when it is not specified
+pub const DEFAULT_STEP: f64 = 1.0;
+
+/// the value which a widget goes back to when the attribute is removed,
+/// returns None for the attributes which has no default value
+pub fn default_value(key: &AttribKey) -> Option<Value> {
+    match key {
+        AttribKey::Value
+        | AttribKey::Label
+        | AttribKey::Uri
+        | AttribKey::Accelerator => Some(Value::from("")),
+        AttribKey::Checked => Some(Value::from(false)),
+        AttribKey::Editable => Some(Value::from(true)),
+        AttribKey::Min => Some(Value::from(DEFAULT_MIN)),
+        AttribKey::Max => Some(Value::from(DEFAULT_MAX)),
+        AttribKey::Step => Some(Value::from(DEFAULT_STEP)),
+        AttribKey::Fraction => Some(Value::from(0.0)),
+        AttribKey::Indeterminate => Some(Value::from(false)),
+        AttribKey::Active => Some(Value::from(true)),
+        AttribKey::Draw => Some(Value::Draw(vec![])),
+        AttribKey::Filters => Some(Value::List(vec![])),
+        _ => None,
+    }
+}
+
+/// the row height of a list view when it is not specified
+pub const DEFAULT_ROW_HEIGHT: f64 = 20.0;
+/// the number of extra rows of a list view when it is not specified
+pub const DEFAULT_OVERSCAN: usize = 3;
+/// the height of a list view when it is not specified
+pub const DEFAULT_VIEWPORT_HEIGHT: f64 = 400.0;
+
+/// return the row height of a list view
+pub fn get_row_height<MSG: 'static>(attrs: &[Attribute<MSG>]) -> f64 {
+    find_value(AttribKey::RowHeight, attrs)
+        .and_then(|v| v.as_f64())
+        .filter(|v| *v > 0.0)
+        .unwrap_or(DEFAULT_ROW_HEIGHT)
+}
+
+/// return the total number of items of a list view
+pub fn get_item_count<MSG: 'static>(attrs: &[Attribute<MSG>]) -> usize {
+    get_index(AttribKey::ItemCount, attrs).unwrap_or(0)
+}
+
+/// return the rows of a list view which are visible at this scroll offset
+pub fn get_visible_range<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+    scroll_offset: f64,
+) -> Range<usize> {
+    let viewport_height = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_VIEWPORT_HEIGHT);
+    visible_range(
+        get_item_count(attrs),
+        get_row_height(attrs),
+        scroll_offset,
+        viewport_height,
+    )
+}
+
+/// return the rows of a list view which are built into nodes, these are the
+/// visible rows at the current scroll offset plus the overscan
+pub fn get_rendered_range<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Range<usize> {
+    let scroll_offset = find_value(AttribKey::ScrollOffset, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+    let overscan =
+        get_index(AttribKey::Overscan, attrs).unwrap_or(DEFAULT_OVERSCAN);
+    let visible = get_visible_range(attrs, scroll_offset);
+    let end = (visible.end + overscan).min(get_item_count(attrs));
+    visible.start.saturating_sub(overscan)..end
+}
+
+/// the rows which are inside the viewport, including the rows which are
+/// only partially visible
+pub fn visible_range(
+    item_count: usize,
+    row_height: f64,
+    scroll_offset: f64,
+    viewport_height: f64,
+) -> Range<usize> {
+    let start = (scroll_offset.max(0.0) / row_height).floor() as usize;
+    let end = ((scroll_offset.max(0.0) + viewport_height.max(0.0)) / row_height)
+        .ceil() as usize;
+    start.min(item_count)..end.min(item_count)
 }
@@ -1,9 +1,13 @@
+use crate::widget::draw::DrawCommand;
 use expanse::result::Layout;
 use expanse::style::PositionType;
 use expanse::style::Style;
 use std::fmt;
 
 /// The possible values of widget attributes
+///
+/// The serde implementation is in `serde_def`, since the static str can only
+/// be deserialized into an owned String.
 #[derive(PartialEq, Debug, Clone)]
 pub enum Value {
     /// generic strings, used in labels, btn labels
@@ -22,6 +26,10 @@ pub enum Value {
     PositionType(PositionType),
     /// float values
     F64(f64),
+    /// a list of values, such as the columns and rows of a table
+    List(Vec<Value>),
+    /// the draw commands of a canvas
+    Draw(Vec<DrawCommand>),
 }
 
 impl Value {
@@ -41,6 +49,14 @@ impl Value {
         }
     }
 
+    /// return the draw commands if it is a Draw variant
+    pub fn as_draw(&self) -> Option<&[DrawCommand]> {
+        match self {
+            Value::Draw(v) => Some(v),
+            _ => None,
+        }
+    }
+
     /// return the f64 value if it is an F64 variant
     pub fn as_f64(&self) -> Option<f64> {
         match self {
@@ -52,7 +68,7 @@ impl Value {
     /// return the &str value if it is a String or Str variant
     pub fn as_str(&self) -> Option<&str> {
         match self {
-            Value::String(v) => Some(&v),
+            Value::String(v) => Some(v),
             Value::Str(v) => Some(v),
             _ => None,
         }
@@ -61,7 +77,7 @@ impl Value {
     /// return the style if it is a Style variant
     pub fn as_style(&self) -> Option<&Style> {
         match self {
-            Value::Style(style) => Some(&style),
+            Value::Style(style) => Some(style),
             _ => None,
         }
     }
@@ -69,7 +85,15 @@ impl Value {
     /// return the layout if it is a layout variant
     pub fn as_layout(&self) -> Option<&Layout> {
         match self {
-            Value::Layout(layout) => Some(&layout),
+            Value::Layout(layout) => Some(layout),
+            _ => None,
+        }
+    }
+
+    /// return the values if it is a List variant
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(v) => Some(v),
             _ => None,
         }
     }
@@ -125,6 +149,12 @@ impl From<u64> for Value {
     }
 }
 
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Value::F64(v as f64)
+    }
+}
+
 impl From<i8> for Value {
     fn from(v: i8) -> Self {
         Value::F64(v as f64)
@@ -173,6 +203,30 @@ impl From<Vec<u8>> for Value {
     }
 }
 
+impl From<Vec<Value>> for Value {
+    fn from(v: Vec<Value>) -> Self {
+        Value::List(v)
+    }
+}
+
+impl From<Vec<&'static str>> for Value {
+    fn from(v: Vec<&'static str>) -> Self {
+        Value::List(v.into_iter().map(Value::from).collect())
+    }
+}
+
+impl From<Vec<String>> for Value {
+    fn from(v: Vec<String>) -> Self {
+        Value::List(v.into_iter().map(Value::from).collect())
+    }
+}
+
+impl From<Vec<DrawCommand>> for Value {
+    fn from(v: Vec<DrawCommand>) -> Self {
+        Value::Draw(v)
+    }
+}
+
 impl From<Layout> for Value {
     fn from(v: Layout) -> Self {
         Value::Layout(v)
@@ -192,6 +246,14 @@ impl fmt::Display for Value {
             Value::Str(s) => write!(f, "{}", s),
             Value::Bool(b) => write!(f, "{}", b),
             Value::F64(v) => write!(f, "{}", v),
+            Value::List(v) => write!(
+                f,
+                "{}",
+                v.iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             _ => todo!(),
         }
     }
@@ -1,14 +1,19 @@
 use crate::widget::attribute;
 use crate::widget::attribute::style;
-use crate::widget::attribute::util::find_value;
-use crate::Widget;
+use crate::widget::attribute::util::{
+    find_callback, find_value, get_item_count, get_options, get_rendered_range,
+    get_row_height, DEFAULT_VIEWPORT_HEIGHT,
+};
+use crate::widget::event::{self, InputEvent};
 use crate::{AttribKey, Attribute, Node, Value};
-use expanse::geometry::Size;
+use crate::{CustomWidget, Widget};
+use expanse::geometry::{Rect, Size};
 use expanse::style::Dimension;
 use expanse::style::FlexDirection;
 use expanse::style::PositionType;
 use expanse::style::Style;
 use mt_dom::{attr, element};
+use std::rc::Rc;
 
 /// a helper function to create widget elements
 pub fn widget<MSG>(
@@ -30,24 +35,17 @@ pub fn column<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
         flex_direction: FlexDirection::Column,
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -73,24 +71,17 @@ pub fn row<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
         flex_direction: FlexDirection::Row,
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -116,24 +107,17 @@ pub fn vpane<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
         flex_direction: FlexDirection::Column,
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -159,24 +143,17 @@ pub fn hpane<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
         flex_direction: FlexDirection::Row,
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -202,12 +179,10 @@ pub fn overlay<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     children.iter_mut().for_each(|child| {
         child.add_attributes_ref_mut(vec![attr(
@@ -242,23 +217,16 @@ pub fn groupbox<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -281,23 +249,16 @@ pub fn button<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -342,15 +303,10 @@ where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -382,15 +338,10 @@ where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -406,15 +357,10 @@ where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -430,15 +376,10 @@ where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -454,15 +395,10 @@ where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -479,23 +415,16 @@ pub fn header_bar<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -524,23 +453,16 @@ pub fn menu_bar<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -565,23 +487,16 @@ pub fn menu<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -599,6 +514,9 @@ where
     widget(Widget::Menu, attrs, children)
 }
 
+/// create a menu item with the text of the `label` attribute, the sub menu
+/// is added as its child. The `on_activate` event is emitted when it is
+/// clicked or the key combination in the `accelerator` attribute is pressed
 pub fn menu_item<MSG>(
     mut attrs: Vec<Attribute<MSG>>,
     children: Vec<Node<MSG>>,
@@ -606,23 +524,16 @@ pub fn menu_item<MSG>(
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -640,21 +551,58 @@ where
     widget(Widget::MenuItem, attrs, children)
 }
 
+/// create a menu item with a check mark, which is shown when the `checked`
+/// attribute is true. The `on_activate` event is emitted with the toggled
+/// checked state
+pub fn check_menu_item<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    attrs.push(style(menu_item_style()));
+    widget(Widget::CheckMenuItem, attrs, vec![])
+}
+
+/// create a menu item with a radio mark, which is shown when the `checked`
+/// attribute is true. The `on_activate` event is emitted with `true`, the app
+/// unchecks the other radio menu items of the group
+pub fn radio_menu_item<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    attrs.push(style(menu_item_style()));
+    widget(Widget::RadioMenuItem, attrs, vec![])
+}
+
+/// create a line which separates the groups of menu items
+pub fn menu_separator<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    attrs.push(style(menu_item_style()));
+    widget(Widget::MenuSeparator, attrs, vec![])
+}
+
+/// the menu items fill the width of the menu
+fn menu_item_style() -> Style {
+    Style {
+        size: Size {
+            width: Dimension::Percent(1.0),
+            height: Dimension::Auto,
+        },
+        ..Default::default()
+    }
+}
+
 /// create a text input
 pub fn search_input<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
 where
     MSG: 'static,
 {
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: Dimension::Percent(1.0),
             height: Dimension::Percent(1.0),
@@ -669,23 +617,16 @@ pub fn link<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
 where
     MSG: 'static,
 {
-    let spec_width = find_value(AttribKey::Width, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
-    let spec_height = find_value(AttribKey::Height, &attrs)
-        .map(|w| w.as_f64())
-        .flatten();
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
 
     let spec_position = find_value(AttribKey::PositionType, &attrs)
-        .map(|w| w.as_position_type())
-        .flatten();
+        .and_then(|w| w.as_position_type());
 
     attrs.push(style(Style {
-        position_type: if let Some(spec_position) = spec_position {
-            spec_position
-        } else {
-            Default::default()
-        },
+        position_type: spec_position.unwrap_or_default(),
         size: Size {
             width: if let Some(width) = spec_width {
                 Dimension::Points(width as f32)
@@ -708,3 +649,608 @@ where
     }));
     widget(Widget::Link, attrs, vec![])
 }
+
+/// create a table, the columns and rows are set with the `columns` and
+/// `rows` attributes
+pub fn table<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Table, attrs, vec![])
+}
+
+/// create a list view which only builds the nodes of the rows that are
+/// visible at the `scroll_offset`, plus the `overscan` rows above and below.
+///
+/// Each row is built with `render_row` from the index of the item, and is
+/// wrapped in a row of `row_height` keyed with the index of the item, so the
+/// rows which stays visible while scrolling are kept as they are.
+/// The rows which are not built are accounted as the top and bottom padding
+/// of the list view, so the layout has the height of all the rows.
+pub fn list_view<MSG, F>(
+    mut attrs: Vec<Attribute<MSG>>,
+    render_row: F,
+) -> Node<MSG>
+where
+    MSG: 'static,
+    F: Fn(usize) -> Node<MSG>,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    let item_count = get_item_count(&attrs);
+    let row_height = get_row_height(&attrs);
+    let rendered = get_rendered_range(&attrs);
+
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Points(DEFAULT_VIEWPORT_HEIGHT as f32)
+            },
+        },
+        padding: Rect {
+            top: Dimension::Points((rendered.start as f64 * row_height) as f32),
+            bottom: Dimension::Points(
+                ((item_count - rendered.end) as f64 * row_height) as f32,
+            ),
+            ..Default::default()
+        },
+        ..Default::default()
+    }));
+    let children = rendered
+        .map(|index| {
+            row(
+                vec![attribute::key(index), attribute::height(row_height)],
+                vec![render_row(index)],
+            )
+        })
+        .collect();
+    widget(Widget::ListView, attrs, children)
+}
+
+/// create a dropdown, the choices are set with the `options` attribute
+/// and the chosen option with the `value` attribute
+pub fn dropdown<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Dropdown, attrs, vec![])
+}
+
+/// create a list box, the choices are set with the `options` attribute and
+/// the selected options with the `value` attribute, which is a list of the
+/// options when `multiple` is set
+pub fn list_box<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::ListBox, attrs, vec![])
+}
+
+/// create a spin button, the number is set with the `value` attribute
+/// within the `min` and `max`, and changed by the `step`
+pub fn spin_button<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::SpinButton, attrs, vec![])
+}
+
+/// create a slider, the number is set with the `value` attribute
+/// within the `min` and `max`, and changed by the `step`
+pub fn slider<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Slider, attrs, vec![])
+}
+
+/// create a progress bar, the completed part is set with the `fraction`
+/// attribute and the optional text with the `label` attribute
+pub fn progress_bar<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::ProgressBar, attrs, vec![])
+}
+
+/// create a spinner, which spins unless `active` is set to false
+pub fn spinner<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Spinner, attrs, vec![])
+}
+
+/// create a notebook of tabs, the children are created with `tab`
+/// and the index of the shown tab is set with the `selected` attribute
+pub fn tabs<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Tabs, attrs, children)
+}
+
+/// create a tab with this label, the child is the content of the tab
+pub fn tab<MSG>(label: &str, child: Node<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    widget(
+        Widget::Tab,
+        vec![
+            attribute::label(label.to_string()),
+            style(Style {
+                flex_direction: FlexDirection::Column,
+                size: Size {
+                    width: Dimension::Percent(1.0),
+                    height: Dimension::Percent(1.0),
+                },
+                ..Default::default()
+            }),
+        ],
+        vec![child],
+    )
+}
+
+/// create a canvas which is drawn with the `draw` attribute, the mouse
+/// events of the canvas has the coordinate local to the canvas
+pub fn canvas<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Canvas, attrs, vec![])
+}
+
+/// create a tree view, the children are the top level items which are
+/// created with `tree_item`
+pub fn tree_view<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::TreeView, attrs, children)
+}
+
+/// create a tree item, the text is set with the `label` attribute and the
+/// nested items are its children. The `key` of the item is the id used in
+/// the tree view events, so it also keeps the expanded state of the item
+/// when the items are reordered
+pub fn tree_item<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        ..Default::default()
+    }));
+    widget(Widget::TreeItem, attrs, children)
+}
+
+/// the label of the button which accepts the dialog
+pub const DIALOG_OK: &str = "OK";
+/// the label of the button which cancels the confirmation dialog
+pub const DIALOG_CANCEL: &str = "Cancel";
+
+/// create a modal dialog which is shown while it is in the view.
+/// The title is set with the `label` attribute, the message with the `value`
+/// attribute and the labels of the buttons with the `options` attribute.
+/// The children are the custom content which is shown below the message.
+///
+/// The `on_response` event is emitted with the label of the clicked button,
+/// or an empty string when the dialog is dismissed, such as closing its
+/// window or pressing escape. The dialog stays open until it is removed
+/// from the view.
+pub fn dialog<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let message = find_value(AttribKey::Value, &attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let callbacks =
+        find_callback(AttribKey::ResponseEvent, &attrs).unwrap_or_default();
+
+    // the message and the buttons are widgets in the dialog, so they are
+    // the same in all of the backends
+    let mut content = vec![];
+    if !message.is_empty() {
+        content.push(text_label(vec![attribute::value(message)]));
+    }
+    content.extend(children);
+    let buttons: Vec<Node<MSG>> = get_options(&attrs)
+        .into_iter()
+        .map(|response| {
+            let mut button_attrs = vec![attribute::label(response.clone())];
+            for cb in callbacks.iter() {
+                let cb = (*cb).clone();
+                let response = response.clone();
+                button_attrs
+                    .push(event::on(AttribKey::ClickEvent, move |_| {
+                        cb.emit(InputEvent::new(response.clone()))
+                    }));
+            }
+            button(button_attrs)
+        })
+        .collect();
+    if !buttons.is_empty() {
+        content.push(row(vec![], buttons));
+    }
+
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    // the dialog is above the other widgets, so it takes no space
+    // in the layout of its parent
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        position_type: PositionType::Absolute,
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Auto
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Auto
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Dialog, attrs, content)
+}
+
+/// create a dialog with the message in the `value` attribute and an OK
+/// button, the buttons can be changed with the `options` attribute
+pub fn alert<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    if find_value(AttribKey::Options, &attrs).is_none() {
+        attrs.push(attribute::options(vec![DIALOG_OK]));
+    }
+    dialog(attrs, vec![])
+}
+
+/// create a dialog with the message in the `value` attribute and the OK
+/// and Cancel buttons, the buttons can be changed with the `options` attribute
+pub fn confirm<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    if find_value(AttribKey::Options, &attrs).is_none() {
+        attrs.push(attribute::options(vec![DIALOG_OK, DIALOG_CANCEL]));
+    }
+    dialog(attrs, vec![])
+}
+
+/// create a file chooser which is shown while it is in the view.
+/// The title is set with the `label` attribute, the kind of the chooser with
+/// the `file_mode` attribute, the shown files with the `filters` attribute
+/// and the directory which is shown first with the `directory` attribute.
+/// In save mode, the `value` attribute is the suggested file name and
+/// `multiple` allows more than one file to be opened.
+///
+/// The `on_files` event is emitted with the chosen paths, or an empty list
+/// when the file chooser is cancelled
+pub fn file_chooser<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    // the file chooser is a dialog, so it takes no space in the layout
+    // of its parent
+    attrs.push(style(Style {
+        position_type: PositionType::Absolute,
+        ..Default::default()
+    }));
+    widget(Widget::FileChooser, attrs, vec![])
+}
+
+/// create a custom widget, the backends convert it using the hook
+/// registered with the name of the custom widget
+pub fn custom<MSG, CW>(
+    custom_widget: CW,
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+    CW: CustomWidget + 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: spec_position.unwrap_or_default(),
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Custom(Rc::new(custom_widget)), attrs, children)
+}
@@ -0,0 +1,97 @@
+//! provides the trait for creating widgets outside of the built-in Widget enum
+//!
+use std::any::Any;
+use std::fmt::Debug;
+
+/// A custom widget is carried by `Widget::Custom`.
+///
+/// Each backend has its own conversion hook for custom widgets which is
+/// registered with the name of the custom widget.
+pub trait CustomWidget: Debug {
+    /// the name of this custom widget, this is used to look up the
+    /// registered backend conversion of this widget.
+    fn name(&self) -> &str;
+
+    /// whether the other custom widget is the same widget with the same
+    /// properties, the custom widget is replaced when it is not the same
+    /// when diffing. The properties are compared by their debug output,
+    /// override this to compare them directly.
+    fn is_same(&self, other: &dyn CustomWidget) -> bool {
+        self.name() == other.name()
+            && format!("{:?}", self) == format!("{:?}", other)
+    }
+
+    /// whether or not the custom widget can contain children
+    fn is_container(&self) -> bool {
+        false
+    }
+
+    /// whether or not the custom widget takes the keyboard focus
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// used for downcasting into the concrete custom widget in the backend hooks
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// A custom widget deserialized from a remote widget tree, it only knows the
+/// name of the custom widget which is used to find the backend conversion.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteCustomWidget {
+    /// the name of the original custom widget
+    pub name: String,
+    /// whether the original custom widget can contain children
+    pub is_container: bool,
+    /// whether the original custom widget takes the keyboard focus
+    #[serde(default)]
+    pub is_focusable: bool,
+}
+
+#[cfg(feature = "serde")]
+impl CustomWidget for RemoteCustomWidget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_container(&self) -> bool {
+        self.is_container
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.is_focusable
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// custom widgets are serialized with their name,
+/// and deserialized into a `RemoteCustomWidget`
+#[cfg(feature = "serde")]
+pub(crate) mod serde_custom {
+    use super::{CustomWidget, RemoteCustomWidget};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::rc::Rc;
+
+    pub(crate) fn serialize<S: Serializer>(
+        custom: &Rc<dyn CustomWidget>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RemoteCustomWidget {
+            name: custom.name().to_string(),
+            is_container: custom.is_container(),
+            is_focusable: custom.is_focusable(),
+        }
+        .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Rc<dyn CustomWidget>, D::Error> {
+        let custom = RemoteCustomWidget::deserialize(deserializer)?;
+        Ok(Rc::new(custom))
+    }
+}
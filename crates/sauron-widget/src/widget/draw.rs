@@ -0,0 +1,339 @@
+//! draw commands of a canvas, these are backend neutral and are modeled
+//! after the path based drawing of cairo and the html canvas
+use std::fmt;
+
+/// a color with red, green, blue and alpha components from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// red component
+    pub red: f64,
+    /// green component
+    pub green: f64,
+    /// blue component
+    pub blue: f64,
+    /// the opacity, 0.0 is fully transparent
+    pub alpha: f64,
+}
+
+impl Color {
+    /// creates an opaque color from the 8 bit red, green and blue components
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color::rgba(red, green, blue, 1.0)
+    }
+
+    /// creates a color from the 8 bit red, green and blue components
+    /// and the alpha from 0.0 to 1.0
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: f64) -> Self {
+        Color {
+            red: red as f64 / 255.0,
+            green: green as f64 / 255.0,
+            blue: blue as f64 / 255.0,
+            alpha,
+        }
+    }
+}
+
+/// black is the default stroke and fill color
+impl Default for Color {
+    fn default() -> Self {
+        Color::rgb(0, 0, 0)
+    }
+}
+
+/// formats the color as a css color, such as `rgba(255,0,0,1)`
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rgba({},{},{},{})",
+            (self.red * 255.0).round(),
+            (self.green * 255.0).round(),
+            (self.blue * 255.0).round(),
+            self.alpha
+        )
+    }
+}
+
+/// A command to draw on the canvas.
+///
+/// The lines and rectangles are added to the current path, which is
+/// then drawn with `Stroke` or `Fill`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawCommand {
+    /// start a new sub path at this point
+    MoveTo(f64, f64),
+    /// add a straight line from the current point to this point
+    LineTo(f64, f64),
+    /// add a line back to the start of the current sub path
+    ClosePath,
+    /// add a rectangle to the path
+    Rect {
+        /// the left edge
+        x: f64,
+        /// the top edge
+        y: f64,
+        /// the width of the rectangle
+        width: f64,
+        /// the height of the rectangle
+        height: f64,
+    },
+    /// draw the outline of the path with the stroke color, then clear it
+    Stroke,
+    /// fill the inside of the path with the fill color, then clear it
+    Fill,
+    /// set the color used in the succeeding strokes
+    StrokeColor(Color),
+    /// set the color used in the succeeding fills and texts
+    FillColor(Color),
+    /// set the width of the succeeding strokes
+    LineWidth(f64),
+    /// draw the text with the fill color, the point is at the start of the
+    /// baseline of the text
+    Text {
+        /// the start of the baseline
+        x: f64,
+        /// the baseline
+        y: f64,
+        /// the text to be drawn
+        text: String,
+    },
+}
+
+impl DrawCommand {
+    /// the commands to stroke a line from one point to another
+    pub fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Vec<DrawCommand> {
+        vec![
+            DrawCommand::MoveTo(x1, y1),
+            DrawCommand::LineTo(x2, y2),
+            DrawCommand::Stroke,
+        ]
+    }
+
+    /// the commands to fill a rectangle with the fill color
+    pub fn fill_rect(
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Vec<DrawCommand> {
+        vec![
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            },
+            DrawCommand::Fill,
+        ]
+    }
+
+    /// the commands to stroke the outline of a rectangle
+    pub fn stroke_rect(
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Vec<DrawCommand> {
+        vec![
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            },
+            DrawCommand::Stroke,
+        ]
+    }
+}
+
+/// a straight line of the path, used by the backends which draws the
+/// path themselves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    /// the start point
+    pub from: (f64, f64),
+    /// the end point
+    pub to: (f64, f64),
+}
+
+/// the path which is built by the commands, used by the backends
+/// which doesn't have a path based drawing
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Path {
+    /// the sub paths, each one is a list of points
+    pub sub_paths: Vec<Vec<(f64, f64)>>,
+    /// whether the last point of the sub path is joined to its first point
+    pub closed: Vec<bool>,
+}
+
+impl Path {
+    /// add the command to the path, returns false if it is not a path command
+    pub fn add(&mut self, command: &DrawCommand) -> bool {
+        match command {
+            DrawCommand::MoveTo(x, y) => {
+                self.sub_paths.push(vec![(*x, *y)]);
+                self.closed.push(false);
+            }
+            DrawCommand::LineTo(x, y) => match self.sub_paths.last_mut() {
+                Some(points) => points.push((*x, *y)),
+                // a line without a starting point starts the sub path
+                None => {
+                    self.sub_paths.push(vec![(*x, *y)]);
+                    self.closed.push(false);
+                }
+            },
+            DrawCommand::ClosePath => {
+                if let Some(closed) = self.closed.last_mut() {
+                    *closed = true;
+                }
+            }
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                self.sub_paths.push(vec![
+                    (*x, *y),
+                    (x + width, *y),
+                    (x + width, y + height),
+                    (*x, y + height),
+                ]);
+                self.closed.push(true);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// the straight lines of all the sub paths
+    pub fn segments(&self) -> Vec<Segment> {
+        let mut segments = vec![];
+        for (points, closed) in self.sub_paths.iter().zip(self.closed.iter()) {
+            for pair in points.windows(2) {
+                segments.push(Segment {
+                    from: pair[0],
+                    to: pair[1],
+                });
+            }
+            if *closed && points.len() > 2 {
+                segments.push(Segment {
+                    from: points[points.len() - 1],
+                    to: points[0],
+                });
+            }
+        }
+        segments
+    }
+
+    /// the path in the svg path data syntax
+    pub fn to_svg_data(&self) -> String {
+        let mut data = vec![];
+        for (points, closed) in self.sub_paths.iter().zip(self.closed.iter()) {
+            for (index, (x, y)) in points.iter().enumerate() {
+                let op = if index == 0 { "M" } else { "L" };
+                data.push(format!("{}{} {}", op, x, y));
+            }
+            if *closed {
+                data.push("Z".to_string());
+            }
+        }
+        data.join(" ")
+    }
+
+    /// whether the point is inside the path using the even-odd rule,
+    /// the sub paths are treated as closed when filling
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let mut is_inside = false;
+        for points in self.sub_paths.iter() {
+            let len = points.len();
+            for index in 0..len {
+                let (x1, y1) = points[index];
+                let (x2, y2) = points[(index + len - 1) % len];
+                if (y1 > y) != (y2 > y)
+                    && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1
+                {
+                    is_inside = !is_inside;
+                }
+            }
+        }
+        is_inside
+    }
+
+    /// whether there is nothing in the path
+    pub fn is_empty(&self) -> bool {
+        self.sub_paths.is_empty()
+    }
+}
+
+/// a shape which is drawn by the stroke, fill or text command, used by
+/// the backends which draws the canvas as svg elements
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// the path, which is filled with the fill color or stroked with the
+    /// stroke color and line width
+    Path {
+        /// the path data in the svg syntax
+        data: String,
+        /// the color of the inside of the path
+        fill: Option<Color>,
+        /// the color and width of the outline of the path
+        stroke: Option<(Color, f64)>,
+    },
+    /// the text with its fill color
+    Text {
+        /// the start of the baseline
+        x: f64,
+        /// the baseline
+        y: f64,
+        /// the text to be drawn
+        text: String,
+        /// the color of the text
+        fill: Color,
+    },
+}
+
+/// the shapes which are drawn by the commands, in the order they are drawn
+pub fn to_shapes(commands: &[DrawCommand]) -> Vec<Shape> {
+    let mut shapes = vec![];
+    let mut path = Path::default();
+    let mut stroke_color = Color::default();
+    let mut fill_color = Color::default();
+    let mut line_width = 1.0;
+    for command in commands {
+        if path.add(command) {
+            continue;
+        }
+        match command {
+            DrawCommand::Stroke => {
+                shapes.push(Shape::Path {
+                    data: path.to_svg_data(),
+                    fill: None,
+                    stroke: Some((stroke_color, line_width)),
+                });
+                path = Path::default();
+            }
+            DrawCommand::Fill => {
+                shapes.push(Shape::Path {
+                    data: path.to_svg_data(),
+                    fill: Some(fill_color),
+                    stroke: None,
+                });
+                path = Path::default();
+            }
+            DrawCommand::StrokeColor(color) => stroke_color = *color,
+            DrawCommand::FillColor(color) => fill_color = *color,
+            DrawCommand::LineWidth(width) => line_width = *width,
+            DrawCommand::Text { x, y, text } => shapes.push(Shape::Text {
+                x: *x,
+                y: *y,
+                text: text.to_string(),
+                fill: fill_color,
+            }),
+            _ => unreachable!("path commands are added to the path"),
+        }
+    }
+    shapes
+}
@@ -4,10 +4,12 @@ use crate::AttribKey;
 use crate::Attribute;
 use crate::Value;
 use mt_dom::Callback;
+use std::ops::Range;
 
 /// A container for generic event and the common values
 /// needed for the user.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// It is a mouse event
     MouseEvent(MouseEvent),
@@ -15,6 +17,10 @@ pub enum Event {
     KeyEvent(KeyEvent),
     /// Events in text_area or text_input
     InputEvent(InputEvent),
+    /// the widget gained the keyboard focus
+    FocusEvent(FocusEvent),
+    /// the widget lost the keyboard focus
+    BlurEvent(FocusEvent),
     /// resize event
     Resize(f32, f32),
 }
@@ -37,6 +43,12 @@ impl From<KeyEvent> for Event {
     }
 }
 
+/// The widget gained or lost the keyboard focus, this is emitted when it is
+/// clicked, tabbed into or focused by `Cmd::focus`
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FocusEvent;
+
 /// A mouse event contains the (x,y) coordinates, buttons and modifier keys
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct MouseEvent {
@@ -49,6 +61,61 @@ pub struct MouseEvent {
     /// which mousebutton is pressed
     pub buttons: MouseButton,
 }
+
+/// the serialized form of `MouseEvent`, where the type is an owned String
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MouseEventDef {
+    r#type: String,
+    coordinate: Coordinate,
+    modifier: Modifier,
+    buttons: MouseButton,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MouseEvent {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        MouseEventDef {
+            r#type: self.r#type.to_string(),
+            coordinate: self.coordinate,
+            modifier: self.modifier,
+            buttons: self.buttons,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// the mouse event type is a static str,
+/// so only the known mouse event types can be deserialized
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MouseEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let me = MouseEventDef::deserialize(deserializer)?;
+        // the type of a default mouse event is empty
+        let r#type =
+            ["click", "mousedown", "mouseup", "mousemove", "dblclick", ""]
+                .iter()
+                .find(|known| **known == me.r#type)
+                .ok_or_else(|| {
+                    serde::de::Error::custom(format!(
+                        "unknown mouse event type: {:?}",
+                        me.r#type
+                    ))
+                })?;
+        Ok(MouseEvent {
+            r#type,
+            coordinate: me.coordinate,
+            modifier: me.modifier,
+            buttons: me.buttons,
+        })
+    }
+}
+
 impl MouseEvent {
     /// creates a mouse click event at x and y location
     pub fn click(x: i32, y: i32) -> Self {
@@ -103,6 +170,7 @@ impl MouseEvent {
 
 /// Keypresses creates a key event
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyEvent {
     /// the key pressed
     pub key_code: KeyCode,
@@ -117,10 +185,88 @@ impl KeyEvent {
             modifier: Modifier::none(),
         }
     }
+
+    /// parse the accelerator such as `Ctrl+S`, `Alt+F4` or `Ctrl+Shift+Z`,
+    /// the modifiers and keys are case insensitive
+    pub fn from_accelerator(accelerator: &str) -> Option<Self> {
+        let mut parts: Vec<&str> =
+            accelerator.split('+').map(|part| part.trim()).collect();
+        let key = parts.pop()?;
+        let mut modifier = Modifier::none();
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => modifier.ctrl_key = true,
+                "alt" => modifier.alt_key = true,
+                "shift" => modifier.shift_key = true,
+                _ => return None,
+            }
+        }
+        let key_code = match key.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "backspace" => KeyCode::Backspace,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "tab" => KeyCode::Tab,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            lower => {
+                let mut chars = lower.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => KeyCode::Char(ch),
+                    (Some('f'), Some(_)) => {
+                        KeyCode::F(lower[1..].parse().ok()?)
+                    }
+                    _ => return None,
+                }
+            }
+        };
+        Some(KeyEvent { key_code, modifier })
+    }
+
+    /// whether the key event is a key chord which can be an accelerator or a
+    /// shortcut, these are the keys pressed with ctrl or alt and the function
+    /// keys
+    pub fn is_accelerator(&self) -> bool {
+        self.modifier.ctrl_key
+            || self.modifier.alt_key
+            || matches!(self.key_code, KeyCode::F(_))
+    }
+
+    /// the text of the accelerator which is shown next to the menu item,
+    /// such as `Ctrl+S`
+    pub fn to_accelerator(&self) -> String {
+        let mut parts = vec![];
+        if self.modifier.ctrl_key {
+            parts.push("Ctrl".to_string());
+        }
+        if self.modifier.alt_key {
+            parts.push("Alt".to_string());
+        }
+        if self.modifier.shift_key {
+            parts.push("Shift".to_string());
+        }
+        parts.push(match &self.key_code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(ch) => ch.to_uppercase().to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Esc => "Esc".to_string(),
+            key_code => format!("{:?}", key_code),
+        });
+        parts.join("+")
+    }
 }
 
 /// The keycode of the character pressed
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyCode {
     /// backspace
     Backspace,
@@ -162,6 +308,7 @@ pub enum KeyCode {
 
 /// Input event is triggered by controls such as text_area and text_input
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputEvent {
     /// the input value
     pub value: Value,
@@ -183,9 +330,11 @@ impl From<Value> for InputEvent {
 }
 
 /// Which mouse button is used
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     /// left mouse button
+    #[default]
     Left,
     /// right mouse button
     Right,
@@ -197,14 +346,9 @@ pub enum MouseButton {
     WheelDown,
 }
 
-impl Default for MouseButton {
-    fn default() -> Self {
-        MouseButton::Left
-    }
-}
-
 /// The coordinate of the event
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinate {
     /// x component of the client
     /// https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientX
@@ -254,6 +398,7 @@ impl Coordinate {
 /// Modifier contains the information of which modifier keys are pressed when the event is
 /// triggered
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Modifier {
     /// whether the alt key is pressed
     pub alt_key: bool,
@@ -356,18 +501,34 @@ where
     })
 }
 
-/// TODO:
+/// create an attribute which attach a callback to the on_focus event,
+/// which is triggered when the widget gains the keyboard focus
+pub fn on_focus<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(FocusEvent) -> MSG + 'static,
+{
+    on(AttribKey::FocusEvent, move |ev: Event| match ev {
+        Event::FocusEvent(fe) => func(fe),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_blur event,
+/// which is triggered when the widget loses the keyboard focus
 pub fn on_blur<F, MSG>(func: F) -> Attribute<MSG>
 where
-    F: Fn(MouseEvent) -> MSG + 'static,
+    F: Fn(FocusEvent) -> MSG + 'static,
 {
     on(AttribKey::BlurEvent, move |ev: Event| match ev {
-        Event::MouseEvent(me) => func(me),
+        Event::BlurEvent(fe) => func(fe),
         _ => unreachable!(),
     })
 }
 
-/// create an attribute which attach a callback to the on_mousemove event
+/// create an attribute which attach a callback to the on_input event,
+/// the value of the input event is the text of the text inputs, a
+/// `Value::F64` in the spin button and slider, and the directory which is
+/// browsed to in the file chooser
 pub fn on_input<F, MSG>(func: F) -> Attribute<MSG>
 where
     F: Fn(InputEvent) -> MSG + 'static,
@@ -397,3 +558,166 @@ where
         _ => unreachable!(),
     })
 }
+
+/// create an attribute which attach a callback to the on_select event,
+/// the value of the input event is the index of the selected item,
+/// or the id of the selected item in a tree view
+pub fn on_select<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::SelectEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_sort event,
+/// the value of the input event is the index of the clicked column
+pub fn on_sort<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::SortEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_row_activate event,
+/// the value of the input event is the index of the activated row,
+/// or the id of the activated item in a tree view
+pub fn on_row_activate<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::RowActivateEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_scroll event,
+/// the value of the input event is the vertical scroll offset
+pub fn on_scroll<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::ScrollEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_visible_range event,
+/// the backends emit the start and end index of the visible rows as a list
+pub fn on_visible_range<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(Range<usize>) -> MSG + 'static,
+{
+    on(AttribKey::VisibleRangeEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => {
+            let index = |i: usize| {
+                input
+                    .value
+                    .as_list()
+                    .and_then(|list| list.get(i))
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0) as usize
+            };
+            func(index(0)..index(1))
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_tab_change event,
+/// the value of the input event is the index of the selected tab
+pub fn on_tab_change<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::TabChangeEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_change event,
+/// the value of the input event is the selected option, or the list of the
+/// selected options in a list box which allows multiple selection
+pub fn on_change<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::ChangeEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_expand event,
+/// the value of the input event is the id of the expanded tree item
+pub fn on_expand<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::ExpandEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_activate event of a
+/// menu item, which is clicked or triggered with its accelerator. The value
+/// of the input event is the new checked state of the check and radio menu
+/// items, and the label of the other menu items
+pub fn on_activate<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::ActivateEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_response event of a
+/// dialog, the value of the input event is the label of the clicked button,
+/// or an empty string when the dialog is dismissed
+pub fn on_response<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::ResponseEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_files event of a
+/// file chooser, the value of the input event is the list of the chosen paths,
+/// which is empty when the file chooser is cancelled. In the web backend the
+/// list has the contents of the chosen files instead, since the paths are
+/// not exposed by the browser
+pub fn on_files<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::FilesEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the on_collapse event,
+/// the value of the input event is the id of the collapsed tree item
+pub fn on_collapse<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(InputEvent) -> MSG + 'static,
+{
+    on(AttribKey::CollapseEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => func(input),
+        _ => unreachable!(),
+    })
+}
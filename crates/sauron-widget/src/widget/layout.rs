@@ -14,7 +14,7 @@ pub fn compute_node_layout<MSG>(
     parent_size: Size<Number>,
 ) {
     let mut stretch = Stretch::new();
-    let stretch_node = build_stretch_node_recursive(&mut stretch, &widget_node)
+    let stretch_node = build_stretch_node_recursive(&mut stretch, widget_node)
         .expect("must have built a style node");
     stretch
         .compute_layout(stretch_node, parent_size)
@@ -0,0 +1,457 @@
+//! a serializable form of the node tree, where the callbacks are replaced by
+//! handler ids, so the tree can be sent to a renderer in another process.
+use crate::{
+    AttribKey, Attribute, Callback, Event, Node, Patch, Value, Widget,
+};
+use mt_dom::patch::{
+    AddAttributes, AppendChildren, ChangeText, InsertNode, RemoveAttributes,
+    RemoveNode, ReplaceNode,
+};
+use mt_dom::{attr, element, on};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// identifies an event callback in the node tree
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HandlerId {
+    /// the id of the handler.
+    /// `RemoteNode::from_node` numbers the handlers in the order they are
+    /// found in a depth first traversal of the tree, while the remote backend
+    /// gives each handler a new id which is never reused.
+    pub id: usize,
+    /// the event key of the callback
+    pub event: AttribKey,
+}
+
+/// a serializable attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RemoteAttribute {
+    /// the plain values of the attribute
+    Value(AttribKey, Vec<Value>),
+    /// the attribute has callbacks which are identified by this handler id
+    Handler(HandlerId),
+}
+
+/// a serializable node tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RemoteNode {
+    /// a widget element
+    Element {
+        /// the widget of this element
+        widget: Widget,
+        /// the attributes of this element
+        attrs: Vec<RemoteAttribute>,
+        /// the child nodes of this element
+        children: Vec<RemoteNode>,
+    },
+    /// a text node
+    Text(String),
+}
+
+/// a serializable patch, the node_idx refers to the node in the old tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RemotePatch {
+    /// add or replace the attributes of the node
+    AddAttributes(usize, Vec<RemoteAttribute>),
+    /// remove the attributes with these keys from the node
+    RemoveAttributes(usize, Vec<AttribKey>),
+    /// append the nodes to the children of the node
+    AppendChildren(usize, Vec<RemoteNode>),
+    /// insert the node before the node
+    InsertNode(usize, RemoteNode),
+    /// remove the node
+    RemoveNode(usize),
+    /// replace the node
+    ReplaceNode(usize, RemoteNode),
+    /// change the text of the text node
+    ChangeText(usize, String),
+}
+
+impl RemoteNode {
+    /// convert the node tree into a serializable node tree
+    pub fn from_node<MSG: 'static>(node: &Node<MSG>) -> Self {
+        let mut next_id = 0;
+        Self::from_node_with(node, &mut |att| {
+            let id = next_id;
+            next_id += 1;
+            HandlerId {
+                id,
+                event: att.name().clone(),
+            }
+        })
+    }
+
+    /// convert the node tree into a serializable node tree, the handler id
+    /// of each attribute with callbacks is created by the `handler_id`
+    /// function
+    pub fn from_node_with<MSG, F>(node: &Node<MSG>, handler_id: &mut F) -> Self
+    where
+        MSG: 'static,
+        F: FnMut(&Attribute<MSG>) -> HandlerId,
+    {
+        match node {
+            Node::Element(element) => {
+                let attrs = to_remote_attributes(
+                    element.get_attributes().iter(),
+                    handler_id,
+                );
+                let children = element
+                    .get_children()
+                    .iter()
+                    .map(|child| Self::from_node_with(child, handler_id))
+                    .collect();
+                RemoteNode::Element {
+                    widget: element.tag().clone(),
+                    attrs,
+                    children,
+                }
+            }
+            Node::Text(txt) => RemoteNode::Text(txt.text.to_string()),
+        }
+    }
+
+    /// convert back into a node tree, the handlers are turned into callbacks
+    /// which emit the msg created by the `handler` function
+    pub fn into_node<MSG, F>(self, handler: F) -> Node<MSG>
+    where
+        MSG: 'static,
+        F: Fn(HandlerId, Event) -> MSG + Clone + 'static,
+    {
+        match self {
+            RemoteNode::Element {
+                widget,
+                attrs,
+                children,
+            } => {
+                let mut node_attrs = vec![];
+                for att in attrs {
+                    match att {
+                        RemoteAttribute::Value(key, values) => node_attrs
+                            .extend(
+                                values
+                                    .into_iter()
+                                    .map(|value| attr(key.clone(), value)),
+                            ),
+                        RemoteAttribute::Handler(handler_id) => {
+                            let handler = handler.clone();
+                            let event_key = handler_id.event.clone();
+                            node_attrs.push(on(
+                                event_key,
+                                Callback::from(move |event: Event| {
+                                    handler(handler_id.clone(), event)
+                                }),
+                            ));
+                        }
+                    }
+                }
+                let children = children
+                    .into_iter()
+                    .map(|child| child.into_node(handler.clone()))
+                    .collect();
+                element(widget, node_attrs, children)
+            }
+            RemoteNode::Text(txt) => mt_dom::text(txt),
+        }
+    }
+
+    /// returns the handler ids in this node tree
+    pub fn handler_ids(&self) -> Vec<&HandlerId> {
+        match self {
+            RemoteNode::Element {
+                attrs, children, ..
+            } => attrs
+                .iter()
+                .filter_map(|att| match att {
+                    RemoteAttribute::Handler(handler_id) => Some(handler_id),
+                    RemoteAttribute::Value(_, _) => None,
+                })
+                .chain(children.iter().flat_map(|child| child.handler_ids()))
+                .collect(),
+            RemoteNode::Text(_) => vec![],
+        }
+    }
+
+    /// apply the patches to this node tree.
+    ///
+    /// The node_idx of the patches refers to the old tree, so the path of each
+    /// node is collected first, then the patches are applied from the last
+    /// node_idx to the first, such that inserting or removing a node will not
+    /// shift the path of the nodes which are yet to be patched.
+    pub fn apply_patches(&mut self, patches: &[RemotePatch]) {
+        let mut paths = HashMap::new();
+        self.find_paths(&mut vec![], &mut 0, &mut paths);
+
+        let mut ordered_patches: Vec<(usize, &RemotePatch)> =
+            patches.iter().enumerate().collect();
+        ordered_patches.sort_by_key(|(order, patch)| patch.sort_key(*order));
+
+        for (_, patch) in ordered_patches {
+            let path = paths
+                .get(&patch.node_idx())
+                .expect("must have a node to patch");
+            match patch {
+                RemotePatch::AddAttributes(_, attrs) => {
+                    let (node_attrs, _) = self.find_node_mut(path).parts_mut();
+                    for att in attrs {
+                        // the changed attribute is kept in its position
+                        match node_attrs.iter_mut().find(|existing| {
+                            existing.name() == att.name()
+                                && existing.is_handler() == att.is_handler()
+                        }) {
+                            Some(existing) => *existing = att.clone(),
+                            None => node_attrs.push(att.clone()),
+                        }
+                    }
+                }
+                RemotePatch::RemoveAttributes(_, keys) => {
+                    let (node_attrs, _) = self.find_node_mut(path).parts_mut();
+                    node_attrs
+                        .retain(|existing| !keys.contains(existing.name()));
+                }
+                RemotePatch::AppendChildren(_, new_children) => {
+                    let (_, children) = self.find_node_mut(path).parts_mut();
+                    children.extend(new_children.iter().cloned());
+                }
+                RemotePatch::InsertNode(_, node) => {
+                    let (parent_path, child_pos) = split_path(path);
+                    let (_, children) =
+                        self.find_node_mut(parent_path).parts_mut();
+                    children.insert(child_pos, node.clone());
+                }
+                RemotePatch::RemoveNode(_) => {
+                    let (parent_path, child_pos) = split_path(path);
+                    let (_, children) =
+                        self.find_node_mut(parent_path).parts_mut();
+                    children.remove(child_pos);
+                }
+                RemotePatch::ReplaceNode(_, replacement) => {
+                    *self.find_node_mut(path) = replacement.clone();
+                }
+                RemotePatch::ChangeText(_, txt) => {
+                    *self.find_node_mut(path) = RemoteNode::Text(txt.clone());
+                }
+            }
+        }
+    }
+
+    /// returns the attributes and children of this element
+    fn parts_mut(
+        &mut self,
+    ) -> (&mut Vec<RemoteAttribute>, &mut Vec<RemoteNode>) {
+        match self {
+            RemoteNode::Element {
+                attrs, children, ..
+            } => (attrs, children),
+            RemoteNode::Text(_) => panic!("text node can not be patched"),
+        }
+    }
+
+    /// collect the path of each node in the tree, the path is the position of
+    /// the node in each of its ancestor's children
+    fn find_paths(
+        &self,
+        cur_path: &mut Vec<usize>,
+        cur_node_idx: &mut usize,
+        paths: &mut HashMap<usize, Vec<usize>>,
+    ) {
+        paths.insert(*cur_node_idx, cur_path.clone());
+        if let RemoteNode::Element { children, .. } = self {
+            for (pos, child) in children.iter().enumerate() {
+                *cur_node_idx += 1;
+                cur_path.push(pos);
+                child.find_paths(cur_path, cur_node_idx, paths);
+                cur_path.pop();
+            }
+        }
+    }
+
+    /// find the node at this path
+    fn find_node_mut(&mut self, path: &[usize]) -> &mut RemoteNode {
+        path.iter().fold(self, |node, pos| {
+            node.parts_mut()
+                .1
+                .get_mut(*pos)
+                .expect("must have a child at this position")
+        })
+    }
+}
+
+impl RemoteAttribute {
+    /// the attribute key of this attribute
+    pub fn name(&self) -> &AttribKey {
+        match self {
+            RemoteAttribute::Value(key, _) => key,
+            RemoteAttribute::Handler(handler_id) => &handler_id.event,
+        }
+    }
+
+    /// whether this attribute is a handler of the callbacks
+    pub fn is_handler(&self) -> bool {
+        matches!(self, RemoteAttribute::Handler(_))
+    }
+}
+
+impl RemotePatch {
+    /// convert the patch into a serializable patch, the handler id of each
+    /// attribute with callbacks is created by the `handler_id` function
+    pub fn from_patch<MSG, F>(patch: &Patch<MSG>, handler_id: &mut F) -> Self
+    where
+        MSG: 'static,
+        F: FnMut(&Attribute<MSG>) -> HandlerId,
+    {
+        match patch {
+            Patch::AddAttributes(AddAttributes {
+                node_idx, attrs, ..
+            }) => RemotePatch::AddAttributes(
+                *node_idx,
+                to_remote_attributes(attrs.iter().copied(), handler_id),
+            ),
+            Patch::RemoveAttributes(RemoveAttributes {
+                node_idx,
+                attrs,
+                ..
+            }) => RemotePatch::RemoveAttributes(
+                *node_idx,
+                attrs.iter().map(|att| att.name().clone()).collect(),
+            ),
+            Patch::AppendChildren(AppendChildren {
+                node_idx,
+                children,
+                ..
+            }) => RemotePatch::AppendChildren(
+                *node_idx,
+                children
+                    .iter()
+                    .map(|(_, child)| {
+                        RemoteNode::from_node_with(*child, handler_id)
+                    })
+                    .collect(),
+            ),
+            Patch::InsertNode(InsertNode { node_idx, node, .. }) => {
+                RemotePatch::InsertNode(
+                    *node_idx,
+                    RemoteNode::from_node_with(node, handler_id),
+                )
+            }
+            Patch::RemoveNode(RemoveNode { node_idx, .. }) => {
+                RemotePatch::RemoveNode(*node_idx)
+            }
+            Patch::ReplaceNode(ReplaceNode {
+                node_idx,
+                replacement,
+                ..
+            }) => RemotePatch::ReplaceNode(
+                *node_idx,
+                RemoteNode::from_node_with(replacement, handler_id),
+            ),
+            Patch::ChangeText(ChangeText { node_idx, new, .. }) => {
+                RemotePatch::ChangeText(*node_idx, new.text.to_string())
+            }
+        }
+    }
+
+    /// the node_idx in the old tree which this patch is applied to
+    pub fn node_idx(&self) -> usize {
+        match self {
+            RemotePatch::AddAttributes(node_idx, _)
+            | RemotePatch::RemoveAttributes(node_idx, _)
+            | RemotePatch::AppendChildren(node_idx, _)
+            | RemotePatch::InsertNode(node_idx, _)
+            | RemotePatch::RemoveNode(node_idx)
+            | RemotePatch::ReplaceNode(node_idx, _)
+            | RemotePatch::ChangeText(node_idx, _) => *node_idx,
+        }
+    }
+
+    /// patches are sorted by descending node_idx, patches on the same node
+    /// are ordered such that the attributes and children are set first
+    /// before the node is removed or replaced, then the nodes inserted before
+    /// it are inserted in reverse order so they end up in their original order.
+    fn sort_key(&self, order: usize) -> (std::cmp::Reverse<usize>, u8, isize) {
+        let node_idx = std::cmp::Reverse(self.node_idx());
+        match self {
+            RemotePatch::AddAttributes(..)
+            | RemotePatch::RemoveAttributes(..)
+            | RemotePatch::ChangeText(..) => (node_idx, 0, order as isize),
+            RemotePatch::AppendChildren(..) => (node_idx, 1, order as isize),
+            RemotePatch::RemoveNode(..) | RemotePatch::ReplaceNode(..) => {
+                (node_idx, 2, order as isize)
+            }
+            RemotePatch::InsertNode(..) => (node_idx, 3, -(order as isize)),
+        }
+    }
+}
+
+/// convert the attributes into serializable attributes
+fn to_remote_attributes<'a, MSG: 'static, F>(
+    attrs: impl Iterator<Item = &'a Attribute<MSG>>,
+    handler_id: &mut F,
+) -> Vec<RemoteAttribute>
+where
+    F: FnMut(&Attribute<MSG>) -> HandlerId,
+{
+    let mut remote_attrs = vec![];
+    for att in attrs {
+        let values: Vec<Value> = att.get_plain().into_iter().cloned().collect();
+        if !values.is_empty() {
+            remote_attrs
+                .push(RemoteAttribute::Value(att.name().clone(), values));
+        }
+        if !att.get_callback().is_empty() {
+            remote_attrs.push(RemoteAttribute::Handler(handler_id(att)));
+        }
+    }
+    remote_attrs
+}
+
+/// split the path into the path of the parent and the position in the parent
+fn split_path(path: &[usize]) -> (&[usize], usize) {
+    path.split_last()
+        .map(|(pos, parent)| (parent, *pos))
+        .expect("must not insert or remove the root node")
+}
+
+/// emit the callbacks identified by the handler id in the node tree,
+/// returning the resulting msgs.
+/// The handler id must be created with `RemoteNode::from_node` of the same
+/// node tree
+pub fn emit_handler<MSG>(
+    node: &Node<MSG>,
+    handler_id: &HandlerId,
+    event: Event,
+) -> Vec<MSG>
+where
+    MSG: 'static,
+{
+    find_handler(node, handler_id.id, &mut 0)
+        .filter(|att| *att.name() == handler_id.event)
+        .map(|att| {
+            att.get_callback()
+                .into_iter()
+                .map(|cb| cb.emit(event.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// find the attribute with callbacks which is numbered with this id,
+/// counting in the same order as `RemoteNode::from_node`
+fn find_handler<'a, MSG: 'static>(
+    node: &'a Node<MSG>,
+    id: usize,
+    cur_id: &mut usize,
+) -> Option<&'a Attribute<MSG>> {
+    let element = node.as_element_ref()?;
+    for att in element.get_attributes() {
+        if !att.get_callback().is_empty() {
+            if *cur_id == id {
+                return Some(att);
+            }
+            *cur_id += 1;
+        }
+    }
+    element
+        .get_children()
+        .iter()
+        .find_map(|child| find_handler(child, id, cur_id))
+}
@@ -0,0 +1,205 @@
+//! snapshot testing of widget trees.
+//!
+//! The node tree is serialized into a stable text which contains the widget
+//! tags, the sorted attributes, the computed layout and the callbacks of each
+//! widget.
+//! The text is then compared with a committed `.snap` file.
+use crate::widget::layout::compute_node_layout;
+use crate::{Attribute, Node, Value, Widget};
+use expanse::geometry::Size;
+use expanse::number::Number;
+use std::fmt::Write;
+use std::{fs, path::Path};
+
+/// when this environment variable is set, the snapshot files are overwritten
+/// with the current snapshot instead of being compared
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// serialize the node tree into a stable text,
+/// the layout of the nodes are computed using this size of the parent
+pub fn to_snapshot<MSG: 'static>(
+    mut node: Node<MSG>,
+    parent_size: Size<f32>,
+) -> String {
+    compute_node_layout(
+        &mut node,
+        Size {
+            width: Number::Defined(parent_size.width),
+            height: Number::Defined(parent_size.height),
+        },
+    );
+    let mut buffer = String::new();
+    write_node(&mut buffer, &node, 0).expect("must write to string");
+    buffer
+}
+
+/// compare the snapshot of the node tree with the content of the snap file,
+/// panics with a readable diff when they differ.
+///
+/// The snap file is only created or overwritten when `UPDATE_SNAPSHOTS`
+/// environment variable is set, a missing snap file is a failure otherwise.
+pub fn assert_snapshot<MSG: 'static, P: AsRef<Path>>(
+    snap_file: P,
+    node: Node<MSG>,
+    parent_size: Size<f32>,
+) {
+    let snap_file = snap_file.as_ref();
+    let current = to_snapshot(node, parent_size);
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_some() {
+        if let Some(parent) = snap_file.parent() {
+            fs::create_dir_all(parent).expect("must create the snap directory");
+        }
+        fs::write(snap_file, &current).expect("must write the snap file");
+        return;
+    }
+    if !snap_file.exists() {
+        panic!(
+            "snapshot {} is missing, run with {}=1 to create it",
+            snap_file.display(),
+            UPDATE_SNAPSHOTS
+        );
+    }
+    let expected =
+        fs::read_to_string(snap_file).expect("must read the snap file");
+    if expected != current {
+        eprintln!(
+            "snapshot {} does not match (- expected, + current):\n{}",
+            snap_file.display(),
+            line_diff(&expected, &current)
+        );
+        panic!(
+            "snapshot {} does not match, rerun with {}=1 to update it",
+            snap_file.display(),
+            UPDATE_SNAPSHOTS
+        );
+    }
+}
+
+fn write_node<MSG: 'static>(
+    buffer: &mut String,
+    node: &Node<MSG>,
+    depth: usize,
+) -> std::fmt::Result {
+    let indent = "  ".repeat(depth);
+    match node {
+        Node::Element(element) => {
+            writeln!(buffer, "{}{}", indent, widget_name(element.tag()))?;
+            let mut attrs: Vec<&Attribute<MSG>> =
+                element.get_attributes().iter().collect();
+            attrs.sort_by(|a, b| a.name().cmp(b.name()));
+            for att in attrs {
+                write_attribute(buffer, att, &indent)?;
+            }
+            for child in element.get_children() {
+                write_node(buffer, child, depth + 1)?;
+            }
+            Ok(())
+        }
+        Node::Text(txt) => writeln!(buffer, "{}{:?}", indent, txt.text),
+    }
+}
+
+fn write_attribute<MSG: 'static>(
+    buffer: &mut String,
+    att: &Attribute<MSG>,
+    indent: &str,
+) -> std::fmt::Result {
+    let values: Vec<String> =
+        att.get_plain().into_iter().map(value_to_string).collect();
+    if !values.is_empty() {
+        writeln!(
+            buffer,
+            "{}  - {:?}: {}",
+            indent,
+            att.name(),
+            values.join(", ")
+        )?;
+    }
+    let callbacks = att.get_callback().len();
+    if callbacks > 0 {
+        writeln!(
+            buffer,
+            "{}  - {:?}: {} callback(s)",
+            indent,
+            att.name(),
+            callbacks
+        )?;
+    }
+    Ok(())
+}
+
+fn widget_name(widget: &Widget) -> String {
+    match widget {
+        Widget::Custom(custom) => format!("Custom({})", custom.name()),
+        _ => format!("{:?}", widget),
+    }
+}
+
+/// the bytes are summarized with their length and checksum
+/// to keep the snapshot readable
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(v) => format!("{:?}", v),
+        Value::Str(v) => format!("{:?}", v),
+        Value::Bool(v) => v.to_string(),
+        Value::F64(v) => v.to_string(),
+        Value::Bytes(v) => format!("<{} bytes, fnv:{:016x}>", v.len(), fnv(v)),
+        Value::Layout(layout) => format!(
+            "x={} y={} width={} height={}",
+            layout.location.x,
+            layout.location.y,
+            layout.size.width,
+            layout.size.height
+        ),
+        Value::Style(v) => format!("{:?}", v),
+        Value::PositionType(v) => format!("{:?}", v),
+        Value::Draw(v) => format!("<{} draw commands>", v.len()),
+        Value::List(v) => format!(
+            "[{}]",
+            v.iter().map(value_to_string).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// FNV-1a hash, this doesn't change between rust versions unlike the
+/// std DefaultHasher
+fn fnv(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// a line based diff of the 2 texts using the longest common subsequence
+fn line_diff(expected: &str, current: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = current.lines().collect();
+    // lcs[i][j] is the length of the lcs of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len()
+            && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j])
+        {
+            diff.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        } else {
+            diff.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        }
+    }
+    diff
+}
@@ -1,6 +1,6 @@
 use sauron_native::{
     widget::{attribute::*, event::*, *},
-    Component, Node,
+    Cmd, Component, Node,
 };
 
 pub struct App {
@@ -56,6 +56,12 @@ pub enum Msg {
     Nothing,
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl App {
     pub fn new() -> App {
         App {
@@ -67,7 +73,7 @@ impl App {
 }
 
 impl Component<Msg> for App {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
         match msg {
             Msg::Click => {
                 self.click_count += 1;
@@ -86,6 +92,7 @@ impl Component<Msg> for App {
             }
             Msg::Nothing => {}
         }
+        Cmd::none()
     }
 
     fn view(&self) -> Node<Msg> {
@@ -3,13 +3,21 @@
 use sauron_native::backend::gtk_ui::GtkBackend;
 #[cfg(feature = "with-nwg")]
 use sauron_native::backend::nwg_ui::NwgBackend;
-#[cfg(feature = "with-tui")]
-use sauron_native::backend::text_ui::TuiBackend;
 #[cfg(feature = "with-titik")]
 use sauron_native::backend::titik_ui::TitikBackend;
+#[cfg(any(
+    feature = "with-titik",
+    feature = "with-gtk",
+    feature = "with-nwg"
+))]
 use sauron_native::Backend;
 
 pub mod app;
+#[cfg(any(
+    feature = "with-titik",
+    feature = "with-gtk",
+    feature = "with-nwg"
+))]
 use app::App;
 
 fn main() {
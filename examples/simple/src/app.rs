@@ -1,6 +1,6 @@
 use sauron_native::{
     widget::{attribute::*, event::*, *},
-    Component, Node,
+    Cmd, Component, Node, Shortcut,
 };
 
 pub struct App {
@@ -28,7 +28,7 @@ impl App {
 }
 
 impl Component<Msg> for App {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
         match msg {
             Msg::Click => {
                 self.click_count += 1;
@@ -43,6 +43,14 @@ impl Component<Msg> for App {
                 self.paragraph_text = txt;
             }
         }
+        Cmd::none()
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut<Msg>> {
+        vec![
+            Shortcut::new(Modifier::ctrl(), KeyCode::Up, Msg::Click),
+            Shortcut::new(Modifier::ctrl(), KeyCode::Down, Msg::Decrement),
+        ]
     }
 
     fn view(&self) -> Node<Msg> {
@@ -53,36 +61,32 @@ impl Component<Msg> for App {
                     vec![],
                     vec![
                         menu_item(
-                            vec![],
-                            vec![
-                                text_label(vec![value("File")]),
-                                menu(
-                                    vec![],
-                                    vec![
-                                        menu_item(
-                                            vec![],
-                                            vec![text_label(vec![value(
-                                                "Open",
-                                            )])],
-                                        ),
-                                        menu_item(
-                                            vec![],
-                                            vec![text_label(vec![value(
-                                                "Close",
-                                            )])],
-                                        ),
-                                    ],
-                                ),
-                            ],
-                        ),
-                        menu_item(
-                            vec![],
-                            vec![text_label(vec![value("About")])],
-                        ),
-                        menu_item(
-                            vec![],
-                            vec![text_label(vec![value("Quit")])],
+                            vec![label("File")],
+                            vec![menu(
+                                vec![],
+                                vec![
+                                    menu_item(
+                                        vec![
+                                            label("Increment"),
+                                            accelerator("Ctrl+Up"),
+                                            on_activate(|_| Msg::Click),
+                                        ],
+                                        vec![],
+                                    ),
+                                    menu_item(
+                                        vec![
+                                            label("Decrement"),
+                                            accelerator("Ctrl+Down"),
+                                            on_activate(|_| Msg::Decrement),
+                                        ],
+                                        vec![],
+                                    ),
+                                    menu_separator(vec![]),
+                                    menu_item(vec![label("Close")], vec![]),
+                                ],
+                            )],
                         ),
+                        menu_item(vec![label("About")], vec![]),
                     ],
                 ),
                 header_bar(
@@ -3,6 +3,7 @@ use sauron_native::widget::event::*;
 use sauron_native::widget::*;
 use sauron_native::*;
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Serialize, Deserialize)]
 pub struct Model {
@@ -43,7 +44,7 @@ pub enum Msg {
 }
 
 impl Component<Msg> for Model {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
         match msg {
             Msg::Add => {
                 self.entries.push(Entry::new(&self.value, self.uid));
@@ -80,6 +81,7 @@ impl Component<Msg> for Model {
                         entry.editing = !entry.editing;
                     }
                 });
+                return Cmd::focus(format!("edit-{}", id));
             }
             Msg::ToggleAll => {
                 let is_all_completed = !self.is_all_completed();
@@ -104,6 +106,7 @@ impl Component<Msg> for Model {
             }
             Msg::NoOp => {}
         }
+        Cmd::none()
     }
 
     fn view(&self) -> Node<Msg> {
@@ -135,6 +138,12 @@ impl Entry {
     }
 }
 
+impl Default for Model {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Model {
     pub fn new() -> Self {
         Model {
@@ -196,10 +205,11 @@ impl Model {
                 text_label(vec![value("todos")]),
                 text_input(vec![
                     name("new-todo"),
+                    autofocus(true),
                     placeholder("What needs to be done?"),
                     value(self.value.to_string()),
                     on_input(|v: InputEvent| Msg::Update(v.value.to_string())),
-                    on_enter(|event: KeyEvent| Msg::Add),
+                    on_enter(|_event: KeyEvent| Msg::Add),
                 ]),
             ],
         )
@@ -230,7 +240,7 @@ impl Model {
                         text_label(vec![
                             on_doubleclick(move |_| Msg::ToggleEdit(entry_id)),
                             for_(entry_id.to_string()),
-                            value(format!("{}", entry.description)),
+                            value(entry.description.to_string()),
                         ]),
                         button(vec![
                             name("destroy"),
@@ -241,6 +251,7 @@ impl Model {
                 ),
                 text_input(vec![
                     name("edit"),
+                    id(format!("edit-{}", entry_id)),
                     value(&entry.description),
                     on_input(move |input: InputEvent| {
                         Msg::UpdateEntry(entry_id, input.value.to_string())
@@ -322,12 +333,12 @@ impl Model {
     }
 }
 
-impl ToString for Visibility {
-    fn to_string(&self) -> String {
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Visibility::All => "All".to_string(),
-            Visibility::Active => "Active".to_string(),
-            Visibility::Completed => "Completed".to_string(),
+            Visibility::All => write!(f, "All"),
+            Visibility::Active => write!(f, "Active"),
+            Visibility::Completed => write!(f, "Completed"),
         }
     }
 }
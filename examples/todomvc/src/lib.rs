@@ -1,6 +1,7 @@
 //#![deny(warnings)]
 #[cfg(feature = "with-web")]
 use sauron_native::backend::HtmlApp;
+#[cfg(feature = "with-web")]
 use sauron_native::Backend;
 #[cfg(feature = "with-web")]
 use wasm_bindgen::prelude::*;
@@ -0,0 +1,40 @@
+//! snapshot of the todomvc view, rerun with `UPDATE_SNAPSHOTS=1` to update
+//! the snap files when the view is changed intentionally
+use expanse::geometry::Size;
+use sauron_native::widget::snapshot::assert_snapshot;
+use sauron_native::Component;
+use todomvc::app::{Model, Msg};
+
+const WINDOW_SIZE: Size<f32> = Size {
+    width: 800.0,
+    height: 600.0,
+};
+
+fn snap_file(name: &str) -> String {
+    format!(
+        "{}/tests/snapshots/{}.snap",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+#[test]
+fn empty_todos() {
+    let model = Model::new();
+    assert_snapshot(snap_file("empty_todos"), model.view(), WINDOW_SIZE);
+}
+
+#[test]
+fn todos_with_a_completed_entry() {
+    let mut model = Model::new();
+    for description in &["buy milk", "walk the dog"] {
+        model.update(Msg::Update(description.to_string()));
+        model.update(Msg::Add);
+    }
+    model.update(Msg::Toggle(0));
+    assert_snapshot(
+        snap_file("todos_with_a_completed_entry"),
+        model.view(),
+        WINDOW_SIZE,
+    );
+}
@@ -2,7 +2,29 @@
 //!
 use crate::Component;
 
-#[cfg(feature = "with-web")]
+pub mod event_router;
+pub mod headless_ui;
+pub use headless_ui::HeadlessBackend;
+#[cfg(feature = "with-static-html")]
+pub mod static_html;
+#[cfg(feature = "with-static-html")]
+pub use static_html::{
+    render_to_html_page, render_to_html_string, render_to_html_string_with_size,
+};
+
+#[cfg(any(
+    feature = "with-gtk",
+    feature = "with-titik",
+    feature = "with-static-html"
+))]
+pub(crate) mod registry;
+
+#[cfg(feature = "with-remote")]
+pub mod remote_ui;
+#[cfg(feature = "with-remote")]
+pub use remote_ui::{RemoteBackend, RemoteRenderer};
+
+#[cfg(feature = "with-static-html")]
 pub mod web_ui;
 #[cfg(feature = "with-web")]
 pub use web_ui::HtmlApp;
@@ -40,4 +62,8 @@ where
 pub trait Dispatch<MSG> {
     /// dispatch the msg which will subsequently change the application state
     fn dispatch(&self, msg: MSG);
+
+    /// focus the widget with this id, or the first focusable widget inside
+    /// it. The backends which can not focus a widget ignore this.
+    fn focus(&self, _id: &str) {}
 }
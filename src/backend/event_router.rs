@@ -0,0 +1,106 @@
+//! routes the events emitted by the native controls into the callbacks of the
+//! widget the control is created from.
+//!
+//! This doesn't depend on any native toolkit, the backend only needs to convert
+//! its own events into `NativeEvent` and identify the control with an id.
+use crate::widget::attribute::find_callback;
+use crate::widget::event::{InputEvent, MouseEvent};
+use crate::{AttribKey, Attribute, Event};
+use std::{collections::HashMap, hash::Hash};
+
+/// The events of the native controls which are propagated into the app
+#[derive(Debug, Clone, PartialEq)]
+pub enum NativeEvent {
+    /// a button is clicked
+    Click,
+    /// the text of a text input or text area is changed
+    TextChanged(String),
+    /// a checkbox or radio button is toggled to this checked state
+    Toggled(bool),
+}
+
+impl NativeEvent {
+    /// convert into the event and the attribute key of the callbacks
+    /// which will be triggered by this event
+    pub fn to_event(&self) -> (AttribKey, Event) {
+        match self {
+            NativeEvent::Click => {
+                (AttribKey::ClickEvent, Event::from(MouseEvent::click(0, 0)))
+            }
+            NativeEvent::TextChanged(text) => (
+                AttribKey::InputEvent,
+                Event::from(InputEvent::new(text.to_string())),
+            ),
+            NativeEvent::Toggled(checked) => (
+                AttribKey::InputEvent,
+                Event::from(InputEvent::new(*checked)),
+            ),
+        }
+    }
+}
+
+/// keeps the attributes of each of the native controls which has event
+/// callbacks, keyed by the id of the control
+pub struct EventRouter<ID, MSG> {
+    callbacks: HashMap<ID, Vec<Attribute<MSG>>>,
+}
+
+impl<ID, MSG> EventRouter<ID, MSG>
+where
+    ID: Hash + Eq,
+    MSG: 'static,
+{
+    /// creates an empty event router
+    pub fn new() -> Self {
+        EventRouter {
+            callbacks: HashMap::new(),
+        }
+    }
+
+    /// register the event callbacks in the attributes of the widget which
+    /// the control with this id is created from
+    pub fn register(&mut self, id: ID, attrs: &[Attribute<MSG>]) {
+        let callbacks: Vec<Attribute<MSG>> = attrs
+            .iter()
+            .filter(|att| !att.get_callback().is_empty())
+            .cloned()
+            .collect();
+        if !callbacks.is_empty() {
+            self.callbacks.insert(id, callbacks);
+        }
+    }
+
+    /// remove all the registered callbacks,
+    /// this is called when the controls are recreated
+    pub fn clear(&mut self) {
+        self.callbacks.clear();
+    }
+
+    /// returns true if the control with this id has registered callbacks
+    pub fn is_registered(&self, id: &ID) -> bool {
+        self.callbacks.contains_key(id)
+    }
+
+    /// emit the callbacks of the control with this id which are triggered
+    /// by the native event, returning the resulting msgs
+    pub fn route(&self, id: &ID, native_event: &NativeEvent) -> Vec<MSG> {
+        let (event_key, event) = native_event.to_event();
+        self.callbacks
+            .get(id)
+            .and_then(|attrs| find_callback(event_key, attrs))
+            .map(|callbacks| {
+                callbacks.iter().map(|cb| cb.emit(event.clone())).collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<ID, MSG> Default for EventRouter<ID, MSG>
+where
+    ID: Hash + Eq,
+    MSG: 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
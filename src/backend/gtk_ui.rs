@@ -1,5 +1,7 @@
 //! gtk backend
 use super::Dispatch;
+use crate::shortcut::find_shortcut;
+use crate::sub::{emit_sub, Subscriptions};
 use crate::widget::layout::compute_node_layout;
 use crate::{AttribKey, Backend, Component, Node};
 use expanse::geometry::Size;
@@ -7,17 +9,37 @@ use expanse::number::Number;
 use gio::{prelude::*, ApplicationFlags};
 pub use gtk;
 use gtk::{
-    prelude::*, Application, ApplicationWindow, Button, CheckButton, Container,
-    Entry, EventBox, Frame, HeaderBar, Image, LinkButton, Menu, MenuBar,
-    MenuItem, Overlay, Paned, RadioButton, ScrolledWindow, SearchEntry,
+    prelude::*, Application, ApplicationWindow, Button, CheckButton,
+    ComboBoxText, Entry, EventBox, Frame, HeaderBar, Image, Label, LinkButton,
+    ListBox, Menu, MenuBar, MenuItem, Notebook, Overlay, Paned, ProgressBar,
+    RadioButton, Scale, ScrolledWindow, SearchEntry, SpinButton, Spinner,
     TextView, WidgetExt,
 };
-use log::*;
-use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    marker::PhantomData,
+    rc::Rc,
+};
 
 mod apply_patches;
+mod canvas;
+mod convert_event;
 mod convert_widget;
+mod custom_widget;
+mod dialog;
+mod file_chooser;
+mod focus;
 mod images;
+mod list_view;
+mod menu;
+mod numeric;
+mod progress;
+mod select;
+mod table;
+mod tree_view;
+
+pub use custom_widget::{register_custom_widget, GtkCustomWidget};
 
 /// backend using gtk
 pub struct GtkBackend<APP, MSG>
@@ -26,10 +48,14 @@ where
 {
     app: Rc<RefCell<APP>>,
     current_vdom: Rc<RefCell<Node<MSG>>>,
-    root_node: Rc<RefCell<Option<GtkWidget>>>,
     application: Application,
     application_window: Rc<RefCell<Option<ApplicationWindow>>>,
     window_size: Rc<RefCell<(i32, i32)>>,
+    /// whether the patches are being applied, the handlers of the widgets
+    /// are blocked while the setters emit their signals
+    is_patching: Rc<Cell<bool>>,
+    /// the timers of the subscriptions of the app
+    subscriptions: Rc<RefCell<Subscriptions<MSG, glib::SourceId>>>,
     _phantom_msg: PhantomData<MSG>,
 }
 
@@ -56,6 +82,25 @@ pub(crate) enum GtkWidget {
     Menu(Menu),
     MenuItem(MenuItem),
     SearchInput(SearchEntry),
+    Table(ScrolledWindow),
+    ListView(ScrolledWindow),
+    Tabs(Notebook),
+    /// the page of the tab and the label in the tab strip
+    Tab(gtk::Box, Label),
+    Dropdown(ComboBoxText),
+    ListBox(ListBox),
+    SpinButton(SpinButton),
+    Slider(Scale),
+    ProgressBar(ProgressBar),
+    Spinner(Spinner),
+    TreeView(ScrolledWindow),
+    /// the image of the drawing wrapped in an event box
+    Canvas(EventBox),
+    /// the placeholder which the dialog is attached to
+    Dialog(gtk::Box),
+    /// the placeholder which the file chooser dialog is attached to
+    FileChooser(gtk::Box),
+    Custom(gtk::Widget),
 }
 
 impl<APP, MSG> Clone for GtkBackend<APP, MSG> {
@@ -63,10 +108,11 @@ impl<APP, MSG> Clone for GtkBackend<APP, MSG> {
         GtkBackend {
             app: Rc::clone(&self.app),
             current_vdom: Rc::clone(&self.current_vdom),
-            root_node: Rc::clone(&self.root_node),
             application: self.application.clone(),
             application_window: self.application_window.clone(),
             window_size: self.window_size.clone(),
+            is_patching: Rc::clone(&self.is_patching),
+            subscriptions: Rc::clone(&self.subscriptions),
             _phantom_msg: PhantomData,
         }
     }
@@ -77,7 +123,7 @@ where
     MSG: Debug + Clone + 'static,
     APP: Component<MSG> + 'static,
 {
-    fn new(app: APP) {
+    fn start(app: APP) {
         let app_title = app.title();
 
         let (initial_width, initial_height) = (800, 600);
@@ -95,19 +141,18 @@ where
         )
         .expect("Failed to start app");
 
-        let root_widget: Option<GtkWidget> = None;
         let backend = GtkBackend {
             app: Rc::new(RefCell::new(app)),
             current_vdom: Rc::new(RefCell::new(current_vdom)),
-            root_node: Rc::new(RefCell::new(root_widget)),
             application_window: Rc::new(RefCell::new(None)),
             application,
             window_size: Rc::new(RefCell::new((initial_width, initial_height))),
+            is_patching: Rc::new(Cell::new(false)),
+            subscriptions: Rc::new(RefCell::new(Subscriptions::new())),
             _phantom_msg: PhantomData,
         };
 
         let root_widget = convert_widget::from_node_tree(&backend, &root_vdom);
-        *backend.root_node.borrow_mut() = Some(root_widget);
 
         let backend_clone = backend.clone();
 
@@ -117,16 +162,27 @@ where
             application_window.move_(1000, 500);
             application_window.set_icon_name(Some("applications-graphics"));
             application_window.set_title(&app_title);
-            application_window.add(
-                backend_clone
-                    .root_node
-                    .borrow()
-                    .as_ref()
-                    .expect("must have a root node")
-                    .as_widget()
-                    .expect("must be a widget"),
-            );
-            let backend_clone2 = backend_clone.clone();
+            application_window.add_accel_group(&menu::accel_group());
+            // the shortcuts are handled before the focused widget and the
+            // accelerators of the menu items
+            let backend_clone3 = backend_clone.clone();
+            application_window.connect_key_press_event(move |_win, event| {
+                let msg = convert_event::from_gtk_key_event(event).and_then(
+                    |key_event| {
+                        find_shortcut(&*backend_clone3.app.borrow(), &key_event)
+                    },
+                );
+                match msg {
+                    Some(msg) => {
+                        backend_clone3.dispatch(msg);
+                        Inhibit(true)
+                    }
+                    None => Inhibit(false),
+                }
+            });
+            application_window
+                .add(root_widget.as_widget().expect("must be a widget"));
+            let _backend_clone2 = backend_clone.clone();
             /*
             application_window.connect_size_allocate(move |_win, rect| {
                 println!(
@@ -142,6 +198,10 @@ where
             application_window.show_all();
             *backend_clone.application_window.borrow_mut() =
                 Some(application_window);
+
+            backend_clone.update_subscriptions();
+            let cmd = backend_clone.app.borrow().init();
+            cmd.emit(Rc::new(backend_clone.clone()));
         });
 
         backend.application.run(&[]);
@@ -182,47 +242,51 @@ where
                 height: Number::Defined(adjusted_h),
             },
         );
-        {
-            let current_vdom = self.current_vdom.borrow();
-            let diff = mt_dom::diff_with_key(
-                &current_vdom,
-                &new_view,
-                &AttribKey::Key,
-            );
-            apply_patches::apply_patches(
-                self,
-                &current_vdom,
-                &self.root_container(),
-                &diff,
-            );
-        }
-        *self.current_vdom.borrow_mut() = new_view;
+        // the current vdom is not borrowed while the patches are applied
+        let current_vdom = self.current_vdom.replace(new_view.clone());
+        let diff =
+            mt_dom::diff_with_key(&current_vdom, &new_view, &AttribKey::Key);
+        let window = self
+            .application_window
+            .borrow()
+            .clone()
+            .expect("must have a window");
+        self.is_patching.set(true);
+        apply_patches::apply_patches(
+            self,
+            &current_vdom,
+            window.upcast_ref(),
+            &diff,
+        );
+        self.is_patching.set(false);
     }
 
-    fn root_container(&self) -> Rc<Container> {
-        let root_widget = self.root_node.borrow();
-        if let Some(root_widget) = &*root_widget {
-            match &root_widget {
-                GtkWidget::GBox(gbox) => {
-                    let container: &Container = gbox.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                GtkWidget::Paned(paned) => {
-                    let container: &Container = paned.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                _ => panic!("expecting it to be a container"),
-            }
-        } else {
-            panic!("must have a root widget");
-        }
+    /// diff the subscriptions of the app, the timers of the new subscriptions
+    /// are added and the ones which are removed from the app are stopped
+    fn update_subscriptions(&self) {
+        let subs = self.app.borrow().subscriptions();
+        let backend = self.clone();
+        self.subscriptions.borrow_mut().update(
+            subs,
+            |interval, msg_fn| {
+                let backend = backend.clone();
+                glib::timeout_add_local(
+                    interval.as_millis() as u32,
+                    move || {
+                        backend.dispatch(emit_sub(&msg_fn));
+                        glib::Continue(true)
+                    },
+                )
+            },
+            glib::source_remove,
+        );
     }
 
     // https://shallowsky.com/blog/programming/styling-gtk3-with-css-python.html
     // https://developer.gnome.org/gtk3/stable/chap-css-properties.html
     // https://stackoverflow.com/questions/44401069/how-can-i-trigger-or-force-a-css-transition-in-gtk
     fn setup_css() {
-        let STYLE: &'static str = r#"
+        let style: &'static str = r#"
             #special_label {
                 font-family: monospace;
             }
@@ -247,7 +311,7 @@ where
         "#;
         let provider = gtk::CssProvider::new();
         provider
-            .load_from_data(STYLE.as_bytes())
+            .load_from_data(style.as_bytes())
             .expect("Failed to load CSS");
         gtk::StyleContext::add_provider_for_screen(
             &gdk::Screen::get_default()
@@ -264,7 +328,7 @@ where
     MSG: Clone + Debug + 'static,
 {
     fn init(app: APP) {
-        GtkBackend::new(app);
+        GtkBackend::start(app);
     }
 }
 
@@ -277,8 +341,22 @@ where
     where
         MSG: Debug,
     {
-        self.app.borrow_mut().update(msg);
+        // the msgs emitted while patching are from the signals of the
+        // setters rather than the user, so they are ignored the same as
+        // blocking the handlers of the widgets
+        if self.is_patching.get() {
+            return;
+        }
+        let cmd = self.app.borrow_mut().update(msg);
         self.redraw();
+        self.update_subscriptions();
+        cmd.emit(Rc::new(self.clone()));
+    }
+
+    fn focus(&self, id: &str) {
+        if !focus::focus_by_id(id) {
+            log::warn!("there is no widget with id: {}", id);
+        }
     }
 }
 
@@ -369,6 +447,63 @@ impl GtkWidget {
                 let widget: &gtk::Widget = entry.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::Table(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ListView(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Tabs(notebook) => {
+                let widget: &gtk::Widget = notebook.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Tab(page, _tab_label) => {
+                let widget: &gtk::Widget = page.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Dropdown(combo) => {
+                let widget: &gtk::Widget = combo.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ListBox(list_box) => {
+                let widget: &gtk::Widget = list_box.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::SpinButton(spin) => {
+                let widget: &gtk::Widget = spin.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Slider(scale) => {
+                let widget: &gtk::Widget = scale.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ProgressBar(progress_bar) => {
+                let widget: &gtk::Widget = progress_bar.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Spinner(spinner) => {
+                let widget: &gtk::Widget = spinner.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::TreeView(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Canvas(event_box) => {
+                let widget: &gtk::Widget = event_box.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Dialog(placeholder) => {
+                let widget: &gtk::Widget = placeholder.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::FileChooser(placeholder) => {
+                let widget: &gtk::Widget = placeholder.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Custom(widget) => Some(widget),
         }
     }
 }
@@ -1,32 +1,86 @@
+use super::canvas;
 use super::convert_widget;
+use super::custom_widget;
+use super::dialog;
+use super::file_chooser;
+use super::focus;
+use super::images;
+use super::list_view;
+use super::menu;
+use super::numeric;
+use super::progress;
+use super::select;
+use super::table;
+use super::tree_view;
 use super::Dispatch;
+use super::GtkWidget;
+use crate::image_util;
 use crate::{
-    widget::attribute::util::is_scrollable, AttribKey, Attribute, Node, Patch,
+    widget::attribute::util::{default_value, is_scrollable},
+    AttribKey, Attribute, Node, Patch, Value,
 };
-use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
 use gtk::{
-    prelude::*, Button, Container, ContainerExt, EventBox, Image, Label,
-    MenuItem, Overlay, TextView, Widget,
+    prelude::*, Button, ComboBoxText, Container, ContainerExt, Entry, EventBox,
+    Frame, Image, Label, LinkButton, ListBox, Menu, MenuItem, MenuShell,
+    Notebook, Overlay, Paned, ProgressBar, Scale, ScrolledWindow, SpinButton,
+    Spinner, TextView, ToggleButton, TreeView, Widget,
 };
-use mt_dom::patch::{AddAttributes, AppendChildren, RemoveNode, ReplaceNode};
-use std::{collections::HashMap, fmt::Debug};
+use mt_dom::patch::{
+    AddAttributes, AppendChildren, ChangeText, InsertNode, RemoveAttributes,
+    RemoveNode, ReplaceNode,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
+
+/// the gtk widgets of the node to be patched
+struct NodeHandle {
+    /// the widget which has the attributes of the node,
+    /// this could be wrapped inside the outer widget such as a ScrolledWindow
+    widget: Widget,
+    /// the outermost widget of the node, this is the one added to the parent
+    outer_widget: Widget,
+    /// the container where the children of the node are added
+    children_container: Option<Container>,
+    /// the container of the parent node and the position of this node in it
+    parent: Option<(Container, usize)>,
+    /// the tree view and the path of the tree item in its model,
+    /// the path of the tree view itself is empty
+    tree_item: Option<(TreeView, Vec<i32>)>,
+}
 
+/// apply the patches to the gtk widgets in the window.
+///
+/// The handles of the widgets are collected first from the current vdom,
+/// then the patches are applied from the last node_idx to the first,
+/// such that inserting or removing a widget will not shift the position
+/// of the widgets which are yet to be patched.
 pub fn apply_patches<MSG, DSP>(
     program: &DSP,
     node: &Node<MSG>,
-    root_container: &Container,
-    patches: &Vec<Patch<MSG>>,
+    window: &Container,
+    patches: &[Patch<MSG>],
 ) where
     MSG: Debug + 'static,
     DSP: Clone + Dispatch<MSG> + 'static,
 {
-    let nodes_to_patch = find_nodes(node, root_container, patches);
+    let nodes_to_patch = find_nodes(node, window, patches);
+
+    let mut ordered_patches: Vec<(usize, &Patch<MSG>)> =
+        patches.iter().enumerate().collect();
+    ordered_patches.sort_by_key(|(order, patch)| patch_sort_key(*order, patch));
 
-    for patch in patches {
+    for (_, patch) in ordered_patches {
         let patch_node_idx = patch.node_idx();
-        let widget = nodes_to_patch
+        let handle = nodes_to_patch
             .get(&patch_node_idx)
             .expect("must have a node to patch");
+        if let Some((tree_view, path)) = &handle.tree_item {
+            if tree_view::apply_tree_patch(tree_view, path, patch) {
+                continue;
+            }
+        }
         match patch {
             Patch::AddAttributes(AddAttributes {
                 tag,
@@ -34,62 +88,55 @@ pub fn apply_patches<MSG, DSP>(
                 new_node_idx: _,
                 attrs,
             }) => {
-                set_widget_attributes(tag, widget, attrs);
+                set_widget_id(&handle.outer_widget, attrs);
+                set_widget_attributes(tag, &handle.widget, attrs);
+            }
+            Patch::RemoveAttributes(RemoveAttributes {
+                tag,
+                node_idx: _,
+                new_node_idx: _,
+                attrs,
+            }) => {
+                if attrs.iter().any(|att| *att.name() == AttribKey::Id) {
+                    handle.outer_widget.set_widget_name("");
+                }
+                remove_widget_attributes(tag, &handle.widget, attrs);
             }
             Patch::AppendChildren(AppendChildren {
                 tag,
                 node_idx: _,
                 children,
             }) => {
-                match tag {
-                    crate::Widget::Overlay => {
-                        let overlay = widget
-                            .downcast_ref::<Overlay>()
-                            .expect("must be an overlay");
-                        for (child_idx, child) in children {
-                            if let Some(element) = child.as_element_ref() {
-                                let child =
-                                    convert_widget::from_node(program, element);
-                                let widget = child
-                                    .as_widget()
-                                    .expect("must be a widget");
-                                //Note: overlay have different behavior when adding child widget
-                                overlay.add_overlay(widget);
-                                overlay.set_child_index(widget, -1);
-                                widget.show();
-                                overlay.show_all();
-                            }
-                        }
-                    }
-
-                    _ => {
-                        let container = widget
-                            .downcast_ref::<Container>()
-                            .expect("must be a container");
-                        for (child_idx, child) in children {
-                            if let Some(element) = child.as_element_ref() {
-                                let child = convert_widget::from_node(
-                                    program, &element,
-                                );
-                                let widget = child
-                                    .as_widget()
-                                    .expect("must be a widget");
-                                //Note: overlay have different behavior when adding child widget
-                                container.add(widget);
-                                widget.show();
-                            }
-                        }
-                    }
+                let container = handle
+                    .children_container
+                    .as_ref()
+                    .expect("must be a container");
+                for (_child_idx, child) in children {
+                    let child = convert_widget::from_node_tree(program, child);
+                    let child_pos =
+                        get_widget_children::<MSG>(tag, container).len();
+                    insert_child(container, child_pos, &child);
                 }
             }
+            // the parent of the root widget is the window
+            Patch::InsertNode(InsertNode {
+                tag: _,
+                node_idx: _,
+                new_node_idx: _,
+                node,
+            }) => {
+                let (parent, child_pos) =
+                    handle.parent.as_ref().expect("must have a parent");
+                let child = convert_widget::from_node_tree(program, node);
+                insert_child(parent, *child_pos, &child);
+            }
             Patch::RemoveNode(RemoveNode {
                 tag: _,
                 node_idx: _,
             }) => {
-                let parent = widget.get_parent().expect("must have a parent");
-                if let Some(container) = parent.downcast_ref::<Container>() {
-                    container.remove(widget);
-                }
+                let (parent, _child_pos) =
+                    handle.parent.as_ref().expect("must have a parent");
+                remove_child(parent, &handle.outer_widget);
             }
             Patch::ReplaceNode(ReplaceNode {
                 tag: _,
@@ -97,93 +144,282 @@ pub fn apply_patches<MSG, DSP>(
                 new_node_idx: _,
                 replacement,
             }) => {
-                root_container.remove(widget);
-                if let Some(new_element) = replacement.as_element_ref() {
-                    let new_widget =
-                        convert_widget::from_node(program, new_element);
-                    let new_widget =
-                        new_widget.as_widget().expect("must be a widget");
-                    root_container.add(new_widget);
-                    new_widget.show();
-                }
+                let (parent, child_pos) =
+                    handle.parent.as_ref().expect("must have a parent");
+                let new_widget =
+                    convert_widget::from_node_tree(program, replacement);
+                remove_child(parent, &handle.outer_widget);
+                insert_child(parent, *child_pos, &new_widget);
+            }
+            // the text node is a label inside an event box
+            Patch::ChangeText(ChangeText { new, .. }) => {
+                set_text_label(&handle.outer_widget, &new.text);
             }
-            _ => {
-                println!("container: {:?}", root_container);
-                println!("todo for: {:?}", patch);
+        }
+    }
+}
+
+/// patches are sorted by descending node_idx, patches on the same node
+/// are ordered such that the attributes and children are set first
+/// before the node is removed or replaced, then the nodes inserted before it
+/// are inserted in reverse order so they end up in their original order.
+fn patch_sort_key<MSG>(
+    order: usize,
+    patch: &Patch<MSG>,
+) -> (std::cmp::Reverse<usize>, u8, isize) {
+    let node_idx = std::cmp::Reverse(patch.node_idx());
+    match patch {
+        Patch::AddAttributes(_)
+        | Patch::RemoveAttributes(_)
+        | Patch::ChangeText(_) => (node_idx, 0, order as isize),
+        Patch::AppendChildren(_) => (node_idx, 1, order as isize),
+        Patch::RemoveNode(_) | Patch::ReplaceNode(_) => {
+            (node_idx, 2, order as isize)
+        }
+        Patch::InsertNode(_) => (node_idx, 3, -(order as isize)),
+    }
+}
+
+/// insert the child widget into the container at this position
+fn insert_child(container: &Container, child_pos: usize, child: &GtkWidget) {
+    let widget = child.as_widget().expect("must be a widget");
+    match child {
+        // the sub menu of a menu item is set rather than added
+        GtkWidget::Menu(sub_menu) if container.is::<MenuItem>() => {
+            let menu_item = container
+                .downcast_ref::<MenuItem>()
+                .expect("must be a menu item");
+            menu_item.set_submenu(Some(sub_menu));
+        }
+        // the page of the tab is inserted together with its label
+        GtkWidget::Tab(page, tab_label) if container.is::<Notebook>() => {
+            let notebook = container
+                .downcast_ref::<Notebook>()
+                .expect("must be a notebook");
+            notebook.insert_page(page, Some(tab_label), Some(child_pos as u32));
+        }
+        _ => {
+            if let Some(gbox) = container.downcast_ref::<gtk::Box>() {
+                gbox.add(widget);
+                gbox.reorder_child(widget, child_pos as i32);
+                focus::update_focus_chain(gbox);
+            } else if let Some(menu_shell) =
+                container.downcast_ref::<MenuShell>()
+            {
+                menu_shell.insert(widget, child_pos as i32);
+            } else if let Some(overlay) = container.downcast_ref::<Overlay>() {
+                //Note: overlay have different behavior when adding child widget
+                overlay.add_overlay(widget);
+                overlay.reorder_overlay(widget, child_pos as i32);
+            } else if let Some(paned) = container.downcast_ref::<Paned>() {
+                if child_pos == 0 {
+                    paned.pack1(widget, true, true);
+                } else {
+                    paned.pack2(widget, true, true);
+                }
+            } else {
+                container.add(widget);
             }
         }
     }
+    widget.show_all();
+}
+
+/// set the text of the label of a text node
+fn set_text_label(event_box: &Widget, text: &str) {
+    let event_box = event_box
+        .downcast_ref::<EventBox>()
+        .expect("must be an event box");
+    let event_box_children = event_box.get_children();
+    let label = event_box_children
+        .first()
+        .and_then(|child| child.downcast_ref::<Label>())
+        .expect("must be a label");
+    label.set_text(text);
+}
+
+/// remove the child widget from the container
+fn remove_child(container: &Container, child: &Widget) {
+    if container.is::<MenuItem>() && child.is::<Menu>() {
+        let menu_item = container
+            .downcast_ref::<MenuItem>()
+            .expect("must be a menu item");
+        menu_item.set_submenu(None::<&Menu>);
+    } else {
+        container.remove(child);
+        if let Some(gbox) = container.downcast_ref::<gtk::Box>() {
+            focus::update_focus_chain(gbox);
+        }
+    }
+}
+
+/// the id is the name of the outermost widget, which is used to find the
+/// widget when it is focused
+fn set_widget_id<MSG: 'static>(
+    outer_widget: &Widget,
+    attrs: &[&Attribute<MSG>],
+) {
+    if let Some(id) = attrs
+        .iter()
+        .find(|att| *att.name() == AttribKey::Id)
+        .and_then(|att| att.get_plain().first().copied())
+    {
+        outer_widget.set_widget_name(&id.to_string());
+    }
 }
 
 fn set_widget_attributes<MSG: 'static>(
     tag: &crate::Widget,
     widget: &Widget,
     attrs: &[&Attribute<MSG>],
+) {
+    if let crate::Widget::Custom(custom) = tag {
+        if let Some(hook) =
+            custom_widget::find_custom_widget::<MSG>(custom.as_ref())
+        {
+            hook.set_attributes(widget, attrs);
+        }
+        return;
+    }
+    // the columns of a table, the options of a dropdown and the range of a
+    // slider are set first since the rows and the value depends on them
+    let (first, rest): (Vec<&&Attribute<MSG>>, Vec<&&Attribute<MSG>>) =
+        attrs.iter().partition(|att| {
+            matches!(
+                att.name(),
+                AttribKey::Columns
+                    | AttribKey::Options
+                    | AttribKey::Min
+                    | AttribKey::Max
+                    | AttribKey::Step
+            )
+        });
+    for att in first.into_iter().chain(rest) {
+        for value in att.get_plain() {
+            set_widget_attribute(tag, widget, att.name(), value);
+        }
+    }
+}
+
+/// removed attributes are reset to their default value
+fn remove_widget_attributes<MSG: 'static>(
+    tag: &crate::Widget,
+    widget: &Widget,
+    attrs: &[&Attribute<MSG>],
+) {
+    for att in attrs {
+        if let Some(default_value) = default_value(att.name()) {
+            set_widget_attribute(tag, widget, att.name(), &default_value);
+        }
+    }
+}
+
+fn set_widget_attribute(
+    tag: &crate::Widget,
+    widget: &Widget,
+    key: &AttribKey,
+    value: &Value,
 ) {
     match tag {
         crate::Widget::Button => {
             let button =
                 widget.downcast_ref::<Button>().expect("must be a button");
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Label => {
-                            button.set_label(&value.to_string())
-                        }
-                        _ => (),
-                    }
-                }
+            if let AttribKey::Label = key {
+                button.set_label(&value.to_string());
             }
         }
-        crate::Widget::TextArea => {
+        crate::Widget::Link => {
+            let link_btn = widget
+                .downcast_ref::<LinkButton>()
+                .expect("must be a link button");
+            match key {
+                AttribKey::Label => link_btn.set_label(&value.to_string()),
+                AttribKey::Uri => link_btn.set_uri(&value.to_string()),
+                _ => (),
+            }
+        }
+        crate::Widget::TextArea | crate::Widget::Paragraph => {
             let text_view =
                 widget.downcast_ref::<TextView>().unwrap_or_else(|| {
                     panic!("must be a text_view, found: {:?}", widget)
                 });
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Value => {
-                            if let Some(buffer) = text_view.get_buffer() {
-                                buffer.set_text(&value.to_string());
-                            }
-                        }
-                        AttribKey::Editable => {
-                            let editable = value.as_bool();
-                            text_view.set_editable(editable);
-                        }
-                        _ => (),
+            match key {
+                AttribKey::Value => {
+                    if let Some(buffer) = text_view.get_buffer() {
+                        buffer.set_text(&value.to_string());
                     }
                 }
+                AttribKey::Editable => {
+                    let editable = value.as_bool();
+                    text_view.set_editable(editable);
+                }
+                _ => (),
+            }
+        }
+        // search entry is also an entry
+        crate::Widget::TextInput | crate::Widget::SearchInput => {
+            let entry = widget.downcast_ref::<Entry>().unwrap_or_else(|| {
+                panic!("must be an entry, found: {:?}", widget)
+            });
+            if let AttribKey::Value = key {
+                let buffer = entry.get_buffer();
+                let text = value.to_string();
+                // setting the same text will emit the input event again
+                if buffer.get_text() != text {
+                    buffer.set_text(&text);
+                }
+            }
+        }
+        // radio button is also a toggle button
+        crate::Widget::Checkbox | crate::Widget::Radio => {
+            let toggle =
+                widget.downcast_ref::<ToggleButton>().unwrap_or_else(|| {
+                    panic!("must be a toggle button, found: {:?}", widget)
+                });
+            match key {
+                AttribKey::Label => toggle.set_label(&value.to_string()),
+                AttribKey::Value | AttribKey::Checked => {
+                    toggle.set_active(value.as_bool())
+                }
+                _ => (),
+            }
+        }
+        crate::Widget::GroupBox => {
+            let frame = widget
+                .downcast_ref::<Frame>()
+                .unwrap_or_else(|| panic!("must be a frame {:?}", widget));
+            if let AttribKey::Label = key {
+                frame.set_label(Some(value.to_string().as_str()));
             }
         }
+        crate::Widget::MenuItem
+        | crate::Widget::CheckMenuItem
+        | crate::Widget::RadioMenuItem => {
+            let menu_item = widget
+                .downcast_ref::<MenuItem>()
+                .unwrap_or_else(|| panic!("must be a menu item {:?}", widget));
+            menu::set_menu_item_attribute(menu_item, key, value);
+        }
         crate::Widget::Svg => {
             let image = widget
                 .downcast_ref::<Image>()
                 .unwrap_or_else(|| panic!("must be an image {:?}", widget));
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Data => {
-                            if let Some(bytes) = value.as_bytes() {
-                                let pixbuf_loader =
-                                    PixbufLoader::new_with_mime_type(
-                                        "image/svg+xml",
-                                    )
-                                    .expect("error loader");
-                                pixbuf_loader
-                                    .write(bytes)
-                                    .expect("Unable to write svg data into pixbuf_loader");
-                                pixbuf_loader
-                                    .close()
-                                    .expect("error creating pixbuf");
-                                let pixbuf = pixbuf_loader.get_pixbuf();
-                                image.set_from_pixbuf(Some(
-                                    &pixbuf.expect("error in pixbuf_loader"),
-                                ));
-                            }
-                        }
-                        _ => (),
+            if let AttribKey::Data = key {
+                if let Some(bytes) = value.as_bytes() {
+                    images::set_image_data(image, "image/svg+xml", bytes);
+                }
+            }
+        }
+        crate::Widget::Image => {
+            let image = widget
+                .downcast_ref::<Image>()
+                .unwrap_or_else(|| panic!("must be an image {:?}", widget));
+            if let AttribKey::Data = key {
+                if let Some(bytes) = value.as_bytes() {
+                    if let Some(mime) = image_util::image_mime_type(bytes) {
+                        images::set_image_data(image, mime, bytes);
+                    } else {
+                        image.clear();
                     }
                 }
             }
@@ -195,65 +431,245 @@ fn set_widget_attributes<MSG: 'static>(
                 });
             let event_box_children = event_box.get_children();
             let child1 =
-                event_box_children.get(0).expect("must have one child");
+                event_box_children.first().expect("must have one child");
             let label = child1.downcast_ref::<Label>().unwrap_or_else(|| {
                 panic!("must be a label, found: {:?}", widget)
             });
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Value => label.set_text(&value.to_string()),
-                        _ => (),
-                    }
+            if let AttribKey::Value = key {
+                label.set_text(&value.to_string());
+            }
+        }
+        crate::Widget::Table => {
+            let tree_view =
+                widget.downcast_ref::<TreeView>().unwrap_or_else(|| {
+                    panic!("must be a tree view, found: {:?}", widget)
+                });
+            table::set_table_attribute(tree_view, key, value);
+        }
+        crate::Widget::ListView => {
+            let scroll =
+                widget.downcast_ref::<ScrolledWindow>().unwrap_or_else(|| {
+                    panic!("must be a scrolled window, found: {:?}", widget)
+                });
+            list_view::set_list_view_attribute(scroll, key, value);
+        }
+        crate::Widget::Dropdown => {
+            let combo =
+                widget.downcast_ref::<ComboBoxText>().unwrap_or_else(|| {
+                    panic!("must be a combo box, found: {:?}", widget)
+                });
+            select::set_dropdown_attribute(combo, key, value);
+        }
+        crate::Widget::ListBox => {
+            let list_box =
+                widget.downcast_ref::<ListBox>().unwrap_or_else(|| {
+                    panic!("must be a list box, found: {:?}", widget)
+                });
+            select::set_list_box_attribute(list_box, key, value);
+        }
+        crate::Widget::SpinButton => {
+            let spin =
+                widget.downcast_ref::<SpinButton>().unwrap_or_else(|| {
+                    panic!("must be a spin button, found: {:?}", widget)
+                });
+            numeric::set_numeric_attribute(&spin.get_adjustment(), key, value);
+        }
+        crate::Widget::Slider => {
+            let scale = widget.downcast_ref::<Scale>().unwrap_or_else(|| {
+                panic!("must be a scale, found: {:?}", widget)
+            });
+            numeric::set_numeric_attribute(&scale.get_adjustment(), key, value);
+        }
+        crate::Widget::ProgressBar => {
+            let progress_bar =
+                widget.downcast_ref::<ProgressBar>().unwrap_or_else(|| {
+                    panic!("must be a progress bar, found: {:?}", widget)
+                });
+            progress::set_progress_bar_attribute(progress_bar, key, value);
+        }
+        crate::Widget::Spinner => {
+            let spinner =
+                widget.downcast_ref::<Spinner>().unwrap_or_else(|| {
+                    panic!("must be a spinner, found: {:?}", widget)
+                });
+            progress::set_spinner_attribute(spinner, key, value);
+        }
+        crate::Widget::Dialog => {
+            let dialog =
+                widget.downcast_ref::<gtk::Dialog>().unwrap_or_else(|| {
+                    panic!("must be a dialog, found: {:?}", widget)
+                });
+            dialog::set_dialog_attribute(dialog, key, value);
+        }
+        crate::Widget::FileChooser => {
+            let file_chooser = widget
+                .downcast_ref::<gtk::FileChooserDialog>()
+                .unwrap_or_else(|| {
+                    panic!("must be a file chooser dialog, found: {:?}", widget)
+                });
+            file_chooser::set_file_chooser_attribute(file_chooser, key, value);
+        }
+        crate::Widget::Canvas => {
+            let event_box =
+                widget.downcast_ref::<EventBox>().unwrap_or_else(|| {
+                    panic!("must be an eventbox, found: {:?}", widget)
+                });
+            let event_box_children = event_box.get_children();
+            let child1 =
+                event_box_children.first().expect("must have one child");
+            let image = child1.downcast_ref::<Image>().unwrap_or_else(|| {
+                panic!("must be an image, found: {:?}", widget)
+            });
+            canvas::set_canvas_attribute(image, key, value);
+        }
+        crate::Widget::Tabs => {
+            let notebook =
+                widget.downcast_ref::<Notebook>().unwrap_or_else(|| {
+                    panic!("must be a notebook, found: {:?}", widget)
+                });
+            if let AttribKey::Selected = key {
+                let selected = value.as_f64().map(|v| v as u32);
+                // switching to the same page will emit the event again, a
+                // different page emits it while patching which is ignored by
+                // the is_patching guard of the backend
+                if notebook.get_current_page() != selected {
+                    notebook.set_current_page(selected);
                 }
             }
         }
-        _ => {
-            println!("todo for other widgets");
+        crate::Widget::Tab => {
+            if let AttribKey::Label = key {
+                let notebook = widget
+                    .get_parent()
+                    .and_then(|parent| parent.downcast::<Notebook>().ok())
+                    .expect("tab must be in a notebook");
+                notebook.set_tab_label_text(widget, &value.to_string());
+            }
         }
+        // the containers and the separator has no attributes that can be
+        // changed
+        crate::Widget::Vbox
+        | crate::Widget::Hbox
+        | crate::Widget::Vpane
+        | crate::Widget::Hpane
+        | crate::Widget::Overlay
+        | crate::Widget::HeaderBar
+        | crate::Widget::MenuBar
+        | crate::Widget::Menu
+        | crate::Widget::MenuSeparator => (),
+        // the tree items are rows which are patched in the tree view
+        crate::Widget::TreeView | crate::Widget::TreeItem => (),
+        // custom widgets set all of its attributes at once
+        crate::Widget::Custom(_) => (),
     }
 }
 
+/// find the handles of the nodes to be patched, the root widget is the
+/// child of the window
 fn find_nodes<MSG>(
     node: &Node<MSG>,
-    container: &Container,
+    window: &Container,
     patches: &[Patch<MSG>],
-) -> HashMap<usize, Widget>
+) -> HashMap<usize, NodeHandle>
 where
     MSG: 'static,
 {
-    let mut nodes_to_find: HashMap<usize, &crate::Widget> = HashMap::new();
-    let mut cur_node_idx = 0;
-
-    for patch in patches {
-        let tag = patch.tag().expect("must have a tag");
-        nodes_to_find.insert(patch.node_idx(), tag);
-    }
-    find_nodes_recursive(node, container, &mut cur_node_idx, &nodes_to_find)
+    let nodes_to_find: HashSet<usize> =
+        patches.iter().map(|patch| patch.node_idx()).collect();
+    let mut nodes_to_patch: HashMap<usize, NodeHandle> = HashMap::new();
+    let window_children = window.get_children();
+    let root_widget = window_children.first().expect("must have a root widget");
+    find_nodes_recursive(
+        node,
+        root_widget,
+        Some((window.clone(), 0)),
+        &mut 0,
+        &nodes_to_find,
+        &mut nodes_to_patch,
+    );
+    nodes_to_patch
 }
 
 fn find_nodes_recursive<MSG>(
     node: &Node<MSG>,
-    container: &Container,
+    outer_widget: &Widget,
+    parent: Option<(Container, usize)>,
     cur_node_idx: &mut usize,
-    nodes_to_find: &HashMap<usize, &crate::Widget>,
-) -> HashMap<usize, Widget>
-where
+    nodes_to_find: &HashSet<usize>,
+    nodes_to_patch: &mut HashMap<usize, NodeHandle>,
+) where
     MSG: 'static,
 {
-    let tag = node.tag().expect("must have a tag");
-    let mut nodes_to_patch: HashMap<usize, Widget> = HashMap::new();
+    // the text nodes are labels which have no children
+    let (tag, attrs) = match (node.tag(), node.get_attributes()) {
+        (Some(tag), Some(attrs)) => (tag, attrs),
+        _ => {
+            if nodes_to_find.contains(cur_node_idx) {
+                nodes_to_patch.insert(
+                    *cur_node_idx,
+                    NodeHandle {
+                        widget: outer_widget.clone(),
+                        outer_widget: outer_widget.clone(),
+                        children_container: None,
+                        parent,
+                        tree_item: None,
+                    },
+                );
+            }
+            return;
+        }
+    };
 
-    if let Some(_) = nodes_to_find.get(cur_node_idx) {
-        let container_widget: Widget = container.clone().upcast();
-        nodes_to_patch.insert(*cur_node_idx, container_widget);
+    if *tag == crate::Widget::TreeView {
+        let tree_view = get_actual_node_to_patch(tag, outer_widget, attrs)
+            .downcast::<TreeView>()
+            .expect("must be a tree view");
+        if nodes_to_find.contains(cur_node_idx) {
+            nodes_to_patch.insert(
+                *cur_node_idx,
+                NodeHandle {
+                    widget: tree_view.clone().upcast(),
+                    outer_widget: outer_widget.clone(),
+                    children_container: None,
+                    parent,
+                    tree_item: Some((tree_view.clone(), vec![])),
+                },
+            );
+        }
+        find_tree_items(
+            node,
+            &tree_view,
+            &[],
+            cur_node_idx,
+            nodes_to_find,
+            nodes_to_patch,
+        );
+        return;
     }
 
-    if tag.is_container() {
-        let node_children = node.get_children().expect("must have children");
+    let children_container = if tag.is_container() {
+        Some(get_children_container(tag, outer_widget, attrs))
+    } else {
+        None
+    };
 
-        let attrs = node.get_attributes().expect("must have have attributes");
-        let widget_children = get_widget_children(tag, container, &attrs);
+    if nodes_to_find.contains(cur_node_idx) {
+        let widget = get_actual_node_to_patch(tag, outer_widget, attrs);
+        nodes_to_patch.insert(
+            *cur_node_idx,
+            NodeHandle {
+                widget,
+                outer_widget: outer_widget.clone(),
+                children_container: children_container.clone(),
+                parent,
+                tree_item: None,
+            },
+        );
+    }
+
+    if let Some(container) = children_container {
+        let node_children = node.get_children().expect("must have children");
+        let widget_children = get_widget_children::<MSG>(tag, &container);
 
         assert_eq!(
             node_children.len(),
@@ -261,37 +677,61 @@ where
             "must have the same children len, in widget: {:?}",
             tag
         );
-        for (child_node, widget_child) in
-            node_children.iter().zip(widget_children.iter())
+        for (child_pos, (child_node, widget_child)) in
+            node_children.iter().zip(widget_children.iter()).enumerate()
         {
             *cur_node_idx += 1;
-            let child_tag = child_node.tag().expect("must have a child tag");
-            if let Some(_patch_tag) = nodes_to_find.get(&cur_node_idx) {
-                let child_attrs =
-                    child_node.get_attributes().expect("must have attributes");
-                let widget: Widget = get_actual_node_to_patch(
-                    child_tag,
-                    widget_child,
-                    &child_attrs,
-                );
-                nodes_to_patch.insert(*cur_node_idx, widget);
-            }
-            if child_tag.is_container() {
-                if let Some(container) =
-                    widget_child.downcast_ref::<Container>()
-                {
-                    let child_nodes_to_patch = find_nodes_recursive(
-                        child_node,
-                        container,
-                        cur_node_idx,
-                        nodes_to_find,
-                    );
-                    nodes_to_patch.extend(child_nodes_to_patch);
-                }
-            }
+            find_nodes_recursive(
+                child_node,
+                widget_child,
+                Some((container.clone(), child_pos)),
+                cur_node_idx,
+                nodes_to_find,
+                nodes_to_patch,
+            );
         }
     }
-    nodes_to_patch
+}
+
+/// the tree items are rows in the model of the tree view,
+/// so their handles are the tree view and the path of the row
+fn find_tree_items<MSG>(
+    node: &Node<MSG>,
+    tree_view: &TreeView,
+    path: &[i32],
+    cur_node_idx: &mut usize,
+    nodes_to_find: &HashSet<usize>,
+    nodes_to_patch: &mut HashMap<usize, NodeHandle>,
+) where
+    MSG: 'static,
+{
+    let node_children = node.get_children().expect("must have children");
+    for (child_pos, child_node) in node_children.iter().enumerate() {
+        *cur_node_idx += 1;
+        let mut child_path = path.to_vec();
+        child_path.push(child_pos as i32);
+        if nodes_to_find.contains(cur_node_idx) {
+            let widget: Widget = tree_view.clone().upcast();
+            nodes_to_patch.insert(
+                *cur_node_idx,
+                NodeHandle {
+                    widget: widget.clone(),
+                    outer_widget: widget,
+                    children_container: None,
+                    parent: None,
+                    tree_item: Some((tree_view.clone(), child_path.clone())),
+                },
+            );
+        }
+        find_tree_items(
+            child_node,
+            tree_view,
+            &child_path,
+            cur_node_idx,
+            nodes_to_find,
+            nodes_to_patch,
+        );
+    }
 }
 
 /// return the actual node to be patched
@@ -309,47 +749,53 @@ where
     MSG: 'static,
 {
     match child_tag {
-        crate::Widget::TextArea => {
-            if is_scrollable(&attrs) {
-                // ScrolledWindow -> TextArea
-                let scrolled_window = widget_child
-                    .downcast_ref::<gtk::ScrolledWindow>()
-                    .expect("must be a scrolled window container");
-                let scrolled_window_children = scrolled_window.get_children();
-                let text_area =
-                    scrolled_window_children.get(0).expect("must have a child");
-                let text_area: Widget = text_area.clone().upcast();
-                text_area
-            } else {
-                let text_area: Widget = widget_child.clone().upcast();
-                text_area
-            }
+        crate::Widget::TextArea if is_scrollable(attrs) => {
+            // ScrolledWindow -> TextArea
+            let scrolled_window = widget_child
+                .downcast_ref::<gtk::ScrolledWindow>()
+                .expect("must be a scrolled window container");
+            let scrolled_window_children = scrolled_window.get_children();
+            let text_area =
+                scrolled_window_children.first().expect("must have a child");
+            let text_area: Widget = text_area.clone().upcast();
+            text_area
         }
-        crate::Widget::Svg => {
-            if is_scrollable(&attrs) {
-                // ScrolledWindow -> ViewPort -> Image
-                let scrolled_window = widget_child
-                    .downcast_ref::<gtk::ScrolledWindow>()
-                    .expect("must be a scrolled window container");
+        crate::Widget::Svg if is_scrollable(attrs) => {
+            // ScrolledWindow -> ViewPort -> Image
+            let scrolled_window = widget_child
+                .downcast_ref::<gtk::ScrolledWindow>()
+                .expect("must be a scrolled window container");
 
-                let scrolled_window_children = scrolled_window.get_children();
-                let view_port = scrolled_window_children
-                    .get(0)
-                    .expect("scrolled window must have a child");
-                let view_port = view_port
-                    .downcast_ref::<gtk::Viewport>()
-                    .expect("must be a viewport container");
+            let scrolled_window_children = scrolled_window.get_children();
+            let view_port = scrolled_window_children
+                .first()
+                .expect("scrolled window must have a child");
+            let view_port = view_port
+                .downcast_ref::<gtk::Viewport>()
+                .expect("must be a viewport container");
 
-                let view_port_children = view_port.get_children();
-                let svg_image = view_port_children
-                    .get(0)
-                    .expect("view port must have svg image as child");
-                let svg_image: Widget = svg_image.clone().upcast();
-                svg_image
-            } else {
-                let svg_image: Widget = widget_child.clone().upcast();
-                svg_image
-            }
+            let view_port_children = view_port.get_children();
+            let svg_image = view_port_children
+                .first()
+                .expect("view port must have svg image as child");
+            let svg_image: Widget = svg_image.clone().upcast();
+            svg_image
+        }
+        crate::Widget::Table | crate::Widget::TreeView => {
+            // ScrolledWindow -> TreeView
+            let scrolled_window = widget_child
+                .downcast_ref::<gtk::ScrolledWindow>()
+                .expect("must be a scrolled window container");
+            let scrolled_window_children = scrolled_window.get_children();
+            let tree_view =
+                scrolled_window_children.first().expect("must have a child");
+            let tree_view: Widget = tree_view.clone().upcast();
+            tree_view
+        }
+        crate::Widget::Dialog | crate::Widget::FileChooser => {
+            // Placeholder -> attached Dialog
+            let dialog: Widget = dialog::find_dialog(widget_child).upcast();
+            dialog
         }
         _ => {
             let widget: Widget = widget_child.clone().upcast();
@@ -358,73 +804,81 @@ where
     }
 }
 
-/// return the children of this widget
+/// return the container where the children of the widget are added
 /// dealing with special case where widgets are wrapped with scrolled window
-fn get_widget_children<MSG>(
+fn get_children_container<MSG>(
     tag: &crate::Widget,
-    container: &Container,
+    widget: &Widget,
     attrs: &[crate::Attribute<MSG>],
-) -> Vec<Widget>
+) -> Container
 where
     MSG: 'static,
 {
+    let container = widget
+        .downcast_ref::<Container>()
+        .unwrap_or_else(|| panic!("must be a container: {:?}", widget));
     match *tag {
+        // the children are in the dialog which is attached to the placeholder
+        crate::Widget::Dialog => {
+            dialog::get_content(&dialog::find_dialog(widget)).upcast()
+        }
         // special case for GroupBox since GroupBox have a frame wrapper
         // GroupBox(Frame(Box))
         crate::Widget::GroupBox => {
             let frame_children = container.get_children();
             let gbox_widget =
-                frame_children.get(0).expect("must have one child");
-            let gbox = gbox_widget
-                .downcast_ref::<gtk::Box>()
-                .expect("must be a container");
-            gbox.get_children()
-        }
-        crate::Widget::Vbox | crate::Widget::Hbox => {
-            if is_scrollable(attrs) {
-                println!("VBOX is SCROLLABLE..");
-                container.downcast_ref::<gtk::ScrolledWindow>()
-                            .unwrap_or_else(|| {
-                                panic!(
-                                    "container must be a ScrolledWindow, but found: {:?}",
-                                    container
-                                )
-                            });
-
-                // ScrolledWindow -> VBox
-                let scrolled_children = container.get_children();
-                assert_eq!(
-                    scrolled_children.len(),
-                    1,
-                    "There should only be one children.. that is the real vbox"
-                );
-                let scrolled_widget =
-                    scrolled_children.get(0).expect("must have one child");
-                let view_port = scrolled_widget
-                    .downcast_ref::<gtk::Viewport>()
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "must be a view port, but found: {:?}",
-                            scrolled_widget
-                        )
-                    });
-                let viewport_children = view_port.get_children();
-                let box_widget =
-                    viewport_children.get(0).expect("must have 1 child");
+                frame_children.first().expect("must have one child");
+            gbox_widget
+                .downcast_ref::<Container>()
+                .expect("must be a container")
+                .clone()
+        }
+        crate::Widget::Vbox | crate::Widget::Hbox | crate::Widget::ListView
+            if is_scrollable(attrs) || *tag == crate::Widget::ListView =>
+        {
+            // ScrolledWindow -> ViewPort -> Box,
+            // the list view is always wrapped in a scrolled window
+            let scrolled_children = container.get_children();
+            assert_eq!(
+                scrolled_children.len(),
+                1,
+                "There should only be one children.. that is the real vbox"
+            );
+            let scrolled_widget =
+                scrolled_children.first().expect("must have one child");
+            let view_port = scrolled_widget
+                .downcast_ref::<gtk::Viewport>()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "must be a view port, but found: {:?}",
+                        scrolled_widget
+                    )
+                });
+            let viewport_children = view_port.get_children();
+            let box_widget =
+                viewport_children.first().expect("must have 1 child");
 
-                let box_container = box_widget
-                    .downcast_ref::<gtk::Box>()
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "must be a gtk::Box.. but found: {:?}",
-                            box_widget,
-                        )
-                    });
-                box_container.get_children()
-            } else {
-                container.get_children()
-            }
+            box_widget
+                .downcast_ref::<Container>()
+                .unwrap_or_else(|| {
+                    panic!("must be a gtk::Box.. but found: {:?}", box_widget,)
+                })
+                .clone()
         }
+        _ => container.clone(),
+    }
+}
+
+/// return the children of the container which corresponds to the
+/// children nodes
+fn get_widget_children<MSG>(
+    tag: &crate::Widget,
+    container: &Container,
+) -> Vec<Widget>
+where
+    MSG: 'static,
+{
+    match *tag {
         // special case for SubMenu in MenuItem since
         // sub_menu is not returned as children for the menu_item
         // instead, we get the sub menu as part of it's children
@@ -441,6 +895,12 @@ where
             }
             widgets
         }
+        crate::Widget::Custom(ref custom) => {
+            match custom_widget::find_custom_widget::<MSG>(custom.as_ref()) {
+                Some(hook) => hook.get_children(container.upcast_ref()),
+                None => container.get_children(),
+            }
+        }
         _ => container.get_children(),
     }
 }
@@ -0,0 +1,160 @@
+//! canvas widget, the draw commands are rendered into an image surface
+//! which is shown in an image wrapped in an event box for the mouse events
+use super::Dispatch;
+use crate::widget::draw::{Color, DrawCommand};
+use crate::widget::event::MouseEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use gtk::{prelude::*, EventBox, Image};
+
+/// create the canvas with the size of its layout, the coordinates of the
+/// mouse events are local to the canvas
+pub(crate) fn new_canvas<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    width: f32,
+    height: f32,
+) -> EventBox
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let image = Image::new();
+    image.set_size_request(width as i32, height as i32);
+    let commands = find_value(AttribKey::Draw, attrs)
+        .and_then(|v| v.as_draw())
+        .unwrap_or(&[]);
+    render(&image, commands);
+
+    let event_box = EventBox::new();
+    event_box.add_events(gdk::EventMask::POINTER_MOTION_MASK);
+    if let Some(callbacks) = find_callback(AttribKey::ClickEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            event_box.connect_button_release_event(move |_view, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::click(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::MouseDown, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            event_box.connect_button_press_event(move |_view, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::pressed(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::MouseUp, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            event_box.connect_button_release_event(move |_view, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::release(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::MouseMove, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            event_box.connect_motion_notify_event(move |_view, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::mousemove(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+
+    event_box.add(&image);
+    image.show();
+    event_box.show();
+    event_box
+}
+
+/// set the attribute of the canvas which is changed by a patch
+pub(crate) fn set_canvas_attribute(
+    image: &Image,
+    key: &AttribKey,
+    value: &Value,
+) {
+    if let AttribKey::Draw = key {
+        render(image, value.as_draw().unwrap_or(&[]));
+    }
+}
+
+/// render the commands into a new surface of the size of the image
+fn render(image: &Image, commands: &[DrawCommand]) {
+    let (width, height) = image.get_size_request();
+    let surface = cairo::ImageSurface::create(
+        cairo::Format::ARgb32,
+        width.max(1),
+        height.max(1),
+    )
+    .expect("must create an image surface");
+    draw_commands(&cairo::Context::new(&surface), commands);
+    let surface: &cairo::Surface = &surface;
+    image.set_from_surface(Some(surface));
+}
+
+fn draw_commands(cr: &cairo::Context, commands: &[DrawCommand]) {
+    let mut stroke_color = Color::default();
+    let mut fill_color = Color::default();
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(x, y) => cr.move_to(*x, *y),
+            DrawCommand::LineTo(x, y) => cr.line_to(*x, *y),
+            DrawCommand::ClosePath => cr.close_path(),
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => cr.rectangle(*x, *y, *width, *height),
+            DrawCommand::Stroke => {
+                set_source_color(cr, &stroke_color);
+                cr.stroke();
+            }
+            DrawCommand::Fill => {
+                set_source_color(cr, &fill_color);
+                cr.fill();
+            }
+            DrawCommand::StrokeColor(color) => stroke_color = *color,
+            DrawCommand::FillColor(color) => fill_color = *color,
+            DrawCommand::LineWidth(line_width) => {
+                cr.set_line_width(*line_width)
+            }
+            DrawCommand::Text { x, y, text } => {
+                set_source_color(cr, &fill_color);
+                // the text is not part of the current path
+                let path = cr.copy_path();
+                cr.new_path();
+                cr.move_to(*x, *y);
+                cr.show_text(text);
+                cr.new_path();
+                cr.append_path(&path);
+            }
+        }
+    }
+}
+
+fn set_source_color(cr: &cairo::Context, color: &Color) {
+    cr.set_source_rgba(color.red, color.green, color.blue, color.alpha);
+}
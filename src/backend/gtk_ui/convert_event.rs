@@ -0,0 +1,43 @@
+//! converts the gtk events into sauron native events
+use crate::widget::event::{KeyCode, KeyEvent, Modifier};
+
+/// convert the key pressed in gtk into a key event, the character keys are
+/// lowercase so they match the shortcuts which are pressed with shift
+pub(crate) fn from_gtk_key_event(event: &gdk::EventKey) -> Option<KeyEvent> {
+    let keyval = gdk::keyval_to_lower(event.get_keyval());
+    let name = gdk::keyval_name(keyval)?;
+    let key_code = match name.as_str() {
+        "Return" | "KP_Enter" => KeyCode::Enter,
+        "BackSpace" => KeyCode::Backspace,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "Page_Up" => KeyCode::PageUp,
+        "Page_Down" => KeyCode::PageDown,
+        "Tab" => KeyCode::Tab,
+        "ISO_Left_Tab" => KeyCode::BackTab,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Escape" => KeyCode::Esc,
+        function
+            if function.starts_with('F')
+                && function.len() > 1
+                && function[1..].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            KeyCode::F(function[1..].parse().ok()?)
+        }
+        _ => KeyCode::Char(gdk::keyval_to_unicode(keyval)?),
+    };
+    let state = event.get_state();
+    Some(KeyEvent {
+        key_code,
+        modifier: Modifier {
+            alt_key: state.contains(gdk::ModifierType::MOD1_MASK),
+            ctrl_key: state.contains(gdk::ModifierType::CONTROL_MASK),
+            shift_key: state.contains(gdk::ModifierType::SHIFT_MASK),
+        },
+    })
+}
@@ -1,8 +1,20 @@
+use super::canvas;
+use super::custom_widget;
+use super::dialog;
+use super::file_chooser;
+use super::focus;
 use super::images;
+use super::list_view;
+use super::menu;
+use super::numeric;
+use super::progress;
+use super::select;
+use super::table;
+use super::tree_view;
 use super::Dispatch;
 use super::GtkWidget;
 use crate::image_util;
-use crate::widget::attribute::util::get_layout;
+use crate::widget::attribute::util::{get_index, get_layout};
 use crate::widget::event::{InputEvent, KeyEvent, MouseEvent};
 use crate::{
     widget::attribute::{find_callback, find_value, util::is_scrollable},
@@ -13,11 +25,11 @@ use gio::prelude::*;
 use gtk::{
     prelude::*, Adjustment, Button, CheckButton, Entry, EntryBuffer, EventBox,
     Frame, HeaderBar, Image, Label, LabelBuilder, LinkButton, Menu, MenuBar,
-    MenuItem, Orientation, Overlay, Paned, RadioButton, ScrolledWindow,
-    SearchEntry, TextBuffer, TextBufferExt, TextTagTable, TextView,
-    TextViewExt, WidgetExt,
+    Notebook, Orientation, Overlay, Paned, RadioButton, ScrolledWindow,
+    SearchEntry, SeparatorMenuItem, TextBuffer, TextBufferExt, TextTagTable,
+    TextView, TextViewExt, WidgetExt,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
 
 pub(crate) fn from_node_tree<DSP, MSG>(
     program: &DSP,
@@ -28,11 +40,23 @@ where
     DSP: Clone + Dispatch<MSG> + 'static,
 {
     match widget_node {
-        crate::Node::Element(element) => from_node(program, &element),
-        crate::Node::Text(_) => unreachable!(),
+        crate::Node::Element(element) => from_node(program, element),
+        // the text nodes are shown as labels
+        crate::Node::Text(txt) => GtkWidget::Label(text_label(&txt.text)),
     }
 }
 
+/// the label of a text node, it is wrapped in an event box like the label
+/// widget so they are patched the same way
+pub(crate) fn text_label(text: &str) -> EventBox {
+    let event_box = EventBox::new();
+    let label = LabelBuilder::new().label(text).name("label").build();
+    event_box.add(&label);
+    label.show();
+    event_box.show();
+    event_box
+}
+
 pub(crate) fn from_node<MSG, DSP>(
     program: &DSP,
     element: &crate::Element<MSG>,
@@ -44,17 +68,23 @@ where
     let widget: &Widget = element.tag();
     let attrs: &[Attribute<MSG>] = element.get_attributes();
     let children: &[crate::Node<MSG>] = element.get_children();
-    let layout = get_layout(&element).expect("must have a layout");
+    let layout = get_layout(element).expect("must have a layout");
     let width = layout.size.width;
     let height = layout.size.height;
 
     let mut widget_children: Vec<GtkWidget> = vec![];
+    // the tree items are rows in the tree view rather than widgets
+    let children: &[crate::Node<MSG>] = if *widget == Widget::TreeView {
+        &[]
+    } else {
+        children
+    };
     for child in children.iter() {
-        let gtk_child = from_node_tree(program, &child);
+        let gtk_child = from_node_tree(program, child);
         widget_children.push(gtk_child);
     }
 
-    match widget {
+    let gtk_widget = match widget {
         // gbox can have many children
         Widget::Vbox | Widget::Hbox => {
             let orientation = match widget {
@@ -75,7 +105,8 @@ where
                     );
                 }
             }
-            if is_scrollable(&attrs) {
+            focus::update_focus_chain(&gbox);
+            if is_scrollable(attrs) {
                 println!("wrapping the gbox with ScrolledWindow");
                 let scroll = ScrolledWindow::new(
                     None::<&Adjustment>,
@@ -93,9 +124,8 @@ where
             }
         }
         Widget::GroupBox => {
-            let label = find_value(AttribKey::Label, &attrs)
-                .map(|v| v.as_str())
-                .flatten();
+            let label =
+                find_value(AttribKey::Label, attrs).and_then(|v| v.as_str());
             let frame = Frame::new(label);
             let vbox = gtk::Box::new(Orientation::Vertical, 0);
             //vbox.set_size_request(width as i32, height as i32);
@@ -110,6 +140,7 @@ where
                     );
                 }
             }
+            focus::update_focus_chain(&vbox);
             frame.add(&vbox);
             GtkWidget::GroupBox(frame)
         }
@@ -127,29 +158,29 @@ where
                 log::warn!("pane children excess of 2 is ignored");
             }
             if let Some(child1) =
-                widget_children.get(0).map(|c| c.as_widget()).flatten()
+                widget_children.first().and_then(|c| c.as_widget())
             {
                 let is_resizable =
-                    find_value(AttribKey::Resizable, &child1_attrs)
+                    find_value(AttribKey::Resizable, child1_attrs)
                         .map(|v| v.as_bool())
                         .unwrap_or(true);
                 hpane.pack1(child1, true, true);
                 hpane.set_child_resize(child1, is_resizable);
             }
             if let Some(child2) =
-                widget_children.get(1).map(|c| c.as_widget()).flatten()
+                widget_children.get(1).and_then(|c| c.as_widget())
             {
                 let child2_attrs =
                     children[1].get_attributes().expect("must have attributes");
                 let is_resizable =
-                    find_value(AttribKey::Resizable, &child2_attrs)
+                    find_value(AttribKey::Resizable, child2_attrs)
                         .map(|v| v.as_bool())
                         .unwrap_or(true);
                 hpane.pack2(child2, true, true);
                 hpane.set_child_resize(child2, is_resizable);
             }
             if let Some(first_child) =
-                children.first().map(|c| c.as_element_ref()).flatten()
+                children.first().and_then(|c| c.as_element_ref())
             {
                 let child1_layout =
                     get_layout(first_child).expect("must have a layout");
@@ -168,17 +199,17 @@ where
                 log::warn!("pane children excess of 2 is ignored");
             }
             if let Some(child1) =
-                widget_children.get(0).map(|c| c.as_widget()).flatten()
+                widget_children.first().and_then(|c| c.as_widget())
             {
                 vpane.pack1(child1, true, true);
             }
             if let Some(child2) =
-                widget_children.get(1).map(|c| c.as_widget()).flatten()
+                widget_children.get(1).and_then(|c| c.as_widget())
             {
                 vpane.pack2(child2, true, true);
             }
             if let Some(first_child) =
-                children.first().map(|c| c.as_element_ref()).flatten()
+                children.first().and_then(|c| c.as_element_ref())
             {
                 let child1_layout =
                     get_layout(first_child).expect("must have a layout");
@@ -190,11 +221,10 @@ where
         Widget::Button => {
             println!("it's a button");
             let label =
-                find_value(AttribKey::Label, &attrs).map(|v| v.to_string());
+                find_value(AttribKey::Label, attrs).map(|v| v.to_string());
 
-            let svg_image_data = find_value(AttribKey::SvgImage, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten();
+            let svg_image_data = find_value(AttribKey::SvgImage, attrs)
+                .and_then(|v| v.as_bytes());
             let btn = Button::new();
             if let Some(label) = label {
                 btn.set_label(&label);
@@ -215,7 +245,7 @@ where
 
             if let Some(svg_image_data) = svg_image_data {
                 println!("got an svg image here..");
-                let svg_image: Image = images::svg_image(&svg_image_data);
+                let svg_image: Image = images::svg_image(svg_image_data);
                 btn.set_image(Some(&svg_image));
             }
 
@@ -226,7 +256,7 @@ where
             let buffer = TextBuffer::new(None::<&TextTagTable>);
             let text_view = TextView::new_with_buffer(&buffer);
 
-            let txt = find_value(AttribKey::Value, &attrs)
+            let txt = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
@@ -235,7 +265,7 @@ where
             GtkWidget::Paragraph(text_view)
         }
         Widget::TextInput => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
@@ -244,8 +274,7 @@ where
             let style_context = entry.get_style_context();
             style_context.add_class("text_input");
 
-            if let Some(callbacks) =
-                find_callback(AttribKey::InputEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs)
             {
                 for cb in callbacks {
                     let cb_clone = cb.clone();
@@ -258,12 +287,11 @@ where
                     });
                 }
             }
-            if let Some(callbacks) = find_callback(AttribKey::Activate, &attrs)
-            {
+            if let Some(callbacks) = find_callback(AttribKey::Activate, attrs) {
                 for cb in callbacks {
                     let program_clone = program.clone();
                     let cb_clone = cb.clone();
-                    entry.connect_activate(move |entry| {
+                    entry.connect_activate(move |_entry| {
                         let key_event = KeyEvent::enter();
                         let msg = cb_clone.emit(key_event);
                         program_clone.dispatch(msg);
@@ -273,7 +301,7 @@ where
             GtkWidget::TextInput(entry)
         }
         Widget::Label => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
@@ -287,7 +315,7 @@ where
                     // TODO: find a way to expresse
                     // click event using button_press and button_release
                     event_box.connect_button_press_event(
-                        move |_view, event| {
+                        move |_view, _event| {
                             println!("btn is clicked..");
                             let mouse_event = MouseEvent::default();
                             let msg = cb_clone.emit(mouse_event);
@@ -297,7 +325,7 @@ where
                     );
                 }
             }
-            if let Some(callbacks) = find_callback(AttribKey::MouseDown, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::MouseDown, attrs)
             {
                 for cb in callbacks {
                     println!("label has some mouse down");
@@ -316,7 +344,7 @@ where
                     );
                 }
             }
-            if let Some(callbacks) = find_callback(AttribKey::MouseUp, &attrs) {
+            if let Some(callbacks) = find_callback(AttribKey::MouseUp, attrs) {
                 for cb in callbacks {
                     println!("label has some mouse up");
                     let cb_clone = cb.clone();
@@ -335,7 +363,7 @@ where
                 }
             }
 
-            if let Some(callbacks) = find_callback(AttribKey::MouseMove, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::MouseMove, attrs)
             {
                 for cb in callbacks {
                     println!("label has some mouse up");
@@ -355,8 +383,7 @@ where
                 }
             }
 
-            let label =
-                LabelBuilder::new().label(&*value).name("label").build();
+            let label = LabelBuilder::new().label(&value).name("label").build();
 
             event_box.add(&label);
 
@@ -365,11 +392,12 @@ where
             GtkWidget::Label(event_box)
         }
         Widget::Checkbox => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Checked, attrs)
+                .or_else(|| find_value(AttribKey::Value, attrs))
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
@@ -379,11 +407,12 @@ where
             GtkWidget::Checkbox(cb)
         }
         Widget::Radio => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Checked, attrs)
+                .or_else(|| find_value(AttribKey::Value, attrs))
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
             let rb = RadioButton::new_with_label(&label);
@@ -392,35 +421,22 @@ where
             GtkWidget::Radio(rb)
         }
         Widget::Image => {
-            let bytes: &[u8] = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
+            let bytes: &[u8] = find_value(AttribKey::Data, attrs)
+                .and_then(|v| v.as_bytes())
                 .unwrap_or(&[]);
             let image = Image::new();
-            let mime = image_util::image_mime_type(&bytes)
-                .expect("unsupported have mime type");
-            let pixbuf_loader =
-                PixbufLoader::new_with_mime_type(mime).expect("error loader");
-            pixbuf_loader
-                .write(&bytes)
-                .expect("Unable to write svg data into pixbuf_loader");
-
-            pixbuf_loader.close().expect("error creating pixbuf");
-
-            let pixbuf = pixbuf_loader.get_pixbuf();
-
-            image.set_from_pixbuf(Some(
-                &pixbuf.expect("error in pixbuf_loader"),
-            ));
+            // the image is left empty if the data is not a supported image
+            if let Some(mime) = image_util::image_mime_type(bytes) {
+                images::set_image_data(&image, mime, bytes);
+            }
 
             //image.set_size_request(width as i32, height as i32);
             GtkWidget::Image(image)
         }
         Widget::Svg => {
             let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
+            let bytes = find_value(AttribKey::Data, attrs)
+                .and_then(|v| v.as_bytes())
                 .unwrap_or(&empty);
             let image = Image::new();
             let pixbuf_loader =
@@ -437,7 +453,7 @@ where
             image.set_from_pixbuf(Some(
                 &pixbuf.expect("error in pixbuf_loader"),
             ));
-            if let Some(callbacks) = find_callback(AttribKey::MouseDown, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::MouseDown, attrs)
             {
                 for cb in callbacks {
                     println!("textview has some mouse down");
@@ -456,7 +472,7 @@ where
             }
 
             //image.set_size_request(width as i32, height as i32);
-            if is_scrollable(&attrs) {
+            if is_scrollable(attrs) {
                 let scroll = ScrolledWindow::new(
                     None::<&Adjustment>,
                     None::<&Adjustment>,
@@ -468,19 +484,18 @@ where
             }
         }
         Widget::TextArea => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let editable = find_value(AttribKey::Editable, &attrs)
+            let editable = find_value(AttribKey::Editable, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(true);
 
             let buffer = TextBuffer::new(None::<&TextTagTable>);
             buffer.set_text(&value);
 
-            if let Some(callbacks) =
-                find_callback(AttribKey::InputEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs)
             {
                 for cb in callbacks {
                     let cb_clone = cb.clone();
@@ -505,7 +520,7 @@ where
             text_view.set_monospace(true);
             text_view.set_editable(editable);
 
-            if let Some(callbacks) = find_callback(AttribKey::MouseDown, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::MouseDown, attrs)
             {
                 for cb in callbacks {
                     println!("textview has some mouse down");
@@ -527,7 +542,7 @@ where
 
             //text_view.set_size_request(width as i32, height as i32);
 
-            if is_scrollable(&attrs) {
+            if is_scrollable(attrs) {
                 let scroll = ScrolledWindow::new(
                     None::<&Adjustment>,
                     None::<&Adjustment>,
@@ -590,6 +605,7 @@ where
         }
         Widget::Menu => {
             let menu = Menu::new();
+            menu.set_accel_group(Some(&menu::accel_group()));
 
             for child in widget_children.iter() {
                 if let Some(child_widget) = child.as_widget() {
@@ -604,24 +620,7 @@ where
             GtkWidget::Menu(menu)
         }
         Widget::MenuItem => {
-            let menu_item = MenuItem::new();
-
-            if let Some(callbacks) = find_callback(AttribKey::ClickEvent, attrs)
-            {
-                println!("menu item has click event");
-                for cb in callbacks {
-                    let cb_clone = cb.clone();
-                    let program_clone = program.clone();
-                    menu_item.connect_activate(move |_| {
-                        println!("menu item is clicked..");
-                        let mouse_event = MouseEvent::default();
-                        let msg = cb_clone.emit(mouse_event);
-                        program_clone.dispatch(msg);
-                    });
-                }
-            } else {
-                println!("No click event for menu item");
-            }
+            let menu_item = menu::new_menu_item(program, widget, attrs);
 
             for child in widget_children.iter() {
                 match child {
@@ -642,15 +641,21 @@ where
             }
             GtkWidget::MenuItem(menu_item)
         }
+        // the check and radio menu items are check menu items in gtk
+        Widget::CheckMenuItem | Widget::RadioMenuItem => {
+            GtkWidget::MenuItem(menu::new_menu_item(program, widget, attrs))
+        }
+        Widget::MenuSeparator => {
+            GtkWidget::MenuItem(SeparatorMenuItem::new().upcast())
+        }
         Widget::SearchInput => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let _value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
             let entry = SearchEntry::new();
 
-            if let Some(callbacks) =
-                find_callback(AttribKey::InputEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs)
             {
                 for cb in callbacks {
                     let cb_clone = cb.clone();
@@ -666,17 +671,16 @@ where
             GtkWidget::SearchInput(entry)
         }
         Widget::Link => {
-            let label: Option<&str> = find_value(AttribKey::Label, &attrs)
-                .map(|v| v.as_str())
-                .flatten();
+            let label: Option<&str> =
+                find_value(AttribKey::Label, attrs).and_then(|v| v.as_str());
 
-            let uri = find_value(AttribKey::Uri, &attrs)
+            let uri = find_value(AttribKey::Uri, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
             let link_btn = LinkButton::new_with_label(&uri, label);
             if let Some(label) = label {
-                link_btn.set_label(&label);
+                link_btn.set_label(label);
             }
             if let Some(callbacks) = find_callback(AttribKey::ClickEvent, attrs)
             {
@@ -695,5 +699,127 @@ where
             //link_btn.set_size_request(width as i32, height as i32);
             GtkWidget::LinkButton(link_btn)
         }
-    }
+        Widget::Table => GtkWidget::Table(table::new_table(program, attrs)),
+        Widget::ListView => GtkWidget::ListView(list_view::new_list_view(
+            program,
+            attrs,
+            &widget_children,
+        )),
+        Widget::Dropdown => {
+            GtkWidget::Dropdown(select::new_dropdown(program, attrs))
+        }
+        Widget::ListBox => {
+            GtkWidget::ListBox(select::new_list_box(program, attrs))
+        }
+        Widget::SpinButton => {
+            GtkWidget::SpinButton(numeric::new_spin_button(program, attrs))
+        }
+        Widget::Slider => {
+            GtkWidget::Slider(numeric::new_slider(program, attrs))
+        }
+        Widget::ProgressBar => {
+            GtkWidget::ProgressBar(progress::new_progress_bar(attrs))
+        }
+        Widget::Spinner => GtkWidget::Spinner(progress::new_spinner(attrs)),
+        Widget::TreeView => GtkWidget::TreeView(tree_view::new_tree_view(
+            program,
+            attrs,
+            element.get_children(),
+        )),
+        Widget::TreeItem => {
+            unreachable!("tree items are converted in the tree view")
+        }
+        Widget::Dialog => {
+            let (placeholder, content) =
+                dialog::new_dialog(program, attrs, width, height);
+            for child in widget_children.iter() {
+                if let Some(child_widget) = child.as_widget() {
+                    content.add(child_widget);
+                }
+            }
+            GtkWidget::Dialog(placeholder)
+        }
+        Widget::FileChooser => GtkWidget::FileChooser(
+            file_chooser::new_file_chooser(program, attrs),
+        ),
+        Widget::Canvas => {
+            GtkWidget::Canvas(canvas::new_canvas(program, attrs, width, height))
+        }
+        Widget::Tabs => {
+            let notebook = Notebook::new();
+            for child in widget_children.iter() {
+                if let GtkWidget::Tab(page, tab_label) = child {
+                    notebook.append_page(page, Some(tab_label));
+                } else {
+                    log::warn!("tabs can only have tab as children");
+                }
+            }
+            if let Some(selected) = get_index(AttribKey::Selected, attrs) {
+                notebook.set_current_page(Some(selected as u32));
+            }
+            if let Some(callbacks) =
+                find_callback(AttribKey::TabChangeEvent, attrs)
+            {
+                for cb in callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    notebook.connect_switch_page(
+                        move |_notebook, _page, page_num| {
+                            let input_event = InputEvent::new(page_num);
+                            let msg = cb_clone.emit(input_event);
+                            program_clone.dispatch(msg);
+                        },
+                    );
+                }
+            }
+            GtkWidget::Tabs(notebook)
+        }
+        Widget::Tab => {
+            let label = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let page = gtk::Box::new(Orientation::Vertical, 0);
+            for child in widget_children.iter() {
+                if let Some(child_widget) = child.as_widget() {
+                    page.add(child_widget);
+                }
+            }
+            focus::update_focus_chain(&page);
+            // notebook refuses to switch to a page which is not visible
+            page.show();
+            GtkWidget::Tab(page, Label::new(Some(&label)))
+        }
+        Widget::Custom(custom) => {
+            let children: Vec<gtk::Widget> = widget_children
+                .iter()
+                .filter_map(|child| child.as_widget().cloned())
+                .collect();
+            let hook =
+                custom_widget::find_custom_widget::<MSG>(custom.as_ref());
+            let gtk_widget = match hook {
+                Some(hook) => hook.build_widget(
+                    custom.as_ref(),
+                    attrs,
+                    children,
+                    Rc::new(program.clone()),
+                ),
+                // the children are shown in a box in place of the custom
+                // widget which has no conversion
+                None => {
+                    log::error!(
+                        "no gtk conversion for the custom widget `{}`",
+                        custom.name()
+                    );
+                    let placeholder = gtk::Box::new(Orientation::Vertical, 0);
+                    for child in children.iter() {
+                        placeholder.add(child);
+                    }
+                    placeholder.upcast()
+                }
+            };
+            GtkWidget::Custom(gtk_widget)
+        }
+    };
+    focus::set_focus_attributes(program, &gtk_widget, attrs);
+    gtk_widget
 }
@@ -0,0 +1,57 @@
+//! conversion hooks of custom widgets for the gtk backend
+//!
+use crate::backend::{registry, Dispatch};
+use crate::{widget::CustomWidget, Attribute};
+use gtk::{prelude::*, Container};
+use std::rc::Rc;
+
+/// Implement this to convert a custom widget into a gtk widget
+pub trait GtkCustomWidget<MSG> {
+    /// create the gtk widget of the custom widget,
+    /// the children are already converted into gtk widgets
+    fn build_widget(
+        &self,
+        custom_widget: &dyn CustomWidget,
+        attrs: &[Attribute<MSG>],
+        children: Vec<gtk::Widget>,
+        program: Rc<dyn Dispatch<MSG>>,
+    ) -> gtk::Widget;
+
+    /// update the gtk widget with the changed attributes
+    fn set_attributes(
+        &self,
+        _widget: &gtk::Widget,
+        _attrs: &[&Attribute<MSG>],
+    ) {
+    }
+
+    /// return the gtk widgets which corresponds to the children nodes
+    /// of the custom widget, this is used for finding the widgets to patch
+    fn get_children(&self, widget: &gtk::Widget) -> Vec<gtk::Widget> {
+        widget
+            .downcast_ref::<Container>()
+            .map(|container| container.get_children())
+            .unwrap_or_default()
+    }
+}
+
+/// register the gtk conversion of the custom widget with this name
+pub fn register_custom_widget<MSG, HOOK>(name: &str, hook: HOOK)
+where
+    MSG: 'static,
+    HOOK: GtkCustomWidget<MSG> + 'static,
+{
+    let hook: Rc<dyn GtkCustomWidget<MSG>> = Rc::new(hook);
+    registry::register_hook(name, hook);
+}
+
+/// find the gtk conversion of the custom widget, returns None if
+/// there is no conversion registered with the name of the custom widget
+pub(crate) fn find_custom_widget<MSG>(
+    custom_widget: &dyn CustomWidget,
+) -> Option<Rc<dyn GtkCustomWidget<MSG>>>
+where
+    MSG: 'static,
+{
+    registry::find_hook::<dyn GtkCustomWidget<MSG>>(custom_widget.name())
+}
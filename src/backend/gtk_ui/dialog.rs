@@ -0,0 +1,124 @@
+//! dialog widget, the dialog is a toplevel window so the widget which is
+//! added to the parent is an empty placeholder which the dialog is attached
+//! to. The dialog is shown when the placeholder is mapped and destroyed
+//! together with the placeholder.
+use super::Dispatch;
+use crate::widget::event::InputEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use gtk::{prelude::*, Dialog, Orientation, ResponseType, Widget, Window};
+
+/// the name of the box in the content area of the dialog where the children
+/// are added, the content area also has the empty action area
+const CONTENT_NAME: &str = "dialog-content";
+
+/// create the dialog and its placeholder, the children of the dialog are
+/// added into the returned content box
+pub(crate) fn new_dialog<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    width: f32,
+    height: f32,
+) -> (gtk::Box, gtk::Box)
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let dialog = Dialog::new();
+    dialog.set_modal(true);
+    // the dialog fits its content when it has no size
+    if width > 0.0 && height > 0.0 {
+        dialog.set_default_size(width as i32, height as i32);
+    }
+    if let Some(title) = find_value(AttribKey::Label, attrs) {
+        dialog.set_title(&title.to_string());
+    }
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+    content.set_widget_name(CONTENT_NAME);
+    dialog
+        .get_content_area()
+        .pack_start(&content, true, true, 0);
+
+    // the buttons are in the content, so the only response from gtk is
+    // when the dialog is dismissed
+    if let Some(callbacks) = find_callback(AttribKey::ResponseEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            dialog.connect_response(move |_dialog, response| {
+                if response == ResponseType::DeleteEvent {
+                    let input_event = InputEvent::new("");
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+    (attach_to_placeholder(&dialog), content)
+}
+
+/// create the placeholder which the dialog is attached to, the dialog is
+/// shown when the placeholder is mapped and kept until it is destroyed
+pub(crate) fn attach_to_placeholder<D>(dialog: &D) -> gtk::Box
+where
+    D: IsA<Dialog>,
+{
+    let dialog: Dialog = dialog.clone().upcast();
+    // the dialog is kept until it is removed from the view
+    dialog.connect_delete_event(|_dialog, _event| Inhibit(true));
+
+    let placeholder = gtk::Box::new(Orientation::Vertical, 0);
+    dialog.set_attached_to(Some(&placeholder));
+    let dialog_clone = dialog.clone();
+    placeholder.connect_map(move |placeholder| {
+        if let Some(window) = placeholder
+            .get_toplevel()
+            .and_then(|toplevel| toplevel.downcast::<Window>().ok())
+        {
+            dialog_clone.set_transient_for(Some(&window));
+        }
+        dialog_clone.show_all();
+    });
+    placeholder.connect_destroy(move |_placeholder| {
+        dialog.destroy();
+    });
+    placeholder
+}
+
+/// the dialog which is attached to this placeholder
+pub(crate) fn find_dialog(placeholder: &Widget) -> Dialog {
+    Window::list_toplevels()
+        .into_iter()
+        .filter_map(|toplevel| toplevel.downcast::<Dialog>().ok())
+        .find(|dialog| dialog.get_attached_to().as_ref() == Some(placeholder))
+        .unwrap_or_else(|| {
+            panic!("must have a dialog attached to: {:?}", placeholder)
+        })
+}
+
+/// the box in the dialog where its children are added
+pub(crate) fn get_content(dialog: &Dialog) -> gtk::Box {
+    dialog
+        .get_content_area()
+        .get_children()
+        .into_iter()
+        .filter(|child| {
+            child.get_widget_name().map(|n| n.as_str() == CONTENT_NAME)
+                == Some(true)
+        })
+        .find_map(|child| child.downcast::<gtk::Box>().ok())
+        .expect("must have a content box")
+}
+
+/// set the attribute of the dialog which is changed by a patch
+pub(crate) fn set_dialog_attribute(
+    dialog: &Dialog,
+    key: &AttribKey,
+    value: &Value,
+) {
+    if let AttribKey::Label = key {
+        dialog.set_title(&value.to_string());
+    }
+}
@@ -0,0 +1,136 @@
+//! file chooser widget, this is a dialog which is attached to a placeholder
+//! in the same way as the dialog widget
+use super::dialog;
+use super::Dispatch;
+use crate::widget::attribute::util::get_file_mode;
+use crate::widget::attribute::FileMode;
+use crate::widget::event::InputEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use gtk::{
+    prelude::*, FileChooserAction, FileChooserDialog, FileFilter, ResponseType,
+    Window,
+};
+
+/// create the file chooser dialog and the placeholder which it is attached to
+pub(crate) fn new_file_chooser<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> gtk::Box
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let (action, accept_label) = match get_file_mode(attrs) {
+        FileMode::Open => (FileChooserAction::Open, "Open"),
+        FileMode::Save => (FileChooserAction::Save, "Save"),
+        FileMode::SelectFolder => (FileChooserAction::SelectFolder, "Select"),
+    };
+    let title = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| accept_label.to_string());
+    let file_chooser =
+        FileChooserDialog::new(Some(&title), None::<&Window>, action);
+    file_chooser.add_button("Cancel", ResponseType::Cancel);
+    file_chooser.add_button(accept_label, ResponseType::Accept);
+    file_chooser.set_modal(true);
+    file_chooser.set_do_overwrite_confirmation(true);
+    for att in attrs {
+        for value in att.get_plain() {
+            set_file_chooser_attribute(&file_chooser, att.name(), value);
+        }
+    }
+
+    if let Some(callbacks) = find_callback(AttribKey::FilesEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            file_chooser.connect_response(move |file_chooser, response| {
+                // the dialog is dismissed or cancelled with no files
+                let paths: Vec<String> = if response == ResponseType::Accept {
+                    file_chooser
+                        .get_filenames()
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect()
+                } else {
+                    vec![]
+                };
+                let input_event = InputEvent::new(paths);
+                let msg = cb_clone.emit(input_event);
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            file_chooser.connect_current_folder_changed(move |file_chooser| {
+                if let Some(folder) = file_chooser.get_current_folder() {
+                    let input_event =
+                        InputEvent::new(folder.display().to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+    dialog::attach_to_placeholder(&file_chooser)
+}
+
+/// set the attribute of the file chooser which is changed by a patch,
+/// the file mode can not be changed once the file chooser is shown
+pub(crate) fn set_file_chooser_attribute(
+    file_chooser: &FileChooserDialog,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Label => file_chooser.set_title(&value.to_string()),
+        AttribKey::Directory => {
+            let directory = value.to_string();
+            // changing to the same folder will emit the input event again
+            if file_chooser.get_current_folder().as_deref()
+                != Some(std::path::Path::new(&directory))
+            {
+                file_chooser.set_current_folder(&directory);
+            }
+        }
+        AttribKey::Value
+            if file_chooser.get_action() == FileChooserAction::Save =>
+        {
+            file_chooser.set_current_name(value.to_string());
+        }
+        AttribKey::Multiple => {
+            file_chooser.set_select_multiple(value.as_bool())
+        }
+        AttribKey::Filters => {
+            let filters: Vec<String> = value
+                .as_list()
+                .map(|list| list.iter().map(|v| v.to_string()).collect())
+                .unwrap_or_default();
+            set_filters(file_chooser, &filters);
+        }
+        _ => (),
+    }
+}
+
+/// replace the filter of the file chooser, all of the patterns are in one
+/// filter so the files matching any of them are shown
+fn set_filters(file_chooser: &FileChooserDialog, patterns: &[String]) {
+    for filter in file_chooser.list_filters() {
+        file_chooser.remove_filter(&filter);
+    }
+    if patterns.is_empty() {
+        return;
+    }
+    let filter = FileFilter::new();
+    filter.set_name(Some(&patterns.join(", ")));
+    for pattern in patterns {
+        filter.add_pattern(pattern);
+    }
+    file_chooser.add_filter(&filter);
+}
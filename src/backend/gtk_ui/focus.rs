@@ -0,0 +1,144 @@
+//! keyboard focus of the widgets, the id of the widget is set as its name so
+//! it can be found when it is focused by a Cmd
+use super::Dispatch;
+use super::GtkWidget;
+use crate::widget::attribute::util::is_autofocus;
+use crate::widget::event::FocusEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Event,
+};
+use gtk::{prelude::*, Container, DirectionType, ScrolledWindow, Widget};
+use std::{cell::Cell, rc::Rc};
+
+/// set the id and the autofocus of the widget, then connect the focus and
+/// blur callbacks
+pub(crate) fn set_focus_attributes<MSG, DSP>(
+    program: &DSP,
+    gtk_widget: &GtkWidget,
+    attrs: &[Attribute<MSG>],
+) where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let widget = match gtk_widget.as_widget() {
+        Some(widget) => widget,
+        None => return,
+    };
+    if let Some(id) = find_value(AttribKey::Id, attrs) {
+        widget.set_widget_name(&id.to_string());
+    }
+    if is_autofocus(attrs) {
+        // the widget is only focused the first time it is shown
+        let is_autofocused = Cell::new(false);
+        widget.connect_map(move |widget| {
+            if !is_autofocused.replace(true) {
+                focus_widget(widget);
+            }
+        });
+    }
+    connect_focus_events(program, widget, attrs);
+}
+
+/// the focus and blur events are emitted when the widget itself or any of
+/// the widgets inside it gains or loses the focus, such as the button of a
+/// dropdown or the tree view inside a scrolled window
+fn connect_focus_events<MSG, DSP>(
+    program: &DSP,
+    widget: &Widget,
+    attrs: &[Attribute<MSG>],
+) where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let focus_callbacks: Vec<_> = find_callback(AttribKey::FocusEvent, attrs)
+        .unwrap_or_default()
+        .into_iter()
+        .cloned()
+        .collect();
+    let blur_callbacks: Vec<_> = find_callback(AttribKey::BlurEvent, attrs)
+        .unwrap_or_default()
+        .into_iter()
+        .cloned()
+        .collect();
+    if focus_callbacks.is_empty() && blur_callbacks.is_empty() {
+        return;
+    }
+    let has_focus = Rc::new(Cell::new(false));
+    let program_clone = program.clone();
+    let set_focus = Rc::new(move |focused: bool| {
+        if has_focus.replace(focused) == focused {
+            return;
+        }
+        let (callbacks, event) = if focused {
+            (&focus_callbacks, Event::FocusEvent(FocusEvent))
+        } else {
+            (&blur_callbacks, Event::BlurEvent(FocusEvent))
+        };
+        for cb in callbacks.iter() {
+            let msg = cb.emit(event.clone());
+            program_clone.dispatch(msg);
+        }
+    });
+
+    let set_focus_clone = set_focus.clone();
+    widget.connect_focus_in_event(move |_widget, _event| {
+        set_focus_clone(true);
+        Inhibit(false)
+    });
+    let set_focus_clone = set_focus.clone();
+    widget.connect_focus_out_event(move |_widget, _event| {
+        set_focus_clone(false);
+        Inhibit(false)
+    });
+    if let Some(container) = widget.downcast_ref::<Container>() {
+        // the focus child is already set when the handler is called, it is
+        // None when the focus leaves the container
+        container.connect_set_focus_child(move |container, _child| {
+            set_focus(container.get_focus_child().is_some());
+        });
+    }
+}
+
+/// focus the widget with this id in any of the windows, returns false if
+/// there is no widget with this id
+pub(crate) fn focus_by_id(id: &str) -> bool {
+    let found = gtk::Window::list_toplevels()
+        .iter()
+        .find_map(|toplevel| find_by_name(toplevel, id));
+    match found {
+        Some(widget) => {
+            focus_widget(&widget);
+            true
+        }
+        None => false,
+    }
+}
+
+/// find the widget with this name, including the widget itself
+fn find_by_name(widget: &Widget, name: &str) -> Option<Widget> {
+    if widget.get_widget_name().map(|n| n.as_str() == name) == Some(true) {
+        return Some(widget.clone());
+    }
+    widget
+        .downcast_ref::<Container>()?
+        .get_children()
+        .iter()
+        .find_map(|child| find_by_name(child, name))
+}
+
+/// focus the widget, or the first focusable widget inside it when the
+/// widget itself is a wrapper such as a scrolled window or a box
+fn focus_widget(widget: &Widget) {
+    if widget.get_can_focus() && !widget.is::<ScrolledWindow>() {
+        widget.grab_focus();
+    } else {
+        widget.child_focus(DirectionType::TabForward);
+    }
+}
+
+/// Tab and BackTab moves the focus in the order of the children rather than
+/// their position on the screen, this is the same order as the view
+pub(crate) fn update_focus_chain(gbox: &gtk::Box) {
+    gbox.set_focus_chain(&gbox.get_children());
+}
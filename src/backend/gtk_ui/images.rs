@@ -3,16 +3,21 @@ use gtk::{Image, ImageExt};
 
 pub fn svg_image(bytes: &[u8]) -> Image {
     let image = Image::new();
-    let pixbuf_loader = PixbufLoader::new_with_mime_type("image/svg+xml")
-        .expect("error loader");
+    set_image_data(&image, "image/svg+xml", bytes);
+    image
+}
+
+/// load the image data with the mime type into the image widget
+pub fn set_image_data(image: &Image, mime: &str, bytes: &[u8]) {
+    let pixbuf_loader =
+        PixbufLoader::new_with_mime_type(mime).expect("error loader");
     pixbuf_loader
         .write(bytes)
-        .expect("Unable to write svg data into pixbuf_loader");
+        .expect("Unable to write image data into pixbuf_loader");
 
     pixbuf_loader.close().expect("error creating pixbuf");
 
     let pixbuf = pixbuf_loader.get_pixbuf();
 
     image.set_from_pixbuf(Some(&pixbuf.expect("error in pixbuf_loader")));
-    image
 }
@@ -0,0 +1,145 @@
+//! list view widget, the rows are added into a box in a scrolled window
+//! where the rows which are not built are the top and bottom margin of the box
+use super::Dispatch;
+use super::GtkWidget;
+use crate::widget::attribute::util::{get_row_height, visible_range};
+use crate::widget::event::InputEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use expanse::style::{Dimension, Style};
+use gtk::{
+    prelude::*, Adjustment, Bin, Orientation, ScrolledWindow, WidgetExt,
+};
+
+/// create the box of the rows wrapped in a scrolled window
+pub(crate) fn new_list_view<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    widget_children: &[GtkWidget],
+) -> ScrolledWindow
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let row_height = get_row_height(attrs);
+    let gbox = gtk::Box::new(Orientation::Vertical, 0);
+    // rows added by the patches needs to have the same height
+    // TODO: the row height is not updated when it is changed by a patch
+    gbox.connect_add(move |_gbox, child| {
+        child.set_size_request(-1, row_height as i32);
+    });
+    for child in widget_children.iter() {
+        if let Some(child_widget) = child.as_widget() {
+            gbox.add(child_widget);
+        }
+    }
+    if let Some(style) =
+        find_value(AttribKey::Style, attrs).and_then(|v| v.as_style())
+    {
+        set_padding(&gbox, style);
+    }
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&gbox);
+
+    if let Some(adjustment) = scroll.get_vadjustment() {
+        if let Some(scroll_offset) =
+            find_value(AttribKey::ScrollOffset, attrs).and_then(|v| v.as_f64())
+        {
+            adjustment.set_value(scroll_offset);
+        }
+        if let Some(callbacks) = find_callback(AttribKey::ScrollEvent, attrs) {
+            for cb in callbacks {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                adjustment.connect_value_changed(move |adjustment| {
+                    let input_event = InputEvent::new(adjustment.get_value());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+        }
+        if let Some(callbacks) =
+            find_callback(AttribKey::VisibleRangeEvent, attrs)
+        {
+            for cb in callbacks {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                adjustment.connect_value_changed(move |adjustment| {
+                    // the upper bound of the adjustment is the height of
+                    // all the rows, including the rows which are not built
+                    let item_count =
+                        (adjustment.get_upper() / row_height).round() as usize;
+                    let range = visible_range(
+                        item_count,
+                        row_height,
+                        adjustment.get_value(),
+                        adjustment.get_page_size(),
+                    );
+                    let input_event = InputEvent::new(vec![
+                        Value::from(range.start),
+                        Value::from(range.end),
+                    ]);
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+        }
+    }
+    scroll
+}
+
+/// set the attribute of the list view which is changed by a patch
+pub(crate) fn set_list_view_attribute(
+    scroll: &ScrolledWindow,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Style => {
+            if let Some(style) = value.as_style() {
+                set_padding(&get_rows_box(scroll), style);
+            }
+        }
+        AttribKey::ScrollOffset => {
+            if let (Some(adjustment), Some(scroll_offset)) =
+                (scroll.get_vadjustment(), value.as_f64())
+            {
+                // setting the same value will emit the scroll event again,
+                // a changed value emits it while patching which is ignored
+                // by the is_patching guard of the backend
+                if adjustment.get_value() != scroll_offset {
+                    adjustment.set_value(scroll_offset);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// the rows which are not built takes up the space above and below
+/// the rows which are built
+fn set_padding(gbox: &gtk::Box, style: &Style) {
+    let points = |dimension: Dimension| match dimension {
+        Dimension::Points(points) => points as i32,
+        _ => 0,
+    };
+    gbox.set_margin_top(points(style.padding.top));
+    gbox.set_margin_bottom(points(style.padding.bottom));
+}
+
+/// ScrolledWindow -> ViewPort -> Box
+fn get_rows_box(scroll: &ScrolledWindow) -> gtk::Box {
+    let view_port = scroll
+        .get_child()
+        .expect("scrolled window must have a child")
+        .downcast::<Bin>()
+        .expect("must be a viewport");
+    view_port
+        .get_child()
+        .expect("view port must have a child")
+        .downcast::<gtk::Box>()
+        .expect("must be a box")
+}
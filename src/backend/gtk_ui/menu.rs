@@ -0,0 +1,169 @@
+//! menu items, the accelerators of the menu items are kept in an accel group
+//! which is added to the application window
+use super::Dispatch;
+use crate::widget::event::{InputEvent, KeyCode, KeyEvent};
+use crate::{
+    widget::attribute::find_callback, AttribKey, Attribute, Value, Widget,
+};
+use glib::WeakRef;
+use gtk::{prelude::*, AccelFlags, AccelGroup, CheckMenuItem, MenuItem};
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    /// the accel group of all the menu items, shared with the window
+    static ACCEL_GROUP: AccelGroup = AccelGroup::new();
+    /// the accelerators which are added to the menu items, they are removed
+    /// when the accelerator is changed by a patch
+    static ACCELERATORS: RefCell<Vec<(WeakRef<MenuItem>, u32, gdk::ModifierType)>> =
+        const { RefCell::new(vec![]) };
+    /// whether the checked state is set by a patch rather than by the user,
+    /// the activate signal is emitted in both cases
+    static IS_PATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// the accel group which has the accelerators of the menu items
+pub(crate) fn accel_group() -> AccelGroup {
+    ACCEL_GROUP.with(|accel_group| accel_group.clone())
+}
+
+/// create a menu item, a check menu item or a radio menu item which is a
+/// check menu item drawn as a radio
+pub(crate) fn new_menu_item<MSG, DSP>(
+    program: &DSP,
+    widget: &Widget,
+    attrs: &[Attribute<MSG>],
+) -> MenuItem
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let menu_item: MenuItem = match widget {
+        Widget::CheckMenuItem | Widget::RadioMenuItem => {
+            let check_menu_item = CheckMenuItem::new();
+            check_menu_item.set_draw_as_radio(*widget == Widget::RadioMenuItem);
+            check_menu_item.upcast()
+        }
+        _ => MenuItem::new(),
+    };
+    for att in attrs {
+        for value in att.get_plain() {
+            set_menu_item_attribute(&menu_item, att.name(), value);
+        }
+    }
+
+    if let Some(callbacks) = find_callback(AttribKey::ActivateEvent, attrs) {
+        let callbacks: Vec<_> = callbacks.into_iter().cloned().collect();
+        let program_clone = program.clone();
+        menu_item.connect_activate(move |menu_item| {
+            if IS_PATCHING.with(|is_patching| is_patching.get()) {
+                return;
+            }
+            let value = match menu_item.downcast_ref::<CheckMenuItem>() {
+                // gtk unchecks the radio when it is activated again, it is
+                // checked back which emits the activate signal once more
+                Some(check) if check.get_draw_as_radio() => {
+                    if !check.get_active() {
+                        check.set_active(true);
+                        return;
+                    }
+                    Value::from(true)
+                }
+                Some(check) => Value::from(check.get_active()),
+                None => Value::from(
+                    menu_item
+                        .get_label()
+                        .map(|label| label.to_string())
+                        .unwrap_or_default(),
+                ),
+            };
+            for cb in callbacks.iter() {
+                let input_event = InputEvent::new(value.clone());
+                let msg = cb.emit(input_event);
+                program_clone.dispatch(msg);
+            }
+        });
+    }
+    menu_item
+}
+
+/// set the attribute of the menu item which is changed by a patch
+pub(crate) fn set_menu_item_attribute(
+    menu_item: &MenuItem,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Label => menu_item.set_label(&value.to_string()),
+        AttribKey::Accelerator => set_accelerator(
+            menu_item,
+            KeyEvent::from_accelerator(&value.to_string()),
+        ),
+        AttribKey::Checked => {
+            // setting the checked state emits the activate signal, which is
+            // ignored here and by the is_patching guard of the backend
+            if let Some(check) = menu_item.downcast_ref::<CheckMenuItem>() {
+                IS_PATCHING.with(|is_patching| is_patching.set(true));
+                check.set_active(value.as_bool());
+                IS_PATCHING.with(|is_patching| is_patching.set(false));
+            }
+        }
+        _ => (),
+    }
+}
+
+/// the accelerator is added to the accel group of the window, the previous
+/// accelerator of the menu item is removed so it can be changed by a patch
+fn set_accelerator(menu_item: &MenuItem, key_event: Option<KeyEvent>) {
+    ACCELERATORS.with(|accelerators| {
+        let mut accelerators = accelerators.borrow_mut();
+        // the destroyed menu items are forgotten
+        accelerators.retain(|(item, _key, _modifier)| item.upgrade().is_some());
+        if let Some(pos) = accelerators
+            .iter()
+            .position(|(item, _, _)| item.upgrade().as_ref() == Some(menu_item))
+        {
+            let (_item, key, modifier) = accelerators.remove(pos);
+            menu_item.remove_accelerator(&accel_group(), key, modifier);
+        }
+        let (key, modifier) = match key_event.as_ref().map(to_gtk_accelerator) {
+            Some((key, modifier)) if key != 0 => (key, modifier),
+            _ => return,
+        };
+        menu_item.add_accelerator(
+            "activate",
+            &accel_group(),
+            key,
+            modifier,
+            AccelFlags::VISIBLE,
+        );
+        accelerators.push((menu_item.downgrade(), key, modifier));
+    });
+}
+
+/// the key value and the modifier of the accelerator in gtk
+fn to_gtk_accelerator(key_event: &KeyEvent) -> (u32, gdk::ModifierType) {
+    let key_name = match &key_event.key_code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(ch) => ch.to_lowercase().to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Enter => "Return".to_string(),
+        KeyCode::Backspace => "BackSpace".to_string(),
+        KeyCode::PageUp => "Page_Up".to_string(),
+        KeyCode::PageDown => "Page_Down".to_string(),
+        KeyCode::BackTab => "ISO_Left_Tab".to_string(),
+        KeyCode::Esc => "Escape".to_string(),
+        KeyCode::Null => return (0, gdk::ModifierType::empty()),
+        key_code => format!("{:?}", key_code),
+    };
+    let mut modifier = gdk::ModifierType::empty();
+    if key_event.modifier.ctrl_key {
+        modifier |= gdk::ModifierType::CONTROL_MASK;
+    }
+    if key_event.modifier.alt_key {
+        modifier |= gdk::ModifierType::MOD1_MASK;
+    }
+    if key_event.modifier.shift_key {
+        modifier |= gdk::ModifierType::SHIFT_MASK;
+    }
+    (gdk::keyval_from_name(&key_name), modifier)
+}
@@ -0,0 +1,84 @@
+//! spin button and slider widgets, both are backed by an adjustment
+use super::Dispatch;
+use crate::widget::attribute::util::{get_numeric_range, get_numeric_value};
+use crate::widget::event::InputEvent;
+use crate::{widget::attribute::find_callback, AttribKey, Attribute, Value};
+use gtk::{prelude::*, Adjustment, Orientation, Scale, SpinButton};
+
+/// create a spin button of the numeric range
+pub(crate) fn new_spin_button<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> SpinButton
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let (min, max, step) = get_numeric_range(attrs);
+    let spin = SpinButton::new_with_range(min, max, step);
+    spin.set_value(get_numeric_value(attrs));
+    connect_value_changed(program, attrs, &spin.get_adjustment());
+    spin
+}
+
+/// create a horizontal scale of the numeric range
+pub(crate) fn new_slider<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> Scale
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let (min, max, step) = get_numeric_range(attrs);
+    let scale = Scale::new_with_range(Orientation::Horizontal, min, max, step);
+    scale.set_value(get_numeric_value(attrs));
+    connect_value_changed(program, attrs, &scale.get_adjustment());
+    scale
+}
+
+/// emit the input event with the new value of the adjustment
+fn connect_value_changed<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    adjustment: &Adjustment,
+) where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            adjustment.connect_value_changed(move |adjustment| {
+                let input_event = InputEvent::new(adjustment.get_value());
+                let msg = cb_clone.emit(input_event);
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+}
+
+/// set the attribute of the spin button or slider which is changed by a patch
+pub(crate) fn set_numeric_attribute(
+    adjustment: &Adjustment,
+    key: &AttribKey,
+    value: &Value,
+) {
+    let number = match value.as_f64() {
+        Some(number) => number,
+        None => return,
+    };
+    match key {
+        // setting the same value will emit the input event again, a
+        // different value emits it while patching which is ignored by the
+        // is_patching guard of the backend
+        AttribKey::Value if adjustment.get_value() != number => {
+            adjustment.set_value(number)
+        }
+        AttribKey::Min => adjustment.set_lower(number),
+        AttribKey::Max => adjustment.set_upper(number),
+        AttribKey::Step => adjustment.set_step_increment(number),
+        _ => (),
+    }
+}
@@ -0,0 +1,108 @@
+//! progress bar and spinner widgets
+use crate::widget::attribute::util::get_fraction;
+use crate::{widget::attribute::find_value, AttribKey, Attribute, Value};
+use gtk::{prelude::*, ProgressBar, Spinner};
+
+/// the style class of the progress bar which is pulsing
+const INDETERMINATE_CLASS: &str = "indeterminate";
+/// the interval in milliseconds of the pulse of an indeterminate progress bar
+const PULSE_INTERVAL: u32 = 100;
+
+/// create a progress bar, an indeterminate progress bar pulses until
+/// it is set to be determinate again
+pub(crate) fn new_progress_bar<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> ProgressBar {
+    let progress_bar = ProgressBar::new();
+    progress_bar.set_fraction(get_fraction(attrs));
+    if let Some(label) = find_value(AttribKey::Label, attrs) {
+        set_text(&progress_bar, &label.to_string());
+    }
+    let is_indeterminate = find_value(AttribKey::Indeterminate, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    set_indeterminate(&progress_bar, is_indeterminate);
+
+    let weak_progress_bar = progress_bar.downgrade();
+    glib::timeout_add_local(PULSE_INTERVAL, move || {
+        match weak_progress_bar.upgrade() {
+            Some(progress_bar) => {
+                if progress_bar
+                    .get_style_context()
+                    .has_class(INDETERMINATE_CLASS)
+                {
+                    progress_bar.pulse();
+                }
+                glib::Continue(true)
+            }
+            // the progress bar is already removed
+            None => glib::Continue(false),
+        }
+    });
+    progress_bar
+}
+
+/// set the attribute of the progress bar which is changed by a patch
+pub(crate) fn set_progress_bar_attribute(
+    progress_bar: &ProgressBar,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Fraction => {
+            if let Some(fraction) = value.as_f64() {
+                progress_bar.set_fraction(fraction.clamp(0.0, 1.0));
+            }
+        }
+        AttribKey::Label => set_text(progress_bar, &value.to_string()),
+        AttribKey::Indeterminate => {
+            set_indeterminate(progress_bar, value.as_bool())
+        }
+        _ => (),
+    }
+}
+
+/// the text is only shown when there is one
+fn set_text(progress_bar: &ProgressBar, text: &str) {
+    progress_bar.set_show_text(!text.is_empty());
+    progress_bar.set_text(Some(text));
+}
+
+/// the pulse is driven by the timer which checks for the style class
+fn set_indeterminate(progress_bar: &ProgressBar, is_indeterminate: bool) {
+    let style_context = progress_bar.get_style_context();
+    if is_indeterminate {
+        style_context.add_class(INDETERMINATE_CLASS);
+    } else {
+        style_context.remove_class(INDETERMINATE_CLASS);
+    }
+}
+
+/// create a spinner, which is spinning by default
+pub(crate) fn new_spinner<MSG: 'static>(attrs: &[Attribute<MSG>]) -> Spinner {
+    let spinner = Spinner::new();
+    let is_active = find_value(AttribKey::Active, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(true);
+    set_active(&spinner, is_active);
+    spinner
+}
+
+/// set the attribute of the spinner which is changed by a patch
+pub(crate) fn set_spinner_attribute(
+    spinner: &Spinner,
+    key: &AttribKey,
+    value: &Value,
+) {
+    if let AttribKey::Active = key {
+        set_active(spinner, value.as_bool());
+    }
+}
+
+fn set_active(spinner: &Spinner, is_active: bool) {
+    if is_active {
+        spinner.start();
+    } else {
+        spinner.stop();
+    }
+}
@@ -0,0 +1,209 @@
+//! dropdown and list box widgets, the options are identified by their text
+use super::Dispatch;
+use crate::widget::attribute::util::{
+    get_options, get_selected_values, selected_values,
+};
+use crate::widget::event::InputEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use gtk::{
+    prelude::*, ComboBoxText, Label, ListBox, ListBoxRow, SelectionMode,
+};
+
+/// create a combo box of the options
+pub(crate) fn new_dropdown<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> ComboBoxText
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let combo = ComboBoxText::new();
+    set_dropdown_options(&combo, &get_options(attrs));
+    if let Some(value) = get_selected_values(attrs).first() {
+        set_dropdown_value(&combo, value);
+    }
+    if let Some(callbacks) = find_callback(AttribKey::ChangeEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            combo.connect_changed(move |combo| {
+                // the active option is cleared when the options are replaced
+                if let Some(text) = combo.get_active_text() {
+                    let input_event = InputEvent::new(text.to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+    combo
+}
+
+/// set the attribute of the dropdown which is changed by a patch
+pub(crate) fn set_dropdown_attribute(
+    combo: &ComboBoxText,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Options => {
+            let active = combo.get_active_id();
+            let options: Vec<String> = value
+                .as_list()
+                .unwrap_or(&[])
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            set_dropdown_options(combo, &options);
+            if let Some(active) = active {
+                set_dropdown_value(combo, &active);
+            }
+        }
+        AttribKey::Value => {
+            if let Some(value) = selected_values(value).first() {
+                set_dropdown_value(combo, value);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// replace the options of the combo box, the text of the option is also
+/// used as its id
+fn set_dropdown_options(combo: &ComboBoxText, options: &[String]) {
+    combo.remove_all();
+    for option in options {
+        combo.append(Some(option), option);
+    }
+}
+
+fn set_dropdown_value(combo: &ComboBoxText, value: &str) {
+    // setting the same option will emit the change event again, a different
+    // option emits it while patching which is ignored by the is_patching
+    // guard of the backend
+    if combo.get_active_id().as_deref() != Some(value) {
+        combo.set_active_id(Some(value));
+    }
+}
+
+/// create a list box of the options
+pub(crate) fn new_list_box<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> ListBox
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let list_box = ListBox::new();
+    let is_multiple = find_value(AttribKey::Multiple, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    set_selection_mode(&list_box, is_multiple);
+    set_list_box_options(&list_box, &get_options(attrs));
+    set_list_box_values(&list_box, &get_selected_values(attrs));
+    if let Some(callbacks) = find_callback(AttribKey::ChangeEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            list_box.connect_selected_rows_changed(move |list_box| {
+                let selected = get_list_box_values(list_box);
+                let input_event = if list_box.get_selection_mode()
+                    == SelectionMode::Multiple
+                {
+                    InputEvent::new(selected)
+                } else {
+                    InputEvent::new(
+                        selected.into_iter().next().unwrap_or_default(),
+                    )
+                };
+                let msg = cb_clone.emit(input_event);
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+    list_box
+}
+
+/// set the attribute of the list box which is changed by a patch
+pub(crate) fn set_list_box_attribute(
+    list_box: &ListBox,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Options => {
+            let selected = get_list_box_values(list_box);
+            let options: Vec<String> = value
+                .as_list()
+                .unwrap_or(&[])
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            set_list_box_options(list_box, &options);
+            set_list_box_values(list_box, &selected);
+        }
+        AttribKey::Value => {
+            set_list_box_values(list_box, &selected_values(value))
+        }
+        AttribKey::Multiple => set_selection_mode(list_box, value.as_bool()),
+        _ => (),
+    }
+}
+
+fn set_selection_mode(list_box: &ListBox, is_multiple: bool) {
+    list_box.set_selection_mode(if is_multiple {
+        SelectionMode::Multiple
+    } else {
+        SelectionMode::Single
+    });
+}
+
+/// replace the rows of the list box, each row is a label of the option
+fn set_list_box_options(list_box: &ListBox, options: &[String]) {
+    for row in list_box.get_children() {
+        list_box.remove(&row);
+    }
+    for option in options {
+        let label = Label::new(Some(option));
+        label.set_xalign(0.0);
+        list_box.add(&label);
+    }
+    list_box.show_all();
+}
+
+/// select the rows of these options
+fn set_list_box_values(list_box: &ListBox, values: &[String]) {
+    // selecting the same rows will emit the change event again
+    if get_list_box_values(list_box) == values {
+        return;
+    }
+    list_box.unselect_all();
+    for row in list_box.get_children() {
+        if let Ok(row) = row.downcast::<ListBoxRow>() {
+            if values.iter().any(|value| *value == row_text(&row)) {
+                list_box.select_row(Some(&row));
+            }
+        }
+    }
+}
+
+/// the text of the selected rows in the order they appear in the list
+fn get_list_box_values(list_box: &ListBox) -> Vec<String> {
+    let mut rows = list_box.get_selected_rows();
+    rows.sort_by_key(|row| row.get_index());
+    rows.iter().map(row_text).collect()
+}
+
+/// the text of the label in the row
+fn row_text(row: &ListBoxRow) -> String {
+    row.get_child()
+        .and_then(|child| child.downcast::<Label>().ok())
+        .and_then(|label| label.get_text())
+        .map(|text| text.to_string())
+        .unwrap_or_default()
+}
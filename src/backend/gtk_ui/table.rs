@@ -0,0 +1,281 @@
+//! table widget, a TreeView with a ListStore of the cell texts
+use super::Dispatch;
+use crate::widget::attribute::util::{get_index, get_table_data};
+use crate::widget::event::{Event, InputEvent};
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Value,
+};
+use gtk::{
+    prelude::*, Adjustment, CellRendererText, ListStore, ScrolledWindow,
+    SortType, TreePath, TreeView, TreeViewColumn,
+};
+use mt_dom::Callback;
+
+/// create the tree view of the table wrapped in a scrolled window
+pub(crate) fn new_table<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> ScrolledWindow
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let (columns, rows) = get_table_data(attrs);
+    let tree_view = TreeView::new();
+    set_columns(&tree_view, &columns);
+    set_rows(&tree_view, &rows);
+    set_sort_indicator(
+        &tree_view,
+        get_index(AttribKey::SortColumn, attrs),
+        find_value(AttribKey::SortDescending, attrs)
+            .map(|v| v.as_bool())
+            .unwrap_or(false),
+    );
+    set_selected(&tree_view, get_index(AttribKey::Selected, attrs));
+
+    let is_sortable = find_value(AttribKey::Sortable, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    if is_sortable {
+        let callbacks: Vec<_> = find_callback(AttribKey::SortEvent, attrs)
+            .unwrap_or_default()
+            .into_iter()
+            .cloned()
+            .collect();
+        let program_clone = program.clone();
+        let connect_sort = move |tree_view: &TreeView| {
+            connect_sort_callbacks(tree_view, &program_clone, &callbacks)
+        };
+        connect_sort(&tree_view);
+        // the columns which are added by the patches are connected as well
+        tree_view.connect_columns_changed(connect_sort);
+    }
+    if let Some(callbacks) = find_callback(AttribKey::SelectEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.get_selection().connect_changed(move |selection| {
+                let (paths, _model) = selection.get_selected_rows();
+                if let Some(row) = paths.first().and_then(first_index) {
+                    let input_event = InputEvent::new(row as f64);
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::RowActivateEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_activated(move |_view, path, _column| {
+                if let Some(row) = first_index(path) {
+                    let input_event = InputEvent::new(row as f64);
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&tree_view);
+    scroll
+}
+
+/// make the columns clickable which emits the sort event with the index of
+/// the column, the columns which are already clickable are connected before
+fn connect_sort_callbacks<MSG, DSP>(
+    tree_view: &TreeView,
+    program: &DSP,
+    callbacks: &[Callback<Event, MSG>],
+) where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    for column in tree_view.get_columns() {
+        if column.get_clickable() {
+            continue;
+        }
+        column.set_clickable(true);
+        let callbacks = callbacks.to_vec();
+        let program_clone = program.clone();
+        column.connect_clicked(move |column| {
+            // the index is looked up when clicked, since the columns before
+            // it could be removed by the patches
+            let index = column
+                .get_tree_view()
+                .and_then(|view| view.downcast::<TreeView>().ok())
+                .and_then(|view| {
+                    view.get_columns().iter().position(|c| c == column)
+                });
+            if let Some(index) = index {
+                for cb in callbacks.iter() {
+                    let input_event = InputEvent::new(index as f64);
+                    let msg = cb.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            }
+        });
+    }
+}
+
+/// set the column headers of the table, the model is recreated when the
+/// number of columns changed.
+/// TODO: the rows are cleared when the number of columns changed without the
+/// rows being patched as well
+pub(crate) fn set_columns(tree_view: &TreeView, columns: &[String]) {
+    let existing = tree_view.get_columns();
+    for column in existing.iter().skip(columns.len()) {
+        tree_view.remove_column(column);
+    }
+    for (index, title) in columns.iter().enumerate() {
+        if let Some(column) = existing.get(index) {
+            column.set_title(title);
+        } else {
+            let column = TreeViewColumn::new();
+            let cell = CellRendererText::new();
+            column.set_title(title);
+            column.pack_start(&cell, true);
+            column.add_attribute(&cell, "text", index as i32);
+            tree_view.append_column(&column);
+        }
+    }
+    if existing.len() != columns.len() || tree_view.get_model().is_none() {
+        let column_types = vec![glib::Type::String; columns.len()];
+        let store = ListStore::new(&column_types);
+        tree_view.set_model(Some(&store));
+    }
+}
+
+/// replace the rows of the table, the cells which are in excess of the
+/// number of columns are ignored
+pub(crate) fn set_rows(tree_view: &TreeView, rows: &[Vec<String>]) {
+    let store = tree_view
+        .get_model()
+        .and_then(|model| model.downcast::<ListStore>().ok())
+        .expect("must have a list store");
+    // the selection is lost when the rows are cleared
+    let selected = selected_row(tree_view);
+    store.clear();
+    let column_len = store.get_n_columns() as usize;
+    let column_indexes: Vec<u32> = (0..column_len as u32).collect();
+    for row in rows {
+        let cells: Vec<String> = (0..column_len)
+            .map(|index| row.get(index).cloned().unwrap_or_default())
+            .collect();
+        let values: Vec<&dyn ToValue> =
+            cells.iter().map(|cell| cell as &dyn ToValue).collect();
+        store.insert_with_values(None, &column_indexes, &values);
+    }
+    set_selected(tree_view, selected.filter(|row| *row < rows.len()));
+}
+
+/// show the sort indicator in the header of the sorted column
+pub(crate) fn set_sort_indicator(
+    tree_view: &TreeView,
+    sort_column: Option<usize>,
+    is_descending: bool,
+) {
+    for (index, column) in tree_view.get_columns().iter().enumerate() {
+        let is_sorted = Some(index) == sort_column;
+        column.set_sort_indicator(is_sorted);
+        if is_sorted {
+            column.set_sort_order(if is_descending {
+                SortType::Descending
+            } else {
+                SortType::Ascending
+            });
+        }
+    }
+}
+
+/// select the row at this index, or clear the selection
+pub(crate) fn set_selected(tree_view: &TreeView, row: Option<usize>) {
+    // selecting the same row again will emit the select event again
+    if selected_row(tree_view) == row {
+        return;
+    }
+    // the changed signal which is emitted here while patching is ignored by
+    // the is_patching guard of the backend
+    let selection = tree_view.get_selection();
+    match row {
+        Some(row) => {
+            selection.select_path(&TreePath::new_from_indicesv(&[row as i32]))
+        }
+        None => selection.unselect_all(),
+    }
+}
+
+/// the index of the selected row
+fn selected_row(tree_view: &TreeView) -> Option<usize> {
+    let (paths, _model) = tree_view.get_selection().get_selected_rows();
+    paths.first().and_then(first_index)
+}
+
+/// the index of the row in a list store
+fn first_index(path: &TreePath) -> Option<usize> {
+    path.get_indices().first().map(|index| *index as usize)
+}
+
+/// set the attribute of the table which is changed by a patch
+pub(crate) fn set_table_attribute(
+    tree_view: &TreeView,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match key {
+        AttribKey::Columns => {
+            let columns: Vec<String> = value
+                .as_list()
+                .unwrap_or(&[])
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            set_columns(tree_view, &columns);
+        }
+        AttribKey::Rows => {
+            let rows: Vec<Vec<String>> = value
+                .as_list()
+                .unwrap_or(&[])
+                .iter()
+                .map(|row| {
+                    row.as_list()
+                        .unwrap_or(&[])
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect()
+                })
+                .collect();
+            set_rows(tree_view, &rows);
+        }
+        AttribKey::Selected => {
+            let row = value.as_f64().filter(|v| *v >= 0.0).map(|v| v as usize);
+            set_selected(tree_view, row);
+        }
+        AttribKey::SortColumn => {
+            let sort_column =
+                value.as_f64().filter(|v| *v >= 0.0).map(|v| v as usize);
+            let is_descending = tree_view
+                .get_columns()
+                .iter()
+                .find(|column| column.get_sort_indicator())
+                .map(|column| column.get_sort_order() == SortType::Descending)
+                .unwrap_or(false);
+            set_sort_indicator(tree_view, sort_column, is_descending);
+        }
+        AttribKey::SortDescending => {
+            for column in tree_view.get_columns() {
+                if column.get_sort_indicator() {
+                    column.set_sort_order(if value.as_bool() {
+                        SortType::Descending
+                    } else {
+                        SortType::Ascending
+                    });
+                }
+            }
+        }
+        _ => (),
+    }
+}
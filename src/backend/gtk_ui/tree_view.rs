@@ -0,0 +1,321 @@
+//! tree view widget, the tree items are the rows of a TreeStore
+//! rather than widgets, so the patches on the tree items are applied
+//! to the rows at the path of the item
+use super::Dispatch;
+use crate::widget::attribute::util::{get_tree_item_id, get_tree_item_state};
+use crate::widget::event::InputEvent;
+use crate::{
+    widget::attribute::{find_callback, find_value},
+    AttribKey, Attribute, Node, Patch, Value,
+};
+use gtk::{
+    prelude::*, Adjustment, CellRendererText, ScrolledWindow, TreeIter,
+    TreeModel, TreePath, TreeStore, TreeView, TreeViewColumn,
+};
+use mt_dom::patch::{
+    AddAttributes, AppendChildren, InsertNode, RemoveAttributes, RemoveNode,
+    ReplaceNode,
+};
+
+/// the column of the text of the item
+const LABEL_COLUMN: u32 = 0;
+/// the column of the id of the item which is used in the events
+const ID_COLUMN: u32 = 1;
+
+/// create the tree view of the items wrapped in a scrolled window
+pub(crate) fn new_tree_view<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    items: &[Node<MSG>],
+) -> ScrolledWindow
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let store = TreeStore::new(&[glib::Type::String, glib::Type::String]);
+    let tree_view = TreeView::new_with_model(&store);
+    tree_view.set_headers_visible(false);
+    let column = TreeViewColumn::new();
+    let cell = CellRendererText::new();
+    column.pack_start(&cell, true);
+    column.add_attribute(&cell, "text", LABEL_COLUMN as i32);
+    tree_view.append_column(&column);
+
+    let items: Vec<&Node<MSG>> = items.iter().collect();
+    insert_items(&tree_view, &[], 0, &items);
+
+    if let Some(callbacks) = find_callback(AttribKey::ExpandEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_expanded(move |tree_view, iter, _path| {
+                let input_event = InputEvent::new(item_id(tree_view, iter));
+                let msg = cb_clone.emit(input_event);
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::CollapseEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_collapsed(move |tree_view, iter, _path| {
+                let input_event = InputEvent::new(item_id(tree_view, iter));
+                let msg = cb_clone.emit(input_event);
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::SelectEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            let tree_view_clone = tree_view.clone();
+            tree_view.get_selection().connect_changed(move |selection| {
+                if let Some((_model, iter)) = selection.get_selected() {
+                    let input_event =
+                        InputEvent::new(item_id(&tree_view_clone, &iter));
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::RowActivateEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_activated(move |tree_view, path, _column| {
+                if let Some(iter) = get_store(tree_view).get_iter(path) {
+                    let input_event =
+                        InputEvent::new(item_id(tree_view, &iter));
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&tree_view);
+    scroll
+}
+
+/// apply the patch on the tree item at this path, an empty path is the
+/// tree view itself where only the appended items are handled here.
+/// Returns false if the patch is not handled, so it is applied to the
+/// tree view as a widget instead.
+pub(crate) fn apply_tree_patch<MSG>(
+    tree_view: &TreeView,
+    path: &[i32],
+    patch: &Patch<MSG>,
+) -> bool
+where
+    MSG: 'static,
+{
+    let store = get_store(tree_view);
+    match patch {
+        Patch::AppendChildren(AppendChildren { children, .. }) => {
+            let position = if path.is_empty() {
+                store.iter_n_children(None)
+            } else {
+                let iter = get_iter(&store, path);
+                store.iter_n_children(Some(&iter))
+            };
+            let items: Vec<&Node<MSG>> =
+                children.iter().map(|(_idx, child)| *child).collect();
+            insert_items(tree_view, path, position as usize, &items);
+            true
+        }
+        _ if path.is_empty() => false,
+        Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+            for att in attrs {
+                for value in att.get_plain() {
+                    set_item_attribute(tree_view, path, att.name(), value);
+                }
+            }
+            true
+        }
+        Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
+            for att in attrs {
+                let default_value = match att.name() {
+                    AttribKey::Label => Value::from(""),
+                    AttribKey::Expanded | AttribKey::Selected => {
+                        Value::from(false)
+                    }
+                    _ => continue,
+                };
+                set_item_attribute(tree_view, path, att.name(), &default_value);
+            }
+            true
+        }
+        Patch::InsertNode(InsertNode { node, .. }) => {
+            let (parent_path, position) = split_path(path);
+            insert_items(tree_view, parent_path, position, &[*node]);
+            true
+        }
+        Patch::RemoveNode(RemoveNode { .. }) => {
+            store.remove(&get_iter(&store, path));
+            true
+        }
+        Patch::ReplaceNode(ReplaceNode { replacement, .. }) => {
+            store.remove(&get_iter(&store, path));
+            let (parent_path, position) = split_path(path);
+            insert_items(tree_view, parent_path, position, &[*replacement]);
+            true
+        }
+        // the rows are labeled with the label attribute of the tree items,
+        // the text nodes inside them are not shown
+        Patch::ChangeText(_) => true,
+    }
+}
+
+/// insert the tree items as rows under the item at the parent path,
+/// starting at this position
+fn insert_items<MSG>(
+    tree_view: &TreeView,
+    parent_path: &[i32],
+    position: usize,
+    items: &[&Node<MSG>],
+) where
+    MSG: 'static,
+{
+    let store = get_store(tree_view);
+    for (index, item) in items.iter().enumerate() {
+        let mut path = parent_path.to_vec();
+        path.push((position + index) as i32);
+        insert_rows(&store, &path, item);
+        set_item_states(tree_view, &path, item);
+    }
+}
+
+/// insert the row of the item at this path together with the rows of
+/// its nested items
+fn insert_rows<MSG>(store: &TreeStore, path: &[i32], item: &Node<MSG>)
+where
+    MSG: 'static,
+{
+    let attrs = item.get_attributes().unwrap_or(&[]);
+    let label = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let id = get_tree_item_id(attrs);
+    let (parent_path, position) = split_path(path);
+    let parent_iter = if parent_path.is_empty() {
+        None
+    } else {
+        Some(get_iter(store, parent_path))
+    };
+    store.insert_with_values(
+        parent_iter.as_ref(),
+        Some(position as u32),
+        &[LABEL_COLUMN, ID_COLUMN],
+        &[&label, &id],
+    );
+    for (index, child) in item.get_children().unwrap_or(&[]).iter().enumerate()
+    {
+        let mut child_path = path.to_vec();
+        child_path.push(index as i32);
+        insert_rows(store, &child_path, child);
+    }
+}
+
+/// expand and select the inserted items, the parent is expanded before
+/// its nested items since the rows of a collapsed item can not be expanded
+fn set_item_states<MSG>(tree_view: &TreeView, path: &[i32], item: &Node<MSG>)
+where
+    MSG: 'static,
+{
+    let (is_expanded, is_selected) =
+        get_tree_item_state(item.get_attributes().unwrap_or(&[]));
+    set_expanded(tree_view, path, is_expanded);
+    set_selected(tree_view, path, is_selected);
+    for (index, child) in item.get_children().unwrap_or(&[]).iter().enumerate()
+    {
+        let mut child_path = path.to_vec();
+        child_path.push(index as i32);
+        set_item_states(tree_view, &child_path, child);
+    }
+}
+
+/// set the attribute of the tree item which is changed by a patch
+fn set_item_attribute(
+    tree_view: &TreeView,
+    path: &[i32],
+    key: &AttribKey,
+    value: &Value,
+) {
+    let store = get_store(tree_view);
+    match key {
+        AttribKey::Label => {
+            let iter = get_iter(&store, path);
+            store.set_value(&iter, LABEL_COLUMN, &value.to_string().to_value());
+        }
+        AttribKey::Key => {
+            let iter = get_iter(&store, path);
+            store.set_value(&iter, ID_COLUMN, &value.to_string().to_value());
+        }
+        AttribKey::Expanded => set_expanded(tree_view, path, value.as_bool()),
+        AttribKey::Selected => set_selected(tree_view, path, value.as_bool()),
+        _ => (),
+    }
+}
+
+fn set_expanded(tree_view: &TreeView, path: &[i32], is_expanded: bool) {
+    let tree_path = TreePath::new_from_indicesv(path);
+    // expanding an expanded row will emit the expand event again
+    if tree_view.row_expanded(&tree_path) == is_expanded {
+        return;
+    }
+    if is_expanded {
+        tree_view.expand_row(&tree_path, false);
+    } else {
+        tree_view.collapse_row(&tree_path);
+    }
+}
+
+fn set_selected(tree_view: &TreeView, path: &[i32], is_selected: bool) {
+    let selection = tree_view.get_selection();
+    let tree_path = TreePath::new_from_indicesv(path);
+    // selecting a selected row will emit the select event again, the other
+    // rows emit it while patching which is ignored by the is_patching guard
+    // of the backend
+    if selection.path_is_selected(&tree_path) == is_selected {
+        return;
+    }
+    if is_selected {
+        selection.select_path(&tree_path);
+    } else {
+        selection.unselect_path(&tree_path);
+    }
+}
+
+/// the id of the item at this row
+fn item_id(tree_view: &TreeView, iter: &TreeIter) -> String {
+    get_store(tree_view)
+        .get_value(iter, ID_COLUMN as i32)
+        .get::<String>()
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn get_store(tree_view: &TreeView) -> TreeStore {
+    tree_view
+        .get_model()
+        .and_then(|model: TreeModel| model.downcast::<TreeStore>().ok())
+        .expect("must have a tree store")
+}
+
+fn get_iter(store: &TreeStore, path: &[i32]) -> TreeIter {
+    store
+        .get_iter(&TreePath::new_from_indicesv(path))
+        .unwrap_or_else(|| panic!("must have a tree item at {:?}", path))
+}
+
+/// the path of the parent and the position of the item in it
+fn split_path(path: &[i32]) -> (&[i32], usize) {
+    let (position, parent_path) =
+        path.split_last().expect("must not be the tree view itself");
+    (parent_path, *position as usize)
+}
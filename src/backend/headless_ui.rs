@@ -0,0 +1,577 @@
+//! headless backend, the widgets are kept in an in-memory tree
+//! which allows components to be tested without launching gtk or a terminal
+use super::Dispatch;
+use crate::shortcut::find_shortcut;
+use crate::sub::{emit_sub, interval_of, Subscriptions};
+use crate::widget::attribute::util::{
+    find_callback, find_value, get_accelerator, get_activate_value,
+    is_autofocus,
+};
+use crate::widget::event::{FocusEvent, InputEvent, KeyEvent, MouseEvent};
+use crate::{
+    AttribKey, Attribute, Backend, Component, Event, Node, Value, Widget,
+};
+use mt_dom::attr;
+use std::{
+    cell::{Cell, Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+    time::Duration,
+};
+
+mod apply_patches;
+
+/// backend which renders the view into an in-memory widget tree
+pub struct HeadlessBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Rc<RefCell<APP>>,
+    current_vdom: Rc<RefCell<Node<MSG>>>,
+    root_widget: Rc<RefCell<HeadlessWidget<MSG>>>,
+    /// the subscriptions of the app and the time until they are due
+    subscriptions: Rc<RefCell<Subscriptions<MSG, Cell<Duration>>>>,
+}
+
+/// An in-memory widget, it holds the attributes of the node it is created from
+/// including the callbacks which are triggered by the synthetic events
+#[derive(Debug, Clone)]
+pub struct HeadlessWidget<MSG> {
+    widget: Widget,
+    attrs: Vec<Attribute<MSG>>,
+    children: Vec<HeadlessWidget<MSG>>,
+    /// whether this widget has the keyboard focus
+    focused: bool,
+    /// whether the autofocus of this widget is already done, so it is only
+    /// focused once when it is shown
+    autofocused: bool,
+}
+
+/// Selects a widget in the headless widget tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    /// matches the widget with this `id` attribute
+    Id(String),
+    /// matches the widget with this `key` attribute
+    Key(String),
+    /// matches the widget with this `label` attribute
+    Label(String),
+    /// matches the menu item with this accelerator, such as `Ctrl+S`
+    Accelerator(String),
+}
+
+impl<MSG> HeadlessWidget<MSG>
+where
+    MSG: Clone + 'static,
+{
+    /// create the headless widget tree from this node
+    pub fn from_node(node: &Node<MSG>) -> Self {
+        match node {
+            crate::Node::Element(element) => HeadlessWidget {
+                widget: element.tag.clone(),
+                attrs: element.get_attributes().to_vec(),
+                children: element
+                    .get_children()
+                    .iter()
+                    .map(HeadlessWidget::from_node)
+                    .collect(),
+                focused: false,
+                autofocused: false,
+            },
+            // the text nodes are shown as a label with the text as its value
+            crate::Node::Text(txt) => HeadlessWidget {
+                widget: Widget::Label,
+                attrs: vec![attr(
+                    AttribKey::Value,
+                    Value::from(txt.text.to_string()),
+                )],
+                children: vec![],
+                focused: false,
+                autofocused: false,
+            },
+        }
+    }
+
+    /// returns the widget type of this headless widget
+    pub fn widget(&self) -> &Widget {
+        &self.widget
+    }
+
+    /// returns the attributes of this headless widget
+    pub fn get_attributes(&self) -> &[Attribute<MSG>] {
+        &self.attrs
+    }
+
+    /// returns the children of this headless widget
+    pub fn get_children(&self) -> &[HeadlessWidget<MSG>] {
+        &self.children
+    }
+
+    /// returns true if this widget has the keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// return the first value of the attribute key
+    pub fn get_value(&self, key: AttribKey) -> Option<&Value> {
+        find_value(key, &self.attrs)
+    }
+
+    /// returns true if this widget is matched by the selector
+    pub fn is_match(&self, selector: &Selector) -> bool {
+        let (key, expected) = match selector {
+            Selector::Id(id) => (AttribKey::Id, id),
+            Selector::Key(key) => (AttribKey::Key, key),
+            Selector::Label(label) => (AttribKey::Label, label),
+            // the accelerators are compared by their key combination
+            Selector::Accelerator(accelerator) => {
+                return KeyEvent::from_accelerator(accelerator).is_some()
+                    && get_accelerator(&self.attrs)
+                        == KeyEvent::from_accelerator(accelerator);
+            }
+        };
+        self.get_value(key)
+            .map(|v| v.as_str() == Some(expected))
+            .unwrap_or(false)
+    }
+
+    /// find the first widget that matches the selector, including this widget
+    pub fn find(&self, selector: &Selector) -> Option<&Self> {
+        if self.is_match(selector) {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|child| child.find(selector))
+        }
+    }
+
+    /// emit the callbacks attached to the event key of this widget,
+    /// returning the msgs produced by the callbacks
+    pub fn trigger(&self, key: AttribKey, event: Event) -> Vec<MSG> {
+        find_callback(key, &self.attrs)
+            .map(|callbacks| {
+                callbacks.iter().map(|cb| cb.emit(event.clone())).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// the path of the first widget which satisfies the predicate,
+    /// including this widget
+    fn find_path(
+        &self,
+        predicate: &dyn Fn(&Self) -> bool,
+        cur_path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        if predicate(self) {
+            return Some(cur_path.clone());
+        }
+        for (pos, child) in self.children.iter().enumerate() {
+            cur_path.push(pos);
+            let found = child.find_path(predicate, cur_path);
+            cur_path.pop();
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// the paths of the focusable widgets in the order of the view,
+    /// which is the order they are traversed with Tab
+    fn focusable_paths(
+        &self,
+        cur_path: &mut Vec<usize>,
+        paths: &mut Vec<Vec<usize>>,
+    ) {
+        if self.widget.is_focusable() {
+            paths.push(cur_path.clone());
+        }
+        for (pos, child) in self.children.iter().enumerate() {
+            cur_path.push(pos);
+            child.focusable_paths(cur_path, paths);
+            cur_path.pop();
+        }
+    }
+
+    /// mark the widgets with autofocus which are newly shown,
+    /// returning the path of the first one
+    fn take_autofocus(
+        &mut self,
+        cur_path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        let mut first = None;
+        if !self.autofocused && is_autofocus(&self.attrs) {
+            self.autofocused = true;
+            first = Some(cur_path.clone());
+        }
+        for (pos, child) in self.children.iter_mut().enumerate() {
+            cur_path.push(pos);
+            let found = child.take_autofocus(cur_path);
+            cur_path.pop();
+            first = first.or(found);
+        }
+        first
+    }
+
+    /// the widget at this path
+    fn widget_at(&self, path: &[usize]) -> &Self {
+        path.iter().fold(self, |widget, pos| &widget.children[*pos])
+    }
+
+    /// the mutable widget at this path
+    fn widget_at_mut(&mut self, path: &[usize]) -> &mut Self {
+        path.iter()
+            .fold(self, |widget, pos| &mut widget.children[*pos])
+    }
+
+    /// set the attributes, replacing the existing attributes with the same name
+    fn set_attributes(&mut self, attrs: &[&Attribute<MSG>]) {
+        for att in attrs {
+            self.attrs.retain(|existing| existing.name() != att.name());
+        }
+        self.attrs.extend(attrs.iter().map(|att| (*att).clone()));
+    }
+
+    /// remove the attributes with the same name
+    fn remove_attributes(&mut self, attrs: &[&Attribute<MSG>]) {
+        self.attrs.retain(|existing| {
+            !attrs.iter().any(|att| existing.name() == att.name())
+        });
+    }
+}
+
+impl<APP, MSG> Clone for HeadlessBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        HeadlessBackend {
+            app: Rc::clone(&self.app),
+            current_vdom: Rc::clone(&self.current_vdom),
+            root_widget: Rc::clone(&self.root_widget),
+            subscriptions: Rc::clone(&self.subscriptions),
+        }
+    }
+}
+
+impl<APP, MSG> HeadlessBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    /// mount the app into an in-memory widget tree and execute its init Cmd
+    pub fn new(app: APP) -> Self {
+        let current_vdom = app.view();
+        let root_widget = HeadlessWidget::from_node(&current_vdom);
+        let backend = HeadlessBackend {
+            app: Rc::new(RefCell::new(app)),
+            current_vdom: Rc::new(RefCell::new(current_vdom)),
+            root_widget: Rc::new(RefCell::new(root_widget)),
+            subscriptions: Rc::new(RefCell::new(Subscriptions::new())),
+        };
+        backend.autofocus();
+        backend.update_subscriptions();
+        let cmd = backend.app.borrow().init();
+        cmd.emit(Rc::new(backend.clone()));
+        backend
+    }
+
+    /// returns a reference to the app, used for inspecting its state
+    pub fn app(&self) -> Ref<'_, APP> {
+        self.app.borrow()
+    }
+
+    /// returns the root of the in-memory widget tree
+    pub fn root_widget(&self) -> Ref<'_, HeadlessWidget<MSG>> {
+        self.root_widget.borrow()
+    }
+
+    /// returns true if a widget matches the selector
+    pub fn exists(&self, selector: &Selector) -> bool {
+        self.root_widget.borrow().find(selector).is_some()
+    }
+
+    /// return the value of the attribute key of the widget matched by the selector
+    pub fn get_value(
+        &self,
+        selector: &Selector,
+        key: AttribKey,
+    ) -> Option<Value> {
+        self.root_widget
+            .borrow()
+            .find(selector)
+            .and_then(|widget| widget.get_value(key).cloned())
+    }
+
+    /// click the widget matched by the selector
+    pub fn click(&self, selector: &Selector) -> bool {
+        self.fire(selector, AttribKey::ClickEvent, MouseEvent::click(0, 0))
+    }
+
+    /// type in the value into the widget matched by the selector
+    pub fn input<V: Into<Value>>(&self, selector: &Selector, value: V) -> bool {
+        self.fire(selector, AttribKey::InputEvent, InputEvent::new(value))
+    }
+
+    /// press a key in the widget matched by the selector
+    pub fn keypress(&self, selector: &Selector, key_event: KeyEvent) -> bool {
+        self.fire(selector, AttribKey::KeyEvent, key_event)
+    }
+
+    /// press enter in the widget matched by the selector, this triggers
+    /// the `on_enter` callbacks
+    pub fn enter(&self, selector: &Selector) -> bool {
+        self.fire(selector, AttribKey::Activate, KeyEvent::enter())
+    }
+
+    /// choose the files in the file chooser matched by the selector, this is
+    /// the scripted stand-in of the file dialog which the user interacts with
+    pub fn choose_files<P: ToString>(
+        &self,
+        selector: &Selector,
+        paths: &[P],
+    ) -> bool {
+        let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+        self.fire(selector, AttribKey::FilesEvent, InputEvent::new(paths))
+    }
+
+    /// activate the menu item matched by the selector, the same as clicking
+    /// it or pressing its accelerator
+    pub fn activate(&self, selector: &Selector) -> bool {
+        let value = match self.root_widget.borrow().find(selector) {
+            Some(widget) => get_activate_value(&widget.widget, &widget.attrs),
+            None => return false,
+        };
+        self.fire(selector, AttribKey::ActivateEvent, InputEvent::new(value))
+    }
+
+    /// press the key chord anywhere in the app, the shortcuts of the
+    /// component are matched first before the accelerators of the menu items
+    pub fn press_keys(&self, key_event: KeyEvent) -> bool {
+        let msg = find_shortcut(&*self.app.borrow(), &key_event);
+        match msg {
+            Some(msg) => {
+                self.dispatch(msg);
+                true
+            }
+            None => self
+                .activate(&Selector::Accelerator(key_event.to_accelerator())),
+        }
+    }
+
+    /// focus the widget matched by the selector, or the first focusable
+    /// widget inside it. Returns false if there is no widget to focus.
+    pub fn focus(&self, selector: &Selector) -> bool {
+        let path = self
+            .root_widget
+            .borrow()
+            .find_path(&|widget| widget.is_match(selector), &mut vec![]);
+        match path {
+            Some(path) => self.focus_path(&path),
+            None => false,
+        }
+    }
+
+    /// returns true if the widget matched by the selector has the focus
+    pub fn is_focused(&self, selector: &Selector) -> bool {
+        self.root_widget
+            .borrow()
+            .find(selector)
+            .map(|widget| widget.focused)
+            .unwrap_or(false)
+    }
+
+    /// press Tab, the focus is moved to the next focusable widget in the
+    /// order of the view and wraps around to the first one
+    pub fn tab(&self) -> bool {
+        self.move_focus(true)
+    }
+
+    /// press BackTab, the focus is moved to the previous focusable widget in
+    /// the order of the view and wraps around to the last one
+    pub fn back_tab(&self) -> bool {
+        self.move_focus(false)
+    }
+
+    fn move_focus(&self, forward: bool) -> bool {
+        let (paths, current) = {
+            let root_widget = self.root_widget.borrow();
+            let mut paths = vec![];
+            root_widget.focusable_paths(&mut vec![], &mut paths);
+            let current = paths
+                .iter()
+                .position(|path| root_widget.widget_at(path).focused);
+            (paths, current)
+        };
+        let len = paths.len();
+        if len == 0 {
+            return false;
+        }
+        let next = match (current, forward) {
+            (Some(current), true) => (current + 1) % len,
+            (Some(current), false) => (current + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        self.focus_path(&paths[next])
+    }
+
+    /// move the focus to the first focusable widget at or inside the path,
+    /// the blur callbacks of the previously focused widget are triggered
+    /// before the focus callbacks of the newly focused widget
+    fn focus_path(&self, path: &[usize]) -> bool {
+        let msgs =
+            {
+                let mut root_widget = self.root_widget.borrow_mut();
+                let mut paths = vec![];
+                root_widget
+                    .widget_at(path)
+                    .focusable_paths(&mut path.to_vec(), &mut paths);
+                let target = match paths.into_iter().next() {
+                    Some(target) => target,
+                    None => return false,
+                };
+                if root_widget.widget_at(&target).focused {
+                    return true;
+                }
+                let mut msgs = vec![];
+                if let Some(previous) =
+                    root_widget.find_path(&|widget| widget.focused, &mut vec![])
+                {
+                    let widget = root_widget.widget_at_mut(&previous);
+                    widget.focused = false;
+                    msgs.extend(widget.trigger(
+                        AttribKey::BlurEvent,
+                        Event::BlurEvent(FocusEvent),
+                    ));
+                }
+                let widget = root_widget.widget_at_mut(&target);
+                widget.focused = true;
+                msgs.extend(widget.trigger(
+                    AttribKey::FocusEvent,
+                    Event::FocusEvent(FocusEvent),
+                ));
+                msgs
+            };
+        for msg in msgs {
+            self.dispatch(msg);
+        }
+        true
+    }
+
+    /// focus the first widget with autofocus which is newly shown
+    fn autofocus(&self) {
+        let path = self.root_widget.borrow_mut().take_autofocus(&mut vec![]);
+        if let Some(path) = path {
+            self.focus_path(&path);
+        }
+    }
+
+    /// advance the clock of the subscriptions by the duration, the msg of
+    /// each subscription is dispatched every time its interval has passed
+    pub fn advance(&self, duration: Duration) {
+        let mut remaining = duration;
+        loop {
+            let next = self
+                .subscriptions
+                .borrow()
+                .running()
+                .iter()
+                .enumerate()
+                .map(|(index, running)| (index, running.handle.get()))
+                .filter(|(_index, due)| *due <= remaining)
+                .min_by_key(|(_index, due)| *due);
+            let (index, due) = match next {
+                Some(next) => next,
+                None => break,
+            };
+            remaining -= due;
+            let msg_fn = {
+                let subscriptions = self.subscriptions.borrow();
+                let running = subscriptions.running();
+                for sub in running {
+                    sub.handle.set(sub.handle.get() - due);
+                }
+                running[index]
+                    .handle
+                    .set(interval_of(running[index].interval));
+                Rc::clone(&running[index].msg_fn)
+            };
+            self.dispatch(emit_sub(&msg_fn));
+        }
+        for sub in self.subscriptions.borrow().running() {
+            sub.handle.set(sub.handle.get() - remaining);
+        }
+    }
+
+    /// diff the subscriptions of the app, the new subscriptions are due
+    /// after their interval
+    fn update_subscriptions(&self) {
+        let subs = self.app.borrow().subscriptions();
+        self.subscriptions.borrow_mut().update(
+            subs,
+            |interval, _msg_fn| Cell::new(interval_of(interval)),
+            |_due| (),
+        );
+    }
+
+    /// fire the event to the callbacks of the event key in the widget
+    /// matched by the selector, then dispatch the resulting msgs.
+    /// Returns false if no widget or callback is found.
+    pub fn fire<E: Into<Event>>(
+        &self,
+        selector: &Selector,
+        key: AttribKey,
+        event: E,
+    ) -> bool {
+        let msgs = match self.root_widget.borrow().find(selector) {
+            Some(widget) => widget.trigger(key, event.into()),
+            None => return false,
+        };
+        let is_fired = !msgs.is_empty();
+        for msg in msgs {
+            self.dispatch(msg);
+        }
+        is_fired
+    }
+}
+
+impl<APP, MSG> Backend<APP, MSG> for HeadlessBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    fn init(app: APP) {
+        HeadlessBackend::new(app);
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG> for HeadlessBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        let cmd = self.app.borrow_mut().update(msg);
+        let new_view = self.app.borrow().view();
+        {
+            let previous_vdom = self.current_vdom.borrow();
+            let diff = mt_dom::diff_with_key(
+                &previous_vdom,
+                &new_view,
+                &AttribKey::Key,
+            );
+            apply_patches::apply_patches(
+                &mut self.root_widget.borrow_mut(),
+                &diff,
+            );
+        }
+        *self.current_vdom.borrow_mut() = new_view;
+        self.autofocus();
+        self.update_subscriptions();
+        cmd.emit(Rc::new(self.clone()));
+    }
+
+    fn focus(&self, id: &str) {
+        HeadlessBackend::focus(self, &Selector::Id(id.to_string()));
+    }
+}
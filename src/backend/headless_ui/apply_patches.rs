@@ -0,0 +1,130 @@
+use super::HeadlessWidget;
+use crate::{AttribKey, Patch, Value};
+use mt_dom::{
+    attr,
+    patch::{
+        AddAttributes, AppendChildren, ChangeText, InsertNode,
+        RemoveAttributes, RemoveNode, ReplaceNode,
+    },
+};
+use std::{collections::HashMap, fmt::Debug};
+
+/// apply the patches to the in-memory widget tree.
+///
+/// The node_idx of the patches refers to the old tree, so the path of each
+/// node is collected first, then the patches are applied from the last node_idx
+/// to the first, such that inserting or removing a node will not shift the
+/// path of the nodes which are yet to be patched.
+pub fn apply_patches<MSG>(
+    root_widget: &mut HeadlessWidget<MSG>,
+    patches: &[Patch<MSG>],
+) where
+    MSG: Clone + Debug + 'static,
+{
+    let mut paths = HashMap::new();
+    find_paths(root_widget, &mut vec![], &mut 0, &mut paths);
+
+    let mut ordered_patches: Vec<(usize, &Patch<MSG>)> =
+        patches.iter().enumerate().collect();
+    ordered_patches.sort_by_key(|(order, patch)| patch_sort_key(*order, patch));
+
+    for (_, patch) in ordered_patches {
+        let patch_node_idx = patch.node_idx();
+        let path = paths
+            .get(&patch_node_idx)
+            .expect("must have a node to patch");
+        match patch {
+            Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+                find_widget_mut(root_widget, path).set_attributes(attrs);
+            }
+            Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
+                find_widget_mut(root_widget, path).remove_attributes(attrs);
+            }
+            Patch::AppendChildren(AppendChildren { children, .. }) => {
+                let widget = find_widget_mut(root_widget, path);
+                for (_child_idx, child) in children {
+                    widget.children.push(HeadlessWidget::from_node(child));
+                }
+            }
+            Patch::InsertNode(InsertNode { node, .. }) => {
+                let (parent_path, child_pos) = path
+                    .split_last()
+                    .map(|(pos, parent)| (parent, *pos))
+                    .expect("must not insert a node before the root node");
+                find_widget_mut(root_widget, parent_path)
+                    .children
+                    .insert(child_pos, HeadlessWidget::from_node(node));
+            }
+            Patch::RemoveNode(RemoveNode { .. }) => {
+                let (parent_path, child_pos) = path
+                    .split_last()
+                    .map(|(pos, parent)| (parent, *pos))
+                    .expect("must not remove the root node");
+                find_widget_mut(root_widget, parent_path)
+                    .children
+                    .remove(child_pos);
+            }
+            Patch::ReplaceNode(ReplaceNode { replacement, .. }) => {
+                *find_widget_mut(root_widget, path) =
+                    HeadlessWidget::from_node(replacement);
+            }
+            // text nodes are labels
+            Patch::ChangeText(ChangeText { new, .. }) => {
+                let value =
+                    attr(AttribKey::Value, Value::from(new.text.to_string()));
+                find_widget_mut(root_widget, path).set_attributes(&[&value]);
+            }
+        }
+    }
+}
+
+/// patches are sorted by descending node_idx, patches on the same node
+/// are ordered such that the attributes and children are set first
+/// before the node is removed or replaced, then the nodes inserted before it
+/// are inserted in reverse order so they end up in their original order.
+fn patch_sort_key<MSG>(
+    order: usize,
+    patch: &Patch<MSG>,
+) -> (std::cmp::Reverse<usize>, u8, isize) {
+    let node_idx = std::cmp::Reverse(patch.node_idx());
+    match patch {
+        Patch::AddAttributes(_)
+        | Patch::RemoveAttributes(_)
+        | Patch::ChangeText(_) => (node_idx, 0, order as isize),
+        Patch::AppendChildren(_) => (node_idx, 1, order as isize),
+        Patch::RemoveNode(_) | Patch::ReplaceNode(_) => {
+            (node_idx, 2, order as isize)
+        }
+        Patch::InsertNode(_) => (node_idx, 3, -(order as isize)),
+    }
+}
+
+/// collect the path of each node in the tree, the path is the position of
+/// the node in each of its ancestor's children
+fn find_paths<MSG>(
+    widget: &HeadlessWidget<MSG>,
+    cur_path: &mut Vec<usize>,
+    cur_node_idx: &mut usize,
+    paths: &mut HashMap<usize, Vec<usize>>,
+) {
+    paths.insert(*cur_node_idx, cur_path.clone());
+    for (pos, child) in widget.children.iter().enumerate() {
+        *cur_node_idx += 1;
+        cur_path.push(pos);
+        find_paths(child, cur_path, cur_node_idx, paths);
+        cur_path.pop();
+    }
+}
+
+/// find the widget at this path
+fn find_widget_mut<'a, MSG>(
+    root_widget: &'a mut HeadlessWidget<MSG>,
+    path: &[usize],
+) -> &'a mut HeadlessWidget<MSG> {
+    path.iter().fold(root_widget, |widget, pos| {
+        widget
+            .children
+            .get_mut(*pos)
+            .expect("must have a child at this position")
+    })
+}
@@ -1,33 +1,70 @@
 //! native windows gui backend
 //!
-use super::Dispatch;
+use super::{
+    event_router::{EventRouter, NativeEvent},
+    Dispatch,
+};
 use crate::{
-    widget::attribute::find_value, AttribKey, Attribute, Backend, Component,
-    Node, Widget,
+    widget::attribute::{find_value, util::default_value},
+    AttribKey, Attribute, Backend, Component, Node, Patch, Value, Widget,
 };
 use image::{bmp::BMPEncoder, ColorType, GenericImageView, ImageEncoder};
+use mt_dom::{
+    attr,
+    patch::{
+        AddAttributes, AppendChildren, ChangeText, RemoveAttributes, RemoveNode,
+    },
+};
 use native_windows_gui as nwg;
 use nwg::{
-    Bitmap, Button, CheckBox, FlexboxLayout, ImageFrame, Label, RadioButton,
-    RichTextBox, TextBox, TextInput, Window,
+    Bitmap, Button, CheckBox, CheckBoxState, ControlHandle, FlexboxLayout,
+    ImageFrame, Label, RadioButton, RadioButtonState, RichTextBox, TextBox,
+    TextInput, Window,
 };
-use std::{cell::RefCell, fmt, fmt::Debug, marker::PhantomData, rc::Rc};
-use stretch::style::FlexDirection;
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    fmt::Debug,
+    marker::PhantomData,
+    rc::Rc,
+};
+use stretch::style::{FlexDirection, Style};
 
 /// native windows Gui backend
 pub struct NwgBackend<APP, MSG>
 where
     MSG: 'static,
 {
-    #[allow(unused)]
     app: Rc<RefCell<APP>>,
-    #[allow(unused)]
     current_vdom: Rc<RefCell<Node<MSG>>>,
-    root_node: Rc<RefCell<Option<NwgWidget>>>,
+    /// the controls of the nodes of the view in DFS order, these are kept
+    /// here so the controls are not destroyed
+    widgets: Rc<RefCell<Vec<NwgWidget>>>,
+    event_router: Rc<RefCell<EventRouter<usize, MSG>>>,
     window: Rc<Window>,
+    /// whether the patches are being applied, the events emitted by the
+    /// setters of the controls are ignored
+    is_patching: Rc<Cell<bool>>,
     _phantom_msg: PhantomData<MSG>,
 }
 
+impl<APP, MSG> Clone for NwgBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        NwgBackend {
+            app: Rc::clone(&self.app),
+            current_vdom: Rc::clone(&self.current_vdom),
+            widgets: Rc::clone(&self.widgets),
+            event_router: Rc::clone(&self.event_router),
+            window: Rc::clone(&self.window),
+            is_patching: Rc::clone(&self.is_patching),
+            _phantom_msg: PhantomData,
+        }
+    }
+}
+
 impl<APP, MSG> NwgBackend<APP, MSG> {
     fn new(app: APP) -> Self
     where
@@ -37,7 +74,6 @@ impl<APP, MSG> NwgBackend<APP, MSG> {
         println!("1 new");
         nwg::init().expect("Failed to init Native Windows GUI");
         let current_vdom = app.view();
-        let root_vdom = app.view();
 
         let mut window: Window = Window::default();
         Window::builder()
@@ -54,25 +90,22 @@ impl<APP, MSG> NwgBackend<APP, MSG> {
 
         println!("2 new");
 
-        let root_widget: Option<NwgWidget> = None;
-
         let backend = NwgBackend {
             app: Rc::new(RefCell::new(app)),
             current_vdom: Rc::new(RefCell::new(current_vdom)),
-            root_node: Rc::new(RefCell::new(root_widget)),
+            widgets: Rc::new(RefCell::new(vec![])),
+            event_router: Rc::new(RefCell::new(EventRouter::new())),
             window: Rc::new(window),
+            is_patching: Rc::new(Cell::new(false)),
             _phantom_msg: PhantomData,
         };
 
         println!("3 new");
-        println!("3.1 new");
-        let (root_widget, _children_widgets) =
-            NwgWidget::from_node_tree(&backend.window, &backend, root_vdom);
-        println!("3.5 new");
-        *backend.root_node.borrow_mut() = Some(root_widget);
+        backend.rebuild(&backend.current_vdom.borrow());
         println!("3.6 new");
 
         let events_window = backend.window.clone();
+        let events_backend = backend.clone();
 
         println!("4 new");
         let handler = nwg::full_bind_event_handler(
@@ -86,17 +119,224 @@ impl<APP, MSG> NwgBackend<APP, MSG> {
                             nwg::stop_thread_dispatch();
                         }
                     }
-                    Event::OnButtonClick => {}
+                    Event::OnButtonClick | Event::OnTextInput => {
+                        // the controls are not borrowed while dispatching
+                        let msgs = events_backend.route_event(evt, handle);
+                        for msg in msgs {
+                            events_backend.dispatch(msg);
+                        }
+                    }
                     _ => {}
                 }
             },
         );
+        let cmd = backend.app.borrow().init();
+        cmd.emit(Rc::new(backend.clone()));
         nwg::dispatch_thread_events();
         nwg::unbind_event_handler(&handler);
         println!("last part new");
 
         backend
     }
+
+    /// recreate the controls of the window from the view
+    fn rebuild(&self, view: &Node<MSG>)
+    where
+        MSG: Debug + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let widgets = NwgWidget::from_node_tree(&self.window, self, view);
+        *self.widgets.borrow_mut() = widgets;
+        self.register_callbacks(view);
+    }
+
+    /// apply the patches to the existing controls, the patches are applied
+    /// from the last node_idx to the first such that adding or removing
+    /// controls will not shift the controls which are yet to be patched.
+    ///
+    /// The flexbox layouts can only append their children, so the controls
+    /// are recreated when a node is inserted or replaced.
+    fn apply_patches(
+        &self,
+        old_vdom: &Node<MSG>,
+        new_vdom: &Node<MSG>,
+        patches: &[Patch<MSG>],
+    ) where
+        MSG: Debug + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let is_structural = patches.iter().any(|patch| {
+            matches!(patch, Patch::InsertNode(_) | Patch::ReplaceNode(_))
+        });
+        if is_structural {
+            self.rebuild(new_vdom);
+            return;
+        }
+        let mut spans = vec![];
+        node_spans(old_vdom, None, &mut spans);
+        let mut ordered_patches: Vec<&Patch<MSG>> = patches.iter().collect();
+        ordered_patches.sort_by_key(|patch| patch_sort_key(patch));
+        {
+            let mut widgets = self.widgets.borrow_mut();
+            for patch in ordered_patches {
+                let node_idx = patch.node_idx();
+                let (parent_idx, span) = spans[node_idx];
+                match patch {
+                    Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+                        for att in attrs {
+                            for value in att.get_plain() {
+                                widgets[node_idx]
+                                    .set_attribute(att.name(), value);
+                            }
+                        }
+                    }
+                    Patch::RemoveAttributes(RemoveAttributes {
+                        attrs, ..
+                    }) => {
+                        for att in attrs {
+                            if let Some(value) = default_value(att.name()) {
+                                widgets[node_idx]
+                                    .set_attribute(att.name(), &value);
+                            }
+                        }
+                    }
+                    Patch::AppendChildren(AppendChildren {
+                        children, ..
+                    }) => {
+                        let mut position = node_idx + span;
+                        for (_child_idx, child) in children {
+                            let child_widgets = NwgWidget::from_node_tree(
+                                &self.window,
+                                self,
+                                child,
+                            );
+                            widgets[node_idx].add_child(&child_widgets[0]);
+                            let len = child_widgets.len();
+                            widgets.splice(position..position, child_widgets);
+                            position += len;
+                        }
+                    }
+                    Patch::RemoveNode(RemoveNode { .. }) => {
+                        if let Some(parent_idx) = parent_idx {
+                            let child = &widgets[node_idx];
+                            widgets[parent_idx].remove_child(child);
+                        }
+                        // the controls are destroyed when dropped
+                        widgets.drain(node_idx..node_idx + span);
+                    }
+                    // the text nodes are labels
+                    Patch::ChangeText(ChangeText { new, .. }) => {
+                        widgets[node_idx].set_attribute(
+                            &AttribKey::Value,
+                            &Value::from(new.text.to_string()),
+                        );
+                    }
+                    Patch::InsertNode(_) | Patch::ReplaceNode(_) => {
+                        unreachable!("the controls are recreated instead")
+                    }
+                }
+            }
+        }
+        self.register_callbacks(new_vdom);
+    }
+
+    /// register the event callbacks of the nodes of the view to their
+    /// controls, which are in the same DFS order as the nodes
+    fn register_callbacks(&self, view: &Node<MSG>)
+    where
+        MSG: 'static,
+    {
+        let widgets = self.widgets.borrow();
+        let mut event_router = self.event_router.borrow_mut();
+        event_router.clear();
+        register_node_callbacks(&widgets, &mut event_router, view, &mut 0);
+    }
+
+    /// convert the nwg event into the msgs of the callbacks attached to the
+    /// control with this handle
+    fn route_event(&self, evt: nwg::Event, handle: ControlHandle) -> Vec<MSG>
+    where
+        MSG: 'static,
+    {
+        // the setters of the controls emit their events while the controls
+        // are mutably borrowed for patching
+        if self.is_patching.get() {
+            return vec![];
+        }
+        let id = match control_id(handle) {
+            Some(id) => id,
+            None => return vec![],
+        };
+        self.widgets
+            .borrow()
+            .iter()
+            .find(|widget| widget.handle() == Some(handle))
+            .and_then(|widget| widget.to_native_event(evt))
+            .map(|native_event| {
+                self.event_router.borrow().route(&id, &native_event)
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// the id of the control used in the event router, which is the address of
+/// its window handle
+fn control_id(handle: ControlHandle) -> Option<usize> {
+    handle.hwnd().map(|hwnd| hwnd as usize)
+}
+
+fn register_node_callbacks<MSG>(
+    widgets: &[NwgWidget],
+    event_router: &mut EventRouter<usize, MSG>,
+    node: &Node<MSG>,
+    node_idx: &mut usize,
+) where
+    MSG: 'static,
+{
+    let id = widgets
+        .get(*node_idx)
+        .and_then(|widget| widget.handle())
+        .and_then(control_id);
+    if let (Some(id), Some(attrs)) = (id, node.get_attributes()) {
+        event_router.register(id, attrs);
+    }
+    for child in node.get_children().unwrap_or(&[]) {
+        *node_idx += 1;
+        register_node_callbacks(widgets, event_router, child, node_idx);
+    }
+}
+
+/// collect the index of the parent and the number of nodes in the subtree
+/// of each of the nodes in DFS order, returning the size of this subtree
+fn node_spans<MSG>(
+    node: &Node<MSG>,
+    parent_idx: Option<usize>,
+    spans: &mut Vec<(Option<usize>, usize)>,
+) -> usize {
+    let node_idx = spans.len();
+    spans.push((parent_idx, 1));
+    let span = 1 + node
+        .get_children()
+        .unwrap_or(&[])
+        .iter()
+        .map(|child| node_spans(child, Some(node_idx), spans))
+        .sum::<usize>();
+    spans[node_idx].1 = span;
+    span
+}
+
+/// patches are sorted by descending node_idx, the attributes and children
+/// of a node are patched before the node is removed
+fn patch_sort_key<MSG>(patch: &Patch<MSG>) -> (std::cmp::Reverse<usize>, u8) {
+    let node_idx = std::cmp::Reverse(patch.node_idx());
+    match patch {
+        Patch::AddAttributes(_)
+        | Patch::RemoveAttributes(_)
+        | Patch::ChangeText(_) => (node_idx, 0),
+        Patch::AppendChildren(_) => (node_idx, 1),
+        Patch::RemoveNode(_) | Patch::ReplaceNode(_) => (node_idx, 2),
+        Patch::InsertNode(_) => (node_idx, 3),
+    }
 }
 
 impl<APP, MSG> Backend<APP, MSG> for NwgBackend<APP, MSG>
@@ -115,7 +355,24 @@ where
     MSG: Debug + 'static,
     APP: Component<MSG> + 'static,
 {
-    fn dispatch(&self, _msg: MSG) {}
+    fn dispatch(&self, msg: MSG) {
+        // the setters of the controls emit their events while patching
+        if self.is_patching.get() {
+            return;
+        }
+        let cmd = self.app.borrow_mut().update(msg);
+        let new_view = self.app.borrow().view();
+        let old_view = self.current_vdom.replace(new_view);
+        {
+            let new_view = self.current_vdom.borrow();
+            let patches =
+                mt_dom::diff_with_key(&old_view, &new_view, &AttribKey::Key);
+            self.is_patching.set(true);
+            self.apply_patches(&old_view, &new_view, &patches);
+            self.is_patching.set(false);
+        }
+        cmd.emit(Rc::new(self.clone()));
+    }
 }
 
 enum NwgWidget {
@@ -159,40 +416,170 @@ impl NwgWidget {
         }
     }
 
+    /// returns the handle of the control, layouts have no handle
+    fn handle(&self) -> Option<ControlHandle> {
+        match self {
+            NwgWidget::Box(_)
+            | NwgWidget::Overlay(_)
+            | NwgWidget::GroupBox(_) => None,
+            NwgWidget::Button(w) => Some(w.handle),
+            NwgWidget::Label(w) => Some(w.handle),
+            NwgWidget::Paragraph(w) => Some(w.handle),
+            NwgWidget::TextInput(w) => Some(w.handle),
+            NwgWidget::TextArea(w) => Some(w.handle),
+            NwgWidget::Checkbox(w) => Some(w.handle),
+            NwgWidget::Radio(w) => Some(w.handle),
+            NwgWidget::Image(w, _) => Some(w.handle),
+        }
+    }
+
+    /// convert the nwg event emitted by this control into a native event
+    fn to_native_event(&self, evt: nwg::Event) -> Option<NativeEvent> {
+        use nwg::Event;
+        match (self, evt) {
+            (NwgWidget::Button(_), Event::OnButtonClick) => {
+                Some(NativeEvent::Click)
+            }
+            (NwgWidget::Checkbox(w), Event::OnButtonClick) => Some(
+                NativeEvent::Toggled(w.check_state() == CheckBoxState::Checked),
+            ),
+            (NwgWidget::Radio(w), Event::OnButtonClick) => {
+                Some(NativeEvent::Toggled(
+                    w.check_state() == RadioButtonState::Checked,
+                ))
+            }
+            (NwgWidget::TextInput(w), Event::OnTextInput) => {
+                Some(NativeEvent::TextChanged(w.text()))
+            }
+            (NwgWidget::TextArea(w), Event::OnTextInput) => {
+                Some(NativeEvent::TextChanged(w.text()))
+            }
+            _ => None,
+        }
+    }
+
+    /// the layout of the box widgets
+    fn layout(&self) -> Option<&FlexboxLayout> {
+        match self {
+            NwgWidget::Box(layout)
+            | NwgWidget::Overlay(layout)
+            | NwgWidget::GroupBox(layout) => Some(layout),
+            _ => None,
+        }
+    }
+
+    /// append the control of the child to the layout of this box widget,
+    /// layouts can not be nested
+    fn add_child(&self, child: &Self) {
+        if let (Some(layout), Some(handle)) = (self.layout(), child.handle()) {
+            layout
+                .add_child(handle, Style::default())
+                .expect("must add the child to the layout");
+        }
+    }
+
+    /// remove the control of the child from the layout of this box widget
+    fn remove_child(&self, child: &Self) {
+        if let (Some(layout), Some(handle)) = (self.layout(), child.handle()) {
+            layout.remove_child(handle);
+        }
+    }
+
+    /// set the attribute of the control which is changed by a patch
+    fn set_attribute(&mut self, key: &AttribKey, value: &Value) {
+        match (self, key) {
+            (NwgWidget::Button(w), AttribKey::Label) => {
+                w.set_text(&value.to_string())
+            }
+            (NwgWidget::Label(w), AttribKey::Value) => {
+                w.set_text(&value.to_string())
+            }
+            (NwgWidget::Paragraph(w), AttribKey::Value) => {
+                w.set_text(&value.to_string())
+            }
+            (NwgWidget::TextInput(w), AttribKey::Value) => {
+                // setting the same text will move the cursor to the start
+                if w.text() != value.to_string() {
+                    w.set_text(&value.to_string());
+                }
+            }
+            (NwgWidget::TextArea(w), AttribKey::Value) => {
+                if w.text() != value.to_string() {
+                    w.set_text(&value.to_string());
+                }
+            }
+            (NwgWidget::Checkbox(w), AttribKey::Label) => {
+                w.set_text(&value.to_string())
+            }
+            (NwgWidget::Checkbox(w), AttribKey::Checked)
+            | (NwgWidget::Checkbox(w), AttribKey::Value) => {
+                w.set_check_state(if value.as_bool() {
+                    CheckBoxState::Checked
+                } else {
+                    CheckBoxState::Unchecked
+                })
+            }
+            (NwgWidget::Radio(w), AttribKey::Label) => {
+                w.set_text(&value.to_string())
+            }
+            (NwgWidget::Radio(w), AttribKey::Checked)
+            | (NwgWidget::Radio(w), AttribKey::Value) => {
+                w.set_check_state(if value.as_bool() {
+                    RadioButtonState::Checked
+                } else {
+                    RadioButtonState::Unchecked
+                })
+            }
+            (NwgWidget::Image(w, bitmap), AttribKey::Data) => {
+                if let Some(blob) = value.as_bytes() {
+                    let (new_bitmap, _size) = image_bitmap(blob);
+                    w.set_bitmap(Some(&new_bitmap));
+                    *bitmap = new_bitmap;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// create the controls of the node and its descendants,
+    /// the controls are returned in DFS order of the nodes
     fn from_node_tree<MSG, DSP>(
         window: &Window,
         program: &DSP,
-        widget_node: crate::Node<MSG>,
-    ) -> (Self, Vec<Self>)
+        widget_node: &crate::Node<MSG>,
+    ) -> Vec<Self>
     where
         MSG: Debug + 'static,
         DSP: Dispatch<MSG> + 'static,
     {
-        println!("from node tree..");
         match widget_node {
             crate::Node::Element(element) => {
-                println!("element...");
-                let children: Vec<(Self, Vec<Self>)> = element
+                let descendants: Vec<Vec<Self>> = element
                     .children
-                    .into_iter()
+                    .iter()
                     .map(|child| Self::from_node_tree(window, program, child))
                     .collect();
-
-                let mut all_children = vec![];
-                let (direct, indirect): (Vec<Self>, Vec<Vec<Self>>) =
-                    children.into_iter().unzip();
+                let direct: Vec<&Self> =
+                    descendants.iter().map(|widgets| &widgets[0]).collect();
                 let nwg_widget = Self::from_node(
                     window,
                     program,
-                    element.tag,
+                    element.tag.clone(),
                     &direct,
-                    element.attrs,
+                    &element.attrs,
                 );
-                all_children.extend(direct);
-                all_children.extend(indirect.into_iter().flatten());
-                (nwg_widget, all_children)
+                let mut widgets = vec![nwg_widget];
+                widgets.extend(descendants.into_iter().flatten());
+                widgets
             }
-            crate::Node::Text(_txt) => unreachable!(),
+            // text nodes are displayed as labels
+            crate::Node::Text(txt) => vec![Self::from_node(
+                window,
+                program,
+                Widget::Label,
+                &[],
+                &[attr(AttribKey::Value, Value::from(txt.text.to_string()))],
+            )],
         }
     }
 
@@ -200,8 +587,8 @@ impl NwgWidget {
         window: &Window,
         _program: &DSP,
         widget: Widget,
-        children: &Vec<Self>,
-        attrs: Vec<Attribute<MSG>>,
+        children: &[&Self],
+        attrs: &[Attribute<MSG>],
     ) -> Self
     where
         MSG: Debug + 'static,
@@ -341,7 +728,7 @@ impl NwgWidget {
             }
             Widget::Button => {
                 println!("button..");
-                let label = find_value(AttribKey::Label, &attrs)
+                let label = find_value(AttribKey::Label, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
@@ -358,7 +745,7 @@ impl NwgWidget {
 
             Widget::Label => {
                 println!("label..");
-                let label_value = find_value(AttribKey::Value, &attrs)
+                let label_value = find_value(AttribKey::Value, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
@@ -373,7 +760,7 @@ impl NwgWidget {
                 NwgWidget::Label(lbl)
             }
             Widget::Paragraph => {
-                let txt = find_value(AttribKey::Value, &attrs)
+                let txt = find_value(AttribKey::Value, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
                 let mut rtb = RichTextBox::default();
@@ -388,7 +775,7 @@ impl NwgWidget {
             }
             Widget::TextInput => {
                 println!("textinput..");
-                let value = find_value(AttribKey::Value, &attrs)
+                let value = find_value(AttribKey::Value, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
@@ -404,7 +791,7 @@ impl NwgWidget {
             }
             Widget::TextArea => {
                 println!("textinput..");
-                let value = find_value(AttribKey::Value, &attrs)
+                let value = find_value(AttribKey::Value, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
@@ -421,17 +808,23 @@ impl NwgWidget {
 
             Widget::Checkbox => {
                 println!("checkbox..");
-                let label = find_value(AttribKey::Label, &attrs)
+                let label = find_value(AttribKey::Label, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
-                let _value = find_value(AttribKey::Value, &attrs)
+                let checked = find_value(AttribKey::Checked, attrs)
+                    .or_else(|| find_value(AttribKey::Value, attrs))
                     .map(|v| v.as_bool())
                     .unwrap_or(false);
 
                 let mut checkbox = CheckBox::default();
                 CheckBox::builder()
                     .text(&label)
+                    .check_state(if checked {
+                        CheckBoxState::Checked
+                    } else {
+                        CheckBoxState::Unchecked
+                    })
                     .parent(window)
                     .build(&mut checkbox)
                     .expect("must build checkbox");
@@ -440,17 +833,23 @@ impl NwgWidget {
             }
             Widget::Radio => {
                 println!("radio button..");
-                let label = find_value(AttribKey::Label, &attrs)
+                let label = find_value(AttribKey::Label, attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
-                let _value = find_value(AttribKey::Value, &attrs)
+                let checked = find_value(AttribKey::Checked, attrs)
+                    .or_else(|| find_value(AttribKey::Value, attrs))
                     .map(|v| v.as_bool())
                     .unwrap_or(false);
 
                 let mut radio = RadioButton::default();
                 RadioButton::builder()
                     .text(&label)
+                    .check_state(if checked {
+                        RadioButtonState::Checked
+                    } else {
+                        RadioButtonState::Unchecked
+                    })
                     .parent(window)
                     .build(&mut radio)
                     .expect("must build checkbox");
@@ -459,29 +858,12 @@ impl NwgWidget {
             }
             Widget::Image => {
                 let empty = vec![];
-                let blob = find_value(AttribKey::Data, &attrs)
+                let blob = find_value(AttribKey::Data, attrs)
                     .map(|v| v.as_bytes())
                     .flatten()
                     .unwrap_or(&empty);
 
-                let img = image::load_from_memory(&blob).expect("should load");
-                let (width, height) = img.dimensions();
-                let mut bytes: Vec<u8> = vec![];
-
-                BMPEncoder::new(&mut bytes)
-                    .write_image(
-                        &img.to_rgb().into_raw(),
-                        width,
-                        height,
-                        ColorType::Rgb8,
-                    )
-                    .expect("must write image");
-
-                let mut bitmap = Bitmap::default();
-                Bitmap::builder()
-                    .source_bin(Some(&bytes))
-                    .build(&mut bitmap)
-                    .expect("must not error");
+                let (bitmap, (width, height)) = image_bitmap(blob);
 
                 let mut image_frame = ImageFrame::default();
                 ImageFrame::builder()
@@ -495,7 +877,7 @@ impl NwgWidget {
             }
             Widget::Svg => {
                 let empty = vec![];
-                let bytes = find_value(AttribKey::Data, &attrs)
+                let bytes = find_value(AttribKey::Data, attrs)
                     .map(|v| v.as_bytes())
                     .flatten()
                     .unwrap_or(&empty);
@@ -574,3 +956,21 @@ impl NwgWidget {
         }
     }
 }
+
+/// convert the image data into a bitmap, returning it with its size
+fn image_bitmap(blob: &[u8]) -> (Bitmap, (u32, u32)) {
+    let img = image::load_from_memory(blob).expect("should load");
+    let (width, height) = img.dimensions();
+    let mut bytes: Vec<u8> = vec![];
+
+    BMPEncoder::new(&mut bytes)
+        .write_image(&img.to_rgb().into_raw(), width, height, ColorType::Rgb8)
+        .expect("must write image");
+
+    let mut bitmap = Bitmap::default();
+    Bitmap::builder()
+        .source_bin(Some(&bytes))
+        .build(&mut bitmap)
+        .expect("must not error");
+    (bitmap, (width, height))
+}
@@ -0,0 +1,40 @@
+//! keeps the backend conversion hooks of the custom widgets
+//!
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+
+thread_local! {
+    /// the hooks are keyed by the type of the hook trait and the name of the custom widget
+    static CUSTOM_WIDGET_HOOKS: RefCell<HashMap<(TypeId, String), Rc<dyn Any>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// register the hook of the custom widget with this name
+pub(crate) fn register_hook<HOOK>(name: &str, hook: Rc<HOOK>)
+where
+    HOOK: ?Sized + 'static,
+{
+    CUSTOM_WIDGET_HOOKS.with(|hooks| {
+        hooks
+            .borrow_mut()
+            .insert((TypeId::of::<HOOK>(), name.to_string()), Rc::new(hook));
+    });
+}
+
+/// find the registered hook of the custom widget with this name
+pub(crate) fn find_hook<HOOK>(name: &str) -> Option<Rc<HOOK>>
+where
+    HOOK: ?Sized + 'static,
+{
+    CUSTOM_WIDGET_HOOKS.with(|hooks| {
+        hooks
+            .borrow()
+            .get(&(TypeId::of::<HOOK>(), name.to_string()))
+            .and_then(|hook| hook.downcast_ref::<Rc<HOOK>>())
+            .cloned()
+    })
+}
@@ -0,0 +1,357 @@
+//! remote backend, the component runs in this process while the view is
+//! rendered by a renderer in another process.
+//!
+//! The initial view is sent to the renderer as a serializable node tree, then
+//! each update only sends the patches. The callbacks stay in this process,
+//! the renderer sends back the events together with the handler id of the
+//! callbacks which are then dispatched to the component.
+use super::Dispatch;
+use crate::shortcut::find_shortcut;
+use crate::sub::{emit_sub, Subscriptions};
+use crate::widget::{
+    event::KeyEvent,
+    remote::{HandlerId, RemoteNode, RemotePatch},
+};
+use crate::{AttribKey, Attribute, Backend, Component, Event, Node};
+use mt_dom::Callback;
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    io::{self, Read, Write},
+    rc::Rc,
+    sync::mpsc::RecvTimeoutError,
+    time::Instant,
+};
+
+mod renderer;
+pub mod wire;
+
+pub use renderer::{pump, RemoteRenderer, RendererMsg};
+pub use wire::{ToComponent, ToRenderer, PROTOCOL_VERSION};
+
+/// the callbacks of each handler id
+type Handlers<MSG> = Rc<RefCell<HashMap<HandlerId, Vec<Callback<Event, MSG>>>>>;
+
+/// backend which sends the view of the app to a remote renderer
+pub struct RemoteBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Rc<RefCell<APP>>,
+    current_vdom: Rc<RefCell<Node<MSG>>>,
+    /// the view as it is mounted in the renderer
+    remote_vdom: Rc<RefCell<RemoteNode>>,
+    /// the callbacks of the handlers which are still in the remote view
+    handlers: Handlers<MSG>,
+    next_handler_id: Rc<Cell<usize>>,
+    /// the key chords of the shortcuts as they are sent to the renderer
+    shortcuts: Rc<RefCell<Vec<KeyEvent>>>,
+    /// the subscriptions of the app and the time when they are due
+    subscriptions: Rc<RefCell<Subscriptions<MSG, Cell<Instant>>>>,
+    writer: Rc<RefCell<Box<dyn Write>>>,
+    /// the error when sending to the renderer, the app is no longer updated
+    /// and `run` returns this error
+    send_error: Rc<RefCell<Option<io::Error>>>,
+}
+
+impl<APP, MSG> Clone for RemoteBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        RemoteBackend {
+            app: Rc::clone(&self.app),
+            current_vdom: Rc::clone(&self.current_vdom),
+            remote_vdom: Rc::clone(&self.remote_vdom),
+            handlers: Rc::clone(&self.handlers),
+            next_handler_id: Rc::clone(&self.next_handler_id),
+            shortcuts: Rc::clone(&self.shortcuts),
+            subscriptions: Rc::clone(&self.subscriptions),
+            writer: Rc::clone(&self.writer),
+            send_error: Rc::clone(&self.send_error),
+        }
+    }
+}
+
+impl<APP, MSG> RemoteBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    /// mount the app into the renderer on the other side of the writer,
+    /// then execute its init Cmd
+    pub fn new<W>(app: APP, writer: W) -> io::Result<Self>
+    where
+        W: Write + 'static,
+    {
+        let handlers = Rc::new(RefCell::new(HashMap::new()));
+        let next_handler_id = Rc::new(Cell::new(0));
+        let current_vdom = app.view();
+        let root = RemoteNode::from_node_with(
+            &current_vdom,
+            &mut register_handler(&handlers, &next_handler_id),
+        );
+        let shortcuts = shortcut_keys(&app);
+        let mut writer: Box<dyn Write> = Box::new(writer);
+        wire::write_frame(
+            &mut writer,
+            &ToRenderer::Mount {
+                title: app.title(),
+                root: root.clone(),
+                shortcuts: shortcuts.clone(),
+            },
+        )?;
+        let backend = RemoteBackend {
+            app: Rc::new(RefCell::new(app)),
+            current_vdom: Rc::new(RefCell::new(current_vdom)),
+            remote_vdom: Rc::new(RefCell::new(root)),
+            handlers,
+            next_handler_id,
+            shortcuts: Rc::new(RefCell::new(shortcuts)),
+            subscriptions: Rc::new(RefCell::new(Subscriptions::new())),
+            writer: Rc::new(RefCell::new(writer)),
+            send_error: Rc::new(RefCell::new(None)),
+        };
+        backend.update_subscriptions();
+        let cmd = backend.app.borrow().init();
+        cmd.emit(Rc::new(backend.clone()));
+        Ok(backend)
+    }
+
+    /// mount the app into the renderer listening on this unix socket, then
+    /// receive its events until the renderer is closed
+    #[cfg(unix)]
+    pub fn connect<P>(app: APP, path: P) -> io::Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        let reader = stream.try_clone()?;
+        RemoteBackend::new(app, stream)?.run(reader)
+    }
+
+    /// returns a reference to the app, used for inspecting its state
+    pub fn app(&self) -> Ref<'_, APP> {
+        self.app.borrow()
+    }
+
+    /// receive the events from the renderer and dispatch the msgs of their
+    /// callbacks, this blocks until the renderer is closed or the patches
+    /// can not be sent to it. The events are received in a separate thread,
+    /// so the subscriptions are ticked while waiting for them.
+    pub fn run<R>(&self, reader: R) -> io::Result<()>
+    where
+        R: Read + Send + 'static,
+    {
+        let receiver = wire::spawn_reader(reader);
+        loop {
+            if let Some(e) = self.send_error.borrow_mut().take() {
+                return Err(e);
+            }
+            let next_due = self.subscriptions.borrow().next_due();
+            let received = match next_due {
+                Some(due) => receiver.recv_timeout(
+                    due.saturating_duration_since(Instant::now()),
+                ),
+                None => receiver.recv().map_err(RecvTimeoutError::from),
+            };
+            match received {
+                Ok(Ok(payload)) => self.receive(wire::decode(&payload)?),
+                Ok(Err(e)) if wire::is_closed(&e) => return Ok(()),
+                Ok(Err(e)) => return Err(e),
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+            self.tick();
+        }
+    }
+
+    /// dispatch the msgs of the message received from the renderer
+    fn receive(&self, message: ToComponent) {
+        match message {
+            ToComponent::Event(handler_id, event) => {
+                for msg in self.emit_handler(&handler_id, *event) {
+                    self.dispatch(msg);
+                }
+            }
+            ToComponent::Shortcut(key_event) => {
+                let msg = find_shortcut(&*self.app.borrow(), &key_event);
+                if let Some(msg) = msg {
+                    self.dispatch(msg);
+                }
+            }
+        }
+    }
+
+    /// diff the subscriptions of the app, the new subscriptions are due
+    /// after their interval
+    fn update_subscriptions(&self) {
+        let subs = self.app.borrow().subscriptions();
+        self.subscriptions.borrow_mut().update_due(subs);
+    }
+
+    /// dispatch the msgs of the subscriptions which are due
+    fn tick(&self) {
+        let due = self.subscriptions.borrow().take_due(Instant::now());
+        for msg_fn in due {
+            self.dispatch(emit_sub(&msg_fn));
+        }
+    }
+
+    /// emit the callbacks of the handler, returning the resulting msgs.
+    /// The events of handlers which are already removed from the view
+    /// are ignored.
+    pub fn emit_handler(
+        &self,
+        handler_id: &HandlerId,
+        event: Event,
+    ) -> Vec<MSG> {
+        self.handlers
+            .borrow()
+            .get(handler_id)
+            .map(|callbacks| {
+                callbacks.iter().map(|cb| cb.emit(event.clone())).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// send the message to the renderer, the error is kept so `run` returns
+    /// it. Returns false when the message is not sent.
+    fn send(&self, message: &ToRenderer) -> bool {
+        match wire::write_frame(&mut *self.writer.borrow_mut(), message) {
+            Ok(()) => true,
+            Err(e) => {
+                log::error!("unable to send to the renderer: {}", e);
+                *self.send_error.borrow_mut() = Some(e);
+                false
+            }
+        }
+    }
+
+    /// send the shortcuts of the app when they are changed
+    fn update_shortcuts(&self) -> bool {
+        let shortcuts = shortcut_keys(&*self.app.borrow());
+        if *self.shortcuts.borrow() == shortcuts {
+            return true;
+        }
+        let is_sent = self.send(&ToRenderer::Shortcuts(shortcuts.clone()));
+        if is_sent {
+            *self.shortcuts.borrow_mut() = shortcuts;
+        }
+        is_sent
+    }
+
+    /// remove the callbacks of the handlers which are no longer in the
+    /// remote view
+    fn prune_handlers(&self) {
+        let remote_vdom = self.remote_vdom.borrow();
+        let active: HashSet<&HandlerId> =
+            remote_vdom.handler_ids().into_iter().collect();
+        self.handlers
+            .borrow_mut()
+            .retain(|handler_id, _| active.contains(handler_id));
+    }
+}
+
+/// the key chords of the shortcuts of the app
+fn shortcut_keys<APP, MSG>(app: &APP) -> Vec<KeyEvent>
+where
+    APP: Component<MSG>,
+{
+    app.shortcuts()
+        .into_iter()
+        .map(|shortcut| shortcut.key_event)
+        .collect()
+}
+
+/// returns a function which stores the callbacks of the attribute
+/// under a newly created handler id.
+/// A new id is created for each attribute, since the renderer keeps the
+/// handler ids of the widgets which are not patched.
+fn register_handler<MSG>(
+    handlers: &Handlers<MSG>,
+    next_handler_id: &Rc<Cell<usize>>,
+) -> impl FnMut(&Attribute<MSG>) -> HandlerId
+where
+    MSG: 'static,
+{
+    let handlers = Rc::clone(handlers);
+    let next_handler_id = Rc::clone(next_handler_id);
+    move |att: &Attribute<MSG>| {
+        let handler_id = HandlerId {
+            id: next_handler_id.get(),
+            event: att.name().clone(),
+        };
+        next_handler_id.set(handler_id.id + 1);
+        let callbacks = att.get_callback().into_iter().cloned().collect();
+        handlers.borrow_mut().insert(handler_id.clone(), callbacks);
+        handler_id
+    }
+}
+
+impl<APP, MSG> Backend<APP, MSG> for RemoteBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    /// communicate with the renderer through stdin and stdout,
+    /// the app must not print into stdout
+    fn init(app: APP) {
+        let result = RemoteBackend::new(app, io::stdout())
+            .and_then(|backend| backend.run(io::stdin()));
+        if let Err(e) = result {
+            log::error!("the renderer is disconnected: {}", e);
+        }
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG> for RemoteBackend<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: Clone + Debug + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        if self.send_error.borrow().is_some() {
+            return;
+        }
+        let cmd = self.app.borrow_mut().update(msg);
+        let new_view = self.app.borrow().view();
+        {
+            let previous_vdom = self.current_vdom.borrow();
+            let diff = mt_dom::diff_with_key(
+                &previous_vdom,
+                &new_view,
+                &AttribKey::Key,
+            );
+            if !diff.is_empty() {
+                let mut register_handler =
+                    register_handler(&self.handlers, &self.next_handler_id);
+                let patches: Vec<RemotePatch> = diff
+                    .iter()
+                    .map(|patch| {
+                        RemotePatch::from_patch(patch, &mut register_handler)
+                    })
+                    .collect();
+                if !self.send(&ToRenderer::Patch(patches.clone())) {
+                    return;
+                }
+                self.remote_vdom.borrow_mut().apply_patches(&patches);
+                self.prune_handlers();
+            }
+        }
+        *self.current_vdom.borrow_mut() = new_view;
+        if !self.update_shortcuts() {
+            return;
+        }
+        self.update_subscriptions();
+        cmd.emit(Rc::new(self.clone()));
+    }
+
+    /// the widget is focused by the backend of the renderer
+    fn focus(&self, id: &str) {
+        if self.send_error.borrow().is_none() {
+            self.send(&ToRenderer::Focus(id.to_string()));
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! the renderer side of the remote backend
+use super::wire::{self, ToComponent, ToRenderer};
+use crate::backend::Dispatch;
+use crate::widget::{
+    event::KeyEvent,
+    remote::{HandlerId, RemoteNode},
+};
+use crate::{Cmd, Component, Event, Node, Shortcut, Sub};
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{Receiver, TryRecvError},
+    time::Duration,
+};
+
+/// how often the messages received in the reader thread are checked
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// A component which mirrors the view of the remote component,
+/// it can be mounted into any backend such as gtk or titik.
+/// The events of the widgets are sent back to the remote component.
+pub struct RemoteRenderer {
+    title: String,
+    root: RemoteNode,
+    /// the key chords of the shortcuts of the remote component
+    shortcuts: Vec<KeyEvent>,
+    writer: Box<dyn Write>,
+    /// the payload of the frames received by the reader thread, this is None
+    /// when the messages are pumped into the program instead
+    receiver: Option<Receiver<io::Result<Vec<u8>>>>,
+}
+
+/// the msgs of the remote renderer
+#[derive(Debug, Clone)]
+pub enum RendererMsg {
+    /// the event is triggered on a widget with this handler
+    Event(HandlerId, Event),
+    /// the key chord of a shortcut of the remote component is pressed
+    Shortcut(KeyEvent),
+    /// the message is received from the remote component
+    Received(ToRenderer),
+    /// apply the messages which are received by the reader thread
+    Poll,
+}
+
+impl RemoteRenderer {
+    /// create the renderer from the view which is mounted by the remote
+    /// component, this blocks until the mount message is received.
+    pub fn new<R, W>(reader: &mut R, writer: W) -> io::Result<Self>
+    where
+        R: Read,
+        W: Write + 'static,
+    {
+        match wire::read_frame(reader)? {
+            ToRenderer::Mount {
+                title,
+                root,
+                shortcuts,
+            } => Ok(RemoteRenderer {
+                title,
+                root,
+                shortcuts,
+                writer: Box::new(writer),
+                receiver: None,
+            }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "expecting a mount message before the other messages",
+            )),
+        }
+    }
+
+    /// create the renderer from the view which is mounted by the remote
+    /// component, then receive the next messages in a separate thread.
+    ///
+    /// The received messages are applied by the subscription of the renderer,
+    /// so it can be mounted into the backends which run their own event loop
+    /// such as gtk and titik.
+    pub fn spawn<R, W>(mut reader: R, writer: W) -> io::Result<Self>
+    where
+        R: Read + Send + 'static,
+        W: Write + 'static,
+    {
+        let mut renderer = RemoteRenderer::new(&mut reader, writer)?;
+        renderer.receiver = Some(wire::spawn_reader(reader));
+        Ok(renderer)
+    }
+
+    /// wait for the remote component to connect to the unix socket at this
+    /// path, the messages are received in a separate thread
+    #[cfg(unix)]
+    pub fn accept<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        let (stream, _addr) = listener.accept()?;
+        let writer = stream.try_clone()?;
+        RemoteRenderer::spawn(stream, writer)
+    }
+
+    /// returns the view as it is mounted in this renderer
+    pub fn root(&self) -> &RemoteNode {
+        &self.root
+    }
+
+    /// apply the message received from the remote component, the focus is
+    /// returned as a Cmd for the backend where the renderer is mounted
+    fn receive(&mut self, message: ToRenderer) -> Cmd<RendererMsg> {
+        match message {
+            ToRenderer::Mount {
+                title,
+                root,
+                shortcuts,
+            } => {
+                self.title = title;
+                self.root = root;
+                self.shortcuts = shortcuts;
+            }
+            ToRenderer::Patch(patches) => {
+                self.root.apply_patches(&patches);
+            }
+            ToRenderer::Shortcuts(shortcuts) => self.shortcuts = shortcuts,
+            ToRenderer::Focus(id) => return Cmd::focus(id),
+        }
+        Cmd::none()
+    }
+
+    /// apply the messages received by the reader thread so far
+    fn poll(&mut self) -> Cmd<RendererMsg> {
+        let mut cmds = vec![];
+        while let Some(receiver) = &self.receiver {
+            match receiver.try_recv() {
+                Ok(Ok(payload)) => match wire::decode(&payload) {
+                    Ok(message) => cmds.push(self.receive(message)),
+                    Err(e) => {
+                        log::error!("unable to decode the message: {}", e)
+                    }
+                },
+                Ok(Err(e)) => {
+                    if !wire::is_closed(&e) {
+                        log::error!("unable to receive the message: {}", e);
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    log::info!("the remote component is closed");
+                    self.receiver = None;
+                }
+            }
+        }
+        Cmd::batch(cmds)
+    }
+}
+
+impl Component<RendererMsg> for RemoteRenderer {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn update(&mut self, msg: RendererMsg) -> Cmd<RendererMsg> {
+        match msg {
+            RendererMsg::Event(handler_id, event) => {
+                if let Err(e) = wire::write_frame(
+                    &mut self.writer,
+                    &ToComponent::Event(handler_id, Box::new(event)),
+                ) {
+                    log::error!("unable to send the event: {}", e);
+                }
+                Cmd::none()
+            }
+            RendererMsg::Shortcut(key_event) => {
+                if let Err(e) = wire::write_frame(
+                    &mut self.writer,
+                    &ToComponent::Shortcut(key_event),
+                ) {
+                    log::error!("unable to send the shortcut: {}", e);
+                }
+                Cmd::none()
+            }
+            RendererMsg::Received(message) => self.receive(message),
+            RendererMsg::Poll => self.poll(),
+        }
+    }
+
+    fn view(&self) -> Node<RendererMsg> {
+        self.root.clone().into_node(RendererMsg::Event)
+    }
+
+    /// the shortcuts of the remote component are sent back to it
+    fn shortcuts(&self) -> Vec<Shortcut<RendererMsg>> {
+        self.shortcuts
+            .iter()
+            .map(|key_event| {
+                Shortcut::new(
+                    key_event.modifier,
+                    key_event.key_code.clone(),
+                    RendererMsg::Shortcut(key_event.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// the received messages are polled until the remote component is closed
+    fn subscriptions(&self) -> Vec<Sub<RendererMsg>> {
+        if self.receiver.is_some() {
+            vec![Sub::every(POLL_INTERVAL, || RendererMsg::Poll)]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// receive the messages from the remote component and dispatch them into the
+/// program where the renderer is mounted, this blocks until the remote
+/// component is closed.
+///
+/// The backends which run their own event loop such as gtk and titik can't
+/// be blocked, the renderer is created with `RemoteRenderer::spawn` for them
+/// instead.
+pub fn pump<R>(
+    reader: &mut R,
+    program: &dyn Dispatch<RendererMsg>,
+) -> io::Result<()>
+where
+    R: Read,
+{
+    loop {
+        match wire::read_frame(reader) {
+            Ok(message) => program.dispatch(RendererMsg::Received(message)),
+            Err(e) if wire::is_closed(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! the wire format of the messages between the component and the renderer.
+//!
+//! Each frame is the protocol version and the length of the payload, both as
+//! 4 bytes big endian, followed by the payload which is the message in json.
+use crate::widget::{
+    event::KeyEvent,
+    remote::{HandlerId, RemoteNode, RemotePatch},
+};
+use crate::Event;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// the version of the protocol, this is incremented whenever the messages
+/// change in a way which is not compatible with the previous version
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// frames which are larger than this are rejected
+const MAX_FRAME_LEN: u32 = 64 * 1024 * 1024;
+
+/// messages sent from the component to the renderer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ToRenderer {
+    /// mount the initial view of the component
+    Mount {
+        /// the title of the component
+        title: String,
+        /// the root node of the view
+        root: RemoteNode,
+        /// the key chords of the shortcuts of the component
+        shortcuts: Vec<KeyEvent>,
+    },
+    /// apply the patches to the mounted view
+    Patch(Vec<RemotePatch>),
+    /// the shortcuts of the component are changed to these key chords
+    Shortcuts(Vec<KeyEvent>),
+    /// focus the widget with this id, or the first focusable widget inside it
+    Focus(String),
+}
+
+/// messages sent from the renderer to the component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ToComponent {
+    /// the event is triggered on the widget with this handler
+    Event(HandlerId, Box<Event>),
+    /// the key chord of a shortcut of the component is pressed
+    Shortcut(KeyEvent),
+}
+
+/// write the message as a frame into the writer
+pub fn write_frame<W, M>(writer: &mut W, message: &M) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    M: Serialize,
+{
+    let payload = serde_json::to_vec(message)?;
+    if payload.len() > MAX_FRAME_LEN as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame of {} bytes is too large", payload.len()),
+        ));
+    }
+    writer.write_all(&PROTOCOL_VERSION.to_be_bytes())?;
+    writer.write_all(&(payload.len() as u32).to_be_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+/// read the next frame from the reader, this blocks until the whole frame is
+/// received. Returns an error of kind `UnexpectedEof` when the other side
+/// is closed, and `InvalidData` when the frame is not of the same protocol
+/// version.
+pub fn read_frame<R, M>(reader: &mut R) -> io::Result<M>
+where
+    R: Read + ?Sized,
+    M: DeserializeOwned,
+{
+    decode(&read_payload(reader)?)
+}
+
+/// read the payload of the next frame from the reader, without decoding it
+pub(crate) fn read_payload<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: Read + ?Sized,
+{
+    let version = read_u32(reader)?;
+    if version != PROTOCOL_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "unsupported protocol version: {}, expecting: {}",
+                version, PROTOCOL_VERSION
+            ),
+        ));
+    }
+    let len = read_u32(reader)?;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes is too large", len),
+        ));
+    }
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+/// read the payload of the frames in a separate thread, the reading stops
+/// after the first error which is also sent
+pub(crate) fn spawn_reader<R>(mut reader: R) -> Receiver<io::Result<Vec<u8>>>
+where
+    R: Read + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let payload = read_payload(&mut reader);
+        let is_err = payload.is_err();
+        if sender.send(payload).is_err() || is_err {
+            break;
+        }
+    });
+    receiver
+}
+
+/// decode the message from the payload of a frame
+pub(crate) fn decode<M: DeserializeOwned>(payload: &[u8]) -> io::Result<M> {
+    Ok(serde_json::from_slice(payload)?)
+}
+
+/// whether the error is caused by the other side being closed
+pub fn is_closed(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::BrokenPipe
+    )
+}
+
+fn read_u32<R: Read + ?Sized>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_be_bytes(bytes))
+}
@@ -0,0 +1,76 @@
+//! renders the widget tree into a static html string.
+//!
+//! The widgets are converted into html with the same conversion as the
+//! web backend, then the sauron node is rendered into a string, so it can be
+//! used in ordinary binaries to produce static pages of the app view.
+use super::web_ui::convert_widget;
+use crate::widget::layout::compute_node_layout;
+use expanse::geometry::Size;
+use expanse::number::Number;
+use sauron::Render;
+use std::fmt::Debug;
+
+/// the width of the page which the layout is computed against
+const DEFAULT_PAGE_WIDTH: f32 = 1024.0;
+/// the height of the page which the layout is computed against
+const DEFAULT_PAGE_HEIGHT: f32 = 768.0;
+
+/// render the widget node tree into an html string
+pub fn render_to_html_string<MSG>(widget_node: &crate::Node<MSG>) -> String
+where
+    MSG: Clone + Debug + 'static,
+{
+    render_to_html_string_with_size(
+        widget_node,
+        DEFAULT_PAGE_WIDTH,
+        DEFAULT_PAGE_HEIGHT,
+    )
+}
+
+/// render the widget node tree into an html string, the layout of the
+/// widgets is computed to fit in this width and height
+pub fn render_to_html_string_with_size<MSG>(
+    widget_node: &crate::Node<MSG>,
+    width: f32,
+    height: f32,
+) -> String
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut view = widget_node.clone();
+    compute_node_layout(
+        &mut view,
+        Size {
+            width: Number::Defined(width),
+            height: Number::Defined(height),
+        },
+    );
+    let html_node = convert_widget::widget_tree_to_html_node(&view, &mut 0);
+    let mut buffer = String::new();
+    html_node.render(&mut buffer).expect("must write to string");
+    buffer
+}
+
+/// render the widget node tree into a complete html document
+pub fn render_to_html_page<MSG>(
+    title: &str,
+    widget_node: &crate::Node<MSG>,
+) -> String
+where
+    MSG: Clone + Debug + 'static,
+{
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        escape_html(title),
+        render_to_html_string(widget_node)
+    )
+}
+
+/// escape the characters which has special meaning in html
+fn escape_html(txt: &str) -> String {
+    txt.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,29 +1,49 @@
 //! functionalities specific to titik ui
 //!
 use crate::{
+    shortcut::find_shortcut,
+    sub::{emit_sub, Subscriptions},
     widget::{
-        attribute::{find_callback, find_value},
-        Widget,
+        attribute::{
+            find_callback,
+            util::{find_accelerated, get_activate_value},
+        },
+        event::InputEvent,
     },
-    AttribKey, Attribute, Backend, Component, Node,
+    AttribKey, Backend, Component, Event, Node,
 };
-use image::GenericImageView;
-use mt_dom::Callback;
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
+    collections::VecDeque,
     fmt::Debug,
     io::{self},
     marker::PhantomData,
     rc::Rc,
+    time::Instant,
 };
-use titik::{
-    renderer::Renderer, Button, Checkbox, Dispatch, FlexBox, GroupBox, Image,
-    Link, Radio, TextArea, TextInput, TextLabel, Widget as Control,
-};
+use titik::Dispatch;
 
 mod apply_patches;
+mod canvas;
 mod convert_event;
 mod convert_widget;
+mod custom_widget;
+mod dialog;
+mod dropdown;
+mod event_loop;
+mod file_chooser;
+mod focus;
+mod list_view;
+mod menu;
+mod numeric;
+mod progress;
+mod screen;
+mod selection_list;
+mod tabs;
+mod tree;
+
+pub use custom_widget::{register_custom_widget, TitikCustomWidget};
+pub use screen::TitikScreen;
 
 /// Titik Backend
 pub struct TitikBackend<APP, MSG>
@@ -32,14 +52,100 @@ where
 {
     app: Rc<RefCell<APP>>,
     current_dom: Rc<RefCell<Node<MSG>>>,
+    msg_queue: MsgQueue<MSG>,
+    /// the subscriptions of the app and the time when they are due
+    subscriptions: Rc<RefCell<Subscriptions<MSG, Cell<Instant>>>>,
     _phantom_msg: PhantomData<MSG>,
 }
 
+/// Msgs dispatched from a Cmd are queued here, since the titik renderer only
+/// lends the root_node when it calls dispatch.
+struct MsgQueue<MSG> {
+    msgs: Rc<RefCell<VecDeque<MSG>>>,
+    /// the index of the widget which is focused next by the event loop,
+    /// which is requested by the Cmds or the autofocus
+    focus: Rc<RefCell<Option<usize>>>,
+    current_dom: Rc<RefCell<Node<MSG>>>,
+}
+
+impl<MSG> Clone for MsgQueue<MSG> {
+    fn clone(&self) -> Self {
+        MsgQueue {
+            msgs: Rc::clone(&self.msgs),
+            focus: Rc::clone(&self.focus),
+            current_dom: Rc::clone(&self.current_dom),
+        }
+    }
+}
+
+impl<MSG: 'static> crate::backend::Dispatch<MSG> for MsgQueue<MSG> {
+    fn dispatch(&self, msg: MSG) {
+        self.msgs.borrow_mut().push_back(msg);
+    }
+
+    fn focus(&self, id: &str) {
+        match focus::find_by_id(&self.current_dom.borrow(), id) {
+            Some(idx) => *self.focus.borrow_mut() = Some(idx),
+            None => log::warn!("there is no widget with id: {}", id),
+        }
+    }
+}
+
 impl<APP, MSG> TitikBackend<APP, MSG>
 where
     APP: Component<MSG> + 'static,
     MSG: Debug + 'static,
 {
+    /// update the app with the msg, then apply the changes in the view to the root_node
+    fn update_and_patch(
+        &self,
+        msg: MSG,
+        root_node: &mut dyn titik::Widget<MSG>,
+    ) {
+        let cmd = self.app.borrow_mut().update(msg);
+        let new_view = self.app.borrow().view();
+
+        {
+            let previous_dom = self.current_dom.borrow();
+            let diff = mt_dom::diff_with_key(
+                &previous_dom,
+                &new_view,
+                &AttribKey::Key,
+            );
+            apply_patches::apply_patches(
+                &self,
+                root_node,
+                &previous_dom,
+                &new_view,
+                &diff,
+            );
+            if let Some(idx) = focus::find_patched_autofocus(&diff) {
+                *self.msg_queue.focus.borrow_mut() = Some(idx);
+            }
+        }
+
+        *self.current_dom.borrow_mut() = new_view;
+        self.update_subscriptions();
+        cmd.emit(Rc::new(self.msg_queue.clone()));
+    }
+
+    /// diff the subscriptions of the app, the new subscriptions are due
+    /// after their interval
+    fn update_subscriptions(&self) {
+        let subs = self.app.borrow().subscriptions();
+        self.subscriptions.borrow_mut().update_due(subs);
+    }
+
+    /// process the msgs that are queued by the executed Cmds
+    fn process_queued_msgs(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        loop {
+            let msg = self.msg_queue.msgs.borrow_mut().pop_front();
+            match msg {
+                Some(msg) => self.update_and_patch(msg, root_node),
+                None => break,
+            }
+        }
+    }
 }
 
 impl<APP, MSG> Backend<APP, MSG> for TitikBackend<APP, MSG>
@@ -51,16 +157,34 @@ where
         let mut stdout = io::stdout();
         let vdom = app.view();
         let current_dom = app.view();
-        let mut root_node = convert_widget::from_node_tree(vdom);
+        let mut root_node = convert_widget::from_node_tree(&vdom);
 
+        let autofocus = focus::find_autofocus(&current_dom);
+        let current_dom = Rc::new(RefCell::new(current_dom));
         let backend = TitikBackend {
             app: Rc::new(RefCell::new(app)),
-            current_dom: Rc::new(RefCell::new(current_dom)),
+            current_dom: Rc::clone(&current_dom),
+            msg_queue: MsgQueue {
+                msgs: Rc::new(RefCell::new(VecDeque::new())),
+                focus: Rc::new(RefCell::new(autofocus)),
+                current_dom,
+            },
+            subscriptions: Rc::new(RefCell::new(Subscriptions::new())),
             _phantom_msg: PhantomData,
         };
-        let mut renderer =
-            Renderer::new(&mut stdout, Some(&backend), root_node.as_mut());
-        renderer.run().expect("must run");
+        backend.update_subscriptions();
+        let cmd = backend.app.borrow().init();
+        cmd.emit(Rc::new(backend.msg_queue.clone()));
+        backend.process_queued_msgs(root_node.as_mut());
+
+        let event_loop = event_loop::EventLoop::new(
+            &mut stdout,
+            &backend,
+            root_node.as_mut(),
+        );
+        if let Err(e) = event_loop.and_then(|mut event_loop| event_loop.run()) {
+            log::error!("unable to run the event loop: {}", e);
+        }
     }
 }
 
@@ -72,22 +196,85 @@ where
     /// root_node is added as argument in this dispatch function so that they are in the same
     /// borrow, otherwise an AlreadyBorrowedError will be invoke at runtime.
     fn dispatch(&self, msg: MSG, root_node: &mut dyn titik::Widget<MSG>) {
-        eprintln!("dispatching... {:?}", msg);
-        self.app.borrow_mut().update(msg);
-        let new_view = self.app.borrow().view();
-        let current_view = self.app.borrow().view();
+        self.update_and_patch(msg, root_node);
+        self.process_queued_msgs(root_node);
+    }
+}
 
-        {
-            let previous_dom = self.current_dom.borrow();
-            let diff = mt_dom::diff_with_key(
-                &previous_dom,
-                &new_view,
-                &AttribKey::Key,
-            );
-            eprintln!("diff: {:#?}", diff);
-            apply_patches::apply_patches(&self, root_node, &diff);
+impl<APP, MSG> event_loop::Program<MSG> for TitikBackend<APP, MSG>
+where
+    MSG: Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn next_due(&self) -> Option<Instant> {
+        self.subscriptions.borrow().next_due()
+    }
+
+    fn tick(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        let due = self.subscriptions.borrow().take_due(Instant::now());
+        for msg_fn in due {
+            self.dispatch(emit_sub(&msg_fn), root_node);
+        }
+    }
+
+    fn take_focus(&self) -> Option<usize> {
+        self.msg_queue.focus.borrow_mut().take()
+    }
+
+    fn focus_order(&self) -> Vec<usize> {
+        focus::focus_order(&self.current_dom.borrow())
+    }
+
+    fn change_focus(
+        &self,
+        blurred: Option<usize>,
+        focused: Option<usize>,
+        root_node: &mut dyn titik::Widget<MSG>,
+    ) {
+        let msgs = focus::focus_change_msgs(
+            &self.current_dom.borrow(),
+            blurred,
+            focused,
+        );
+        for msg in msgs {
+            self.dispatch(msg, root_node);
         }
+    }
 
-        *self.current_dom.borrow_mut() = current_view;
+    fn process_key(
+        &self,
+        key_event: &titik::event::KeyEvent,
+        root_node: &mut dyn titik::Widget<MSG>,
+    ) -> bool {
+        let key_event = convert_event::to_accelerator_key(key_event);
+        let shortcut = find_shortcut(&*self.app.borrow(), &key_event);
+        if let Some(msg) = shortcut {
+            self.dispatch(msg, root_node);
+            return true;
+        }
+        let msgs: Option<Vec<MSG>> = {
+            let current_dom = self.current_dom.borrow();
+            find_accelerated(&current_dom, &key_event).map(|element| {
+                let value =
+                    get_activate_value(element.tag(), element.get_attributes());
+                find_callback(
+                    AttribKey::ActivateEvent,
+                    element.get_attributes(),
+                )
+                .unwrap_or_default()
+                .into_iter()
+                .map(|cb| cb.emit(Event::from(InputEvent::new(value.clone()))))
+                .collect()
+            })
+        };
+        match msgs {
+            Some(msgs) => {
+                for msg in msgs {
+                    self.dispatch(msg, root_node);
+                }
+                true
+            }
+            None => false,
+        }
     }
 }
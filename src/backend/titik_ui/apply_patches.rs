@@ -1,21 +1,51 @@
+use super::canvas::Canvas;
 use super::convert_widget;
-use crate::{AttribKey, Attribute, Patch, Widget};
-use mt_dom::patch::{AddAttributes, AppendChildren, RemoveNode};
-use std::fmt::Debug;
-use titik::{Button, TextArea};
+use super::custom_widget;
+use super::dialog::Dialog;
+use super::dropdown::Dropdown;
+use super::list_view::ListView;
+use super::numeric::NumberInput;
+use super::progress::Progress;
+use super::selection_list::SelectionList;
+use super::tabs::{TabPage, Tabs};
+use super::tree::TreeNode;
+use crate::{
+    widget::attribute::util::{default_value, selected_values},
+    AttribKey, Attribute, Node, Patch, Widget,
+};
+use mt_dom::attr;
+use mt_dom::patch::{
+    AddAttributes, AppendChildren, ChangeText, InsertNode, RemoveAttributes,
+    RemoveNode, ReplaceNode,
+};
+use std::{collections::HashMap, fmt::Debug};
+use titik::{
+    Button, Checkbox, GroupBox, Radio, TextArea, TextInput, TextLabel,
+};
 
+/// apply the patches to the titik widget tree.
+///
+/// The node_idx of the patches refers to the old vdom, so the patches are applied
+/// from the last node_idx to the first, such that inserting or removing a widget
+/// will not shift the node_idx of the widgets which are yet to be patched.
 pub fn apply_patches<MSG, DSP>(
     _program: &DSP,
     root_node: &mut dyn titik::Widget<MSG>,
+    old_vdom: &Node<MSG>,
+    new_vdom: &Node<MSG>,
     patches: &[Patch<MSG>],
 ) where
     MSG: Debug + 'static,
 {
-    for patch in patches {
+    let mut parents = HashMap::new();
+    find_parents(old_vdom, &mut 0, &mut parents);
+
+    let mut ordered_patches: Vec<(usize, &Patch<MSG>)> =
+        patches.iter().enumerate().collect();
+    ordered_patches.sort_by_key(|(order, patch)| patch_sort_key(*order, patch));
+
+    for (_, patch) in ordered_patches {
         let patch_node_idx = patch.node_idx();
-        let widget: &mut dyn titik::Widget<MSG> =
-            titik::find_widget_mut(root_node, patch_node_idx)
-                .expect("must have a node to patch");
         match patch {
             Patch::AddAttributes(AddAttributes {
                 tag,
@@ -23,44 +53,326 @@ pub fn apply_patches<MSG, DSP>(
                 new_node_idx,
                 attrs,
             }) => {
-                eprintln!("setting attributes...");
-                set_widget_attributes::<MSG>(tag, widget, attrs);
+                patch_attributes(
+                    root_node,
+                    new_vdom,
+                    &parents,
+                    tag,
+                    patch_node_idx,
+                    *new_node_idx,
+                    attrs,
+                );
             }
-            Patch::AppendChildren(AppendChildren {
+            Patch::RemoveAttributes(RemoveAttributes {
                 tag,
                 node_idx: _,
+                new_node_idx,
+                attrs,
+            }) => {
+                // the removed attributes are reset to their default value,
+                // the widget is recreated when one of them has no default
+                let defaults: Option<Vec<Attribute<MSG>>> = attrs
+                    .iter()
+                    .filter(|att| !is_ignored(att.name()))
+                    .map(|att| {
+                        default_value(att.name())
+                            .map(|value| attr(att.name().clone(), value))
+                    })
+                    .collect();
+                match defaults {
+                    Some(defaults) => {
+                        let defaults: Vec<&Attribute<MSG>> =
+                            defaults.iter().collect();
+                        patch_attributes(
+                            root_node,
+                            new_vdom,
+                            &parents,
+                            tag,
+                            patch_node_idx,
+                            *new_node_idx,
+                            &defaults,
+                        );
+                    }
+                    None => recreate_widget(
+                        root_node,
+                        new_vdom,
+                        &parents,
+                        patch_node_idx,
+                        *new_node_idx,
+                    ),
+                }
+            }
+            Patch::AppendChildren(AppendChildren {
+                tag: _,
+                node_idx: _,
                 children,
             }) => {
-                eprintln!("adding children..");
-                for (child_idx, child) in children {
-                    let child_element =
-                        child.as_element_ref().expect("must be an element");
-                    let child_widget = convert_widget::from_node(
-                        &child_element.tag,
-                        &child_element.attrs,
-                    );
-                    eprintln!("added 1 {:?} to {:?}", child_widget, widget);
+                let widget: &mut dyn titik::Widget<MSG> =
+                    titik::find_widget_mut(root_node, patch_node_idx)
+                        .expect("must have a node to patch");
+                for (_child_idx, child) in children {
+                    let child_widget = convert_widget::from_node_tree(child);
                     let added = widget.add_child(child_widget);
-                    eprintln!("widget becomes: {:?}", widget);
                     assert!(added);
                 }
             }
+            Patch::InsertNode(InsertNode {
+                tag: _,
+                node_idx: _,
+                new_node_idx: _,
+                node,
+            }) => {
+                let (parent_idx, child_pos) = parents
+                    .get(&patch_node_idx)
+                    .expect("can not insert a node before the root widget");
+                let parent: &mut dyn titik::Widget<MSG> =
+                    titik::find_widget_mut(root_node, *parent_idx)
+                        .expect("must have a parent widget");
+                insert_child(
+                    parent,
+                    *child_pos,
+                    convert_widget::from_node_tree(node),
+                );
+            }
             Patch::RemoveNode(RemoveNode { tag: _, node_idx }) => {
                 titik::remove_widget(root_node, *node_idx);
             }
-            // todo for other patches here.
-            _ => eprintln!("todo for: {:?}", patch),
+            Patch::ReplaceNode(ReplaceNode {
+                tag: _,
+                node_idx: _,
+                new_node_idx: _,
+                replacement,
+            }) => {
+                let (parent_idx, child_pos) = parents
+                    .get(&patch_node_idx)
+                    .expect("the root widget can not be replaced");
+                replace_child(
+                    root_node,
+                    *parent_idx,
+                    *child_pos,
+                    convert_widget::from_node_tree(replacement),
+                );
+            }
+            Patch::ChangeText(ChangeText { node_idx, new, .. }) => {
+                let label: &mut TextLabel =
+                    titik::find_widget_mut(root_node, *node_idx)
+                        .and_then(|widget| widget.as_any_mut().downcast_mut())
+                        .expect("the text node must be a text label");
+                label.set_value(&new.text);
+            }
+        }
+    }
+}
+
+/// set the attributes of the widget in place, or recreate it from the new
+/// node when they can not be set in place
+fn patch_attributes<MSG>(
+    root_node: &mut dyn titik::Widget<MSG>,
+    new_vdom: &Node<MSG>,
+    parents: &HashMap<usize, (usize, usize)>,
+    tag: &Widget,
+    node_idx: usize,
+    new_node_idx: usize,
+    attrs: &[&Attribute<MSG>],
+) where
+    MSG: Debug + 'static,
+{
+    let widget: &mut dyn titik::Widget<MSG> =
+        titik::find_widget_mut(root_node, node_idx)
+            .expect("must have a node to patch");
+    let is_set = match tag {
+        // the cells of the table are aligned with each other, so they are
+        // laid out again from the new node. The table is not recreated, so
+        // it stays focused
+        Widget::Table => {
+            let new_node = find_node(new_vdom, new_node_idx)
+                .expect("must have the new node");
+            let list: &mut SelectionList<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a selection list");
+            convert_widget::set_table_lines(
+                list,
+                new_node.get_attributes().unwrap_or(&[]),
+            );
+            true
+        }
+        // the rows and the scroll offset are set from the new node, the rows
+        // themselves are patched as the children
+        Widget::ListView => {
+            let new_node = find_node(new_vdom, new_node_idx)
+                .expect("must have the new node");
+            let list_view: &mut ListView<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a list view");
+            convert_widget::set_list_view_attributes(
+                list_view,
+                new_node.get_attributes().unwrap_or(&[]),
+            );
+            true
+        }
+        // the frames of the progress bar and the spinner are made from all
+        // of their attributes, so they are set from the new node
+        Widget::ProgressBar | Widget::Spinner => {
+            let new_node = find_node(new_vdom, new_node_idx)
+                .expect("must have the new node");
+            let new_attrs = new_node.get_attributes().unwrap_or(&[]);
+            let frames = if matches!(tag, Widget::Spinner) {
+                convert_widget::spinner_frames(new_attrs)
+            } else {
+                convert_widget::progress_bar_frames(new_attrs)
+            };
+            let progress: &mut Progress<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a progress");
+            progress.set_frames(frames);
+            true
         }
+        _ => set_widget_attributes::<MSG>(tag, widget, attrs),
+    };
+    if !is_set {
+        recreate_widget(root_node, new_vdom, parents, node_idx, new_node_idx);
     }
 }
 
-fn set_widget_attributes<MSG: 'static>(
+/// replace the widget with a new one which is created from the new node
+fn recreate_widget<MSG>(
+    root_node: &mut dyn titik::Widget<MSG>,
+    new_vdom: &Node<MSG>,
+    parents: &HashMap<usize, (usize, usize)>,
+    node_idx: usize,
+    new_node_idx: usize,
+) where
+    MSG: Debug + 'static,
+{
+    let new_node =
+        find_node(new_vdom, new_node_idx).expect("must have the new node");
+    let (parent_idx, child_pos) = parents
+        .get(&node_idx)
+        .expect("the root widget can not be recreated");
+    replace_child(
+        root_node,
+        *parent_idx,
+        *child_pos,
+        convert_widget::from_node_tree(new_node),
+    );
+}
+
+/// patches are sorted by descending node_idx, patches on the same node
+/// are ordered such that the attributes and children are set first
+/// before the node is removed or replaced, then the nodes inserted before it
+/// are inserted in reverse order so they end up in their original order.
+fn patch_sort_key<MSG>(
+    order: usize,
+    patch: &Patch<MSG>,
+) -> (std::cmp::Reverse<usize>, u8, isize) {
+    let node_idx = std::cmp::Reverse(patch.node_idx());
+    match patch {
+        Patch::AddAttributes(_)
+        | Patch::RemoveAttributes(_)
+        | Patch::ChangeText(_) => (node_idx, 0, order as isize),
+        Patch::AppendChildren(_) => (node_idx, 1, order as isize),
+        Patch::RemoveNode(_) | Patch::ReplaceNode(_) => {
+            (node_idx, 2, order as isize)
+        }
+        Patch::InsertNode(_) => (node_idx, 3, -(order as isize)),
+    }
+}
+
+/// collect the parent node_idx and the position in the parent of each node
+fn find_parents<MSG>(
+    node: &Node<MSG>,
+    cur_node_idx: &mut usize,
+    parents: &mut HashMap<usize, (usize, usize)>,
+) {
+    let node_idx = *cur_node_idx;
+    for (child_pos, child) in
+        node.get_children().unwrap_or(&[]).iter().enumerate()
+    {
+        *cur_node_idx += 1;
+        parents.insert(*cur_node_idx, (node_idx, child_pos));
+        find_parents(child, cur_node_idx, parents);
+    }
+}
+
+/// find the node with this node_idx
+fn find_node<MSG>(node: &Node<MSG>, node_idx: usize) -> Option<&Node<MSG>> {
+    fn find_node_recursive<'a, MSG>(
+        node: &'a Node<MSG>,
+        node_idx: usize,
+        cur_node_idx: &mut usize,
+    ) -> Option<&'a Node<MSG>> {
+        if node_idx == *cur_node_idx {
+            return Some(node);
+        }
+        for child in node.get_children().unwrap_or(&[]) {
+            *cur_node_idx += 1;
+            if let Some(found) =
+                find_node_recursive(child, node_idx, cur_node_idx)
+            {
+                return Some(found);
+            }
+        }
+        None
+    }
+    find_node_recursive(node, node_idx, &mut 0)
+}
+
+/// insert the child widget at this position in the parent.
+/// Titik only has `add_child` which appends to the end, so the succeeding
+/// children are taken out and added back after the inserted child.
+fn insert_child<MSG>(
+    parent: &mut dyn titik::Widget<MSG>,
+    child_pos: usize,
+    child: Box<dyn titik::Widget<MSG>>,
+) where
+    MSG: 'static,
+{
+    let children_len = parent.children().map(|c| c.len()).unwrap_or(0);
+    let mut succeeding = vec![];
+    for _ in child_pos..children_len {
+        if let Some(taken) = parent.take_child(child_pos) {
+            succeeding.push(taken);
+        }
+    }
+    let added = parent.add_child(child);
+    assert!(added);
+    for taken in succeeding {
+        parent.add_child(taken);
+    }
+}
+
+/// replace the child of the parent at this position with a new widget
+fn replace_child<MSG>(
+    root_node: &mut dyn titik::Widget<MSG>,
+    parent_idx: usize,
+    child_pos: usize,
+    new_widget: Box<dyn titik::Widget<MSG>>,
+) where
+    MSG: 'static,
+{
+    let parent: &mut dyn titik::Widget<MSG> =
+        titik::find_widget_mut(root_node, parent_idx)
+            .expect("must have a parent widget");
+    parent
+        .take_child(child_pos)
+        .expect("must have a child to replace");
+    insert_child(parent, child_pos, new_widget);
+}
+
+/// set the changed attributes of the widget,
+/// returns false if the widget can not be updated in place and needs
+/// to be recreated from the new node instead.
+fn set_widget_attributes<MSG: Debug + 'static>(
     tag: &crate::Widget,
     widget: &mut dyn titik::Widget<MSG>,
     attrs: &[&Attribute<MSG>],
-) {
+) -> bool {
+    let mut is_set = true;
     match tag {
-        Widget::TextArea => {
+        Widget::TextArea | Widget::Paragraph => {
             let text_area: &mut TextArea<MSG> = widget
                 .as_any_mut()
                 .downcast_mut()
@@ -69,9 +381,25 @@ fn set_widget_attributes<MSG: 'static>(
                 for value in att.get_plain() {
                     match att.name() {
                         AttribKey::Value => {
-                            text_area.set_value(&value.to_string());
+                            text_area.set_value(value.to_string());
                         }
-                        _ => (),
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::TextInput | Widget::SearchInput => {
+            let text_input: &mut TextInput = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a text input");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Value => {
+                            text_input.set_value(value.to_string());
+                        }
+                        key => is_set &= is_ignored(key),
                     }
                 }
             }
@@ -81,18 +409,242 @@ fn set_widget_attributes<MSG: 'static>(
                 .as_any_mut()
                 .downcast_mut()
                 .expect("must be a button");
-
             for att in attrs {
                 for value in att.get_plain() {
                     match att.name() {
                         AttribKey::Label => {
-                            btn.set_label(&value.to_string());
+                            btn.set_label(value.to_string());
                         }
-                        _ => (),
+                        key => is_set &= is_ignored(key),
                     }
                 }
             }
         }
-        _ => eprintln!("todo for other widgets.. {:?}", tag),
+        Widget::Checkbox => {
+            let checkbox: &mut Checkbox<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a checkbox");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Value | AttribKey::Checked => {
+                            checkbox.set_checked(value.as_bool());
+                        }
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::Radio => {
+            let radio: &mut Radio<MSG> =
+                widget.as_any_mut().downcast_mut().expect("must be a radio");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Value | AttribKey::Checked => {
+                            radio.set_checked(value.as_bool());
+                        }
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::Tabs => {
+            let tabs: &mut Tabs<MSG> =
+                widget.as_any_mut().downcast_mut().expect("must be tabs");
+            for att in attrs {
+                for value in att.get_plain() {
+                    if let AttribKey::Selected = att.name() {
+                        tabs.set_selected(
+                            value
+                                .as_f64()
+                                .filter(|v| *v >= 0.0)
+                                .map(|v| v as usize)
+                                .unwrap_or(0),
+                        );
+                    }
+                }
+            }
+        }
+        Widget::Tab => {
+            let page: &mut TabPage<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a tab page");
+            for att in attrs {
+                for value in att.get_plain() {
+                    if let AttribKey::Label = att.name() {
+                        page.set_label(&value.to_string());
+                    }
+                }
+            }
+        }
+        Widget::Dropdown => {
+            let dropdown: &mut Dropdown<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a dropdown");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Options => dropdown.set_options(
+                            value
+                                .as_list()
+                                .map(|options| {
+                                    options
+                                        .iter()
+                                        .map(|v| v.to_string())
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        ),
+                        AttribKey::Value => dropdown.set_value(
+                            selected_values(value)
+                                .into_iter()
+                                .next()
+                                .unwrap_or_default(),
+                        ),
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::SpinButton | Widget::Slider => {
+            let input: &mut NumberInput<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a number input");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match (att.name(), value.as_f64()) {
+                        (AttribKey::Value, Some(number)) => {
+                            input.set_value(number)
+                        }
+                        (AttribKey::Min, Some(number)) => input.set_min(number),
+                        (AttribKey::Max, Some(number)) => input.set_max(number),
+                        (AttribKey::Step, Some(number)) => {
+                            input.set_step(number)
+                        }
+                        (key, _) => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::TreeItem => {
+            let item: &mut TreeNode<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a tree item");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Label => item.set_label(&value.to_string()),
+                        AttribKey::Key => item.set_key(Some(value.to_string())),
+                        AttribKey::Expanded => {
+                            item.set_expanded(value.as_bool())
+                        }
+                        AttribKey::Selected => {
+                            item.set_selected(value.as_bool())
+                        }
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::Canvas => {
+            let canvas: &mut Canvas<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a canvas");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Draw => {
+                            canvas.set_commands(value.as_draw().unwrap_or(&[]))
+                        }
+                        key => is_set &= is_ignored(key),
+                    }
+                }
+            }
+        }
+        Widget::Dialog => {
+            let dialog: &mut Dialog<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a dialog");
+            for att in attrs {
+                for value in att.get_plain() {
+                    if let AttribKey::Label = att.name() {
+                        dialog.set_label(&value.to_string());
+                    }
+                }
+            }
+        }
+        Widget::GroupBox => {
+            let groupbox: &mut GroupBox<MSG> = widget
+                .as_any_mut()
+                .downcast_mut()
+                .expect("must be a groupbox");
+            for att in attrs {
+                for value in att.get_plain() {
+                    if let AttribKey::Label = att.name() {
+                        groupbox.set_label(&value.to_string());
+                    }
+                }
+            }
+        }
+        Widget::Custom(custom) => {
+            if let Some(hook) =
+                custom_widget::find_custom_widget::<MSG>(custom.as_ref())
+            {
+                hook.set_attributes(widget, attrs);
+            }
+        }
+        // the label, image, link, list box, file chooser and menu item
+        // widgets are recreated from the new node
+        Widget::Label
+        | Widget::Image
+        | Widget::Svg
+        | Widget::Link
+        | Widget::ListBox
+        | Widget::FileChooser
+        | Widget::MenuItem
+        | Widget::CheckMenuItem
+        | Widget::RadioMenuItem
+        | Widget::MenuSeparator => {
+            is_set = attrs.iter().all(|att| is_ignored(att.name()));
+        }
+        // the rest of the containers has no attributes that can be changed
+        // and they should not be recreated, since it will lose its children
+        Widget::Vbox
+        | Widget::Hbox
+        | Widget::Vpane
+        | Widget::Hpane
+        | Widget::Overlay
+        | Widget::HeaderBar
+        | Widget::MenuBar
+        | Widget::Menu
+        | Widget::TreeView => (),
+        // the table, the list view, the progress bar and the spinner are set
+        // in patch_attributes with their new node
+        Widget::Table
+        | Widget::ListView
+        | Widget::ProgressBar
+        | Widget::Spinner => (),
     }
+    is_set
+}
+
+/// these attributes has no visible effect in titik, so they are ignored when
+/// deciding whether to recreate the widget
+fn is_ignored(key: &AttribKey) -> bool {
+    matches!(
+        key,
+        AttribKey::Key
+            | AttribKey::Id
+            | AttribKey::Style
+            | AttribKey::Layout
+            | AttribKey::Name
+    )
 }
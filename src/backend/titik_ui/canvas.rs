@@ -0,0 +1,334 @@
+//! the canvas, which draws the commands with braille dots. The mouse events
+//! are emitted with the location of the cell in pixels, so the app can use
+//! the same coordinates as the other backends.
+use crate::widget::draw::{Color, DrawCommand, Path};
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::{event::MouseEvent, style},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// the width of a character cell in pixels, a braille character has
+/// 2 columns of dots in a cell
+const CELL_WIDTH: f64 = 10.0;
+/// the height of a character cell in pixels, a braille character has
+/// 4 rows of dots in a cell
+const CELL_HEIGHT: f64 = 20.0;
+/// the size of a braille dot in pixels
+const DOT_SIZE: f64 = 5.0;
+
+/// a character of the canvas and the color it is drawn with
+type CanvasCell = (char, Option<Color>);
+
+#[derive(Debug)]
+pub(crate) struct Canvas<MSG> {
+    layout: Option<Layout>,
+    commands: Vec<DrawCommand>,
+    /// the size in pixels
+    width: Option<f32>,
+    height: Option<f32>,
+    cells: Vec<Vec<CanvasCell>>,
+    /// the following are emitted with the location in pixels
+    on_click: Vec<Callback<(i32, i32), MSG>>,
+    on_mouse_down: Vec<Callback<(i32, i32), MSG>>,
+    on_mouse_up: Vec<Callback<(i32, i32), MSG>>,
+    on_mouse_move: Vec<Callback<(i32, i32), MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> Canvas<MSG> {
+    pub(crate) fn new() -> Self {
+        Canvas {
+            layout: None,
+            commands: vec![],
+            width: None,
+            height: None,
+            cells: vec![],
+            on_click: vec![],
+            on_mouse_down: vec![],
+            on_mouse_up: vec![],
+            on_mouse_move: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_commands(&mut self, commands: &[DrawCommand]) {
+        self.commands = commands.to_vec();
+        self.cells = braille_cells(&self.commands, self.width, self.height);
+    }
+
+    pub(crate) fn add_click_listener(&mut self, cb: Callback<(i32, i32), MSG>) {
+        self.on_click.push(cb);
+    }
+
+    pub(crate) fn add_mouse_down_listener(
+        &mut self,
+        cb: Callback<(i32, i32), MSG>,
+    ) {
+        self.on_mouse_down.push(cb);
+    }
+
+    pub(crate) fn add_mouse_up_listener(
+        &mut self,
+        cb: Callback<(i32, i32), MSG>,
+    ) {
+        self.on_mouse_up.push(cb);
+    }
+
+    pub(crate) fn add_mouse_move_listener(
+        &mut self,
+        cb: Callback<(i32, i32), MSG>,
+    ) {
+        self.on_mouse_move.push(cb);
+    }
+
+    /// the location in pixels at the center of the cell
+    fn location(&self, x: u16, y: u16) -> (i32, i32) {
+        let (left, top) = self
+            .layout
+            .map(|layout| (layout.location.x, layout.location.y))
+            .unwrap_or((0.0, 0.0));
+        let column = (x as f64 - left as f64).max(0.0);
+        let row = (y as f64 - top as f64).max(0.0);
+        (
+            ((column + 0.5) * CELL_WIDTH) as i32,
+            ((row + 0.5) * CELL_HEIGHT) as i32,
+        )
+    }
+}
+
+impl<MSG> Control<MSG> for Canvas<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// a cell for each of the braille characters
+    fn style(&self) -> Style {
+        let rows = self.cells.len() as f32;
+        let columns = self.cells.first().map_or(0, |row| row.len()) as f32;
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: Dimension::Points(columns),
+                height: Dimension::Points(rows),
+            },
+            min_size: Size {
+                width: Dimension::Points(columns),
+                height: Dimension::Points(rows),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, (ch, color)) in row.iter().enumerate() {
+                let mut cell = Cell::new(*ch);
+                if let Some(color) = color {
+                    cell.color(to_terminal_color(color));
+                }
+                buf.set_cell(left + x, top + y, cell);
+            }
+        }
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// the size is in pixels, the cells are drawn again to fit it
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+        self.cells = braille_cells(&self.commands, width, height);
+    }
+
+    /// the click is emitted when the button is released, the mouse move is
+    /// emitted while dragging since the terminal only reports those
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let (callbacks, x, y) = match event {
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                (vec![&self.on_mouse_down], x, y)
+            }
+            Event::Mouse(MouseEvent::Up(_btn, x, y, _modifier)) => {
+                (vec![&self.on_mouse_up, &self.on_click], x, y)
+            }
+            Event::Mouse(MouseEvent::Drag(_btn, x, y, _modifier)) => {
+                (vec![&self.on_mouse_move], x, y)
+            }
+            _ => return vec![],
+        };
+        let location = self.location(x, y);
+        callbacks
+            .into_iter()
+            .flatten()
+            .map(|cb| cb.emit(location))
+            .collect()
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// the color in the 24 bit colors of the terminal, the alpha is ignored
+fn to_terminal_color(color: &Color) -> style::Color {
+    style::Color::Rgb {
+        r: (color.red * 255.0).round() as u8,
+        g: (color.green * 255.0).round() as u8,
+        b: (color.blue * 255.0).round() as u8,
+    }
+}
+
+/// draw the commands with braille dots, a cell has the color of the last
+/// dot which is drawn in it. When there is no size, it is large enough for
+/// all the points of the paths
+fn braille_cells(
+    commands: &[DrawCommand],
+    width: Option<f32>,
+    height: Option<f32>,
+) -> Vec<Vec<CanvasCell>> {
+    let mut extent = Path::default();
+    commands.iter().for_each(|command| {
+        extent.add(command);
+    });
+    let points = extent.sub_paths.iter().flatten();
+    let max_x = points.clone().map(|(x, _)| *x).fold(0.0, f64::max);
+    let max_y = points.map(|(_, y)| *y).fold(0.0, f64::max);
+    let width = width.map(|w| w as f64).unwrap_or(max_x + 1.0);
+    let height = height.map(|h| h as f64).unwrap_or(max_y + 1.0);
+    let columns = (width / CELL_WIDTH).ceil().max(1.0) as usize;
+    let rows = (height / CELL_HEIGHT).ceil().max(1.0) as usize;
+
+    let mut dots = vec![vec![false; columns * 2]; rows * 4];
+    let mut colors: Vec<Vec<Option<Color>>> = vec![vec![None; columns]; rows];
+    let mut texts: Vec<(usize, usize, String, Color)> = vec![];
+    let mut plot = |x: f64, y: f64, color: Color| {
+        let (dot_x, dot_y) = ((x / DOT_SIZE).floor(), (y / DOT_SIZE).floor());
+        if dot_x >= 0.0 && dot_y >= 0.0 {
+            let (dot_x, dot_y) = (dot_x as usize, dot_y as usize);
+            if let Some(dot) =
+                dots.get_mut(dot_y).and_then(|row| row.get_mut(dot_x))
+            {
+                *dot = true;
+                colors[dot_y / 4][dot_x / 2] = Some(color);
+            }
+        }
+    };
+    let mut path = Path::default();
+    let mut stroke_color = Color::default();
+    let mut fill_color = Color::default();
+    for command in commands {
+        if path.add(command) {
+            continue;
+        }
+        match command {
+            DrawCommand::Stroke => {
+                for segment in path.segments() {
+                    let ((x1, y1), (x2, y2)) = (segment.from, segment.to);
+                    let steps = ((x2 - x1).abs().max((y2 - y1).abs())
+                        / DOT_SIZE)
+                        .ceil()
+                        .max(1.0);
+                    for step in 0..=steps as usize {
+                        let t = step as f64 / steps;
+                        plot(
+                            x1 + (x2 - x1) * t,
+                            y1 + (y2 - y1) * t,
+                            stroke_color,
+                        );
+                    }
+                }
+                path = Path::default();
+            }
+            DrawCommand::Fill => {
+                for dot_y in 0..rows * 4 {
+                    for dot_x in 0..columns * 2 {
+                        let x = (dot_x as f64 + 0.5) * DOT_SIZE;
+                        let y = (dot_y as f64 + 0.5) * DOT_SIZE;
+                        if path.contains(x, y) {
+                            plot(x, y, fill_color);
+                        }
+                    }
+                }
+                path = Path::default();
+            }
+            DrawCommand::StrokeColor(color) => stroke_color = *color,
+            DrawCommand::FillColor(color) => fill_color = *color,
+            // the baseline is at the bottom of the cell
+            DrawCommand::Text { x, y, text } => texts.push((
+                (x / CELL_WIDTH).floor().max(0.0) as usize,
+                ((y - 1.0) / CELL_HEIGHT).floor().max(0.0) as usize,
+                text.to_string(),
+                fill_color,
+            )),
+            _ => (),
+        }
+    }
+
+    // the default black is left to the color of the terminal, since it
+    // would not be visible in the dark terminals
+    let mut cells: Vec<Vec<CanvasCell>> = (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| {
+                    let color = colors[row][column]
+                        .filter(|color| *color != Color::default());
+                    (braille_char(&dots, column, row), color)
+                })
+                .collect()
+        })
+        .collect();
+    for (column, row, text, color) in texts {
+        let color = Some(color).filter(|color| *color != Color::default());
+        if let Some(cells_row) = cells.get_mut(row) {
+            for (cell, ch) in
+                cells_row.iter_mut().skip(column).zip(text.chars())
+            {
+                *cell = (ch, color);
+            }
+        }
+    }
+    cells
+}
+
+/// the braille character of the 2x4 dots of this cell
+fn braille_char(dots: &[Vec<bool>], column: usize, row: usize) -> char {
+    // the bits of the dots in the unicode braille patterns
+    const DOT_BITS: [[u32; 2]; 4] =
+        [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+    let mut bits = 0;
+    for (dot_row, row_bits) in DOT_BITS.iter().enumerate() {
+        for (dot_column, bit) in row_bits.iter().enumerate() {
+            if dots[row * 4 + dot_row][column * 2 + dot_column] {
+                bits |= bit;
+            }
+        }
+    }
+    std::char::from_u32(0x2800 + bits).expect("must be a braille character")
+}
@@ -53,6 +53,19 @@ fn from_titik_key_event(tke: titik::event::KeyEvent) -> KeyEvent {
     }
 }
 
+/// the key event which is matched with the accelerators, the character key
+/// is lowercased since it is uppercase when shift is held
+pub(crate) fn to_accelerator_key(tke: &titik::event::KeyEvent) -> KeyEvent {
+    let key_event = from_titik_key_event(*tke);
+    match key_event.key_code {
+        KeyCode::Char(ch) => KeyEvent {
+            key_code: KeyCode::Char(ch.to_ascii_lowercase()),
+            ..key_event
+        },
+        _ => key_event,
+    }
+}
+
 // TODO:
 pub fn from_titik(t_event: titik::Event) -> Event {
     match t_event {
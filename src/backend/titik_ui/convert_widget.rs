@@ -1,21 +1,45 @@
+use super::canvas::Canvas;
 use super::convert_event;
+use super::custom_widget;
+use super::dialog::Dialog;
+use super::dropdown::Dropdown;
+use super::file_chooser::FileChooser;
+use super::list_view::ListView;
+use super::menu::{Menu, MenuItem};
+use super::numeric::NumberInput;
+use super::progress::Progress;
+use super::selection_list::SelectionList;
+use super::tabs::{TabPage, Tabs};
+use super::tree::{Tree, TreeNode};
 use crate::{
     widget::{
-        attribute::{find_callback, find_value},
+        attribute::{
+            find_callback, find_value,
+            util::{
+                get_accelerator, get_activate_value, get_file_mode,
+                get_filters, get_fraction, get_index, get_item_count,
+                get_numeric_range, get_numeric_value, get_options,
+                get_rendered_range, get_row_height, get_selected_values,
+                get_table_data, get_tree_item_state, DEFAULT_VIEWPORT_HEIGHT,
+            },
+        },
+        event::{Event, InputEvent, MouseEvent},
         Widget,
     },
-    AttribKey, Attribute, Backend, Component, Node,
+    AttribKey, Attribute, Value,
 };
 use image::GenericImageView;
 use mt_dom::Callback;
 use std::fmt::Debug;
+use std::ops::Range;
+use std::path::PathBuf;
 use titik::{
-    renderer::Renderer, Button, Checkbox, Dispatch, FlexBox, GroupBox, Image,
-    Link, Radio, TextArea, TextInput, TextLabel, Widget as Control,
+    Button, Checkbox, FlexBox, GroupBox, Image, Link, Radio, TextArea,
+    TextInput, TextLabel, Widget as Control,
 };
 
 pub(crate) fn from_node_tree<MSG>(
-    widget_node: crate::Node<MSG>,
+    widget_node: &crate::Node<MSG>,
 ) -> Box<dyn titik::Widget<MSG>>
 where
     MSG: Debug + 'static,
@@ -23,13 +47,14 @@ where
     match widget_node {
         crate::Node::Element(element) => {
             let mut control = from_node(&element.tag, &element.attrs);
-            for child in element.children {
+            for child in element.children.iter() {
                 let child_widget = from_node_tree(child);
                 control.add_child(child_widget);
             }
             control
         }
-        crate::Node::Text(_txt) => unreachable!(),
+        // the text nodes are shown as labels
+        crate::Node::Text(txt) => Box::new(TextLabel::new(&txt.text)),
     }
 }
 
@@ -64,20 +89,19 @@ where
         }
         Widget::GroupBox => {
             let mut groupbox = GroupBox::new();
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
             groupbox.set_label(&label);
             Box::new(groupbox)
         }
         Widget::Button => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
 
             let mut btn: Button<MSG> = Button::new(&label);
-            if let Some(callbacks) =
-                find_callback(AttribKey::ClickEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::ClickEvent, attrs)
             {
                 for cb in callbacks {
                     let cb = cb.clone();
@@ -91,31 +115,31 @@ where
             Box::new(btn)
         }
         Widget::Paragraph => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
             let textarea = TextArea::new(value);
             Box::new(textarea)
         }
         Widget::TextInput => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
             let input = TextInput::new(value);
             Box::new(input)
         }
         Widget::Checkbox => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
 
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Checked, attrs)
+                .or_else(|| find_value(AttribKey::Value, attrs))
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
             let mut checkbox = Checkbox::new(&label);
-            if let Some(callbacks) =
-                find_callback(AttribKey::InputEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs)
             {
                 for cb in callbacks {
                     eprintln!("checkbox has an input event");
@@ -131,11 +155,12 @@ where
             Box::new(checkbox)
         }
         Widget::Radio => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
 
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Checked, attrs)
+                .or_else(|| find_value(AttribKey::Value, attrs))
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
@@ -145,11 +170,10 @@ where
         }
         Widget::Image => {
             let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
+            let bytes = find_value(AttribKey::Data, attrs)
+                .and_then(|v| v.as_bytes())
                 .unwrap_or(&empty);
-            let image = image::load_from_memory(&bytes).expect("should load");
+            let image = image::load_from_memory(bytes).expect("should load");
             let mut img = Image::new(bytes.to_vec());
             let (width, height) = image.dimensions();
             img.set_size(
@@ -160,19 +184,16 @@ where
         }
         Widget::Svg => todo!(),
         Widget::TextArea => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
-            let height = find_value(AttribKey::Height, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            let width = find_value(AttribKey::Width, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
+                .unwrap_or_default();
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
             let mut textarea = TextArea::new(value);
             textarea.set_size(width, height);
-            if let Some(callbacks) =
-                find_callback(AttribKey::InputEvent, &attrs)
+            if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs)
             {
                 for cb in callbacks {
                     eprintln!("textarea has an input event");
@@ -187,10 +208,10 @@ where
             Box::new(textarea)
         }
         Widget::Label => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
-            let mut text_input = TextLabel::new(value);
+                .unwrap_or_default();
+            let text_input = TextLabel::new(value);
             Box::new(text_input)
         }
         Widget::Overlay => {
@@ -200,73 +221,639 @@ where
         Widget::HeaderBar => {
             //TODO: make a header bar equivalent in titik ui
             let mut flex = FlexBox::new();
-            let height = find_value(AttribKey::Height, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            let width = find_value(AttribKey::Width, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            flex.set_size(width, height);
-            Box::new(flex)
-        }
-        Widget::MenuBar => {
-            let mut flex = FlexBox::new();
-            flex.set_border(true);
-            flex.horizontal();
-            let height = find_value(AttribKey::Height, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            let width = find_value(AttribKey::Width, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
             flex.set_size(width, height);
             Box::new(flex)
         }
-        Widget::Menu => {
-            let mut flex = FlexBox::new();
-            flex.set_border(true);
-            flex.vertical();
-            let height = find_value(AttribKey::Height, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            let width = find_value(AttribKey::Width, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            flex.set_size(width, height);
-            Box::new(flex)
+        Widget::MenuBar | Widget::Menu => {
+            let mut menu = Menu::new(*widget == Widget::MenuBar);
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            menu.set_size(width, height);
+            Box::new(menu)
         }
-        Widget::MenuItem => {
-            let mut flex = FlexBox::new();
-            flex.set_border(true);
-            flex.vertical();
-            let height = find_value(AttribKey::Height, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            let width = find_value(AttribKey::Width, &attrs)
-                .map(|v| v.as_f64().map(|v| v as f32))
-                .flatten();
-            flex.set_size(width, height);
-            Box::new(flex)
+        // the sub menu is added as the child of the menu item
+        Widget::MenuItem | Widget::CheckMenuItem | Widget::RadioMenuItem => {
+            let mut menu_item = MenuItem::new(&menu_item_label(widget, attrs));
+            if let Some(callbacks) =
+                find_callback(AttribKey::ActivateEvent, attrs)
+            {
+                let value = get_activate_value(widget, attrs);
+                for cb in callbacks {
+                    let cb = cb.clone();
+                    let value = value.clone();
+                    menu_item.add_activate_listener(Callback::from(
+                        move |_| {
+                            cb.emit(Event::from(InputEvent::new(value.clone())))
+                        },
+                    ));
+                }
+            }
+            Box::new(menu_item)
         }
+        Widget::MenuSeparator => Box::new(TextLabel::new("─".repeat(20))),
         Widget::SearchInput => {
             // TODO: make a search input equivalent in titik
-            let mut search_input = TextInput::new("");
+            let search_input = TextInput::new("");
             Box::new(search_input)
         }
         Widget::Link => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
             println!("link label: {}", label);
 
-            let uri = find_value(AttribKey::Uri, &attrs)
+            let uri = find_value(AttribKey::Uri, attrs)
                 .map(|v| v.to_string())
-                .unwrap_or(String::new());
+                .unwrap_or_default();
 
             println!("link uri: {}", uri);
 
-            let mut link = Link::new(uri, label);
+            let link = Link::new(uri, label);
             Box::new(link)
         }
+        Widget::Tabs => {
+            let mut tabs = Tabs::new();
+            for cb in find_callback(AttribKey::TabChangeEvent, attrs)
+                .unwrap_or_default()
+            {
+                let cb = cb.clone();
+                tabs.add_tab_change_listener(Callback::from(
+                    move |index: usize| {
+                        cb.emit(Event::from(InputEvent::new(index as u32)))
+                    },
+                ));
+            }
+            tabs.set_selected(
+                get_index(AttribKey::Selected, attrs).unwrap_or(0),
+            );
+            Box::new(tabs)
+        }
+        Widget::Tab => {
+            let label = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            Box::new(TabPage::new(&label))
+        }
+        // the dialog is dismissed with an empty response
+        Widget::Dialog => {
+            let title = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let mut dialog = Dialog::new(&title);
+            for cb in find_callback(AttribKey::ResponseEvent, attrs)
+                .unwrap_or_default()
+            {
+                let cb = cb.clone();
+                dialog.add_dismiss_listener(Callback::from(move |_: ()| {
+                    cb.emit(Event::from(InputEvent::new("")))
+                }));
+            }
+            Box::new(dialog)
+        }
+        Widget::TreeView => {
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let mut tree = Tree::new();
+            tree.set_size(width, height);
+            // the tree view events are emitted with the id of the item
+            let id_callback = |cb: &Callback<Event, MSG>| {
+                let cb = cb.clone();
+                Callback::from(move |id: String| {
+                    cb.emit(Event::from(InputEvent::new(id)))
+                })
+            };
+            for cb in
+                find_callback(AttribKey::SelectEvent, attrs).unwrap_or_default()
+            {
+                tree.add_select_listener(id_callback(cb));
+            }
+            for cb in
+                find_callback(AttribKey::ExpandEvent, attrs).unwrap_or_default()
+            {
+                tree.add_expand_listener(id_callback(cb));
+            }
+            for cb in find_callback(AttribKey::CollapseEvent, attrs)
+                .unwrap_or_default()
+            {
+                tree.add_collapse_listener(id_callback(cb));
+            }
+            for cb in find_callback(AttribKey::RowActivateEvent, attrs)
+                .unwrap_or_default()
+            {
+                tree.add_activate_listener(id_callback(cb));
+            }
+            Box::new(tree)
+        }
+        // the nested items are drawn by the tree view
+        Widget::TreeItem => {
+            let (is_expanded, is_selected) = get_tree_item_state(attrs);
+            let label = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let mut item = TreeNode::new(&label);
+            item.set_key(
+                find_value(AttribKey::Key, attrs).map(|v| v.to_string()),
+            );
+            item.set_expanded(is_expanded);
+            item.set_selected(is_selected);
+            Box::new(item)
+        }
+        // the rows which are built at the scroll offset are shown, scrolling
+        // emits the scroll and the visible range events
+        Widget::ListView => {
+            let mut list_view = ListView::new();
+            set_list_view_attributes(&mut list_view, attrs);
+            for cb in
+                find_callback(AttribKey::ScrollEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                list_view.add_scroll_listener(Callback::from(
+                    move |scroll_offset: f64| {
+                        cb.emit(Event::from(InputEvent::new(scroll_offset)))
+                    },
+                ));
+            }
+            for cb in find_callback(AttribKey::VisibleRangeEvent, attrs)
+                .unwrap_or_default()
+            {
+                let cb = cb.clone();
+                list_view.add_visible_range_listener(Callback::from(
+                    move |range: Range<usize>| {
+                        let range = vec![
+                            Value::from(range.start),
+                            Value::from(range.end),
+                        ];
+                        cb.emit(Event::from(InputEvent::new(range)))
+                    },
+                ));
+            }
+            Box::new(list_view)
+        }
+        // the rows are selected with the arrow keys or a click, and the
+        // selected row is activated with enter. The sortable columns are
+        // sorted with a click on their header, or with `s` on the column
+        // which is picked with the left and right keys
+        Widget::Table => {
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let mut list = SelectionList::new();
+            set_table_lines(&mut list, attrs);
+            list.set_size(width, height);
+            for cb in
+                find_callback(AttribKey::SelectEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                list.add_select_listener(Callback::from(move |row: usize| {
+                    cb.emit(Event::from(InputEvent::new(row as f64)))
+                }));
+            }
+            for cb in find_callback(AttribKey::RowActivateEvent, attrs)
+                .unwrap_or_default()
+            {
+                let cb = cb.clone();
+                list.add_activate_listener(Callback::from(
+                    move |row: usize| {
+                        cb.emit(Event::from(InputEvent::new(row as f64)))
+                    },
+                ));
+            }
+            for cb in
+                find_callback(AttribKey::SortEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                list.add_sort_listener(Callback::from(move |column: usize| {
+                    cb.emit(Event::from(InputEvent::new(column as f64)))
+                }));
+            }
+            Box::new(list)
+        }
+        Widget::Dropdown => {
+            let mut dropdown = Dropdown::new();
+            dropdown.set_options(get_options(attrs));
+            dropdown.set_value(
+                get_selected_values(attrs)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default(),
+            );
+            for cb in
+                find_callback(AttribKey::ChangeEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                dropdown.add_change_listener(Callback::from(
+                    move |value: String| {
+                        cb.emit(Event::from(InputEvent::new(value)))
+                    },
+                ));
+            }
+            Box::new(dropdown)
+        }
+        // the options are selected with the keys like the table, or checked
+        // with space when multiple options can be selected
+        Widget::ListBox => {
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let is_multiple = find_value(AttribKey::Multiple, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let options = get_options(attrs);
+            let selected = get_selected_values(attrs);
+            let selected: Vec<usize> = options
+                .iter()
+                .enumerate()
+                .filter(|(_index, option)| selected.contains(option))
+                .map(|(index, _option)| index)
+                .collect();
+            let mut list = SelectionList::new();
+            list.set_size(width, height);
+            list.set_multiple(is_multiple);
+            if is_multiple {
+                list.set_checked(selected);
+            } else {
+                list.set_selected(selected.first().copied());
+            }
+            list.set_lines(options.clone());
+            for cb in
+                find_callback(AttribKey::ChangeEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                let options = options.clone();
+                if is_multiple {
+                    list.add_toggle_listener(Callback::from(
+                        move |checked: Vec<usize>| {
+                            let values: Vec<String> = checked
+                                .iter()
+                                .filter_map(|index| options.get(*index))
+                                .cloned()
+                                .collect();
+                            cb.emit(Event::from(InputEvent::new(values)))
+                        },
+                    ));
+                } else {
+                    list.add_select_listener(Callback::from(
+                        move |index: usize| {
+                            let value =
+                                options.get(index).cloned().unwrap_or_default();
+                            cb.emit(Event::from(InputEvent::new(value)))
+                        },
+                    ));
+                }
+            }
+            Box::new(list)
+        }
+        Widget::SpinButton | Widget::Slider => {
+            let (min, max, step) = get_numeric_range(attrs);
+            let mut input = NumberInput::new(*widget == Widget::Slider);
+            input.set_range(min, max, step);
+            input.set_value(get_numeric_value(attrs));
+            for cb in
+                find_callback(AttribKey::InputEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                input.add_input_listener(Callback::from(move |value: f64| {
+                    cb.emit(Event::from(InputEvent::new(value)))
+                }));
+            }
+            Box::new(input)
+        }
+        Widget::ProgressBar => {
+            Box::new(Progress::new(progress_bar_frames(attrs)))
+        }
+        Widget::Spinner => Box::new(Progress::new(spinner_frames(attrs))),
+        Widget::Canvas => {
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let mut canvas = Canvas::new();
+            canvas.set_size(width, height);
+            canvas.set_commands(
+                find_value(AttribKey::Draw, attrs)
+                    .and_then(|v| v.as_draw())
+                    .unwrap_or(&[]),
+            );
+            for cb in
+                find_callback(AttribKey::ClickEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                canvas.add_click_listener(Callback::from(
+                    move |(x, y): (i32, i32)| {
+                        cb.emit(Event::from(MouseEvent::click(x, y)))
+                    },
+                ));
+            }
+            for cb in
+                find_callback(AttribKey::MouseDown, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                canvas.add_mouse_down_listener(Callback::from(
+                    move |(x, y): (i32, i32)| {
+                        cb.emit(Event::from(MouseEvent::pressed(x, y)))
+                    },
+                ));
+            }
+            for cb in
+                find_callback(AttribKey::MouseUp, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                canvas.add_mouse_up_listener(Callback::from(
+                    move |(x, y): (i32, i32)| {
+                        cb.emit(Event::from(MouseEvent::release(x, y)))
+                    },
+                ));
+            }
+            for cb in
+                find_callback(AttribKey::MouseMove, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                canvas.add_mouse_move_listener(Callback::from(
+                    move |(x, y): (i32, i32)| {
+                        cb.emit(Event::from(MouseEvent::mousemove(x, y)))
+                    },
+                ));
+            }
+            Box::new(canvas)
+        }
+        // a directory browser, opening a folder emits the input event with
+        // the folder, which is set back as the directory of the chooser
+        Widget::FileChooser => {
+            let directory = find_value(AttribKey::Directory, attrs)
+                .map(|v| PathBuf::from(v.to_string()))
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_default();
+            let height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let width = find_value(AttribKey::Width, attrs)
+                .and_then(|v| v.as_f64().map(|v| v as f32));
+            let mut file_chooser = FileChooser::new(
+                directory,
+                get_file_mode(attrs),
+                &get_filters(attrs),
+            );
+            file_chooser.set_size(width, height);
+            // the typed name is saved in the shown directory
+            if let Some(name) = find_value(AttribKey::Value, attrs) {
+                file_chooser.set_name(&name.to_string());
+            }
+            for cb in
+                find_callback(AttribKey::InputEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                file_chooser.add_input_listener(Callback::from(
+                    move |folder: String| {
+                        cb.emit(Event::from(InputEvent::new(folder)))
+                    },
+                ));
+            }
+            for cb in
+                find_callback(AttribKey::FilesEvent, attrs).unwrap_or_default()
+            {
+                let cb = cb.clone();
+                file_chooser.add_files_listener(Callback::from(
+                    move |paths: Vec<String>| {
+                        cb.emit(Event::from(InputEvent::new(paths)))
+                    },
+                ));
+            }
+            Box::new(file_chooser)
+        }
+        Widget::Custom(custom) => {
+            match custom_widget::find_custom_widget::<MSG>(custom.as_ref()) {
+                Some(hook) => hook.build_widget(custom.as_ref(), attrs),
+                // the children are shown in a box in place of the custom
+                // widget which has no conversion
+                None => {
+                    log::error!(
+                        "no titik conversion for the custom widget `{}`",
+                        custom.name()
+                    );
+                    let mut vbox = FlexBox::new();
+                    vbox.vertical();
+                    Box::new(vbox)
+                }
+            }
+        }
+    }
+}
+
+/// set the viewport, the rows which are built and the scroll offset of the
+/// list view
+pub(crate) fn set_list_view_attributes<MSG: Debug + 'static>(
+    list_view: &mut ListView<MSG>,
+    attrs: &[Attribute<MSG>],
+) {
+    let height = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_VIEWPORT_HEIGHT);
+    let width = find_value(AttribKey::Width, attrs)
+        .and_then(|v| v.as_f64().map(|v| v as f32));
+    list_view.set_size(width, Some(height as f32));
+    list_view.set_rows(
+        get_item_count(attrs),
+        get_row_height(attrs),
+        get_rendered_range(attrs),
+    );
+    list_view.set_scroll_offset(
+        find_value(AttribKey::ScrollOffset, attrs)
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0),
+    );
+}
+
+/// set the column headers, the rows and the selected row of the table. The
+/// sorted column is marked with the sort direction, and the columns can be
+/// sorted when the table is sortable
+pub(crate) fn set_table_lines<MSG: 'static>(
+    list: &mut SelectionList<MSG>,
+    attrs: &[Attribute<MSG>],
+) {
+    let (columns, rows) = get_table_data(attrs);
+    let is_sortable = find_value(AttribKey::Sortable, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let sort_column = get_index(AttribKey::SortColumn, attrs);
+    let is_descending = find_value(AttribKey::SortDescending, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let columns: Vec<String> = columns
+        .into_iter()
+        .enumerate()
+        .map(|(index, title)| match sort_column {
+            Some(sort_column) if sort_column == index => {
+                let indicator = if is_descending { "▼" } else { "▲" };
+                format!("{} {}", title, indicator)
+            }
+            _ => title,
+        })
+        .collect();
+    let widths = column_widths(&columns, &rows);
+    let mut lines = table_grid_lines(&widths, &columns, &rows);
+    let header = lines.remove(0);
+    list.set_header(Some(header));
+    list.set_lines(lines);
+    list.set_selected(get_index(AttribKey::Selected, attrs));
+    if is_sortable {
+        list.set_columns(column_ranges(&widths));
+    } else {
+        list.set_columns(vec![]);
+    }
+}
+
+/// the width of each column, which fits its header and all of its cells
+fn column_widths(columns: &[String], rows: &[Vec<String>]) -> Vec<usize> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(index, title)| {
+            rows.iter()
+                .filter_map(|row| row.get(index))
+                .chain(Some(title))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// the characters of each column in the lines of the table
+fn column_ranges(widths: &[usize]) -> Vec<Range<usize>> {
+    let mut start = 0;
+    widths
+        .iter()
+        .map(|width| {
+            let range = start..start + width;
+            start += width + COLUMN_SEPARATOR.len();
+            range
+        })
+        .collect()
+}
+
+/// the separator between the cells of the table
+const COLUMN_SEPARATOR: &str = " | ";
+
+/// lay out the column headers and the cells of the table into aligned
+/// columns, the first line is the column headers
+fn table_grid_lines(
+    widths: &[usize],
+    columns: &[String],
+    rows: &[Vec<String>],
+) -> Vec<String> {
+    let line = |cells: &[String]| -> String {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(index, width)| {
+                let cell = cells.get(index).map(|c| c.as_str()).unwrap_or("");
+                format!("{:width$}", cell, width = width)
+            })
+            .collect();
+        cells.join(COLUMN_SEPARATOR)
+    };
+    Some(line(columns))
+        .into_iter()
+        .chain(rows.iter().map(|row| line(row)))
+        .collect()
+}
+
+/// the label of the menu item prefixed with its check mark and followed by
+/// its accelerator
+fn menu_item_label<MSG: 'static>(
+    widget: &Widget,
+    attrs: &[Attribute<MSG>],
+) -> String {
+    let label = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let is_checked = find_value(AttribKey::Checked, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let mark = match widget {
+        Widget::CheckMenuItem if is_checked => "[x] ",
+        Widget::CheckMenuItem => "[ ] ",
+        Widget::RadioMenuItem if is_checked => "(•) ",
+        Widget::RadioMenuItem => "( ) ",
+        _ => "",
+    };
+    match get_accelerator(attrs) {
+        Some(key_event) => {
+            format!("{}{}  {}", mark, label, key_event.to_accelerator())
+        }
+        None => format!("{}{}", mark, label),
+    }
+}
+
+/// the bar of the progress, followed by its label
+pub(crate) fn progress_bar_frames<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Vec<String> {
+    let is_indeterminate = find_value(AttribKey::Indeterminate, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let label = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    if is_indeterminate {
+        indeterminate_frames(&label)
+    } else {
+        vec![progress_text(get_fraction(attrs), &label)]
     }
 }
+
+/// the frames of the spinner, which is blank when it is not active
+pub(crate) fn spinner_frames<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Vec<String> {
+    let is_active = find_value(AttribKey::Active, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(true);
+    if is_active {
+        "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏".chars().map(|ch| ch.to_string()).collect()
+    } else {
+        vec![" ".to_string()]
+    }
+}
+
+/// a block which moves back and forth inside the bar
+fn indeterminate_frames(label: &str) -> Vec<String> {
+    const BAR_WIDTH: usize = 20;
+    const BLOCK_WIDTH: usize = 4;
+    let last = BAR_WIDTH - BLOCK_WIDTH;
+    (0..last)
+        .chain((1..=last).rev())
+        .map(|pos| {
+            format!(
+                "{}{}{} {}",
+                "░".repeat(pos),
+                "█".repeat(BLOCK_WIDTH),
+                "░".repeat(last - pos),
+                label
+            )
+        })
+        .collect()
+}
+
+/// a bar of block characters filled up to the fraction,
+/// followed by the percentage and the text
+fn progress_text(fraction: f64, label: &str) -> String {
+    const BAR_WIDTH: usize = 20;
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    format!(
+        "{}{} {:.0}% {}",
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        fraction * 100.0,
+        label
+    )
+}
@@ -0,0 +1,45 @@
+//! conversion hooks of custom widgets for the titik backend
+//!
+use crate::backend::registry;
+use crate::{widget::CustomWidget, Attribute};
+use std::rc::Rc;
+
+/// Implement this to convert a custom widget into a titik widget
+pub trait TitikCustomWidget<MSG> {
+    /// create the titik widget of the custom widget,
+    /// the children are added to it with `add_child`
+    fn build_widget(
+        &self,
+        custom_widget: &dyn CustomWidget,
+        attrs: &[Attribute<MSG>],
+    ) -> Box<dyn titik::Widget<MSG>>;
+
+    /// update the titik widget with the changed attributes
+    fn set_attributes(
+        &self,
+        _widget: &mut dyn titik::Widget<MSG>,
+        _attrs: &[&Attribute<MSG>],
+    ) {
+    }
+}
+
+/// register the titik conversion of the custom widget with this name
+pub fn register_custom_widget<MSG, HOOK>(name: &str, hook: HOOK)
+where
+    MSG: 'static,
+    HOOK: TitikCustomWidget<MSG> + 'static,
+{
+    let hook: Rc<dyn TitikCustomWidget<MSG>> = Rc::new(hook);
+    registry::register_hook(name, hook);
+}
+
+/// find the titik conversion of the custom widget, returns None if
+/// there is no conversion registered with the name of the custom widget
+pub(crate) fn find_custom_widget<MSG>(
+    custom_widget: &dyn CustomWidget,
+) -> Option<Rc<dyn TitikCustomWidget<MSG>>>
+where
+    MSG: 'static,
+{
+    registry::find_hook::<dyn TitikCustomWidget<MSG>>(custom_widget.name())
+}
@@ -0,0 +1,144 @@
+//! the dialog, which is a groupbox that is taken out of the flow of the other
+//! widgets. The event loop centers it in the terminal and draws it above the
+//! other widgets, which are inert while it is shown.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::event::KeyCode,
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    Buffer, Cmd, Event, GroupBox, Widget as Control,
+};
+
+/// the width of the dialog when it has no width
+const DIALOG_WIDTH: f32 = 60.0;
+
+/// the dialog is dismissed with the escape key
+#[derive(Debug)]
+pub(crate) struct Dialog<MSG> {
+    groupbox: GroupBox<MSG>,
+    width: Option<f32>,
+    height: Option<f32>,
+    on_dismiss: Vec<Callback<(), MSG>>,
+}
+
+impl<MSG: 'static> Dialog<MSG> {
+    pub(crate) fn new(title: &str) -> Self {
+        let mut groupbox = GroupBox::new();
+        groupbox.set_label(title);
+        Dialog {
+            groupbox,
+            width: None,
+            height: None,
+            on_dismiss: vec![],
+        }
+    }
+
+    pub(crate) fn set_label(&mut self, title: &str) {
+        self.groupbox.set_label(title);
+    }
+
+    pub(crate) fn add_dismiss_listener(&mut self, cb: Callback<(), MSG>) {
+        self.on_dismiss.push(cb);
+    }
+}
+
+impl<MSG> Control<MSG> for Dialog<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.groupbox.layout()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.groupbox.set_layout(layout);
+    }
+
+    /// the height fits the content when it has no height
+    fn style(&self) -> Style {
+        Style {
+            position_type: PositionType::Absolute,
+            size: Size {
+                width: Dimension::Points(self.width.unwrap_or(DIALOG_WIDTH)),
+                height: self
+                    .height
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Auto),
+            },
+            ..self.groupbox.style()
+        }
+    }
+
+    fn get_offset(&self) -> (f32, f32) {
+        self.groupbox.get_offset()
+    }
+
+    /// the widgets underneath are cleared before the border is drawn
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout().expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let blank = " ".repeat(layout.size.width.max(0.0) as usize);
+        for y in top..top + layout.size.height.max(0.0) as usize {
+            buf.write_str(left, y, &blank);
+        }
+        self.groupbox.draw(buf)
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.groupbox.add_child(child)
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        self.groupbox.children()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        self.groupbox.children_mut()
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        self.groupbox.take_child(index)
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.groupbox.child_mut(index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        match event {
+            Event::Key(key_event) if key_event.code == KeyCode::Esc => {
+                self.on_dismiss.iter().map(|cb| cb.emit(())).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.groupbox.set_id(id);
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.groupbox.get_id()
+    }
+}
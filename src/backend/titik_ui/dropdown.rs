@@ -0,0 +1,255 @@
+//! the dropdown, which shows the selected option and opens a list of the
+//! options below it. The list pushes the widgets below it down while it is
+//! open, since titik draws the widgets in the order of the tree.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// the list is opened with enter, space or a click. The options are picked
+/// with the arrow keys and enter, and escape closes the list
+#[derive(Debug)]
+pub(crate) struct Dropdown<MSG> {
+    layout: Option<Layout>,
+    options: Vec<String>,
+    value: String,
+    /// the option under the cursor while the list is open
+    highlighted: usize,
+    is_open: bool,
+    is_focused: bool,
+    width: Option<f32>,
+    /// emitted with the option which is picked
+    on_change: Vec<Callback<String, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> Dropdown<MSG> {
+    pub(crate) fn new() -> Self {
+        Dropdown {
+            layout: None,
+            options: vec![],
+            value: String::new(),
+            highlighted: 0,
+            is_open: false,
+            is_focused: false,
+            width: None,
+            on_change: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_options(&mut self, options: Vec<String>) {
+        self.options = options;
+        self.highlighted =
+            self.highlighted.min(self.options.len().saturating_sub(1));
+    }
+
+    pub(crate) fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+
+    pub(crate) fn add_change_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_change.push(cb);
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.options.iter().position(|option| *option == self.value)
+    }
+
+    fn open(&mut self) {
+        self.is_open = true;
+        self.highlighted = self.selected_index().unwrap_or(0);
+    }
+
+    /// pick the option at this index, the change event is emitted only
+    /// when it is a different option
+    fn pick(&mut self, index: usize) -> Vec<MSG> {
+        self.is_open = false;
+        match self.options.get(index) {
+            Some(option) if *option != self.value => {
+                self.value = option.clone();
+                let value = self.value.clone();
+                self.on_change
+                    .iter()
+                    .map(|cb| cb.emit(value.clone()))
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// move to the next or previous option, in the list while it is open or
+    /// pick it right away while it is closed
+    fn step(&mut self, forward: bool) -> Vec<MSG> {
+        let last = self.options.len().saturating_sub(1);
+        let current = if self.is_open {
+            Some(self.highlighted)
+        } else {
+            self.selected_index()
+        };
+        let next = match current {
+            Some(current) if forward => (current + 1).min(last),
+            Some(current) => current.saturating_sub(1),
+            None => 0,
+        };
+        if self.is_open {
+            self.highlighted = next;
+            vec![]
+        } else {
+            self.pick(next)
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for Dropdown<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// the open list takes up a line for each of the options
+    fn style(&self) -> Style {
+        let height = if self.is_open {
+            1 + self.options.len()
+        } else {
+            1
+        } as f32;
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(height),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let current = format!("[{} ▾]", self.value);
+        write_line(buf, left, top, &current, self.is_focused && !self.is_open);
+        if self.is_open {
+            for (index, option) in self.options.iter().enumerate() {
+                let marker = if *option == self.value { ">" } else { " " };
+                write_line(
+                    buf,
+                    left,
+                    top + 1 + index,
+                    &format!("{} {}", marker, option),
+                    index == self.highlighted,
+                );
+            }
+        }
+        vec![]
+    }
+
+    /// the list is closed when the focus moves to another widget
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+        if !focused {
+            self.is_open = false;
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, _height: Option<f32>) {
+        self.width = width;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Enter | KeyCode::Char(' ') if self.is_open => {
+                    self.pick(self.highlighted)
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => {
+                    self.open();
+                    vec![]
+                }
+                KeyCode::Esc => {
+                    self.is_open = false;
+                    vec![]
+                }
+                KeyCode::Up => self.step(false),
+                KeyCode::Down => self.step(true),
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_btn, _x, y, _modifier)) => {
+                let top = self
+                    .layout
+                    .map(|layout| layout.location.y as usize)
+                    .unwrap_or(0);
+                match (y as usize).checked_sub(top) {
+                    Some(0) if self.is_open => {
+                        self.is_open = false;
+                        vec![]
+                    }
+                    Some(0) => {
+                        self.open();
+                        vec![]
+                    }
+                    Some(row) if self.is_open => self.pick(row - 1),
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// write the line, in reverse when it is highlighted
+fn write_line(
+    buf: &mut Buffer,
+    x: usize,
+    y: usize,
+    line: &str,
+    is_highlighted: bool,
+) {
+    if is_highlighted {
+        for (i, ch) in line.chars().enumerate() {
+            let mut cell = Cell::new(ch);
+            cell.attributes(vec![Attribute::Reverse]);
+            buf.set_cell(x + i, y, cell);
+        }
+    } else {
+        buf.write_str(x, y, line);
+    }
+}
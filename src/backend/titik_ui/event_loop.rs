@@ -0,0 +1,619 @@
+//! the event loop of the titik backend.
+//!
+//! This is the same loop as the renderer of titik, except the terminal events
+//! are waited with a timeout so the subscriptions of the app are ticked in
+//! between the events, and the animated widgets are drawn again for their next
+//! frame. While a dialog or a menu is open, only the widgets in
+//! the dialog or the menu bar receive the events.
+use super::dialog::Dialog;
+use super::menu::{self, Menu, MenuAction};
+use super::progress;
+use std::{io::Write, ops::Range, time::Instant};
+use titik::{
+    crossterm::{
+        cursor,
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent,
+            KeyModifiers, MouseEvent,
+        },
+        execute, queue, style,
+        terminal::{self, ClearType},
+        Result,
+    },
+    expanse::{geometry::Size, node::Node, number::Number, Stretch},
+    find_widget_mut, Buffer, Dispatch, Event, Widget as Control,
+};
+
+/// the program which is run in the event loop
+pub(crate) trait Program<MSG>: Dispatch<MSG> {
+    /// returns the time when the next subscription is due
+    fn next_due(&self) -> Option<Instant>;
+
+    /// dispatch the msgs of the subscriptions which are due
+    fn tick(&self, root_node: &mut dyn Control<MSG>);
+
+    /// returns the index of the widget which is requested to be focused by
+    /// the Cmds or the autofocus, since it is last called
+    fn take_focus(&self) -> Option<usize>;
+
+    /// returns the index of the focusable widgets in the order of the view
+    fn focus_order(&self) -> Vec<usize>;
+
+    /// dispatch the msgs of the blur and focus events when the focus moves
+    /// from the blurred widget to the focused widget
+    fn change_focus(
+        &self,
+        blurred: Option<usize>,
+        focused: Option<usize>,
+        root_node: &mut dyn Control<MSG>,
+    );
+
+    /// dispatch the msg of the shortcut of the key or else the msgs of the
+    /// menu item which has the key as its accelerator, returns false when
+    /// there is none
+    fn process_key(
+        &self,
+        key_event: &KeyEvent,
+        root_node: &mut dyn Control<MSG>,
+    ) -> bool;
+}
+
+/// the event loop which draws the root_node into the terminal
+pub(crate) struct EventLoop<'a, MSG> {
+    write: &'a mut dyn Write,
+    program: &'a dyn Program<MSG>,
+    root_node: &'a mut dyn Control<MSG>,
+    terminal_size: (u16, u16),
+    focused_widget_idx: Option<usize>,
+    /// the widget which is focused again when the menu bar is left
+    focus_before_menu: Option<usize>,
+}
+
+impl<'a, MSG: 'static> EventLoop<'a, MSG> {
+    pub(crate) fn new(
+        write: &'a mut dyn Write,
+        program: &'a dyn Program<MSG>,
+        root_node: &'a mut dyn Control<MSG>,
+    ) -> Result<Self> {
+        let terminal_size = terminal::size()?;
+        let mut event_loop = EventLoop {
+            write,
+            program,
+            root_node,
+            terminal_size,
+            focused_widget_idx: None,
+            focus_before_menu: None,
+        };
+        event_loop.apply_focus_request();
+        event_loop.recompute_layout();
+        Ok(event_loop)
+    }
+
+    /// run the event loop until any of the quit keys is pressed:
+    /// CTRL-c, CTRL-q, CTRL-d or CTRL-z
+    pub(crate) fn run(&mut self) -> Result<()> {
+        execute!(
+            self.write,
+            terminal::EnterAlternateScreen,
+            EnableMouseCapture
+        )?;
+        terminal::enable_raw_mode()?;
+        queue!(
+            self.write,
+            style::ResetColor,
+            terminal::Clear(ClearType::All),
+            cursor::Hide,
+            cursor::MoveTo(1, 1)
+        )?;
+        let ret = self.event_loop();
+        execute!(
+            self.write,
+            style::ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen,
+            DisableMouseCapture,
+        )?;
+        terminal::disable_raw_mode()?;
+        ret
+    }
+
+    fn event_loop(&mut self) -> Result<()> {
+        loop {
+            self.draw()?;
+            // the animated widgets are drawn again with their next frame
+            let next_frame = progress::has_animation(self.root_node)
+                .then(|| Instant::now() + progress::FRAME_INTERVAL);
+            let timeout = self
+                .program
+                .next_due()
+                .into_iter()
+                .chain(next_frame)
+                .min()
+                .map(|due| due.saturating_duration_since(Instant::now()));
+            let has_event = match timeout {
+                Some(timeout) => event::poll(timeout)?,
+                None => true,
+            };
+            if has_event {
+                let event = Event::from_crossterm(event::read()?);
+                if is_quit(&event) {
+                    return Ok(());
+                }
+                self.process_event(event);
+            }
+            self.program.tick(self.root_node);
+            self.apply_focus_request();
+            self.recompute_layout();
+        }
+    }
+
+    fn draw(&mut self) -> Result<()> {
+        let (width, height) = self.terminal_size;
+        let mut buf = Buffer::new(width as usize, height as usize);
+        buf.reset();
+        let cmds = draw_widgets(&mut buf, self.root_node);
+        buf.render(&mut self.write)?;
+        for cmd in cmds {
+            cmd.execute(self.write)?;
+        }
+        self.write.flush()?;
+        Ok(())
+    }
+
+    fn process_event(&mut self, event: Event) {
+        let dialog = find_dialog(self.root_node, &mut 0);
+        let menu_bar = menu::find_open_menu_bar(self.root_node, &mut 0);
+        let modal = menu_bar.clone().or_else(|| dialog.clone());
+        let is_active = |idx: &usize| {
+            modal.as_ref().is_none_or(|modal| modal.contains(idx))
+        };
+        match &event {
+            // the escape key dismisses the dialog
+            Event::Key(key_event)
+                if key_event.code == KeyCode::Esc
+                    && dialog.is_some()
+                    && menu_bar.is_none() =>
+            {
+                let idx = dialog.as_ref().expect("must have a dialog").start;
+                if let Some(dialog) = find_widget_mut(self.root_node, idx) {
+                    let msgs = dialog.process_event(event.clone());
+                    self.dispatch_msgs(msgs);
+                }
+            }
+            // the keys move around the menus while a menu item is focused,
+            // then the accelerators are matched before the focused widget.
+            // The menu bar and the accelerators are inert while a dialog is
+            // shown
+            Event::Key(key_event) => {
+                let focused = self.focused_widget_idx.filter(is_active);
+                let menu_action = focused
+                    .filter(|idx| menu::is_menu_item(self.root_node, *idx))
+                    .and_then(|idx| {
+                        menu::navigate_menu(self.root_node, idx, key_event)
+                    });
+                if let Some(menu_action) = menu_action {
+                    self.apply_menu_action(menu_action);
+                } else if let Some(idx) = self.tab_focus(key_event, &is_active)
+                {
+                    self.set_focus(Some(idx));
+                } else if dialog.is_some() {
+                    self.send_to_focused(focused, &event);
+                } else if self.program.process_key(key_event, self.root_node) {
+                    self.leave_menu_bar();
+                    self.recompute_layout();
+                } else if let Some(idx) =
+                    menu::enter_menu_bar(self.root_node, key_event)
+                {
+                    self.enter_menu_bar(idx);
+                } else {
+                    self.send_to_focused(focused, &event);
+                }
+            }
+            // mouse clicks sets the focused the widget underneath, a click
+            // outside of the open menus closes them
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                let hit =
+                    node_hit_at(self.root_node, *x as f32, *y as f32, &mut 0)
+                        .into_iter()
+                        .rfind(is_active);
+                match hit {
+                    Some(idx) if menu::is_menu_item(self.root_node, idx) => {
+                        self.enter_menu_bar(idx);
+                        if let Some(menu_action) =
+                            menu::click_menu_item(self.root_node, idx)
+                        {
+                            self.apply_menu_action(menu_action);
+                        }
+                        return;
+                    }
+                    _ if menu_bar.is_some() => {
+                        self.leave_menu_bar();
+                        return;
+                    }
+                    Some(idx) => self.set_focus(Some(idx)),
+                    None => (),
+                }
+            }
+            Event::Resize(width, height) => {
+                self.terminal_size = (*width, *height);
+                self.recompute_layout();
+            }
+            _ => (),
+        }
+        // the mouse events are sent to the widgets underneath the location,
+        // regardless if they are focused or not
+        if let Some((x, y)) = extract_location(&event) {
+            let hits = node_hit_at(self.root_node, x as f32, y as f32, &mut 0);
+            for hit in hits.iter().filter(|hit| is_active(hit)).rev() {
+                if let Some(hit_widget) = find_widget_mut(self.root_node, *hit)
+                {
+                    let msgs = hit_widget.process_event(event.clone());
+                    self.dispatch_msgs(msgs);
+                }
+            }
+        }
+    }
+
+    /// the keypresses are sent to the focused widget
+    fn send_to_focused(&mut self, focused: Option<usize>, event: &Event) {
+        if let Some(idx) = focused {
+            if let Some(focused) = find_widget_mut(self.root_node, idx) {
+                let msgs = focused.process_event(event.clone());
+                self.dispatch_msgs(msgs);
+            }
+        }
+    }
+
+    /// the widget which is focused with Tab or BackTab, the focus moves in
+    /// the order of the view and wraps around at the ends
+    fn tab_focus(
+        &self,
+        key_event: &KeyEvent,
+        is_active: &dyn Fn(&usize) -> bool,
+    ) -> Option<usize> {
+        let backward = match key_event.code {
+            KeyCode::Tab => false,
+            KeyCode::BackTab => true,
+            _ => return None,
+        };
+        let order: Vec<usize> = self
+            .program
+            .focus_order()
+            .into_iter()
+            .filter(|idx| is_active(idx))
+            .collect();
+        let pos = self
+            .focused_widget_idx
+            .and_then(|focused| order.iter().position(|idx| *idx == focused));
+        let next = match (pos, backward) {
+            (Some(pos), false) => (pos + 1) % order.len(),
+            (Some(pos), true) => (pos + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len().checked_sub(1)?,
+        };
+        order.get(next).copied()
+    }
+
+    /// the blur and focus events are emitted when the focus is moved
+    fn set_focus(&mut self, idx: Option<usize>) {
+        let blurred = self.focused_widget_idx;
+        self.focused_widget_idx = idx;
+        set_focused_node(self.root_node, idx, &mut 0);
+        if blurred != idx {
+            self.program.change_focus(blurred, idx, self.root_node);
+        }
+    }
+
+    /// focus the widget which is requested by the Cmds or the autofocus,
+    /// the menus are closed first
+    fn apply_focus_request(&mut self) {
+        if let Some(idx) = self.program.take_focus() {
+            menu::close_menus(self.root_node);
+            self.focus_before_menu = None;
+            self.set_focus(Some(idx));
+        }
+    }
+
+    /// focus the menu item, the widget which was focused before is kept so
+    /// it is focused again when the menu bar is left
+    fn enter_menu_bar(&mut self, idx: usize) {
+        let is_in_menu_bar = self
+            .focused_widget_idx
+            .is_some_and(|idx| menu::is_menu_item(self.root_node, idx));
+        if !is_in_menu_bar {
+            self.focus_before_menu = self.focused_widget_idx;
+        }
+        self.set_focus(Some(idx));
+        self.recompute_layout();
+    }
+
+    /// close the menus and focus the widget which was focused before
+    fn leave_menu_bar(&mut self) {
+        menu::close_menus(self.root_node);
+        let is_in_menu_bar = self
+            .focused_widget_idx
+            .is_some_and(|idx| menu::is_menu_item(self.root_node, idx));
+        if is_in_menu_bar {
+            let focus = self.focus_before_menu.take();
+            self.set_focus(focus);
+        }
+        self.recompute_layout();
+    }
+
+    fn apply_menu_action(&mut self, menu_action: MenuAction<MSG>) {
+        match menu_action {
+            MenuAction::Focus(idx) => {
+                self.set_focus(Some(idx));
+                self.recompute_layout();
+            }
+            MenuAction::Activate(msgs) => {
+                self.leave_menu_bar();
+                self.dispatch_msgs(msgs);
+            }
+            MenuAction::Leave => self.leave_menu_bar(),
+        }
+    }
+
+    fn dispatch_msgs(&mut self, msgs: Vec<MSG>) {
+        for msg in msgs {
+            self.program.dispatch(msg, self.root_node);
+        }
+        self.apply_focus_request();
+        self.recompute_layout();
+    }
+
+    fn recompute_layout(&mut self) {
+        layout_widgets(self.root_node, self.terminal_size);
+    }
+}
+
+/// lay out the widgets in the terminal size, the dialogs are centered
+pub(crate) fn layout_widgets<MSG: 'static>(
+    root_node: &mut dyn Control<MSG>,
+    terminal_size: (u16, u16),
+) {
+    let (width, height) = terminal_size;
+    let mut stretch = Stretch::new();
+    let stretch_node = build_stretch_node(&mut stretch, root_node)
+        .expect("must have built a style node");
+    stretch
+        .compute_layout(
+            stretch_node,
+            Size {
+                width: Number::Defined(width as f32),
+                height: Number::Defined(height as f32),
+            },
+        )
+        .expect("must compute the layout");
+    set_node_layout(root_node, stretch_node, &stretch, (0.0, 0.0), (0.0, 0.0));
+    center_dialogs(root_node, terminal_size);
+}
+
+/// draw the widgets into the buffer, the dialogs and the menus are drawn
+/// last, so they are above the other widgets
+pub(crate) fn draw_widgets<MSG: 'static>(
+    buf: &mut Buffer,
+    root_node: &dyn Control<MSG>,
+) -> Vec<titik::Cmd> {
+    let mut overlays = vec![];
+    let mut cmds = draw_widget(buf, root_node, &mut overlays);
+    while let Some(overlay) = overlays.pop() {
+        cmds.extend(draw_overlay(buf, overlay, &mut overlays));
+    }
+    cmds
+}
+
+/// the keys which quits the event loop
+fn is_quit(event: &Event) -> bool {
+    match event {
+        Event::Key(key_event) => {
+            key_event.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(
+                    key_event.code,
+                    KeyCode::Char('c')
+                        | KeyCode::Char('q')
+                        | KeyCode::Char('d')
+                        | KeyCode::Char('z')
+                )
+        }
+        _ => false,
+    }
+}
+
+/// draw the widget and its children, the hidden widgets such as the tab
+/// pages which are not selected are laid out with no size, so they are not
+/// drawn together with their children. The dialogs and the menus are
+/// collected to be drawn after the rest of the widgets
+fn draw_widget<'w, MSG: 'static>(
+    buf: &mut Buffer,
+    widget: &'w dyn Control<MSG>,
+    overlays: &mut Vec<&'w dyn Control<MSG>>,
+) -> Vec<titik::Cmd> {
+    let is_menu = widget
+        .as_any()
+        .downcast_ref::<Menu<MSG>>()
+        .is_some_and(|menu| !menu.is_bar());
+    if is_menu || widget.as_any().is::<Dialog<MSG>>() {
+        overlays.push(widget);
+        return vec![];
+    }
+    draw_overlay(buf, widget, overlays)
+}
+
+/// draw the widget and its children, including when it is a dialog or a menu
+fn draw_overlay<'w, MSG: 'static>(
+    buf: &mut Buffer,
+    widget: &'w dyn Control<MSG>,
+    overlays: &mut Vec<&'w dyn Control<MSG>>,
+) -> Vec<titik::Cmd> {
+    let is_hidden = widget.layout().is_none_or(|layout| {
+        layout.size.width <= 0.0 || layout.size.height <= 0.0
+    });
+    if is_hidden {
+        return vec![];
+    }
+    let mut cmds = widget.draw(buf);
+    if let Some(children) = widget.children() {
+        for child in children {
+            cmds.extend(draw_widget(buf, child.as_ref(), overlays));
+        }
+    }
+    cmds
+}
+
+/// returns the index of the last dialog and its nested widgets
+fn find_dialog<MSG: 'static>(
+    node: &dyn Control<MSG>,
+    cur_node_idx: &mut usize,
+) -> Option<Range<usize>> {
+    let start = *cur_node_idx;
+    let mut found = None;
+    if let Some(children) = node.children() {
+        for child in children {
+            *cur_node_idx += 1;
+            found = find_dialog(child.as_ref(), cur_node_idx).or(found);
+        }
+    }
+    if found.is_none() && node.as_any().is::<Dialog<MSG>>() {
+        found = Some(start..*cur_node_idx + 1);
+    }
+    found
+}
+
+/// move the dialogs to the center of the terminal
+fn center_dialogs<MSG: 'static>(
+    widget: &mut dyn Control<MSG>,
+    terminal_size: (u16, u16),
+) {
+    if widget.as_any().is::<Dialog<MSG>>() {
+        if let Some(layout) = widget.layout() {
+            let (width, height) = terminal_size;
+            let x = ((width as f32 - layout.size.width) / 2.0).max(0.0).floor();
+            let y = ((height as f32 - layout.size.height) / 2.0)
+                .max(0.0)
+                .floor();
+            let offset = (x - layout.location.x, y - layout.location.y);
+            move_widget(widget, offset);
+        }
+    }
+    if let Some(children) = widget.children_mut() {
+        for child in children {
+            center_dialogs(child.as_mut(), terminal_size);
+        }
+    }
+}
+
+/// move the widget and its children by the offset
+fn move_widget<MSG>(widget: &mut dyn Control<MSG>, offset: (f32, f32)) {
+    if let Some(mut layout) = widget.layout().copied() {
+        layout.location.x += offset.0;
+        layout.location.y += offset.1;
+        widget.set_layout(layout);
+    }
+    if let Some(children) = widget.children_mut() {
+        for child in children {
+            move_widget(child.as_mut(), offset);
+        }
+    }
+}
+
+/// returns the index of the widgets which contains the location
+fn node_hit_at<MSG>(
+    node: &dyn Control<MSG>,
+    x: f32,
+    y: f32,
+    cur_node_idx: &mut usize,
+) -> Vec<usize> {
+    let mut hits = vec![];
+    if let Some(layout) = node.layout() {
+        let loc = layout.location;
+        if x >= loc.x
+            && x < loc.x + layout.size.width
+            && y >= loc.y
+            && y < loc.y + layout.size.height
+        {
+            hits.push(*cur_node_idx);
+        }
+    }
+    if let Some(children) = node.children() {
+        for child in children {
+            *cur_node_idx += 1;
+            hits.extend(node_hit_at(child.as_ref(), x, y, cur_node_idx));
+        }
+    }
+    hits
+}
+
+/// set the widget at this index as focused, while the rest are unfocused
+pub(crate) fn set_focused_node<MSG>(
+    node: &mut dyn Control<MSG>,
+    node_idx: Option<usize>,
+    cur_node_idx: &mut usize,
+) {
+    node.set_focused(node_idx == Some(*cur_node_idx));
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            *cur_node_idx += 1;
+            set_focused_node(child.as_mut(), node_idx, cur_node_idx);
+        }
+    }
+}
+
+fn build_stretch_node<MSG>(
+    stretch: &mut Stretch,
+    widget: &dyn Control<MSG>,
+) -> Option<Node> {
+    let children: Vec<Node> = match widget.children() {
+        Some(children) => children
+            .iter()
+            .filter_map(|child| build_stretch_node(stretch, child.as_ref()))
+            .collect(),
+        None => vec![],
+    };
+    stretch.new_node(widget.style(), &children).ok()
+}
+
+/// set the computed layout into the widgets, the location is relative to
+/// the terminal and the children are placed inside the offset of the parent
+fn set_node_layout<MSG>(
+    widget: &mut dyn Control<MSG>,
+    stretch_node: Node,
+    stretch: &Stretch,
+    parent_loc: (f32, f32),
+    parent_offset: (f32, f32),
+) {
+    let mut layout = *stretch.layout(stretch_node).expect("must have layout");
+    layout.location.x += parent_loc.0 + parent_offset.0;
+    layout.location.y += parent_loc.1 + parent_offset.1;
+    layout.size.width -= parent_offset.0;
+    layout.size.height -= parent_offset.1;
+
+    let child_offset = widget.get_offset();
+    let stretch_children =
+        stretch.children(stretch_node).expect("must get children");
+    let children = widget.children_mut().unwrap_or(&mut []);
+    for (stretch_child, child) in stretch_children.into_iter().zip(children) {
+        set_node_layout(
+            child.as_mut(),
+            stretch_child,
+            stretch,
+            (layout.location.x, layout.location.y),
+            child_offset,
+        );
+    }
+    widget.set_layout(layout);
+}
+
+/// extract the x and y location of a mouse event
+fn extract_location(event: &Event) -> Option<(u16, u16)> {
+    match event {
+        Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier))
+        | Event::Mouse(MouseEvent::Up(_btn, x, y, _modifier))
+        | Event::Mouse(MouseEvent::Drag(_btn, x, y, _modifier)) => {
+            Some((*x, *y))
+        }
+        Event::Mouse(MouseEvent::ScrollDown(x, y, _modifier))
+        | Event::Mouse(MouseEvent::ScrollUp(x, y, _modifier)) => Some((*x, *y)),
+        _ => None,
+    }
+}
@@ -0,0 +1,365 @@
+//! the directory browser of the file chooser, which draws the entries of the
+//! directory as lines. It has no children, so the node index of the widgets
+//! stays the same as the view.
+use crate::widget::attribute::FileMode;
+use mt_dom::Callback;
+use std::{
+    any::Any,
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// a line of the file chooser below the directory
+#[derive(Debug, Clone, PartialEq)]
+enum Row {
+    /// selects the shown directory, in select folder mode
+    SelectFolder,
+    /// the typed file name, in save mode
+    FileName,
+    /// a folder or a file in the directory
+    Entry {
+        label: String,
+        path: PathBuf,
+        is_dir: bool,
+    },
+}
+
+/// the lines are highlighted with the up and down keys and activated with
+/// enter or a click. Opening a folder emits the input event with the folder,
+/// picking a file emits the files event. The file name is typed into its
+/// line in save mode.
+#[derive(Debug)]
+pub(crate) struct FileChooser<MSG> {
+    layout: Option<Layout>,
+    directory: PathBuf,
+    rows: Vec<Row>,
+    /// the file name which is typed in save mode
+    name: String,
+    highlighted: usize,
+    is_focused: bool,
+    width: Option<f32>,
+    height: Option<f32>,
+    /// emitted with the folder which is opened
+    on_input: Vec<Callback<String, MSG>>,
+    /// emitted with the paths which are picked
+    on_files: Vec<Callback<Vec<String>, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> FileChooser<MSG> {
+    pub(crate) fn new(
+        directory: PathBuf,
+        mode: FileMode,
+        filters: &[String],
+    ) -> Self {
+        let action = match mode {
+            FileMode::SelectFolder => Some(Row::SelectFolder),
+            FileMode::Save => Some(Row::FileName),
+            _ => None,
+        };
+        let entries = directory_entries(&directory, mode, filters)
+            .into_iter()
+            .map(|(label, path, is_dir)| Row::Entry {
+                label,
+                path,
+                is_dir,
+            });
+        FileChooser {
+            layout: None,
+            rows: action.into_iter().chain(entries).collect(),
+            directory,
+            name: String::new(),
+            highlighted: 0,
+            is_focused: false,
+            width: None,
+            height: None,
+            on_input: vec![],
+            on_files: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    pub(crate) fn add_input_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_input.push(cb);
+    }
+
+    pub(crate) fn add_files_listener(
+        &mut self,
+        cb: Callback<Vec<String>, MSG>,
+    ) {
+        self.on_files.push(cb);
+    }
+
+    /// the number of rows which fits below the directory
+    fn visible_rows(&self) -> usize {
+        let height = self
+            .layout
+            .map(|layout| layout.size.height.max(0.0) as usize)
+            .unwrap_or(0);
+        height.saturating_sub(1).max(1)
+    }
+
+    /// the first row which is shown, the rows are scrolled until the
+    /// highlighted row is visible
+    fn first_visible(&self) -> usize {
+        let visible = self.visible_rows();
+        if self.highlighted >= visible {
+            self.highlighted + 1 - visible
+        } else {
+            0
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        self.rows.get(self.highlighted) == Some(&Row::FileName)
+    }
+
+    /// emit the events of the highlighted row, an empty file name is not
+    /// emitted
+    fn activate(&self) -> Vec<MSG> {
+        let files = |path: &Path| {
+            let paths = vec![path.display().to_string()];
+            self.on_files
+                .iter()
+                .map(|cb| cb.emit(paths.clone()))
+                .collect()
+        };
+        match self.rows.get(self.highlighted) {
+            Some(Row::SelectFolder) => files(&self.directory),
+            Some(Row::FileName) => {
+                let name = self.name.trim();
+                if name.is_empty() {
+                    vec![]
+                } else {
+                    files(&self.directory.join(name))
+                }
+            }
+            Some(Row::Entry {
+                path, is_dir: true, ..
+            }) => {
+                let path = path.display().to_string();
+                self.on_input
+                    .iter()
+                    .map(|cb| cb.emit(path.clone()))
+                    .collect()
+            }
+            Some(Row::Entry { path, .. }) => files(path),
+            None => vec![],
+        }
+    }
+
+    fn row_label(&self, row: &Row) -> String {
+        match row {
+            Row::SelectFolder => "[select folder]".to_string(),
+            Row::FileName if self.is_focused && self.is_typing() => {
+                format!("name: {}_", self.name)
+            }
+            Row::FileName => format!("name: {}", self.name),
+            Row::Entry { label, .. } => label.clone(),
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for FileChooser<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        let height = self.height.unwrap_or((1 + self.rows.len()) as f32);
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(height),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the directory is shown in bold above the rows, the highlighted row
+    /// is shown in reverse when the file chooser is focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let width = layout.size.width.max(0.0) as usize;
+        buf.write_bold_str(
+            left,
+            top,
+            truncate(&self.directory.display().to_string(), width),
+        );
+        let first = self.first_visible();
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(self.visible_rows());
+        for (line, (index, row)) in rows.enumerate() {
+            let y = top + 1 + line;
+            let label = truncate(&self.row_label(row), width);
+            if index == self.highlighted && self.is_focused {
+                for (i, ch) in label.chars().enumerate() {
+                    let mut cell = Cell::new(ch);
+                    cell.attributes(vec![Attribute::Reverse]);
+                    buf.set_cell(left + i, y, cell);
+                }
+            } else {
+                buf.write_str(left, y, label);
+            }
+        }
+        vec![]
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let last = self.rows.len().saturating_sub(1);
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Up => {
+                    self.highlighted = self.highlighted.saturating_sub(1);
+                    vec![]
+                }
+                KeyCode::Down => {
+                    self.highlighted = (self.highlighted + 1).min(last);
+                    vec![]
+                }
+                KeyCode::PageUp => {
+                    self.highlighted =
+                        self.highlighted.saturating_sub(self.visible_rows());
+                    vec![]
+                }
+                KeyCode::PageDown => {
+                    self.highlighted =
+                        (self.highlighted + self.visible_rows()).min(last);
+                    vec![]
+                }
+                KeyCode::Enter => self.activate(),
+                KeyCode::Char(ch) if self.is_typing() => {
+                    self.name.push(ch);
+                    vec![]
+                }
+                KeyCode::Backspace if self.is_typing() => {
+                    self.name.pop();
+                    vec![]
+                }
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_btn, _x, y, _modifier)) => {
+                let top = self
+                    .layout
+                    .map(|layout| layout.location.y as usize + 1)
+                    .unwrap_or(1);
+                match (y as usize).checked_sub(top) {
+                    Some(line) if self.first_visible() + line <= last => {
+                        self.highlighted = self.first_visible() + line;
+                        self.activate()
+                    }
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// the entries of the directory browser, the parent folder and the sub
+/// folders are listed first, followed by the files which matches the filters.
+/// Only the folders are listed when selecting a folder.
+fn directory_entries(
+    directory: &Path,
+    mode: FileMode,
+    filters: &[String],
+) -> Vec<(String, PathBuf, bool)> {
+    let mut folders = vec![];
+    let mut files = vec![];
+    if let Ok(entries) = std::fs::read_dir(directory) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if path.is_dir() {
+                folders.push((format!("{}/", name), path, true));
+            } else if mode != FileMode::SelectFolder
+                && matches_filters(&name, filters)
+            {
+                files.push((name, path, false));
+            }
+        }
+    }
+    folders.sort();
+    files.sort();
+    let parent = directory
+        .parent()
+        .map(|parent| ("../".to_string(), parent.to_path_buf(), true));
+    parent.into_iter().chain(folders).chain(files).collect()
+}
+
+/// whether the file name matches any of the patterns, such as `*.png`.
+/// All the files matches when there is no filter
+fn matches_filters(name: &str, filters: &[String]) -> bool {
+    filters.is_empty()
+        || filters
+            .iter()
+            .any(|pattern| match pattern.strip_prefix('*') {
+                Some(extension) => name.ends_with(extension),
+                None => name == pattern,
+            })
+}
+
+/// the characters of the line which fits in the width
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
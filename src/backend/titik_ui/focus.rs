@@ -0,0 +1,172 @@
+//! keyboard focus of the widgets. The titik widgets are in the same order as
+//! the nodes of the view, so the widgets are found with the index of their
+//! node.
+use crate::{
+    widget::{
+        attribute::{find_callback, find_value, util::is_autofocus},
+        event::FocusEvent,
+    },
+    AttribKey, Event, Node, Patch,
+};
+use mt_dom::patch::{AppendChildren, InsertNode, ReplaceNode};
+
+/// the index of the widget with this id, or the first focusable widget
+/// inside it
+pub(crate) fn find_by_id<MSG: 'static>(
+    view: &Node<MSG>,
+    id: &str,
+) -> Option<usize> {
+    let (idx, node) = find_first(view, 0, &|node| {
+        node.get_attributes()
+            .and_then(|attrs| find_value(AttribKey::Id, attrs))
+            .is_some_and(|value| value.as_str() == Some(id))
+    })?;
+    find_first(node, idx, &is_focusable).map(|(idx, _node)| idx)
+}
+
+/// the index of the first widget with autofocus in the view
+pub(crate) fn find_autofocus<MSG: 'static>(view: &Node<MSG>) -> Option<usize> {
+    find_first(view, 0, &has_autofocus).map(|(idx, _node)| idx)
+}
+
+/// the index of the first widget with autofocus which is newly shown by the
+/// patches, the widgets which are already shown are not focused again
+pub(crate) fn find_patched_autofocus<MSG: 'static>(
+    patches: &[Patch<MSG>],
+) -> Option<usize> {
+    patches
+        .iter()
+        .flat_map(|patch| match patch {
+            Patch::InsertNode(InsertNode {
+                new_node_idx, node, ..
+            }) => vec![(*new_node_idx, *node)],
+            Patch::ReplaceNode(ReplaceNode {
+                new_node_idx,
+                replacement,
+                ..
+            }) => vec![(*new_node_idx, *replacement)],
+            Patch::AppendChildren(AppendChildren { children, .. }) => {
+                children.clone()
+            }
+            _ => vec![],
+        })
+        .filter_map(|(idx, node)| find_first(node, idx, &has_autofocus))
+        .map(|(idx, _node)| idx)
+        .min()
+}
+
+/// the indices of the focusable widgets in the order of the view, which is
+/// the order the focus moves with Tab
+pub(crate) fn focus_order<MSG>(view: &Node<MSG>) -> Vec<usize> {
+    let mut order = vec![];
+    collect_focusable(view, &mut 0, &mut order);
+    order
+}
+
+/// the msgs of the blur events of the widget which loses the focus, then the
+/// focus events of the widget which gains it. The containers are also
+/// notified, unless the focus moves between the widgets inside them.
+pub(crate) fn focus_change_msgs<MSG: 'static>(
+    view: &Node<MSG>,
+    blurred: Option<usize>,
+    focused: Option<usize>,
+) -> Vec<MSG> {
+    let blurred_path = blurred
+        .and_then(|idx| node_path(view, idx, &mut 0))
+        .unwrap_or_default();
+    let focused_path = focused
+        .and_then(|idx| node_path(view, idx, &mut 0))
+        .unwrap_or_default();
+    let is_in = |path: &[(usize, &Node<MSG>)], idx: usize| {
+        path.iter().any(|(node_idx, _node)| *node_idx == idx)
+    };
+    let blur_msgs = blurred_path
+        .iter()
+        .rev()
+        .filter(|(idx, _node)| !is_in(&focused_path, *idx))
+        .flat_map(|(_idx, node)| {
+            emit(node, AttribKey::BlurEvent, Event::BlurEvent(FocusEvent))
+        });
+    let focus_msgs = focused_path
+        .iter()
+        .rev()
+        .filter(|(idx, _node)| !is_in(&blurred_path, *idx))
+        .flat_map(|(_idx, node)| {
+            emit(node, AttribKey::FocusEvent, Event::FocusEvent(FocusEvent))
+        });
+    blur_msgs.chain(focus_msgs).collect()
+}
+
+fn is_focusable<MSG>(node: &Node<MSG>) -> bool {
+    node.tag().is_some_and(|widget| widget.is_focusable())
+}
+
+fn has_autofocus<MSG: 'static>(node: &Node<MSG>) -> bool {
+    node.get_attributes().is_some_and(is_autofocus)
+}
+
+fn emit<MSG: 'static>(
+    node: &Node<MSG>,
+    key: AttribKey,
+    event: Event,
+) -> Vec<MSG> {
+    node.get_attributes()
+        .and_then(|attrs| find_callback(key, attrs))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|cb| cb.emit(event.clone()))
+        .collect()
+}
+
+fn collect_focusable<MSG>(
+    node: &Node<MSG>,
+    cur_node_idx: &mut usize,
+    order: &mut Vec<usize>,
+) {
+    if is_focusable(node) {
+        order.push(*cur_node_idx);
+    }
+    for child in node.get_children().unwrap_or(&[]) {
+        *cur_node_idx += 1;
+        collect_focusable(child, cur_node_idx, order);
+    }
+}
+
+/// the first node which matches, including this node which is at node_idx
+fn find_first<'a, MSG>(
+    node: &'a Node<MSG>,
+    node_idx: usize,
+    predicate: &dyn Fn(&Node<MSG>) -> bool,
+) -> Option<(usize, &'a Node<MSG>)> {
+    if predicate(node) {
+        return Some((node_idx, node));
+    }
+    let mut child_idx = node_idx + 1;
+    for child in node.get_children().unwrap_or(&[]) {
+        if let Some(found) = find_first(child, child_idx, predicate) {
+            return Some(found);
+        }
+        child_idx += child.node_count();
+    }
+    None
+}
+
+/// the nodes from the root to the node at this index
+fn node_path<'a, MSG>(
+    node: &'a Node<MSG>,
+    node_idx: usize,
+    cur_node_idx: &mut usize,
+) -> Option<Vec<(usize, &'a Node<MSG>)>> {
+    let idx = *cur_node_idx;
+    if idx == node_idx {
+        return Some(vec![(idx, node)]);
+    }
+    for child in node.get_children().unwrap_or(&[]) {
+        *cur_node_idx += 1;
+        if let Some(mut path) = node_path(child, node_idx, cur_node_idx) {
+            path.insert(0, (idx, node));
+            return Some(path);
+        }
+    }
+    None
+}
@@ -0,0 +1,271 @@
+//! the list view, which shows the rows that are built at the scroll offset.
+//! The rows are kept as children so the node index of the widgets stays the
+//! same as the view, while the rows outside of the viewport are hidden.
+use crate::widget::attribute::util::visible_range;
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug, ops::Range};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::{Rect, Size},
+        result::Layout,
+        style::{Dimension, FlexDirection, PositionType, Style},
+    },
+    Buffer, Cmd, Event, Widget as Control,
+};
+
+/// the list is scrolled with the mouse wheel, the arrow keys, page up and
+/// page down, home and end
+#[derive(Debug)]
+pub(crate) struct ListView<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    item_count: usize,
+    row_height: f64,
+    scroll_offset: f64,
+    /// the index of the item of the first row which is built
+    rendered_start: usize,
+    width: Option<f32>,
+    /// the height of the viewport
+    height: f32,
+    is_focused: bool,
+    /// emitted with the new scroll offset
+    on_scroll: Vec<Callback<f64, MSG>>,
+    /// emitted with the rows which are visible at the new scroll offset
+    on_visible_range: Vec<Callback<Range<usize>, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> ListView<MSG> {
+    pub(crate) fn new() -> Self {
+        ListView {
+            layout: None,
+            children: vec![],
+            item_count: 0,
+            row_height: 1.0,
+            scroll_offset: 0.0,
+            rendered_start: 0,
+            width: None,
+            height: 0.0,
+            is_focused: false,
+            on_scroll: vec![],
+            on_visible_range: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_rows(
+        &mut self,
+        item_count: usize,
+        row_height: f64,
+        rendered: Range<usize>,
+    ) {
+        self.item_count = item_count;
+        self.row_height = row_height;
+        self.rendered_start = rendered.start;
+    }
+
+    pub(crate) fn set_scroll_offset(&mut self, scroll_offset: f64) {
+        self.scroll_offset = scroll_offset;
+    }
+
+    pub(crate) fn add_scroll_listener(&mut self, cb: Callback<f64, MSG>) {
+        self.on_scroll.push(cb);
+    }
+
+    pub(crate) fn add_visible_range_listener(
+        &mut self,
+        cb: Callback<Range<usize>, MSG>,
+    ) {
+        self.on_visible_range.push(cb);
+    }
+
+    /// the height of the rows which are built
+    fn rows_height(&self) -> f32 {
+        self.children.len() as f32 * self.row_height as f32
+    }
+
+    /// scroll to the offset, which is kept inside the rows. The events are
+    /// emitted only when the offset is changed
+    fn scroll_to(&mut self, scroll_offset: f64) -> Vec<MSG> {
+        let max = (self.item_count as f64 * self.row_height
+            - self.height as f64)
+            .max(0.0);
+        let scroll_offset = scroll_offset.max(0.0).min(max);
+        if scroll_offset == self.scroll_offset {
+            return vec![];
+        }
+        self.scroll_offset = scroll_offset;
+        let range = visible_range(
+            self.item_count,
+            self.row_height,
+            scroll_offset,
+            self.height as f64,
+        );
+        let scroll_msgs =
+            self.on_scroll.iter().map(|cb| cb.emit(scroll_offset));
+        let range_msgs = self
+            .on_visible_range
+            .iter()
+            .map(|cb| cb.emit(range.clone()));
+        scroll_msgs.chain(range_msgs).collect()
+    }
+
+    fn scroll_by(&mut self, delta: f64) -> Vec<MSG> {
+        self.scroll_to(self.scroll_offset + delta)
+    }
+}
+
+impl<MSG> Control<MSG> for ListView<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    /// the rows which are not fully inside the viewport are hidden
+    fn set_layout(&mut self, mut layout: Layout) {
+        let (row_height, height) = (self.row_height, self.height as f64);
+        let first_row = self.rendered_start as f64 * row_height;
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let row_top =
+                first_row + index as f64 * row_height - self.scroll_offset;
+            if row_top < 0.0 || row_top + row_height > height {
+                hide_widget(child.as_mut());
+            }
+        }
+        layout.size.height = self.height;
+        self.layout = Some(layout);
+    }
+
+    /// the rows are laid out with their full height and shifted up by the
+    /// scroll offset with a negative top padding, while the negative bottom
+    /// margin keeps the space of the list view to its viewport
+    fn style(&self) -> Style {
+        let rows_height = self.rows_height().max(self.height);
+        let shift = (self.scroll_offset
+            - self.rendered_start as f64 * self.row_height)
+            as f32;
+        Style {
+            position_type: PositionType::Relative,
+            flex_direction: FlexDirection::Column,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(rows_height),
+            },
+            padding: Rect {
+                top: Dimension::Points(-shift),
+                ..Default::default()
+            },
+            margin: Rect {
+                bottom: Dimension::Points(self.height - rows_height),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    /// the rows are drawn as the children
+    fn draw(&self, _buf: &mut Buffer) -> Vec<Cmd> {
+        vec![]
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        if let Some(height) = height {
+            self.height = height;
+        }
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let row_height = self.row_height;
+        let page = (self.height as f64 - row_height).max(row_height);
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Up => self.scroll_by(-row_height),
+                KeyCode::Down => self.scroll_by(row_height),
+                KeyCode::PageUp => self.scroll_by(-page),
+                KeyCode::PageDown => self.scroll_by(page),
+                KeyCode::Home => self.scroll_to(0.0),
+                KeyCode::End => self.scroll_to(f64::MAX),
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::ScrollUp(_x, _y, _modifier)) => {
+                self.scroll_by(-row_height)
+            }
+            Event::Mouse(MouseEvent::ScrollDown(_x, _y, _modifier)) => {
+                self.scroll_by(row_height)
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// the widget and its children are laid out with no size, so they are not
+/// drawn nor hit by the mouse
+fn hide_widget<MSG>(widget: &mut dyn Control<MSG>) {
+    if let Some(mut layout) = widget.layout().copied() {
+        layout.size = Size {
+            width: 0.0,
+            height: 0.0,
+        };
+        widget.set_layout(layout);
+    }
+    if let Some(children) = widget.children_mut() {
+        for child in children {
+            hide_widget(child.as_mut());
+        }
+    }
+}
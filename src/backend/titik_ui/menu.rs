@@ -0,0 +1,679 @@
+//! the menu bar and its menus. The menus are opened with F10, with alt and
+//! the first letter of a menu item in the menu bar or by clicking a menu
+//! item, then the menu items are navigated with the arrow keys. An open menu
+//! is taken out of the flow of the other widgets and it is drawn above them.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug, ops::Range};
+use titik::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    expanse::{
+        geometry::{Rect, Size},
+        result::Layout,
+        style::{Dimension, Display, FlexDirection, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Widget as Control,
+};
+
+/// what is done by a key or a click in the menus
+pub(crate) enum MenuAction<MSG> {
+    /// focus the menu item at this index
+    Focus(usize),
+    /// the menu item is activated with these msgs, the menus are closed
+    Activate(Vec<MSG>),
+    /// the menus are closed and the focus goes back to the other widgets
+    Leave,
+}
+
+/// the menu bar, or a menu which is shown below its menu item while it is
+/// open. The menu of a menu item inside another menu is shown beside it.
+#[derive(Debug)]
+pub(crate) struct Menu<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    is_bar: bool,
+    /// whether the menu item of this menu is in another menu
+    is_nested: bool,
+    is_open: bool,
+    width: Option<f32>,
+    height: Option<f32>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> Menu<MSG> {
+    pub(crate) fn new(is_bar: bool) -> Self {
+        Menu {
+            layout: None,
+            children: vec![],
+            is_bar,
+            is_nested: false,
+            is_open: false,
+            width: None,
+            height: None,
+            id: None,
+        }
+    }
+
+    pub(crate) fn is_bar(&self) -> bool {
+        self.is_bar
+    }
+}
+
+impl<MSG> Control<MSG> for Menu<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// the menu fits its menu items inside the border, it is not shown
+    /// while it is closed
+    fn style(&self) -> Style {
+        let size = Size {
+            width: self.width.map(Dimension::Points).unwrap_or(Dimension::Auto),
+            height: self
+                .height
+                .map(Dimension::Points)
+                .unwrap_or(Dimension::Auto),
+        };
+        if self.is_bar {
+            return Style {
+                position_type: PositionType::Relative,
+                flex_direction: FlexDirection::Row,
+                size,
+                ..Default::default()
+            };
+        }
+        let position = if self.is_nested {
+            Rect {
+                start: Dimension::Percent(1.0),
+                top: Dimension::Points(-1.0),
+                ..Default::default()
+            }
+        } else {
+            Rect {
+                start: Dimension::Points(0.0),
+                top: Dimension::Points(1.0),
+                ..Default::default()
+            }
+        };
+        Style {
+            display: if self.is_open {
+                Display::Flex
+            } else {
+                Display::None
+            },
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            position,
+            padding: Rect {
+                start: Dimension::Points(1.0),
+                end: Dimension::Points(1.0),
+                top: Dimension::Points(1.0),
+                bottom: Dimension::Points(1.0),
+            },
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// the widgets underneath the menu are cleared before its border is
+    /// drawn
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        if self.is_bar {
+            return vec![];
+        }
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let width = layout.size.width.max(2.0) as usize;
+        let height = layout.size.height.max(2.0) as usize;
+        let line = "─".repeat(width - 2);
+        buf.write_str(left, top, format!("┌{}┐", line));
+        for y in top + 1..top + height - 1 {
+            buf.write_str(left, y, format!("│{}│", " ".repeat(width - 2)));
+        }
+        buf.write_str(left, top + height - 1, format!("└{}┘", line));
+        vec![]
+    }
+
+    /// the menu items inside a menu are nested, so their menus are shown
+    /// beside them
+    fn add_child(&mut self, mut child: Box<dyn Control<MSG>>) -> bool {
+        if let Some(item) = child.as_any_mut().downcast_mut::<MenuItem<MSG>>() {
+            item.set_nested(!self.is_bar);
+        }
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// a line in the menu bar or in a menu, its menu is added as its child
+#[derive(Debug)]
+pub(crate) struct MenuItem<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    label: String,
+    /// whether it is inside a menu rather than in the menu bar
+    is_nested: bool,
+    is_focused: bool,
+    on_activate: Vec<Callback<(), MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> MenuItem<MSG> {
+    pub(crate) fn new(label: &str) -> Self {
+        MenuItem {
+            layout: None,
+            children: vec![],
+            label: label.to_string(),
+            is_nested: false,
+            is_focused: false,
+            on_activate: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn add_activate_listener(&mut self, cb: Callback<(), MSG>) {
+        self.on_activate.push(cb);
+    }
+
+    /// the menu of the menu item is nested the same as the menu item
+    fn set_nested(&mut self, is_nested: bool) {
+        self.is_nested = is_nested;
+        for child in self.children.iter_mut() {
+            if let Some(menu) = child.as_any_mut().downcast_mut::<Menu<MSG>>() {
+                menu.is_nested = is_nested;
+            }
+        }
+    }
+
+    /// the first letter of the label, which opens the menu together with alt
+    fn mnemonic(&self) -> Option<char> {
+        self.label
+            .chars()
+            .find(|ch| ch.is_alphanumeric())
+            .map(|ch| ch.to_ascii_lowercase())
+    }
+
+    fn activate(&self) -> Vec<MSG> {
+        self.on_activate.iter().map(|cb| cb.emit(())).collect()
+    }
+
+    /// the menu items with a menu are marked with an arrow inside a menu
+    fn text(&self) -> String {
+        if self.is_nested && !self.children.is_empty() {
+            format!(" {} ▸ ", self.label)
+        } else {
+            format!(" {} ", self.label)
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for MenuItem<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// the menu items fill the width of their menu
+    fn style(&self) -> Style {
+        let width = self.text().chars().count() as f32;
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(1.0),
+            },
+            min_size: Size {
+                width: Dimension::Points(width),
+                height: Dimension::Points(1.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the menu item is shown in reverse when it is focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let width = layout.size.width.max(0.0) as usize;
+        let text = format!("{:width$}", self.text(), width = width);
+        for (i, ch) in text.chars().enumerate() {
+            let mut cell = Cell::new(ch);
+            if self.is_focused {
+                cell.attributes(vec![Attribute::Reverse]);
+            }
+            buf.set_cell(left + i, top, cell);
+        }
+        vec![]
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn add_child(&mut self, mut child: Box<dyn Control<MSG>>) -> bool {
+        if let Some(menu) = child.as_any_mut().downcast_mut::<Menu<MSG>>() {
+            menu.is_nested = self.is_nested;
+        }
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// whether the widget at this index is a menu item
+pub(crate) fn is_menu_item<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    idx: usize,
+) -> bool {
+    item_at(root, idx).is_some()
+}
+
+/// open the first menu of the menu bar with F10, or the menu of the menu
+/// item in the menu bar with alt and its first letter. Returns the index of
+/// the menu item which is focused
+pub(crate) fn enter_menu_bar<MSG: 'static>(
+    root: &mut dyn Control<MSG>,
+    key_event: &KeyEvent,
+) -> Option<usize> {
+    let parents = parent_indices(root);
+    let bar = (0..parents.len())
+        .find(|idx| menu_at(root, *idx).is_some_and(|menu| menu.is_bar))?;
+    let items = menu_items(root, &parents, bar);
+    let item = match key_event.code {
+        KeyCode::F(10) => items.first().copied(),
+        KeyCode::Char(ch) if key_event.modifiers == KeyModifiers::ALT => {
+            items.iter().copied().find(|idx| {
+                item_at(root, *idx).and_then(|item| item.mnemonic())
+                    == Some(ch.to_ascii_lowercase())
+            })
+        }
+        _ => None,
+    }?;
+    Some(enter(root, &parents, item))
+}
+
+/// move around the menus with the key while the menu item at this index is
+/// focused, returns None when the key is not used by the menus
+pub(crate) fn navigate_menu<MSG: 'static>(
+    root: &mut dyn Control<MSG>,
+    focused: usize,
+    key_event: &KeyEvent,
+) -> Option<MenuAction<MSG>> {
+    let parents = parent_indices(root);
+    let menu = parents.get(focused).copied().flatten()?;
+    let is_bar = menu_at(root, menu)?.is_bar;
+    let items = menu_items(root, &parents, menu);
+    let position = items.iter().position(|idx| *idx == focused)?;
+    let sub_menu = sub_menu(root, &parents, focused);
+    let parent_item = parents[menu].filter(|idx| is_menu_item(root, *idx));
+    // the menu item of this menu is in another menu rather than the bar
+    let is_nested_menu = parent_item
+        .and_then(|idx| parents[idx])
+        .and_then(|idx| menu_at(root, idx))
+        .is_some_and(|menu| !menu.is_bar);
+
+    let action = match key_event.code {
+        KeyCode::Up | KeyCode::Down if !is_bar => {
+            let next = if key_event.code == KeyCode::Down {
+                (position + 1) % items.len()
+            } else {
+                (position + items.len() - 1) % items.len()
+            };
+            focus(root, items[next], false)
+        }
+        KeyCode::Down | KeyCode::Enter | KeyCode::Char(' ')
+            if sub_menu.is_some() =>
+        {
+            MenuAction::Focus(enter(root, &parents, focused))
+        }
+        KeyCode::Right if !is_bar && sub_menu.is_some() => {
+            MenuAction::Focus(enter(root, &parents, focused))
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            let msgs = item_at(root, focused)?.activate();
+            close_menus(root);
+            MenuAction::Activate(msgs)
+        }
+        KeyCode::Left if is_nested_menu => focus(root, parent_item?, false),
+        // move to the next or previous menu of the menu bar, which is opened
+        // when a menu is already open
+        KeyCode::Left | KeyCode::Right => {
+            let bar_item = if is_bar {
+                focused
+            } else {
+                bar_item(root, &parents, focused)?
+            };
+            let is_open = !is_bar
+                || sub_menu
+                    .and_then(|idx| menu_at(root, idx))
+                    .is_some_and(|menu| menu.is_open);
+            let bar = parents[bar_item]?;
+            let bar_items = menu_items(root, &parents, bar);
+            let position = bar_items.iter().position(|idx| *idx == bar_item)?;
+            let next = if key_event.code == KeyCode::Right {
+                (position + 1) % bar_items.len()
+            } else {
+                (position + bar_items.len() - 1) % bar_items.len()
+            };
+            if is_open {
+                MenuAction::Focus(enter(root, &parents, bar_items[next]))
+            } else {
+                focus(root, bar_items[next], false)
+            }
+        }
+        KeyCode::Esc if !is_bar => focus(root, parent_item?, false),
+        KeyCode::Esc | KeyCode::F(10) => {
+            close_menus(root);
+            MenuAction::Leave
+        }
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// a click on the menu item opens or closes its menu, or activates it when
+/// it has no menu
+pub(crate) fn click_menu_item<MSG: 'static>(
+    root: &mut dyn Control<MSG>,
+    idx: usize,
+) -> Option<MenuAction<MSG>> {
+    let parents = parent_indices(root);
+    let action = match sub_menu(root, &parents, idx) {
+        Some(sub_menu) if menu_at(root, sub_menu)?.is_open => {
+            focus(root, idx, false)
+        }
+        Some(_) => MenuAction::Focus(enter(root, &parents, idx)),
+        None => {
+            let msgs = item_at(root, idx)?.activate();
+            close_menus(root);
+            MenuAction::Activate(msgs)
+        }
+    };
+    Some(action)
+}
+
+/// close all of the menus
+pub(crate) fn close_menus<MSG: 'static>(root: &mut dyn Control<MSG>) {
+    set_open_menus(root, &[], &mut 0);
+}
+
+/// returns the index of the menu bar which has an open menu, and its nested
+/// widgets
+pub(crate) fn find_open_menu_bar<MSG: 'static>(
+    node: &dyn Control<MSG>,
+    cur_node_idx: &mut usize,
+) -> Option<Range<usize>> {
+    let start = *cur_node_idx;
+    let mut found = None;
+    if let Some(children) = node.children() {
+        for child in children {
+            *cur_node_idx += 1;
+            found = find_open_menu_bar(child.as_ref(), cur_node_idx).or(found);
+        }
+    }
+    let is_bar = node
+        .as_any()
+        .downcast_ref::<Menu<MSG>>()
+        .is_some_and(|menu| menu.is_bar);
+    if found.is_none() && is_bar && has_open_menu(node) {
+        found = Some(start..*cur_node_idx + 1);
+    }
+    found
+}
+
+fn has_open_menu<MSG: 'static>(node: &dyn Control<MSG>) -> bool {
+    node.as_any()
+        .downcast_ref::<Menu<MSG>>()
+        .is_some_and(|menu| menu.is_open)
+        || node.children().is_some_and(|children| {
+            children.iter().any(|child| has_open_menu(child.as_ref()))
+        })
+}
+
+/// focus the menu item, the menus which contain it are opened together with
+/// its own menu when `is_open`
+fn focus<MSG: 'static>(
+    root: &mut dyn Control<MSG>,
+    idx: usize,
+    is_open: bool,
+) -> MenuAction<MSG> {
+    let parents = parent_indices(root);
+    let mut open = vec![];
+    let mut ancestor = parents[idx];
+    while let Some(parent) = ancestor {
+        open.push(parent);
+        ancestor = parents[parent];
+    }
+    if is_open {
+        open.extend(sub_menu(root, &parents, idx));
+    }
+    set_open_menus(root, &open, &mut 0);
+    MenuAction::Focus(idx)
+}
+
+/// open the menu of the menu item, returns the index of its first menu item
+/// which is focused, or the menu item itself when it has no menu
+fn enter<MSG: 'static>(
+    root: &mut dyn Control<MSG>,
+    parents: &[Option<usize>],
+    idx: usize,
+) -> usize {
+    let first = sub_menu(root, parents, idx)
+        .and_then(|menu| menu_items(root, parents, menu).first().copied());
+    match first {
+        Some(first) => {
+            focus(root, first, false);
+            first
+        }
+        None => {
+            focus(root, idx, true);
+            idx
+        }
+    }
+}
+
+/// the menu item in the menu bar which contains the menu item at this index
+fn bar_item<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    parents: &[Option<usize>],
+    idx: usize,
+) -> Option<usize> {
+    let mut item = idx;
+    loop {
+        let menu = parents[item]?;
+        if menu_at(root, menu)?.is_bar {
+            return Some(item);
+        }
+        item = parents[menu]?;
+    }
+}
+
+fn set_open_menus<MSG: 'static>(
+    node: &mut dyn Control<MSG>,
+    open: &[usize],
+    cur_node_idx: &mut usize,
+) {
+    if let Some(menu) = node.as_any_mut().downcast_mut::<Menu<MSG>>() {
+        menu.is_open = !menu.is_bar && open.contains(cur_node_idx);
+    }
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            *cur_node_idx += 1;
+            set_open_menus(child.as_mut(), open, cur_node_idx);
+        }
+    }
+}
+
+/// the index of the parent of each widget, in the order of their node_idx
+fn parent_indices<MSG>(root: &dyn Control<MSG>) -> Vec<Option<usize>> {
+    fn collect<MSG>(
+        node: &dyn Control<MSG>,
+        parent: Option<usize>,
+        parents: &mut Vec<Option<usize>>,
+    ) {
+        let idx = parents.len();
+        parents.push(parent);
+        if let Some(children) = node.children() {
+            for child in children {
+                collect(child.as_ref(), Some(idx), parents);
+            }
+        }
+    }
+    let mut parents = vec![];
+    collect(root, None, &mut parents);
+    parents
+}
+
+/// the index of the menu items inside the menu
+fn menu_items<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    parents: &[Option<usize>],
+    menu: usize,
+) -> Vec<usize> {
+    (0..parents.len())
+        .filter(|idx| parents[*idx] == Some(menu) && is_menu_item(root, *idx))
+        .collect()
+}
+
+/// the index of the menu of the menu item
+fn sub_menu<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    parents: &[Option<usize>],
+    item: usize,
+) -> Option<usize> {
+    (0..parents.len()).find(|idx| {
+        parents[*idx] == Some(item) && menu_at(root, *idx).is_some()
+    })
+}
+
+/// the widget at this index, the `find_widget` of titik only finds the
+/// widgets which have no children
+pub(crate) fn widget_at<'a, MSG>(
+    node: &'a dyn Control<MSG>,
+    idx: usize,
+    cur_node_idx: &mut usize,
+) -> Option<&'a dyn Control<MSG>> {
+    if idx == *cur_node_idx {
+        return Some(node);
+    }
+    node.children()?.iter().find_map(|child| {
+        *cur_node_idx += 1;
+        widget_at(child.as_ref(), idx, cur_node_idx)
+    })
+}
+
+fn menu_at<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    idx: usize,
+) -> Option<&Menu<MSG>> {
+    widget_at(root, idx, &mut 0)
+        .and_then(|widget| widget.as_any().downcast_ref())
+}
+
+fn item_at<MSG: 'static>(
+    root: &dyn Control<MSG>,
+    idx: usize,
+) -> Option<&MenuItem<MSG>> {
+    widget_at(root, idx, &mut 0)
+        .and_then(|widget| widget.as_any().downcast_ref())
+}
+
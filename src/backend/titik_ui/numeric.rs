@@ -0,0 +1,225 @@
+//! the spin button and slider, where the value is adjusted with the arrow
+//! keys or by clicking the `-` and `+` at both ends
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// the number of steps which is moved with page up and page down
+const PAGE_STEPS: f64 = 10.0;
+
+/// a number in a range, the slider shows it as a gauge
+#[derive(Debug)]
+pub(crate) struct NumberInput<MSG> {
+    layout: Option<Layout>,
+    min: f64,
+    max: f64,
+    step: f64,
+    value: f64,
+    is_slider: bool,
+    is_focused: bool,
+    width: Option<f32>,
+    /// emitted with the value which is adjusted
+    on_input: Vec<Callback<f64, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> NumberInput<MSG> {
+    pub(crate) fn new(is_slider: bool) -> Self {
+        NumberInput {
+            layout: None,
+            min: 0.0,
+            max: 0.0,
+            step: 1.0,
+            value: 0.0,
+            is_slider,
+            is_focused: false,
+            width: None,
+            on_input: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_range(&mut self, min: f64, max: f64, step: f64) {
+        self.min = min;
+        self.max = max;
+        self.step = step;
+    }
+
+    pub(crate) fn set_min(&mut self, min: f64) {
+        self.min = min;
+    }
+
+    pub(crate) fn set_max(&mut self, max: f64) {
+        self.max = max;
+    }
+
+    pub(crate) fn set_step(&mut self, step: f64) {
+        if step > 0.0 {
+            self.step = step;
+        }
+    }
+
+    pub(crate) fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    pub(crate) fn add_input_listener(&mut self, cb: Callback<f64, MSG>) {
+        self.on_input.push(cb);
+    }
+
+    /// set the value clamped in the range, the input event is emitted only
+    /// when the value is changed
+    fn adjust(&mut self, value: f64) -> Vec<MSG> {
+        let value = value.max(self.min).min(self.max);
+        if value == self.value {
+            return vec![];
+        }
+        self.value = value;
+        self.on_input.iter().map(|cb| cb.emit(value)).collect()
+    }
+
+    fn text(&self) -> String {
+        if self.is_slider {
+            gauge_text(self.min, self.max, self.value)
+        } else {
+            self.value.to_string()
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for NumberInput<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(1.0),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(1.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the value is shown in reverse when it is focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let text = self.text();
+        buf.write_str(left, top, "[-] ");
+        for (i, ch) in text.chars().enumerate() {
+            let mut cell = Cell::new(ch);
+            if self.is_focused {
+                cell.attributes(vec![Attribute::Reverse]);
+            }
+            buf.set_cell(left + 4 + i, top, cell);
+        }
+        buf.write_str(left + 4 + text.chars().count(), top, " [+]");
+        vec![]
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, _height: Option<f32>) {
+        self.width = width;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Left | KeyCode::Down => {
+                    self.adjust(self.value - self.step)
+                }
+                KeyCode::Right | KeyCode::Up => {
+                    self.adjust(self.value + self.step)
+                }
+                KeyCode::PageDown => {
+                    self.adjust(self.value - self.step * PAGE_STEPS)
+                }
+                KeyCode::PageUp => {
+                    self.adjust(self.value + self.step * PAGE_STEPS)
+                }
+                KeyCode::Home => self.adjust(self.min),
+                KeyCode::End => self.adjust(self.max),
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_btn, x, _y, _modifier)) => {
+                let left = self
+                    .layout
+                    .map(|layout| layout.location.x as usize)
+                    .unwrap_or(0);
+                let increase = left + 4 + self.text().chars().count() + 1;
+                let x = x as usize;
+                if x >= left && x < left + 3 {
+                    self.adjust(self.value - self.step)
+                } else if x >= increase && x < increase + 3 {
+                    self.adjust(self.value + self.step)
+                } else {
+                    vec![]
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// a bar filled up to the value in its range, followed by the value
+fn gauge_text(min: f64, max: f64, value: f64) -> String {
+    const GAUGE_WIDTH: usize = 20;
+    let fraction = if max > min {
+        (value - min) / (max - min)
+    } else {
+        0.0
+    };
+    let filled = (fraction * GAUGE_WIDTH as f64).round() as usize;
+    format!(
+        "[{}{}] {}",
+        "#".repeat(filled),
+        "-".repeat(GAUGE_WIDTH - filled),
+        value
+    )
+}
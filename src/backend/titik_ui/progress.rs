@@ -0,0 +1,149 @@
+//! the progress bar and the spinner, which are drawn as a line of text. The
+//! indeterminate progress bar and the active spinner have several frames,
+//! the event loop wakes up while they are shown to draw the next frame.
+use std::{
+    any::Any,
+    fmt::Debug,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+use titik::{
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    Buffer, Cmd, Event, Widget as Control,
+};
+
+/// the time each frame of the animations is shown
+pub(crate) const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// the frame is picked from the time since the widget is created, so the
+/// animation keeps going when the frames are patched
+#[derive(Debug)]
+pub(crate) struct Progress<MSG> {
+    layout: Option<Layout>,
+    frames: Vec<String>,
+    started: Instant,
+    width: Option<f32>,
+    height: Option<f32>,
+    id: Option<String>,
+    _phantom_msg: PhantomData<MSG>,
+}
+
+impl<MSG> Progress<MSG> {
+    pub(crate) fn new(frames: Vec<String>) -> Self {
+        Progress {
+            layout: None,
+            frames,
+            started: Instant::now(),
+            width: None,
+            height: None,
+            id: None,
+            _phantom_msg: PhantomData,
+        }
+    }
+
+    pub(crate) fn set_frames(&mut self, frames: Vec<String>) {
+        self.frames = frames;
+    }
+
+    fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    /// the frame which is shown at this time
+    fn frame(&self) -> &str {
+        if self.frames.is_empty() {
+            return "";
+        }
+        let elapsed =
+            self.started.elapsed().as_millis() / FRAME_INTERVAL.as_millis();
+        &self.frames[elapsed as usize % self.frames.len()]
+    }
+}
+
+impl<MSG> Control<MSG> for Progress<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        let height = self.height.unwrap_or(1.0);
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(height),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let width = layout.size.width.max(0.0) as usize;
+        let line: String = self.frame().chars().take(width).collect();
+        buf.write_str(
+            layout.location.x as usize,
+            layout.location.y as usize,
+            line,
+        );
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, _event: Event) -> Vec<MSG> {
+        vec![]
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// whether any of the widgets is animated, the event loop then wakes up for
+/// the next frame
+pub(crate) fn has_animation<MSG: 'static>(widget: &dyn Control<MSG>) -> bool {
+    let is_animated = widget
+        .as_any()
+        .downcast_ref::<Progress<MSG>>()
+        .is_some_and(|progress| progress.is_animated());
+    is_animated
+        || widget
+            .children()
+            .unwrap_or(&[])
+            .iter()
+            .any(|child| has_animation(child.as_ref()))
+}
@@ -0,0 +1,110 @@
+//! the widgets of a view which are drawn into a buffer instead of the
+//! terminal, so the titik widgets can be checked without a terminal
+use super::{apply_patches, convert_widget, event_loop, menu};
+use crate::{AttribKey, Node};
+use std::fmt::Debug;
+use titik::{
+    expanse::result::Layout, find_widget_mut, Buffer, Event, Widget as Control,
+};
+
+/// the widgets are laid out again after every change, the same as the
+/// event loop does
+pub struct TitikScreen<MSG>
+where
+    MSG: 'static,
+{
+    root_node: Box<dyn Control<MSG>>,
+    current_vdom: Node<MSG>,
+    size: (u16, u16),
+}
+
+impl<MSG> TitikScreen<MSG>
+where
+    MSG: Debug + 'static,
+{
+    /// create the widgets of the view in a screen of this size
+    pub fn new(view: Node<MSG>, width: u16, height: u16) -> Self {
+        let mut screen = TitikScreen {
+            root_node: convert_widget::from_node_tree(&view),
+            current_vdom: view,
+            size: (width, height),
+        };
+        screen.recompute_layout();
+        screen
+    }
+
+    /// apply the changes from the current view to the new view into the
+    /// widgets
+    pub fn patch(&mut self, new_view: Node<MSG>) {
+        let patches = mt_dom::diff_with_key(
+            &self.current_vdom,
+            &new_view,
+            &AttribKey::Key,
+        );
+        apply_patches::apply_patches(
+            &(),
+            self.root_node.as_mut(),
+            &self.current_vdom,
+            &new_view,
+            &patches,
+        );
+        self.current_vdom = new_view;
+        self.recompute_layout();
+    }
+
+    /// focus the widget at this node_idx, then send the event to it.
+    /// Returns the msgs that are emitted by the widget
+    pub fn send_event(&mut self, node_idx: usize, event: Event) -> Vec<MSG> {
+        event_loop::set_focused_node(
+            self.root_node.as_mut(),
+            Some(node_idx),
+            &mut 0,
+        );
+        let msgs = find_widget_mut(self.root_node.as_mut(), node_idx)
+            .map(|widget| widget.process_event(event))
+            .unwrap_or_default();
+        self.recompute_layout();
+        msgs
+    }
+
+    /// returns the widget at this node_idx, including the widgets which
+    /// have children
+    pub fn widget(&self, node_idx: usize) -> Option<&dyn Control<MSG>> {
+        menu::widget_at(self.root_node.as_ref(), node_idx, &mut 0)
+    }
+
+    /// returns the layout of the widget at this node_idx
+    pub fn layout(&self, node_idx: usize) -> Option<Layout> {
+        self.widget(node_idx)
+            .and_then(|widget| widget.layout().copied())
+    }
+
+    /// draw the widgets into a buffer of the screen size
+    pub fn buffer(&self) -> Buffer {
+        let (width, height) = self.size;
+        let mut buf = Buffer::new(width as usize, height as usize);
+        buf.reset();
+        event_loop::draw_widgets(&mut buf, self.root_node.as_ref());
+        buf
+    }
+
+    /// the text in each line of the screen, the trailing spaces are trimmed
+    pub fn lines(&self) -> Vec<String> {
+        let (width, height) = self.size;
+        let mut lines =
+            vec![vec![" ".to_string(); width as usize]; height as usize];
+        let empty = Buffer::new(width as usize, height as usize);
+        for (x, y, cell) in empty.diff(&self.buffer()) {
+            // the wide characters are followed by a null filler
+            lines[y][x] = cell.symbol.replace('\0', "");
+        }
+        lines
+            .into_iter()
+            .map(|line| line.concat().trim_end().to_string())
+            .collect()
+    }
+
+    fn recompute_layout(&mut self) {
+        event_loop::layout_widgets(self.root_node.as_mut(), self.size);
+    }
+}
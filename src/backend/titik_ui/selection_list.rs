@@ -0,0 +1,394 @@
+//! a list of lines where a line is selected with the arrow keys or a click,
+//! the table and the list box are shown with it since titik has no widget
+//! with a selection. The lines can also be checked, for the list box with
+//! multiple selection.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug, ops::Range};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// the lines of a selection list, with a header which can not be selected
+#[derive(Debug)]
+pub(crate) struct SelectionList<MSG> {
+    layout: Option<Layout>,
+    header: Option<String>,
+    lines: Vec<String>,
+    selected: Option<usize>,
+    /// the lines are checked with space or a click, instead of only being
+    /// selected
+    is_multiple: bool,
+    checked: Vec<usize>,
+    /// the characters of each column in the header, when the columns can
+    /// be sorted
+    columns: Vec<Range<usize>>,
+    /// the column which is sorted with the keyboard
+    focused_column: usize,
+    is_focused: bool,
+    width: Option<f32>,
+    height: Option<f32>,
+    /// emitted with the index of the line which is selected
+    on_select: Vec<Callback<usize, MSG>>,
+    /// emitted with the index of the selected line when enter is pressed
+    on_activate: Vec<Callback<usize, MSG>>,
+    /// emitted with the index of the checked lines when a line is checked
+    /// or unchecked
+    on_toggle: Vec<Callback<Vec<usize>, MSG>>,
+    /// emitted with the index of the column which is sorted
+    on_sort: Vec<Callback<usize, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: 'static> SelectionList<MSG> {
+    pub(crate) fn new() -> Self {
+        SelectionList {
+            layout: None,
+            header: None,
+            lines: vec![],
+            selected: None,
+            is_multiple: false,
+            checked: vec![],
+            columns: vec![],
+            focused_column: 0,
+            is_focused: false,
+            width: None,
+            height: None,
+            on_select: vec![],
+            on_activate: vec![],
+            on_toggle: vec![],
+            on_sort: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_header(&mut self, header: Option<String>) {
+        self.header = header;
+    }
+
+    pub(crate) fn set_lines(&mut self, lines: Vec<String>) {
+        self.lines = lines;
+    }
+
+    pub(crate) fn set_selected(&mut self, selected: Option<usize>) {
+        self.selected = selected;
+    }
+
+    pub(crate) fn set_multiple(&mut self, is_multiple: bool) {
+        self.is_multiple = is_multiple;
+    }
+
+    pub(crate) fn set_checked(&mut self, checked: Vec<usize>) {
+        self.checked = checked;
+    }
+
+    pub(crate) fn add_toggle_listener(
+        &mut self,
+        cb: Callback<Vec<usize>, MSG>,
+    ) {
+        self.on_toggle.push(cb);
+    }
+
+    pub(crate) fn set_columns(&mut self, columns: Vec<Range<usize>>) {
+        self.focused_column =
+            self.focused_column.min(columns.len().saturating_sub(1));
+        self.columns = columns;
+    }
+
+    pub(crate) fn add_sort_listener(&mut self, cb: Callback<usize, MSG>) {
+        self.on_sort.push(cb);
+    }
+
+    pub(crate) fn add_select_listener(&mut self, cb: Callback<usize, MSG>) {
+        self.on_select.push(cb);
+    }
+
+    pub(crate) fn add_activate_listener(&mut self, cb: Callback<usize, MSG>) {
+        self.on_activate.push(cb);
+    }
+
+    fn header_height(&self) -> usize {
+        if self.header.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// the number of lines which fits below the header
+    fn visible_lines(&self) -> usize {
+        let height = self
+            .layout
+            .map(|layout| layout.size.height.max(0.0) as usize)
+            .unwrap_or(0);
+        height.saturating_sub(self.header_height()).max(1)
+    }
+
+    /// the first line which is shown, the list is scrolled until the
+    /// selected line is visible
+    fn first_visible(&self) -> usize {
+        let visible = self.visible_lines();
+        match self.selected {
+            Some(selected) if selected >= visible => selected + 1 - visible,
+            _ => 0,
+        }
+    }
+
+    /// select the line, the select event is emitted only when the selected
+    /// line is changed
+    fn select(&mut self, index: usize) -> Vec<MSG> {
+        if self.lines.is_empty() {
+            return vec![];
+        }
+        let index = index.min(self.lines.len() - 1);
+        if self.selected == Some(index) {
+            return vec![];
+        }
+        self.selected = Some(index);
+        self.on_select.iter().map(|cb| cb.emit(index)).collect()
+    }
+
+    /// check or uncheck the line, only when the lines can be checked
+    fn toggle(&mut self, index: usize) -> Vec<MSG> {
+        if !self.is_multiple || index >= self.lines.len() {
+            return vec![];
+        }
+        match self.checked.iter().position(|checked| *checked == index) {
+            Some(pos) => {
+                self.checked.remove(pos);
+            }
+            None => {
+                self.checked.push(index);
+                self.checked.sort_unstable();
+            }
+        }
+        let checked = self.checked.clone();
+        self.on_toggle
+            .iter()
+            .map(|cb| cb.emit(checked.clone()))
+            .collect()
+    }
+
+    /// sort the column, only when the columns can be sorted
+    fn sort(&mut self, column: usize) -> Vec<MSG> {
+        if column >= self.columns.len() {
+            return vec![];
+        }
+        self.focused_column = column;
+        self.on_sort.iter().map(|cb| cb.emit(column)).collect()
+    }
+
+    /// the line with its check mark when the lines can be checked
+    fn line_label(&self, index: usize, line: &str) -> String {
+        if self.is_multiple {
+            let mark = if self.checked.contains(&index) {
+                "x"
+            } else {
+                " "
+            };
+            format!("[{}] {}", mark, line)
+        } else {
+            line.to_string()
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for SelectionList<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        let height = self
+            .height
+            .unwrap_or((self.header_height() + self.lines.len()) as f32);
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(height),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the selected line is marked with `>`, and is shown in reverse
+    /// when the list is focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let width = layout.size.width.max(0.0) as usize;
+        if let Some(header) = &self.header {
+            buf.write_bold_str(left + 2, top, truncate(header, width));
+            // the column which is sorted with the keyboard is highlighted
+            let column = self
+                .columns
+                .get(self.focused_column)
+                .filter(|_column| self.is_focused);
+            if let Some(column) = column {
+                let header = truncate(header, width);
+                for (i, ch) in header.chars().enumerate() {
+                    if column.contains(&i) {
+                        let mut cell = Cell::new(ch);
+                        cell.attributes(vec![Attribute::Reverse]);
+                        buf.set_cell(left + 2 + i, top, cell);
+                    }
+                }
+            }
+        }
+        let first = self.first_visible();
+        let lines = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(self.visible_lines());
+        for (row, (index, line)) in lines.enumerate() {
+            let y = top + self.header_height() + row;
+            let line = &self.line_label(index, line);
+            if self.selected == Some(index) {
+                buf.write_str(left, y, ">");
+                for (i, ch) in truncate(line, width).chars().enumerate() {
+                    let mut cell = Cell::new(ch);
+                    if self.is_focused {
+                        cell.attributes(vec![Attribute::Reverse]);
+                    } else {
+                        cell.bold();
+                    }
+                    buf.set_cell(left + 2 + i, y, cell);
+                }
+            } else {
+                buf.write_str(left + 2, y, truncate(line, width));
+            }
+        }
+        vec![]
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let last = self.lines.len().saturating_sub(1);
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Up => {
+                    self.select(self.selected.unwrap_or(1).saturating_sub(1))
+                }
+                KeyCode::Down => {
+                    self.select(self.selected.map(|s| s + 1).unwrap_or(0))
+                }
+                KeyCode::PageUp => self.select(
+                    self.selected
+                        .unwrap_or(0)
+                        .saturating_sub(self.visible_lines()),
+                ),
+                KeyCode::PageDown => self.select(
+                    self.selected.map_or(0, |s| s + self.visible_lines()),
+                ),
+                KeyCode::Home => self.select(0),
+                KeyCode::End => self.select(last),
+                KeyCode::Left if !self.columns.is_empty() => {
+                    self.focused_column = self.focused_column.saturating_sub(1);
+                    vec![]
+                }
+                KeyCode::Right if !self.columns.is_empty() => {
+                    self.focused_column =
+                        (self.focused_column + 1).min(self.columns.len() - 1);
+                    vec![]
+                }
+                KeyCode::Char('s') => self.sort(self.focused_column),
+                KeyCode::Char(' ') => match self.selected {
+                    Some(selected) => self.toggle(selected),
+                    None => vec![],
+                },
+                KeyCode::Enter => match self.selected {
+                    Some(selected) => self
+                        .on_activate
+                        .iter()
+                        .map(|cb| cb.emit(selected))
+                        .collect(),
+                    None => vec![],
+                },
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                let (left, top) = self
+                    .layout
+                    .map(|layout| {
+                        (layout.location.x as usize, layout.location.y as usize)
+                    })
+                    .unwrap_or((0, 0));
+                // a click on the header sorts the column underneath
+                if self.header.is_some() && y as usize == top {
+                    let column =
+                        (x as usize).checked_sub(left + 2).and_then(|x| {
+                            self.columns.iter().position(|c| c.contains(&x))
+                        });
+                    return match column {
+                        Some(column) => self.sort(column),
+                        None => vec![],
+                    };
+                }
+                let top = top + self.header_height();
+                match (y as usize).checked_sub(top) {
+                    Some(row) if self.first_visible() + row <= last => {
+                        let index = self.first_visible() + row;
+                        let mut msgs = self.select(index);
+                        msgs.extend(self.toggle(index));
+                        msgs
+                    }
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// the characters of the line which fits in the width, after the marker
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width.saturating_sub(2)).collect()
+}
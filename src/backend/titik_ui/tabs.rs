@@ -0,0 +1,339 @@
+//! the tabs, which shows the labels of the pages in a row above the selected
+//! page. The pages which are not selected are kept as children so the node
+//! index of the widgets stays the same as the view, they are hidden instead.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::{Rect, Size},
+        result::Layout,
+        style::{Dimension, Display, FlexDirection, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, GroupBox, Widget as Control,
+};
+
+/// the container of the tab pages, a page is selected with the left and
+/// right arrow keys or by clicking its label
+#[derive(Debug)]
+pub(crate) struct Tabs<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    selected: usize,
+    is_focused: bool,
+    width: Option<f32>,
+    height: Option<f32>,
+    /// emitted with the index of the page which is selected
+    on_tab_change: Vec<Callback<usize, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: Debug + 'static> Tabs<MSG> {
+    pub(crate) fn new() -> Self {
+        Tabs {
+            layout: None,
+            children: vec![],
+            selected: 0,
+            is_focused: false,
+            width: None,
+            height: None,
+            on_tab_change: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn add_tab_change_listener(&mut self, cb: Callback<usize, MSG>) {
+        self.on_tab_change.push(cb);
+    }
+
+    /// show the page at this index and hide the rest
+    pub(crate) fn set_selected(&mut self, selected: usize) {
+        self.selected = selected;
+        for (index, child) in self.children.iter_mut().enumerate() {
+            if let Some(page) =
+                child.as_any_mut().downcast_mut::<TabPage<MSG>>()
+            {
+                page.set_hidden(index != selected);
+            }
+        }
+    }
+
+    /// select the page, the tab change event is emitted only when the
+    /// selected page is changed
+    fn select(&mut self, index: usize) -> Vec<MSG> {
+        if index == self.selected || index >= self.children.len() {
+            return vec![];
+        }
+        self.set_selected(index);
+        self.on_tab_change.iter().map(|cb| cb.emit(index)).collect()
+    }
+
+    /// the labels of the pages and the column where they start
+    fn labels(&self) -> Vec<(usize, String)> {
+        let left = self
+            .layout
+            .map(|layout| layout.location.x as usize)
+            .unwrap_or(0);
+        let mut x = left + 1;
+        self.children
+            .iter()
+            .map(|child| {
+                let label = child
+                    .as_any()
+                    .downcast_ref::<TabPage<MSG>>()
+                    .map(|page| format!(" {} ", page.label))
+                    .unwrap_or_default();
+                let start = x;
+                x += label.chars().count() + 1;
+                (start, label)
+            })
+            .collect()
+    }
+}
+
+impl<MSG> Control<MSG> for Tabs<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// the first row has the labels of the pages
+    fn style(&self) -> Style {
+        Style {
+            position_type: PositionType::Relative,
+            flex_direction: FlexDirection::Column,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: self
+                    .height
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+            },
+            padding: Rect {
+                top: Dimension::Points(1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the label of the selected page is shown in reverse when the tabs
+    /// are focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let top = layout.location.y as usize;
+        for (index, (x, label)) in self.labels().into_iter().enumerate() {
+            if index == self.selected {
+                for (i, ch) in label.chars().enumerate() {
+                    let mut cell = Cell::new(ch);
+                    if self.is_focused {
+                        cell.attributes(vec![Attribute::Reverse]);
+                    } else {
+                        cell.bold();
+                    }
+                    buf.set_cell(x + i, top, cell);
+                }
+            } else {
+                buf.write_str(x, top, label);
+            }
+        }
+        vec![]
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.children.push(child);
+        self.set_selected(self.selected);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Left => self.select(self.selected.saturating_sub(1)),
+                KeyCode::Right => self.select(self.selected + 1),
+                KeyCode::Home => self.select(0),
+                KeyCode::End => {
+                    self.select(self.children.len().saturating_sub(1))
+                }
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                let top = self.layout.map(|layout| layout.location.y as u16);
+                if Some(y) != top {
+                    return vec![];
+                }
+                let hit =
+                    self.labels().into_iter().position(|(start, label)| {
+                        let x = x as usize;
+                        x >= start && x < start + label.chars().count()
+                    });
+                match hit {
+                    Some(index) => self.select(index),
+                    None => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// a page of the tabs, which is a groupbox that is laid out with no size
+/// when it is not selected
+#[derive(Debug)]
+pub(crate) struct TabPage<MSG> {
+    groupbox: GroupBox<MSG>,
+    label: String,
+    is_hidden: bool,
+}
+
+impl<MSG> TabPage<MSG> {
+    pub(crate) fn new(label: &str) -> Self {
+        TabPage {
+            groupbox: GroupBox::new(),
+            label: label.to_string(),
+            is_hidden: false,
+        }
+    }
+
+    pub(crate) fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+
+    fn set_hidden(&mut self, is_hidden: bool) {
+        self.is_hidden = is_hidden;
+    }
+}
+
+impl<MSG> Control<MSG> for TabPage<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.groupbox.layout()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.groupbox.set_layout(layout);
+    }
+
+    fn style(&self) -> Style {
+        let style = self.groupbox.style();
+        if self.is_hidden {
+            Style {
+                display: Display::None,
+                ..style
+            }
+        } else {
+            style
+        }
+    }
+
+    fn get_offset(&self) -> (f32, f32) {
+        self.groupbox.get_offset()
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        self.groupbox.draw(buf)
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.groupbox.add_child(child)
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        self.groupbox.children()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        self.groupbox.children_mut()
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        self.groupbox.take_child(index)
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.groupbox.child_mut(index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.groupbox.set_size(width, height);
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.groupbox.set_id(id);
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.groupbox.get_id()
+    }
+}
@@ -0,0 +1,548 @@
+//! the tree view, which draws the rows of its items. The items are kept as
+//! children with no size so the node index of the widgets stays the same as
+//! the view, while the nested items of the collapsed items are not drawn.
+use mt_dom::Callback;
+use std::{any::Any, fmt::Debug};
+use titik::{
+    crossterm::event::{KeyCode, MouseEvent},
+    expanse::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, Display, PositionType, Style},
+    },
+    renderer::Attribute,
+    Buffer, Cell, Cmd, Event, Widget as Control,
+};
+
+/// a row of the tree, which is an item whose parents are all expanded
+struct Row {
+    /// the position of the item in each level of the tree
+    path: Vec<usize>,
+}
+
+/// the items are selected with the up and down keys, expanded and collapsed
+/// with the right and left keys, and activated with enter
+#[derive(Debug)]
+pub(crate) struct Tree<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    is_focused: bool,
+    width: Option<f32>,
+    height: Option<f32>,
+    /// the following are emitted with the id of the item
+    on_select: Vec<Callback<String, MSG>>,
+    on_expand: Vec<Callback<String, MSG>>,
+    on_collapse: Vec<Callback<String, MSG>>,
+    on_activate: Vec<Callback<String, MSG>>,
+    id: Option<String>,
+}
+
+impl<MSG: Debug + 'static> Tree<MSG> {
+    pub(crate) fn new() -> Self {
+        Tree {
+            layout: None,
+            children: vec![],
+            is_focused: false,
+            width: None,
+            height: None,
+            on_select: vec![],
+            on_expand: vec![],
+            on_collapse: vec![],
+            on_activate: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn add_select_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_select.push(cb);
+    }
+
+    pub(crate) fn add_expand_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_expand.push(cb);
+    }
+
+    pub(crate) fn add_collapse_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_collapse.push(cb);
+    }
+
+    pub(crate) fn add_activate_listener(&mut self, cb: Callback<String, MSG>) {
+        self.on_activate.push(cb);
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = vec![];
+        collect_rows(&self.children, &mut vec![], &mut rows);
+        rows
+    }
+
+    fn item(&self, path: &[usize]) -> Option<&TreeNode<MSG>> {
+        let (first, rest) = path.split_first()?;
+        let mut item: &TreeNode<MSG> =
+            self.children.get(*first)?.as_any().downcast_ref()?;
+        for index in rest {
+            item = item.children.get(*index)?.as_any().downcast_ref()?;
+        }
+        Some(item)
+    }
+
+    fn item_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode<MSG>> {
+        let (first, rest) = path.split_first()?;
+        let mut item: &mut TreeNode<MSG> =
+            self.children.get_mut(*first)?.as_any_mut().downcast_mut()?;
+        for index in rest {
+            item =
+                item.children.get_mut(*index)?.as_any_mut().downcast_mut()?;
+        }
+        Some(item)
+    }
+
+    /// the row of the selected item
+    fn selected_row(&self, rows: &[Row]) -> Option<usize> {
+        rows.iter().position(|row| {
+            self.item(&row.path).is_some_and(|item| item.is_selected)
+        })
+    }
+
+    /// the number of rows which fits in the tree
+    fn visible_rows(&self) -> usize {
+        let height = self
+            .layout
+            .map(|layout| layout.size.height.max(0.0) as usize)
+            .unwrap_or(0);
+        height.max(1)
+    }
+
+    /// the first row which is shown, the tree is scrolled until the
+    /// selected row is visible
+    fn first_visible(&self, rows: &[Row]) -> usize {
+        let visible = self.visible_rows();
+        match self.selected_row(rows) {
+            Some(selected) if selected >= visible => selected + 1 - visible,
+            _ => 0,
+        }
+    }
+
+    /// select the item, the select event is emitted only when the selected
+    /// item is changed
+    fn select(&mut self, path: &[usize]) -> Vec<MSG> {
+        let is_selected = self.item(path).is_none_or(|item| item.is_selected);
+        if is_selected {
+            return vec![];
+        }
+        unselect_all(&mut self.children);
+        let item = self.item_mut(path).expect("must have the item");
+        item.is_selected = true;
+        let id = item.item_id();
+        self.on_select
+            .iter()
+            .map(|cb| cb.emit(id.clone()))
+            .collect()
+    }
+
+    /// expand or collapse the item, the events are emitted only when the
+    /// item has nested items which are shown or hidden
+    fn set_expanded(&mut self, path: &[usize], is_expanded: bool) -> Vec<MSG> {
+        let item = match self.item_mut(path) {
+            Some(item) => item,
+            None => return vec![],
+        };
+        if item.children.is_empty() || item.is_expanded == is_expanded {
+            return vec![];
+        }
+        item.is_expanded = is_expanded;
+        let id = item.item_id();
+        let callbacks = if is_expanded {
+            &self.on_expand
+        } else {
+            &self.on_collapse
+        };
+        callbacks.iter().map(|cb| cb.emit(id.clone())).collect()
+    }
+
+    fn activate(&self, path: &[usize]) -> Vec<MSG> {
+        match self.item(path) {
+            Some(item) => {
+                let id = item.item_id();
+                self.on_activate
+                    .iter()
+                    .map(|cb| cb.emit(id.clone()))
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// expand the selected item, or select its first nested item when it
+    /// is already expanded
+    fn expand_or_enter(&mut self, path: Vec<usize>) -> Vec<MSG> {
+        let (is_expanded, has_children) = match self.item(&path) {
+            Some(item) => (item.is_expanded, !item.children.is_empty()),
+            None => return vec![],
+        };
+        if !has_children {
+            vec![]
+        } else if is_expanded {
+            let mut first_child = path;
+            first_child.push(0);
+            self.select(&first_child)
+        } else {
+            self.set_expanded(&path, true)
+        }
+    }
+
+    /// collapse the selected item, or select its parent when it is
+    /// already collapsed
+    fn collapse_or_leave(&mut self, path: Vec<usize>) -> Vec<MSG> {
+        let is_expanded = self
+            .item(&path)
+            .is_some_and(|item| item.is_expanded && !item.children.is_empty());
+        if is_expanded {
+            self.set_expanded(&path, false)
+        } else if path.len() > 1 {
+            self.select(&path[..path.len() - 1])
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for Tree<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        let height = self.height.unwrap_or(self.rows().len() as f32);
+        Style {
+            position_type: PositionType::Relative,
+            size: Size {
+                width: self
+                    .width
+                    .map(Dimension::Points)
+                    .unwrap_or(Dimension::Percent(1.0)),
+                height: Dimension::Points(height),
+            },
+            min_size: Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(height),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// the selected item is marked with `>`, and is shown in reverse when
+    /// the tree is focused
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let layout = self.layout.expect("must have a layout");
+        let left = layout.location.x as usize;
+        let top = layout.location.y as usize;
+        let rows = self.rows();
+        let visible = rows
+            .iter()
+            .skip(self.first_visible(&rows))
+            .take(self.visible_rows());
+        for (y, row) in visible.enumerate() {
+            let item = match self.item(&row.path) {
+                Some(item) => item,
+                None => continue,
+            };
+            let line = format!(
+                "{}{} {}",
+                "  ".repeat(row.path.len() - 1),
+                item.arrow(),
+                item.label
+            );
+            if item.is_selected {
+                buf.write_str(left, top + y, ">");
+                for (i, ch) in line.chars().enumerate() {
+                    let mut cell = Cell::new(ch);
+                    if self.is_focused {
+                        cell.attributes(vec![Attribute::Reverse]);
+                    } else {
+                        cell.bold();
+                    }
+                    buf.set_cell(left + 2 + i, top + y, cell);
+                }
+            } else {
+                buf.write_str(left + 2, top + y, line);
+            }
+        }
+        vec![]
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.width = width;
+        self.height = height;
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let mut rows = self.rows();
+        if rows.is_empty() {
+            return vec![];
+        }
+        let last = rows.len() - 1;
+        let selected = self.selected_row(&rows);
+        match event {
+            Event::Key(key_event) => {
+                let row = match key_event.code {
+                    KeyCode::Up => selected.unwrap_or(1).saturating_sub(1),
+                    KeyCode::Down => selected.map_or(0, |s| (s + 1).min(last)),
+                    KeyCode::Home => 0,
+                    KeyCode::End => last,
+                    KeyCode::Right | KeyCode::Left | KeyCode::Enter => {
+                        match selected {
+                            Some(selected) => selected,
+                            None => return vec![],
+                        }
+                    }
+                    _ => return vec![],
+                };
+                let path = rows.swap_remove(row).path;
+                match key_event.code {
+                    KeyCode::Right => self.expand_or_enter(path),
+                    KeyCode::Left => self.collapse_or_leave(path),
+                    KeyCode::Enter => self.activate(&path),
+                    _ => self.select(&path),
+                }
+            }
+            // clicking the arrow of an item expands or collapses it,
+            // clicking the rest of the row selects it
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                let (left, top) = self
+                    .layout
+                    .map(|layout| {
+                        (layout.location.x as usize, layout.location.y as usize)
+                    })
+                    .unwrap_or((0, 0));
+                let row = match (y as usize).checked_sub(top) {
+                    Some(row) if self.first_visible(&rows) + row <= last => {
+                        self.first_visible(&rows) + row
+                    }
+                    _ => return vec![],
+                };
+                let path = rows.swap_remove(row).path;
+                let arrow = left + 2 + (path.len() - 1) * 2;
+                if x as usize == arrow {
+                    let is_expanded =
+                        self.item(&path).is_some_and(|item| item.is_expanded);
+                    self.set_expanded(&path, !is_expanded)
+                } else {
+                    self.select(&path)
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// an item of the tree, which is drawn by the tree so it is laid out with
+/// no size
+#[derive(Debug)]
+pub(crate) struct TreeNode<MSG> {
+    layout: Option<Layout>,
+    label: String,
+    key: Option<String>,
+    is_expanded: bool,
+    is_selected: bool,
+    children: Vec<Box<dyn Control<MSG>>>,
+    id: Option<String>,
+}
+
+impl<MSG> TreeNode<MSG> {
+    pub(crate) fn new(label: &str) -> Self {
+        TreeNode {
+            layout: None,
+            label: label.to_string(),
+            key: None,
+            is_expanded: false,
+            is_selected: false,
+            children: vec![],
+            id: None,
+        }
+    }
+
+    pub(crate) fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+
+    pub(crate) fn set_key(&mut self, key: Option<String>) {
+        self.key = key;
+    }
+
+    pub(crate) fn set_expanded(&mut self, is_expanded: bool) {
+        self.is_expanded = is_expanded;
+    }
+
+    pub(crate) fn set_selected(&mut self, is_selected: bool) {
+        self.is_selected = is_selected;
+    }
+
+    /// the id of the item in the tree view events, which is its key or
+    /// its label when it has no key
+    fn item_id(&self) -> String {
+        self.key.clone().unwrap_or_else(|| self.label.clone())
+    }
+
+    fn arrow(&self) -> &str {
+        if self.children.is_empty() {
+            " "
+        } else if self.is_expanded {
+            "▾"
+        } else {
+            "▸"
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for TreeNode<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn style(&self) -> Style {
+        Style {
+            display: Display::None,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, _buf: &mut Buffer) -> Vec<Cmd> {
+        vec![]
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.children.get_mut(index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// collect the rows of the items, the nested items of the collapsed items
+/// are left out
+fn collect_rows<MSG: Debug + 'static>(
+    children: &[Box<dyn Control<MSG>>],
+    path: &mut Vec<usize>,
+    rows: &mut Vec<Row>,
+) {
+    for (index, child) in children.iter().enumerate() {
+        if let Some(item) = child.as_any().downcast_ref::<TreeNode<MSG>>() {
+            path.push(index);
+            rows.push(Row { path: path.clone() });
+            if item.is_expanded {
+                collect_rows(&item.children, path, rows);
+            }
+            path.pop();
+        }
+    }
+}
+
+fn unselect_all<MSG: Debug + 'static>(children: &mut [Box<dyn Control<MSG>>]) {
+    for child in children {
+        if let Some(item) = child.as_any_mut().downcast_mut::<TreeNode<MSG>>() {
+            item.is_selected = false;
+            unselect_all(&mut item.children);
+        }
+    }
+}
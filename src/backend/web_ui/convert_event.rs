@@ -1,3 +1,4 @@
+use crate::widget::attribute::{FileMode, Value};
 use crate::widget::event::{
     Coordinate, InputEvent, KeyCode, KeyEvent, Modifier, MouseButton,
     MouseEvent,
@@ -52,11 +53,172 @@ pub fn from_mouse_event(event: sauron::web_sys::MouseEvent) -> MouseEvent {
     }
 }
 
+/// convert the html mouse event with the x and y relative to the element
+/// which has the listener, such as the canvas
+pub fn from_local_mouse_event(
+    event: sauron::web_sys::MouseEvent,
+) -> MouseEvent {
+    let current_target = event.current_target();
+    let mut mouse_event = from_mouse_event(event);
+    if let Some(element) =
+        current_target.and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+    {
+        let rect = element.get_bounding_client_rect();
+        let coordinate = &mut mouse_event.coordinate;
+        coordinate.x = coordinate.client_x - rect.left() as i32;
+        coordinate.y = coordinate.client_y - rect.top() as i32;
+    }
+    mouse_event
+}
+
+/// the chosen files of the file input which triggered the event, these are
+/// the contents of the files since the browser doesn't expose their paths.
+/// In save mode, the input is a text input of the file name instead.
+pub fn to_chosen_files(event: sauron::Event, mode: FileMode) -> InputEvent {
+    let input: web_sys::HtmlInputElement = event
+        .as_web()
+        .expect("must be a web event")
+        .target()
+        .expect("must have a target")
+        .dyn_into()
+        .expect("must be an input element");
+    if let FileMode::Save = mode {
+        let name = input.value();
+        let names = if name.is_empty() { vec![] } else { vec![name] };
+        return InputEvent::new(names);
+    }
+    let contents: Vec<Value> = match input.files() {
+        Some(files) => (0..files.length())
+            .filter_map(|i| files.get(i))
+            .map(|file| Value::from(read_file(&file)))
+            .collect(),
+        None => vec![],
+    };
+    InputEvent::new(contents)
+}
+
+/// read the content of the file with a synchronous request of its object url,
+/// since the event has to be converted into the msg right away
+fn read_file(file: &web_sys::File) -> Vec<u8> {
+    let url = web_sys::Url::create_object_url_with_blob(file)
+        .expect("must create an object url");
+    let request =
+        web_sys::XmlHttpRequest::new().expect("must create a request");
+    request
+        .open_with_async("GET", &url, false)
+        .expect("must open the request");
+    // each byte is kept as a character of the response text
+    request
+        .override_mime_type("text/plain; charset=x-user-defined")
+        .expect("must override the mime type");
+    request.send().expect("must read the file");
+    web_sys::Url::revoke_object_url(&url).expect("must revoke the object url");
+    request
+        .response_text()
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .encode_utf16()
+        .map(|c| c as u8)
+        .collect()
+}
+
 pub fn to_input_event(input_event: sauron::InputEvent) -> InputEvent {
     // TODO: make a comprehensive conversion here
     InputEvent::new(input_event.value)
 }
 
+/// the input event of a number or range input, the value which can not be
+/// parsed such as an empty number input is replaced with the fallback
+pub fn to_number_input_event(
+    input_event: sauron::InputEvent,
+    fallback: f64,
+) -> InputEvent {
+    InputEvent::new(input_event.value.parse::<f64>().unwrap_or(fallback))
+}
+
+/// the selected options of the select element which triggered the event,
+/// a list of the options when multiple options can be selected
+pub fn to_selected_options(
+    event: sauron::Event,
+    is_multiple: bool,
+) -> InputEvent {
+    let select: web_sys::HtmlSelectElement = event
+        .as_web()
+        .expect("must be a web event")
+        .target()
+        .expect("must have a target")
+        .dyn_into()
+        .expect("must be a select element");
+    if is_multiple {
+        let options = select.selected_options();
+        let selected: Vec<String> = (0..options.length())
+            .filter_map(|i| options.item(i))
+            .filter_map(|option| {
+                option.dyn_into::<web_sys::HtmlOptionElement>().ok()
+            })
+            .map(|option| option.value())
+            .collect();
+        InputEvent::new(selected)
+    } else {
+        InputEvent::new(select.value())
+    }
+}
+
+/// the key event of a key combination which can be a shortcut or an
+/// accelerator
+pub fn to_accelerator(
+    keyboard_event: &sauron::web_sys::KeyboardEvent,
+) -> Option<KeyEvent> {
+    let key = keyboard_event.key();
+    let key_code = match key.as_str() {
+        "Enter" => KeyCode::Enter,
+        "Backspace" => KeyCode::Backspace,
+        "ArrowLeft" => KeyCode::Left,
+        "ArrowRight" => KeyCode::Right,
+        "ArrowUp" => KeyCode::Up,
+        "ArrowDown" => KeyCode::Down,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Tab" => KeyCode::Tab,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Escape" => KeyCode::Esc,
+        " " => KeyCode::Char(' '),
+        function if function.starts_with('F') && function.len() > 1 => {
+            KeyCode::F(function[1..].parse().ok()?)
+        }
+        ch if ch.chars().count() == 1 => {
+            KeyCode::Char(ch.to_lowercase().chars().next()?)
+        }
+        _ => return None,
+    };
+    let modifier = Modifier {
+        alt_key: keyboard_event.alt_key(),
+        ctrl_key: keyboard_event.ctrl_key(),
+        shift_key: keyboard_event.shift_key(),
+    };
+    Some(KeyEvent { key_code, modifier })
+}
+
+/// close the dropdowns of the menus which contains the clicked menu item
+pub fn close_menus(event: &sauron::web_sys::MouseEvent) {
+    let mut target = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+    while let Some(element) = target {
+        let details = element.closest("details.MenuItem").ok().flatten();
+        if let Some(details) = details.as_ref() {
+            details
+                .remove_attribute("open")
+                .expect("must remove the open attribute");
+        }
+        target = details.and_then(|details| details.parent_element());
+    }
+}
+
 pub fn to_key_event(keyboard_event: &sauron::KeyboardEvent) -> KeyEvent {
     KeyEvent {
         key_code: get_keycode(keyboard_event),
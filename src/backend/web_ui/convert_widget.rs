@@ -1,11 +1,28 @@
+#[cfg(feature = "with-web")]
 use super::convert_event;
+use super::custom_widget;
 use crate::image_util;
-use crate::widget::attribute::util::{get_id, get_key, get_layout};
-use crate::widget::event::KeyCode;
+use crate::widget::attribute::util::{
+    get_accelerator, get_file_mode, get_filters, get_fraction, get_id,
+    get_index, get_item_count, get_key, get_layout, get_numeric_range,
+    get_numeric_value, get_options, get_rendered_range, get_row_height,
+    get_selected_values, get_table_data, get_tree_item_id, get_tree_item_state,
+    has_dialog, is_autofocus, DEFAULT_VIEWPORT_HEIGHT,
+};
+#[cfg(feature = "with-web")]
+use crate::widget::attribute::util::{get_activate_value, visible_range};
+use crate::widget::attribute::FileMode;
+use crate::widget::draw::{self, Shape};
+#[cfg(feature = "with-web")]
+use crate::widget::event::{FocusEvent, InputEvent};
 use crate::{widget::attribute::find_value, AttribKey, Widget};
 use sauron::{
-    html::{attributes::*, div, img, input, text},
+    html::{
+        attributes::{name, *},
+        div, img, input, summary, text,
+    },
     prelude::*,
+    svg::{path, svg, tags::text as svg_text},
 };
 use std::fmt::Debug;
 
@@ -26,7 +43,10 @@ where
     }
 }
 
-/// convert Widget into an equivalent html node
+/// convert Widget into an equivalent html node. The listeners are only
+/// attached in the web backend, which leaves their values unused in the
+/// static html
+#[cfg_attr(not(feature = "with-web"), allow(unused_mut, unused_variables))]
 fn widget_to_html<MSG>(
     element: &crate::Element<MSG>,
     cur_node_idx: &mut usize,
@@ -36,19 +56,41 @@ where
 {
     let attrs = element.get_attributes();
 
-    let layout = get_layout(&element).expect("must have a layout");
-    let widget_key = get_key(&element);
-    let widget_id = get_id(&element);
+    let layout = get_layout(element).expect("must have a layout");
+    let widget_key = get_key(element);
+    let widget_id = get_id(element);
 
+    // the tree items are converted together with the events of the tree view
+    let children: &[crate::Node<MSG>] = match element.tag() {
+        Widget::TreeView | Widget::TreeItem => &[],
+        _ => element.get_children(),
+    };
     let mut html_children = vec![];
-    for widget_child in element.get_children().iter() {
+    for widget_child in children.iter() {
         *cur_node_idx += 1;
         // convert all widget child to an html child node
         let html_child: sauron::Node<MSG> =
             widget_tree_to_html_node(widget_child, cur_node_idx);
         html_children.push(html_child);
     }
-    match element.tag() {
+    // the widgets outside of the dialog are inert, so they can not be
+    // focused or clicked while the dialog is shown
+    if children.iter().any(has_dialog) {
+        html_children = html_children
+            .into_iter()
+            .zip(children.iter())
+            .map(|(html_child, widget_child)| {
+                if widget_child.as_element_ref().is_some()
+                    && !has_dialog(widget_child)
+                {
+                    html_child.add_attributes(vec![attr("inert", true)])
+                } else {
+                    html_child
+                }
+            })
+            .collect();
+    }
+    let html = match element.tag() {
         Widget::Vbox => div(
             vec![
                 class("Vbox"),
@@ -126,16 +168,15 @@ where
         ),
         // the children in overlay will be all in absolute
         Widget::Overlay => {
-            let children_len = html_children.len();
+            let _children_len = html_children.len();
             html_children
                 .iter_mut()
                 .zip(element.get_children().iter())
-                .enumerate()
-                .for_each(|(_child_index, (html_child, widget_child))| {
+                .for_each(|(html_child, widget_child)| {
                     let widget_child_element = widget_child
                         .as_element_ref()
                         .expect("must be an element");
-                    let child_layout = get_layout(&widget_child_element)
+                    let _child_layout = get_layout(widget_child_element)
                         .expect("must have a child layout");
 
                     log::error!(
@@ -240,25 +281,24 @@ where
             html_children,
         ),
         Widget::Label => {
-            let value = find_value(AttribKey::Value, &attrs)
+            let value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let is_preformatted = find_value(AttribKey::Preformatted, &attrs)
+            let is_preformatted = find_value(AttribKey::Preformatted, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
-            let is_monospace = find_value(AttribKey::Monospace, &attrs)
+            let is_monospace = find_value(AttribKey::Monospace, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
-            let is_selectable = find_value(AttribKey::Selectable, &attrs)
+            let is_selectable = find_value(AttribKey::Selectable, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(true);
 
-            let label_for = find_value(AttribKey::For, &attrs)
-                .map(|v| v.as_str().map(|v| v.to_string()))
-                .flatten();
+            let label_for = find_value(AttribKey::For, attrs)
+                .and_then(|v| v.as_str().map(|v| v.to_string()));
 
             label(
                 vec![
@@ -289,30 +329,27 @@ where
             )
         }
         Widget::Button => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let widget_name = find_value(AttribKey::Name, &attrs)
+            let widget_name = find_value(AttribKey::Name, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let svg_image_data = find_value(AttribKey::SvgImage, &attrs)
-                .map(|v| v.as_bytes().map(|v| v.to_vec()))
-                .flatten();
+            let svg_image_data = find_value(AttribKey::SvgImage, attrs)
+                .and_then(|v| v.as_bytes().map(|v| v.to_vec()));
 
             let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
             for att in attrs {
-                match att.name() {
-                    AttribKey::ClickEvent => {
-                        for cb in att.get_callback() {
-                            let cb = cb.clone();
-                            attributes.push(on_click(move |ev| {
-                                cb.emit(convert_event::from_mouse_event(ev))
-                            }))
-                        }
+                if att.name() == &AttribKey::ClickEvent {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(on_click(move |ev| {
+                            cb.emit(convert_event::from_mouse_event(ev))
+                        }))
                     }
-                    _ => (),
                 }
             }
 
@@ -350,7 +387,7 @@ where
             .add_attributes(attributes)
         }
         Widget::Paragraph => {
-            let txt_value = find_value(AttribKey::Value, &attrs)
+            let txt_value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
             p(
@@ -372,10 +409,11 @@ where
             )
         }
         Widget::TextInput => {
-            let txt_value = find_value(AttribKey::Value, &attrs)
+            let txt_value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
             let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
             for att in attrs {
                 match att.name() {
                     AttribKey::InputEvent => {
@@ -429,34 +467,32 @@ where
             .add_attributes(attributes)
         }
         Widget::TextArea => {
-            let txt_value = find_value(AttribKey::Value, &attrs)
+            let txt_value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
             let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
             for att in attrs {
-                match att.name() {
-                    AttribKey::InputEvent => {
-                        for cb in att.get_callback() {
-                            let cb = cb.clone();
-                            attributes.push(on_input(move |ev| {
-                                cb.emit(convert_event::to_input_event(ev))
-                            }));
-                        }
+                if att.name() == &AttribKey::InputEvent {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(on_input(move |ev| {
+                            cb.emit(convert_event::to_input_event(ev))
+                        }));
                     }
-                    _ => (),
                 }
             }
 
-            let is_preformatted = find_value(AttribKey::Preformatted, &attrs)
+            let is_preformatted = find_value(AttribKey::Preformatted, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
             log::warn!("preformatted: {}", is_preformatted);
 
-            let is_monospace = find_value(AttribKey::Monospace, &attrs)
+            let is_monospace = find_value(AttribKey::Monospace, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
 
-            let is_selectable = find_value(AttribKey::Selectable, &attrs)
+            let is_selectable = find_value(AttribKey::Selectable, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(true);
             let st: Attribute<MSG> = styles_flag([
@@ -493,10 +529,10 @@ where
             .add_attributes(attributes)
         }
         Widget::Checkbox => {
-            let cb_label = find_value(AttribKey::Label, &attrs)
+            let cb_label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
-            let cb_value = find_value(AttribKey::Value, &attrs)
+            let cb_value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
             let checked = attrs_flag([("checked", "checked", cb_value)]);
@@ -519,7 +555,7 @@ where
                         vec![
                             type_("checkbox"),
                             if let Some(widget_id) = widget_id {
-                                id(&widget_id.to_string())
+                                id(widget_id.to_string())
                             } else {
                                 empty_attr()
                             },
@@ -529,7 +565,7 @@ where
                     .add_attributes(checked),
                     label(
                         vec![if let Some(widget_id) = widget_id {
-                            for_(&widget_id.to_string())
+                            for_(widget_id.to_string())
                         } else {
                             empty_attr()
                         }],
@@ -539,10 +575,10 @@ where
             )
         }
         Widget::Radio => {
-            let cb_label = find_value(AttribKey::Label, &attrs)
+            let cb_label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
-            let cb_value = find_value(AttribKey::Value, &attrs)
+            let cb_value = find_value(AttribKey::Value, attrs)
                 .map(|v| v.as_bool())
                 .unwrap_or(false);
             let checked = attrs_flag([("checked", "checked", cb_value)]);
@@ -564,7 +600,7 @@ where
                         vec![
                             type_("radio"),
                             if let Some(widget_id) = widget_id {
-                                id(&widget_id.to_string())
+                                id(widget_id.to_string())
                             } else {
                                 empty_attr()
                             },
@@ -574,7 +610,7 @@ where
                     .add_attributes(checked),
                     label(
                         vec![if let Some(widget_id) = widget_id {
-                            for_(&widget_id.to_string())
+                            for_(widget_id.to_string())
                         } else {
                             empty_attr()
                         }],
@@ -585,13 +621,10 @@ where
         }
         Widget::Image => {
             let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
+            let bytes = find_value(AttribKey::Data, attrs)
+                .and_then(|v| v.as_bytes())
                 .unwrap_or(&empty);
 
-            let mime_type =
-                image_util::image_mime_type(bytes).expect("unsupported image");
             div(
                 vec![
                     class("Image"),
@@ -608,21 +641,27 @@ where
                     */
                     styles([("overflow", "auto")]),
                 ],
+                // the image has no src if the data is not a supported image
                 vec![img(
-                    vec![src(format!(
-                        "data:{};base64,{}",
-                        mime_type,
-                        base64::encode(bytes)
-                    ))],
+                    vec![if let Some(mime_type) =
+                        image_util::image_mime_type(bytes)
+                    {
+                        src(format!(
+                            "data:{};base64,{}",
+                            mime_type,
+                            base64::encode(bytes)
+                        ))
+                    } else {
+                        empty_attr()
+                    }],
                     vec![],
                 )],
             )
         }
         Widget::Svg => {
             let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
+            let bytes = find_value(AttribKey::Data, attrs)
+                .and_then(|v| v.as_bytes())
                 .unwrap_or(&empty);
             div(
                 vec![
@@ -667,28 +706,129 @@ where
             html_children,
         ),
         Widget::MenuBar => div(
-            vec![if let Some(widget_key) = widget_key {
-                key(widget_key.to_string())
-            } else {
-                empty_attr()
-            }],
-            vec![text("menu bar")],
+            vec![
+                class("MenuBar"),
+                attr("role", "menubar"),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+                styles([("display", "flex"), ("flex-direction", "row")]),
+            ],
+            html_children,
         ),
+        // the menu is a dropdown below the menu item which has it
         Widget::Menu => div(
-            vec![if let Some(widget_key) = widget_key {
-                key(widget_key.to_string())
-            } else {
-                empty_attr()
-            }],
-            vec![text("menu")],
+            vec![
+                class("Menu"),
+                attr("role", "menu"),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+                styles([
+                    ("position", "absolute"),
+                    ("z-index", "1"),
+                    ("display", "flex"),
+                    ("flex-direction", "column"),
+                    ("background-color", "white"),
+                ]),
+            ],
+            html_children,
         ),
-        Widget::MenuItem => div(
-            vec![if let Some(widget_key) = widget_key {
-                key(widget_key.to_string())
+        Widget::MenuItem => {
+            let label = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let has_sub_menu = children.iter().any(|child| {
+                child
+                    .as_element_ref()
+                    .map(|element| *element.tag() == Widget::Menu)
+                    .unwrap_or(false)
+            });
+            // the sub menu is opened and closed by the details element
+            if has_sub_menu {
+                details(
+                    vec![
+                        class("MenuItem"),
+                        if let Some(widget_key) = widget_key {
+                            key(widget_key.to_string())
+                        } else {
+                            empty_attr()
+                        },
+                        styles([("position", "relative")]),
+                    ],
+                    vec![summary(
+                        vec![attr("role", "menuitem")],
+                        vec![text(label)],
+                    )]
+                    .into_iter()
+                    .chain(html_children)
+                    .collect::<Vec<_>>(),
+                )
             } else {
-                empty_attr()
-            }],
-            vec![text("menu item")],
+                button(
+                    vec![
+                        class("MenuItem"),
+                        attr("role", "menuitem"),
+                        if let Some(widget_key) = widget_key {
+                            key(widget_key.to_string())
+                        } else {
+                            empty_attr()
+                        },
+                    ],
+                    menu_item_content(None, &label, attrs)
+                        .into_iter()
+                        .chain(html_children)
+                        .collect::<Vec<_>>(),
+                )
+                .add_attributes(menu_activate_listeners(element.tag(), attrs))
+            }
+        }
+        Widget::CheckMenuItem | Widget::RadioMenuItem => {
+            let label = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let is_checked = find_value(AttribKey::Checked, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let (role, mark) = if *element.tag() == Widget::CheckMenuItem {
+                ("menuitemcheckbox", "✓")
+            } else {
+                ("menuitemradio", "•")
+            };
+            button(
+                vec![
+                    class("MenuItem"),
+                    attr("role", role),
+                    attr("aria-checked", is_checked),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                menu_item_content(
+                    Some(if is_checked { mark } else { "" }),
+                    &label,
+                    attrs,
+                ),
+            )
+            .add_attributes(menu_activate_listeners(element.tag(), attrs))
+        }
+        Widget::MenuSeparator => hr(
+            vec![
+                class("MenuSeparator"),
+                attr("role", "separator"),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+            ],
+            vec![],
         ),
         Widget::SearchInput => div(
             vec![if let Some(widget_key) = widget_key {
@@ -699,26 +839,24 @@ where
             vec![input(vec![type_("text")], vec![])],
         ),
         Widget::Link => {
-            let label = find_value(AttribKey::Label, &attrs)
+            let label = find_value(AttribKey::Label, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
-            let uri = find_value(AttribKey::Uri, &attrs)
+            let uri = find_value(AttribKey::Uri, attrs)
                 .map(|v| v.to_string())
                 .unwrap_or_default();
 
             let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
             for att in attrs {
-                match att.name() {
-                    AttribKey::ClickEvent => {
-                        for cb in att.get_callback() {
-                            let cb = cb.clone();
-                            attributes.push(on_click(move |ev| {
-                                cb.emit(convert_event::from_mouse_event(ev))
-                            }))
-                        }
+                if att.name() == &AttribKey::ClickEvent {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(on_click(move |ev| {
+                            cb.emit(convert_event::from_mouse_event(ev))
+                        }))
                     }
-                    _ => (),
                 }
             }
 
@@ -742,5 +880,897 @@ where
             )
             .add_attributes(attributes)
         }
+        Widget::Table => {
+            let (columns, rows) = get_table_data(attrs);
+            let is_sortable = find_value(AttribKey::Sortable, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let sort_column = get_index(AttribKey::SortColumn, attrs);
+            let is_descending = find_value(AttribKey::SortDescending, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let selected = get_index(AttribKey::Selected, attrs);
+
+            let headers =
+                columns.into_iter().enumerate().map(|(index, column)| {
+                    let mut attributes = vec![];
+                    if is_sortable {
+                        #[cfg(feature = "with-web")]
+                        for att in attrs {
+                            if let AttribKey::SortEvent = att.name() {
+                                for cb in att.get_callback() {
+                                    let cb = cb.clone();
+                                    attributes.push(on_click(move |_| {
+                                        cb.emit(InputEvent::new(index as f64))
+                                    }));
+                                }
+                            }
+                        }
+                    }
+                    let indicator = match sort_column {
+                        Some(sort_column) if sort_column == index => {
+                            if is_descending {
+                                " ▼"
+                            } else {
+                                " ▲"
+                            }
+                        }
+                        _ => "",
+                    };
+                    th(vec![], vec![text(format!("{}{}", column, indicator))])
+                        .add_attributes(attributes)
+                });
+
+            let rows = rows.into_iter().enumerate().map(|(index, row)| {
+                let mut attributes = vec![];
+                #[cfg(feature = "with-web")]
+                for att in attrs {
+                    match att.name() {
+                        AttribKey::SelectEvent => {
+                            for cb in att.get_callback() {
+                                let cb = cb.clone();
+                                attributes.push(on_click(move |_| {
+                                    cb.emit(InputEvent::new(index as f64))
+                                }));
+                            }
+                        }
+                        AttribKey::RowActivateEvent => {
+                            for cb in att.get_callback() {
+                                let cb = cb.clone();
+                                attributes.push(on_dblclick(move |_| {
+                                    cb.emit(InputEvent::new(index as f64))
+                                }));
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                tr(
+                    vec![classes_flag([("selected", selected == Some(index))])],
+                    row.into_iter()
+                        .map(|cell| td(vec![], vec![text(cell)]))
+                        .collect::<Vec<_>>(),
+                )
+                .add_attributes(attributes)
+            });
+
+            table(
+                vec![
+                    class("Table"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                vec![
+                    thead(vec![], vec![tr(vec![], headers.collect())]),
+                    tbody(vec![], rows.collect()),
+                ],
+            )
+        }
+        Widget::ListView => {
+            let item_count = get_item_count(attrs);
+            let row_height = get_row_height(attrs);
+            let viewport_height = find_value(AttribKey::Height, attrs)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(DEFAULT_VIEWPORT_HEIGHT);
+            let rendered = get_rendered_range(attrs);
+
+            let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                match att.name() {
+                    AttribKey::ScrollEvent => {
+                        for cb in att.get_callback() {
+                            let cb = cb.clone();
+                            attributes.push(on_scroll(
+                                move |(scroll_top, _scroll_left)| {
+                                    cb.emit(InputEvent::new(scroll_top))
+                                },
+                            ));
+                        }
+                    }
+                    AttribKey::VisibleRangeEvent => {
+                        for cb in att.get_callback() {
+                            let cb = cb.clone();
+                            attributes.push(on_scroll(
+                                move |(scroll_top, _scroll_left)| {
+                                    let range = visible_range(
+                                        item_count,
+                                        row_height,
+                                        scroll_top as f64,
+                                        viewport_height,
+                                    );
+                                    cb.emit(InputEvent::new(vec![
+                                        crate::Value::from(range.start),
+                                        crate::Value::from(range.end),
+                                    ]))
+                                },
+                            ));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            // the rows which are not built are the padding of the rows
+            let padding_top = rendered.start as f64 * row_height;
+            let padding_bottom =
+                (item_count - rendered.end) as f64 * row_height;
+            div(
+                vec![
+                    class("ListView"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles([
+                        ("height", px(viewport_height)),
+                        ("overflow-y", "auto".to_string()),
+                    ]),
+                ],
+                vec![div(
+                    vec![styles([
+                        ("display", "grid".to_string()),
+                        ("grid-auto-rows", px(row_height)),
+                        ("padding-top", px(padding_top)),
+                        ("padding-bottom", px(padding_bottom)),
+                    ])],
+                    html_children,
+                )],
+            )
+            .add_attributes(attributes)
+        }
+        Widget::Tabs => {
+            let selected = get_index(AttribKey::Selected, attrs).unwrap_or(0);
+            let tab_labels: Vec<String> = element
+                .get_children()
+                .iter()
+                .map(|child| {
+                    child
+                        .get_attributes()
+                        .and_then(|attrs| find_value(AttribKey::Label, attrs))
+                        .map(|v| v.to_string())
+                        .unwrap_or_default()
+                })
+                .collect();
+            let tab_strip =
+                tab_labels
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, tab_label)| {
+                        let mut attributes = vec![];
+                        #[cfg(feature = "with-web")]
+                        for att in attrs {
+                            if let AttribKey::TabChangeEvent = att.name() {
+                                for cb in att.get_callback() {
+                                    let cb = cb.clone();
+                                    attributes.push(on_click(move |_| {
+                                        cb.emit(InputEvent::new(index))
+                                    }));
+                                }
+                            }
+                        }
+                        button(
+                            vec![classes_flag([(
+                                "selected",
+                                index == selected,
+                            )])],
+                            vec![text(tab_label)],
+                        )
+                        .add_attributes(attributes)
+                    });
+            // the tabs which are not selected are hidden, rather than
+            // removed, so they keep their state
+            let tab_contents = html_children.into_iter().enumerate().map(
+                |(index, html_child)| {
+                    if index == selected {
+                        html_child
+                    } else {
+                        html_child
+                            .add_attributes(vec![styles([("display", "none")])])
+                    }
+                },
+            );
+            div(
+                vec![
+                    class("Tabs"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles(vec![
+                        ("display", "flex"),
+                        ("flex-direction", "column"),
+                    ]),
+                ],
+                vec![
+                    div(vec![class("TabStrip")], tab_strip.collect()),
+                    div(vec![class("TabContent")], tab_contents.collect()),
+                ],
+            )
+        }
+        Widget::Tab => div(
+            vec![
+                class("Tab"),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+            ],
+            html_children,
+        ),
+        Widget::Dropdown | Widget::ListBox => {
+            let is_list_box = *element.tag() == Widget::ListBox;
+            let is_multiple = is_list_box
+                && find_value(AttribKey::Multiple, attrs)
+                    .map(|v| v.as_bool())
+                    .unwrap_or(false);
+            let options = get_options(attrs);
+            let selected_values = get_selected_values(attrs);
+
+            let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                if let AttribKey::ChangeEvent = att.name() {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(sauron::html::events::on(
+                            "change",
+                            move |event| {
+                                cb.emit(convert_event::to_selected_options(
+                                    event,
+                                    is_multiple,
+                                ))
+                            },
+                        ));
+                    }
+                }
+            }
+            let options_len = options.len();
+            select(
+                vec![
+                    class(if is_list_box { "ListBox" } else { "Dropdown" }),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    // the list box shows all of its options
+                    if is_list_box {
+                        attr("size", options_len.max(2))
+                    } else {
+                        empty_attr()
+                    },
+                    if is_multiple {
+                        attr("multiple", true)
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                options
+                    .into_iter()
+                    .map(|name| {
+                        let is_selected = selected_values.contains(&name);
+                        option(
+                            vec![
+                                value(name.clone()),
+                                if is_selected {
+                                    attr("selected", true)
+                                } else {
+                                    empty_attr()
+                                },
+                            ],
+                            vec![text(name)],
+                        )
+                    })
+                    .collect(),
+            )
+            .add_attributes(attributes)
+        }
+        Widget::SpinButton | Widget::Slider => {
+            let is_slider = *element.tag() == Widget::Slider;
+            let (min_value, max_value, step) = get_numeric_range(attrs);
+            let number = get_numeric_value(attrs);
+            let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                if let AttribKey::InputEvent = att.name() {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(on_input(move |ev| {
+                            cb.emit(convert_event::to_number_input_event(
+                                ev, min_value,
+                            ))
+                        }));
+                    }
+                }
+            }
+            input(
+                vec![
+                    class(if is_slider { "Slider" } else { "SpinButton" }),
+                    r#type(if is_slider { "range" } else { "number" }),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    attr("min", min_value),
+                    attr("max", max_value),
+                    attr("step", step),
+                    value(number),
+                ],
+                vec![],
+            )
+            .add_attributes(attributes)
+        }
+        Widget::ProgressBar => {
+            let is_indeterminate = find_value(AttribKey::Indeterminate, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let progress_text = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            div(
+                vec![
+                    class("ProgressBar"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                vec![
+                    // a progress element without a value is indeterminate
+                    progress(
+                        vec![
+                            attr("max", 1.0),
+                            if is_indeterminate {
+                                empty_attr()
+                            } else {
+                                value(get_fraction(attrs))
+                            },
+                        ],
+                        vec![],
+                    ),
+                    span(vec![], vec![text(progress_text)]),
+                ],
+            )
+        }
+        Widget::Spinner => {
+            let is_active = find_value(AttribKey::Active, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(true);
+            progress(
+                vec![
+                    class("Spinner"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles_flag([("visibility", "hidden", !is_active)]),
+                ],
+                vec![],
+            )
+        }
+        Widget::TreeView => {
+            let items = tree_items_to_html(element, attrs, cur_node_idx);
+            ul(
+                vec![
+                    class("TreeView"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                items,
+            )
+        }
+        // a tree item outside of a tree view has no events
+        Widget::TreeItem => tree_item_to_html(element, &[], cur_node_idx),
+        Widget::Dialog => {
+            let heading = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+
+            // the buttons are in the children, the close button dismisses
+            // the dialog
+            let mut close_attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                if let AttribKey::ResponseEvent = att.name() {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        close_attributes.push(on_click(move |_| {
+                            cb.emit(InputEvent::new(""))
+                        }));
+                    }
+                }
+            }
+
+            div(
+                vec![
+                    class("DialogOverlay"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles([
+                        ("position", "fixed"),
+                        ("top", "0"),
+                        ("left", "0"),
+                        ("width", "100%"),
+                        ("height", "100%"),
+                        ("display", "flex"),
+                        ("align-items", "center"),
+                        ("justify-content", "center"),
+                        ("background-color", "rgba(0,0,0,0.4)"),
+                    ]),
+                ],
+                vec![div(
+                    vec![
+                        class("Dialog"),
+                        attr("role", "dialog"),
+                        attr("aria-modal", "true"),
+                        attr("aria-label", heading.clone()),
+                        styles([
+                            ("display", "flex"),
+                            ("flex-direction", "column"),
+                            ("background-color", "white"),
+                        ]),
+                    ],
+                    vec![
+                        div(
+                            vec![class("DialogTitle")],
+                            vec![
+                                span(vec![], vec![text(heading)]),
+                                button(
+                                    vec![
+                                        class("DialogClose"),
+                                        attr("aria-label", "Close"),
+                                    ],
+                                    vec![text("×")],
+                                )
+                                .add_attributes(close_attributes),
+                            ],
+                        ),
+                        div(vec![class("DialogContent")], html_children),
+                    ],
+                )],
+            )
+        }
+        Widget::FileChooser => {
+            let mode = get_file_mode(attrs);
+            let heading = find_value(AttribKey::Label, attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let is_multiple = find_value(AttribKey::Multiple, attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            // the patterns such as `*.png` are the extensions in accept
+            let accept: Vec<String> = get_filters(attrs)
+                .iter()
+                .map(|filter_pattern| {
+                    filter_pattern.trim_start_matches('*').to_string()
+                })
+                .filter(|extension| !extension.is_empty())
+                .collect();
+
+            let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                if let AttribKey::FilesEvent = att.name() {
+                    for cb in att.get_callback() {
+                        let cb = cb.clone();
+                        attributes.push(sauron::html::events::on(
+                            "change",
+                            move |ev| {
+                                cb.emit(convert_event::to_chosen_files(
+                                    ev, mode,
+                                ))
+                            },
+                        ));
+                    }
+                }
+            }
+
+            // there is no save dialog in the browser, the file name is
+            // entered in a text input instead
+            let file_input = match mode {
+                FileMode::Save => input(
+                    vec![
+                        r#type("text"),
+                        value(
+                            find_value(AttribKey::Value, attrs)
+                                .map(|v| v.to_string())
+                                .unwrap_or_default(),
+                        ),
+                    ],
+                    vec![],
+                ),
+                FileMode::Open | FileMode::SelectFolder => input(
+                    vec![
+                        r#type("file"),
+                        if accept.is_empty() {
+                            empty_attr()
+                        } else {
+                            attr("accept", accept.join(","))
+                        },
+                        if is_multiple {
+                            attr("multiple", true)
+                        } else {
+                            empty_attr()
+                        },
+                        if mode == FileMode::SelectFolder {
+                            attr("webkitdirectory", true)
+                        } else {
+                            empty_attr()
+                        },
+                    ],
+                    vec![],
+                ),
+            };
+
+            div(
+                vec![
+                    class("FileChooser"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                ],
+                vec![
+                    span(vec![], vec![text(heading)]),
+                    file_input.add_attributes(attributes),
+                ],
+            )
+        }
+        Widget::Canvas => {
+            let commands = find_value(AttribKey::Draw, attrs)
+                .and_then(|v| v.as_draw())
+                .unwrap_or(&[]);
+
+            let mut attributes = vec![];
+            #[cfg(feature = "with-web")]
+            for att in attrs {
+                for cb in att.get_callback() {
+                    let cb = cb.clone();
+                    let listener = match att.name() {
+                        AttribKey::ClickEvent => on_click(move |ev| {
+                            cb.emit(convert_event::from_local_mouse_event(ev))
+                        }),
+                        AttribKey::MouseDown => on_mousedown(move |ev| {
+                            cb.emit(convert_event::from_local_mouse_event(ev))
+                        }),
+                        AttribKey::MouseUp => on_mouseup(move |ev| {
+                            cb.emit(convert_event::from_local_mouse_event(ev))
+                        }),
+                        AttribKey::MouseMove => on_mousemove(move |ev| {
+                            cb.emit(convert_event::from_local_mouse_event(ev))
+                        }),
+                        _ => continue,
+                    };
+                    attributes.push(listener);
+                }
+            }
+
+            svg(
+                vec![
+                    class("Canvas"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    attr("width", layout.size.width),
+                    attr("height", layout.size.height),
+                ],
+                draw::to_shapes(commands)
+                    .into_iter()
+                    .map(shape_to_svg)
+                    .collect::<Vec<_>>(),
+            )
+            .add_attributes(attributes)
+        }
+        Widget::Custom(custom) => {
+            match custom_widget::find_custom_widget::<MSG>(custom.as_ref()) {
+                Some(hook) => {
+                    hook.to_html(custom.as_ref(), attrs, html_children)
+                }
+                // the children are shown in a div in place of the custom
+                // widget which has no conversion
+                None => {
+                    log::error!(
+                        "no web conversion for the custom widget `{}`",
+                        custom.name()
+                    );
+                    div(vec![], html_children)
+                }
+            }
+            .add_attributes(vec![
+                class(custom.name().to_string()),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+            ])
+        }
+    };
+    html.add_attributes(focus_attributes(element.tag(), attrs, widget_id))
+}
+
+/// the id, the autofocus and the focus and blur listeners of the widget.
+/// The listeners are focusin and focusout which bubble up from the focusable
+/// element inside the widget, such as the input of a checkbox
+fn focus_attributes<MSG>(
+    widget: &Widget,
+    attrs: &[crate::Attribute<MSG>],
+    widget_id: Option<&crate::Value>,
+) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut attributes = vec![];
+    // the id of the checkbox and radio is set in their input
+    if let Some(widget_id) = widget_id {
+        if !matches!(widget, Widget::Checkbox | Widget::Radio) {
+            attributes.push(id(widget_id.to_string()));
+        }
+    }
+    if is_autofocus(attrs) {
+        attributes.push(attr("autofocus", true));
+    }
+    #[cfg(feature = "with-web")]
+    for att in attrs {
+        for cb in att.get_callback() {
+            let cb = cb.clone();
+            let listener = match att.name() {
+                AttribKey::FocusEvent => {
+                    sauron::html::events::on("focusin", move |_event| {
+                        cb.emit(crate::Event::FocusEvent(FocusEvent))
+                    })
+                }
+                AttribKey::BlurEvent => {
+                    sauron::html::events::on("focusout", move |_event| {
+                        cb.emit(crate::Event::BlurEvent(FocusEvent))
+                    })
+                }
+                _ => continue,
+            };
+            attributes.push(listener);
+        }
     }
+    attributes
+}
+
+/// the check mark, label and accelerator of the menu item
+fn menu_item_content<MSG>(
+    mark: Option<&str>,
+    label: &str,
+    attrs: &[crate::Attribute<MSG>],
+) -> Vec<sauron::Node<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let accelerator = get_accelerator(attrs)
+        .map(|key_event| key_event.to_accelerator())
+        .unwrap_or_default();
+    let mut content = vec![];
+    if let Some(mark) = mark {
+        content.push(span(vec![class("MenuMark")], vec![text(mark)]));
+    }
+    content.push(span(vec![], vec![text(label)]));
+    content.push(span(vec![class("Accelerator")], vec![text(accelerator)]));
+    content
+}
+
+/// emit the activate event when the menu item is clicked, this also closes
+/// the menus which contains the menu item
+#[cfg(feature = "with-web")]
+fn menu_activate_listeners<MSG>(
+    widget: &Widget,
+    attrs: &[crate::Attribute<MSG>],
+) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let value = get_activate_value(widget, attrs);
+    attrs
+        .iter()
+        .filter(|att| *att.name() == AttribKey::ActivateEvent)
+        .flat_map(|att| att.get_callback())
+        .map(|cb| {
+            let cb = cb.clone();
+            let value = value.clone();
+            on_click(move |ev| {
+                convert_event::close_menus(&ev);
+                cb.emit(InputEvent::new(value.clone()))
+            })
+        })
+        .collect()
+}
+
+/// the static html has no listeners
+#[cfg(not(feature = "with-web"))]
+fn menu_activate_listeners<MSG>(
+    _widget: &Widget,
+    _attrs: &[crate::Attribute<MSG>],
+) -> Vec<sauron::Attribute<MSG>> {
+    vec![]
+}
+
+/// convert the shape drawn on the canvas into an svg element
+fn shape_to_svg<MSG>(shape: Shape) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    match shape {
+        Shape::Path {
+            data: path_data,
+            fill,
+            stroke,
+        } => path(
+            vec![
+                attr("d", path_data),
+                attr(
+                    "fill",
+                    fill.map(|c| c.to_string())
+                        .unwrap_or_else(|| "none".to_string()),
+                ),
+                if let Some((stroke_color, _width)) = stroke {
+                    attr("stroke", stroke_color.to_string())
+                } else {
+                    empty_attr()
+                },
+                if let Some((_color, width)) = stroke {
+                    attr("stroke-width", width)
+                } else {
+                    empty_attr()
+                },
+            ],
+            vec![],
+        ),
+        Shape::Text { x, y, text, fill } => svg_text(
+            vec![attr("x", x), attr("y", y), attr("fill", fill.to_string())],
+            vec![sauron::html::text(text)],
+        ),
+    }
+}
+
+/// convert the nested items of the tree view or tree item
+fn tree_items_to_html<MSG>(
+    element: &crate::Element<MSG>,
+    tree_attrs: &[crate::Attribute<MSG>],
+    cur_node_idx: &mut usize,
+) -> Vec<sauron::Node<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut items = vec![];
+    for child in element.get_children().iter() {
+        *cur_node_idx += 1;
+        if let Some(item) = child.as_element_ref() {
+            items.push(tree_item_to_html(item, tree_attrs, cur_node_idx));
+        }
+    }
+    items
+}
+
+/// convert the tree item into a row with a toggle and the label,
+/// followed by the nested items which are hidden when it is collapsed.
+/// The events of the tree view are attached to each item with its id.
+#[cfg_attr(not(feature = "with-web"), allow(unused_mut, unused_variables))]
+fn tree_item_to_html<MSG>(
+    item: &crate::Element<MSG>,
+    tree_attrs: &[crate::Attribute<MSG>],
+    cur_node_idx: &mut usize,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let attrs = item.get_attributes();
+    let item_id = get_tree_item_id(attrs);
+    let (is_expanded, is_selected) = get_tree_item_state(attrs);
+    let item_label = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let nested_items = tree_items_to_html(item, tree_attrs, cur_node_idx);
+    let toggle = if nested_items.is_empty() {
+        ""
+    } else if is_expanded {
+        "▾"
+    } else {
+        "▸"
+    };
+
+    let mut row_attributes = vec![];
+    let mut toggle_attributes = vec![];
+    #[cfg(feature = "with-web")]
+    for att in tree_attrs {
+        for cb in att.get_callback() {
+            let cb = cb.clone();
+            let item_id = item_id.clone();
+            match att.name() {
+                AttribKey::SelectEvent => {
+                    row_attributes.push(on_click(move |_| {
+                        cb.emit(InputEvent::new(item_id.clone()))
+                    }));
+                }
+                AttribKey::RowActivateEvent => {
+                    row_attributes.push(on_dblclick(move |_| {
+                        cb.emit(InputEvent::new(item_id.clone()))
+                    }));
+                }
+                AttribKey::ExpandEvent if !is_expanded => {
+                    toggle_attributes.push(on_click(move |_| {
+                        cb.emit(InputEvent::new(item_id.clone()))
+                    }));
+                }
+                AttribKey::CollapseEvent if is_expanded => {
+                    toggle_attributes.push(on_click(move |_| {
+                        cb.emit(InputEvent::new(item_id.clone()))
+                    }));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    li(
+        vec![
+            class("TreeItem"),
+            if let Some(widget_key) = get_key(item) {
+                key(widget_key.to_string())
+            } else {
+                empty_attr()
+            },
+        ],
+        vec![
+            div(
+                vec![classes_flag([
+                    ("TreeRow", true),
+                    ("selected", is_selected),
+                ])],
+                vec![
+                    span(vec![class("TreeToggle")], vec![text(toggle)])
+                        .add_attributes(toggle_attributes),
+                    span(vec![], vec![text(item_label)]),
+                ],
+            )
+            .add_attributes(row_attributes),
+            ul(
+                vec![styles_flag([("display", "none", !is_expanded)])],
+                nested_items,
+            ),
+        ],
+    )
 }
@@ -0,0 +1,38 @@
+//! conversion hooks of custom widgets for the web backend
+//!
+use crate::backend::registry;
+use crate::{widget::CustomWidget, Attribute};
+use std::rc::Rc;
+
+/// Implement this to convert a custom widget into an html node
+pub trait WebCustomWidget<MSG> {
+    /// create the html node of the custom widget,
+    /// the children are already converted into html nodes
+    fn to_html(
+        &self,
+        custom_widget: &dyn CustomWidget,
+        attrs: &[Attribute<MSG>],
+        children: Vec<sauron::Node<MSG>>,
+    ) -> sauron::Node<MSG>;
+}
+
+/// register the html conversion of the custom widget with this name
+pub fn register_custom_widget<MSG, HOOK>(name: &str, hook: HOOK)
+where
+    MSG: 'static,
+    HOOK: WebCustomWidget<MSG> + 'static,
+{
+    let hook: Rc<dyn WebCustomWidget<MSG>> = Rc::new(hook);
+    registry::register_hook(name, hook);
+}
+
+/// find the html conversion of the custom widget, returns None if there is
+/// no conversion registered with the name of the custom widget
+pub(crate) fn find_custom_widget<MSG>(
+    custom_widget: &dyn CustomWidget,
+) -> Option<Rc<dyn WebCustomWidget<MSG>>>
+where
+    MSG: 'static,
+{
+    registry::find_hook::<dyn WebCustomWidget<MSG>>(custom_widget.name())
+}
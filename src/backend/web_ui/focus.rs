@@ -0,0 +1,65 @@
+//! keyboard focus of the widgets, the widgets are focused after the dom is
+//! updated since the Cmds are emitted after the view is patched
+use wasm_bindgen::JsCast;
+
+/// the elements which can have the keyboard focus, the widgets such as the
+/// checkbox wraps the focusable input element
+const FOCUSABLE: &str = "input, select, textarea, button, a[href], [tabindex]";
+
+/// focus the widget with this id, returns false if there is no widget with
+/// this id
+pub fn focus_by_id(id: &str) -> bool {
+    match document().get_element_by_id(id) {
+        Some(element) => {
+            focus_element(&element);
+            true
+        }
+        None => false,
+    }
+}
+
+/// focus the first widget with autofocus which is newly shown, the browser
+/// only does this when the page is loaded. The autofocused widgets are
+/// marked, so they are not focused again in the next updates.
+pub fn autofocus() {
+    let elements = document()
+        .query_selector_all("[autofocus]:not([data-autofocused])")
+        .expect("must query the autofocus elements");
+    let mut first = None;
+    for i in 0..elements.length() {
+        if let Some(element) = elements
+            .item(i)
+            .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+        {
+            element
+                .set_attribute("data-autofocused", "")
+                .expect("must set the autofocused attribute");
+            first = first.or(Some(element));
+        }
+    }
+    if let Some(element) = first {
+        focus_element(&element);
+    }
+}
+
+/// focus the element, or the first focusable element inside it when it
+/// is a wrapper such as the div of a checkbox or a vbox
+fn focus_element(element: &web_sys::Element) {
+    let target = if element.matches(FOCUSABLE).unwrap_or(false) {
+        Some(element.clone())
+    } else {
+        element.query_selector(FOCUSABLE).ok().flatten()
+    };
+    if let Some(html_element) =
+        target.and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        html_element.focus().expect("must focus the element");
+    }
+}
+
+fn document() -> web_sys::Document {
+    sauron::web_sys::window()
+        .expect("must have a window")
+        .document()
+        .expect("must have a document")
+}
@@ -0,0 +1,346 @@
+//! the sauron app which mounts the html view of the app into the browser
+use super::{convert_event, convert_widget, focus};
+use crate::widget::attribute::util::{
+    find_accelerated, find_callback, find_dialog, get_activate_value,
+};
+use crate::widget::event::{InputEvent, KeyCode, KeyEvent};
+use crate::{
+    backend::Dispatch,
+    shortcut::find_shortcut,
+    sub::{emit_sub, Subscriptions},
+    widget::layout::compute_node_layout,
+    AttribKey, Backend, Cmd, Component,
+};
+use expanse::geometry::Size;
+use expanse::number::Number;
+use sauron::prelude::*;
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// We wrap the App's Msg with this such that we can add high level behavior of the app
+/// such as automatically computing the layout when the window is resized
+#[derive(Clone)]
+pub enum BackendMsg<MSG> {
+    /// the Wrapped MSG meant for the app
+    AppMsg(MSG),
+    /// the app container is resized
+    Resize(i32, i32),
+    /// a key combination is pressed, which is a shortcut of the app or
+    /// activates the menu item with this accelerator
+    Accelerator(KeyEvent),
+    /// the escape key is pressed, which dismisses the dialog or else is
+    /// matched with the shortcuts of the app
+    Dismiss(KeyEvent),
+}
+
+/// holds the user application,
+/// this just wraps the app, so we can implement the Component trait for it
+pub struct HtmlApp<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    app: APP,
+    browser_size: (i32, i32),
+    /// the intervals of the subscriptions of the app
+    subscriptions: Rc<RefCell<Subscriptions<MSG, Interval>>>,
+    _phantom_data: PhantomData<MSG>,
+}
+
+/// the interval of a subscription, the closure is kept alive until the
+/// interval is cleared
+struct Interval {
+    handle: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl<APP, MSG> HtmlApp<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn new(app: APP) -> Self {
+        let browser_size = Window::get_size();
+        HtmlApp {
+            app,
+            browser_size,
+            subscriptions: Rc::new(RefCell::new(Subscriptions::new())),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+/// dispatches the msg emitted from the app's Cmd into the sauron Program
+struct ProgramDispatcher<APP, MSG>(
+    sauron::Program<HtmlApp<APP, MSG>, BackendMsg<MSG>>,
+)
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static;
+
+impl<APP, MSG> Dispatch<MSG> for ProgramDispatcher<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        sauron::Dispatch::dispatch(&self.0, BackendMsg::AppMsg(msg));
+    }
+
+    fn focus(&self, id: &str) {
+        if !focus::focus_by_id(id) {
+            log::warn!("there is no widget with id: {}", id);
+        }
+    }
+}
+
+impl<APP, MSG> HtmlApp<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// convert the Cmd returned by the app into a sauron Cmd
+    fn to_program_cmd(
+        cmd: Cmd<MSG>,
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        if cmd.is_none() {
+            return sauron::cmd::Cmd::none();
+        }
+        sauron::cmd::Cmd::new(move |program| {
+            cmd.clone().emit(Rc::new(ProgramDispatcher(program)));
+        })
+    }
+
+    /// diff the subscriptions of the app after the update, the intervals of
+    /// the new subscriptions are set and the removed ones are cleared
+    fn update_subscriptions(
+        &self,
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        let subs = self.app.subscriptions();
+        let subscriptions = Rc::clone(&self.subscriptions);
+        sauron::cmd::Cmd::new(
+            move |program: sauron::Program<Self, BackendMsg<MSG>>| {
+                let window =
+                    sauron::web_sys::window().expect("must have a window");
+                subscriptions.borrow_mut().update(
+                    subs.clone(),
+                    |interval, msg_fn| {
+                        let program = program.clone();
+                        let closure: Closure<dyn FnMut()> =
+                            Closure::wrap(Box::new(move || {
+                                sauron::Dispatch::dispatch(
+                                    &program,
+                                    BackendMsg::AppMsg(emit_sub(&msg_fn)),
+                                );
+                            }));
+                        let handle = window
+                        .set_interval_with_callback_and_timeout_and_arguments_0(
+                            closure.as_ref().unchecked_ref(),
+                            interval.as_millis() as i32,
+                        )
+                        .expect("must set the interval");
+                        Interval {
+                            handle,
+                            _closure: closure,
+                        }
+                    },
+                    |interval| {
+                        window.clear_interval_with_handle(interval.handle)
+                    },
+                );
+            },
+        )
+    }
+
+    /// focus the newly shown widgets with autofocus, this is executed after
+    /// the view is patched
+    fn autofocus() -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        sauron::cmd::Cmd::new(|_program| focus::autofocus())
+    }
+
+    /// listen to the key combinations pressed anywhere in the window, since
+    /// the shortcuts and accelerators works no matter which widget has focus.
+    /// Only the keys which are handled by the app are taken and their default
+    /// action in the browser is prevented, such as saving the page with
+    /// ctrl+s. The other keys are still typed into the text inputs.
+    fn on_accelerator(
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        sauron::cmd::Cmd::new(
+            move |program: sauron::Program<Self, BackendMsg<MSG>>| {
+                let listener: Closure<
+                    dyn FnMut(sauron::web_sys::KeyboardEvent),
+                > = Closure::wrap(Box::new(
+                    move |event: sauron::web_sys::KeyboardEvent| {
+                        let key_event =
+                            match convert_event::to_accelerator(&event) {
+                                Some(key_event) => key_event,
+                                None => return,
+                            };
+                        if !program.app.borrow().is_handled(&key_event) {
+                            return;
+                        }
+                        event.prevent_default();
+                        let msg = if key_event.key_code == KeyCode::Esc {
+                            BackendMsg::Dismiss(key_event)
+                        } else {
+                            BackendMsg::Accelerator(key_event)
+                        };
+                        sauron::Dispatch::dispatch(&program, msg);
+                    },
+                ));
+                sauron::web_sys::window()
+                    .expect("must have a window")
+                    .add_event_listener_with_callback(
+                        "keydown",
+                        listener.as_ref().unchecked_ref(),
+                    )
+                    .expect("must add the keydown listener");
+                listener.forget();
+            },
+        )
+    }
+
+    /// whether the key is a shortcut of the app, the accelerator of a menu
+    /// item or the escape key which dismisses a dialog
+    fn is_handled(&self, key_event: &KeyEvent) -> bool {
+        if find_shortcut(&self.app, key_event).is_some() {
+            return true;
+        }
+        let view = self.app.view();
+        if key_event.key_code == KeyCode::Esc {
+            find_dialog(&view).is_some()
+        } else {
+            find_accelerated(&view, key_event).is_some()
+        }
+    }
+
+    /// the msgs of the dialog in the view for its empty response, the escape
+    /// key is matched with the shortcuts when there is no dialog
+    fn dismiss_dialog(&self, key_event: &KeyEvent) -> Vec<MSG> {
+        let view = self.app.view();
+        let dialog = match find_dialog(&view) {
+            Some(dialog) => dialog,
+            None => {
+                return find_shortcut(&self.app, key_event)
+                    .into_iter()
+                    .collect()
+            }
+        };
+        find_callback(AttribKey::ResponseEvent, dialog.get_attributes())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|cb| cb.emit(InputEvent::new("")))
+            .collect()
+    }
+
+    /// update the app with the msgs of the key which is pressed
+    fn update_with(
+        &mut self,
+        msgs: Vec<MSG>,
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        let mut cmds: Vec<_> = msgs
+            .into_iter()
+            .map(|msg| Self::to_program_cmd(self.app.update(msg)))
+            .collect();
+        cmds.insert(0, Self::autofocus());
+        cmds.insert(1, self.update_subscriptions());
+        sauron::cmd::Cmd::batch(cmds)
+    }
+
+    /// the msgs of the menu item in the view which has this accelerator
+    fn activate_accelerator(&self, key_event: &KeyEvent) -> Vec<MSG> {
+        let view = self.app.view();
+        let element = match find_accelerated(&view, key_event) {
+            Some(element) => element,
+            None => return vec![],
+        };
+        let value = get_activate_value(element.tag(), element.get_attributes());
+        find_callback(AttribKey::ActivateEvent, element.get_attributes())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|cb| cb.emit(InputEvent::new(value.clone())))
+            .collect()
+    }
+}
+
+impl<APP, MSG> sauron::Component<BackendMsg<MSG>> for HtmlApp<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn init(&self) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        log::debug!("init in HtmlApp..");
+        sauron::cmd::Cmd::batch(vec![
+            Window::on_resize(BackendMsg::Resize),
+            Self::on_accelerator(),
+            Self::autofocus(),
+            self.update_subscriptions(),
+            Self::to_program_cmd(self.app.init()),
+        ])
+    }
+
+    fn update(
+        &mut self,
+        msg: BackendMsg<MSG>,
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        match msg {
+            BackendMsg::AppMsg(msg) => {
+                let cmd = self.app.update(msg);
+                sauron::cmd::Cmd::batch(vec![
+                    Self::autofocus(),
+                    self.update_subscriptions(),
+                    Self::to_program_cmd(cmd),
+                ])
+            }
+            BackendMsg::Resize(w, h) => {
+                log::debug!("window is resizing..");
+                self.browser_size = (w, h);
+                sauron::cmd::Cmd::none()
+            }
+            BackendMsg::Accelerator(key_event) => {
+                let msgs = match find_shortcut(&self.app, &key_event) {
+                    Some(msg) => vec![msg],
+                    None => self.activate_accelerator(&key_event),
+                };
+                self.update_with(msgs)
+            }
+            BackendMsg::Dismiss(key_event) => {
+                let msgs = self.dismiss_dialog(&key_event);
+                self.update_with(msgs)
+            }
+        }
+    }
+
+    fn view(&self) -> sauron::Node<BackendMsg<MSG>> {
+        let t1 = sauron::now();
+
+        let mut view = self.app.view();
+        let (w, h) = self.browser_size;
+        let (adjusted_w, adjusted_h) = (w as f32 - 100.0, h as f32 - 20.0);
+        compute_node_layout(
+            &mut view,
+            Size {
+                width: Number::Defined(adjusted_w),
+                height: Number::Defined(adjusted_h),
+            },
+        );
+
+        let t2 = sauron::now();
+        log::warn!("layout computation took: {}ms", t2 - t1);
+
+        let html_view = convert_widget::widget_tree_to_html_node(&view, &mut 0);
+        html_view.map_msg(BackendMsg::AppMsg)
+    }
+}
+
+impl<APP, MSG> Backend<APP, MSG> for HtmlApp<APP, MSG>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn init(app: APP) {
+        log::trace!("Html app started..");
+        let html_app = HtmlApp::new(app);
+        sauron::Program::mount_to_body(html_app);
+    }
+}
@@ -1,28 +1,47 @@
 //! provides functionalities for commands to be executed by the system, such as
 //! when the application starts or after the application updates.
 //!
+use crate::backend::Dispatch;
 use std::rc::Rc;
 
+/// the effect of a Cmd, which is called with the program
+pub type Effect<MSG> = Rc<dyn Fn(Rc<dyn Dispatch<MSG>>)>;
+
 /// Cmd is a command to be executed by the system.
 /// This is returned at the init function of a component and is executed right
 /// after instantiation of that component.
-/// Cmd required a DSP object which is the Program as an argument
+/// Cmd required a Dispatch object which is the backend program as an argument
 /// The emit function is called with the program argument.
 /// The callback is supplied with the program an is then executed/emitted.
-pub struct Cmd<DSP>(pub Vec<Rc<dyn Fn(DSP)>>);
+pub struct Cmd<MSG>(pub Vec<Effect<MSG>>);
 
-impl<DSP> Cmd<DSP>
+impl<MSG> Cmd<MSG>
 where
-    DSP: Clone + 'static,
+    MSG: 'static,
 {
     /// creates a new Cmd from a function
     pub fn new<F>(f: F) -> Self
     where
-        F: Fn(DSP) + 'static,
+        F: Fn(Rc<dyn Dispatch<MSG>>) + 'static,
     {
         Cmd(vec![Rc::new(f)])
     }
 
+    /// creates a Cmd which dispatches the msg right after the update
+    pub fn message(msg: MSG) -> Self
+    where
+        MSG: Clone,
+    {
+        Cmd::new(move |program| program.dispatch(msg.clone()))
+    }
+
+    /// creates a Cmd which focuses the widget with this id after the view is
+    /// updated, the `on_blur` and `on_focus` callbacks are triggered
+    pub fn focus<S: ToString>(id: S) -> Self {
+        let id = id.to_string();
+        Cmd::new(move |program| program.focus(&id))
+    }
+
     /// creates a unified Cmd which batches all the other Cmds in one.
     pub fn batch(cmds: Vec<Self>) -> Self {
         let mut callbacks = vec![];
@@ -37,11 +56,22 @@ where
         Cmd(vec![])
     }
 
+    /// returns true if this Cmd has no callback to be executed
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Executes the Cmd
-    pub fn emit(self, program: &DSP) {
+    pub fn emit(self, program: Rc<dyn Dispatch<MSG>>) {
         for cb in self.0 {
-            let program_clone = program.clone();
+            let program_clone = Rc::clone(&program);
             cb(program_clone);
         }
     }
 }
+
+impl<MSG> Clone for Cmd<MSG> {
+    fn clone(&self) -> Self {
+        Cmd(self.0.clone())
+    }
+}
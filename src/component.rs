@@ -1,3 +1,4 @@
+use crate::{Cmd, Shortcut, Sub};
 use sauron_widget::Node;
 
 /// Applications must implement Component
@@ -7,9 +8,32 @@ pub trait Component<MSG> {
         std::any::type_name::<Self>().to_string()
     }
 
-    /// updates the state of the Application based on the supplied msg
-    fn update(&mut self, msg: MSG);
+    /// called once when the backend has mounted the app,
+    /// the returned Cmd is executed right after.
+    fn init(&self) -> Cmd<MSG>
+    where
+        MSG: 'static,
+    {
+        Cmd::none()
+    }
+
+    /// updates the state of the Application based on the supplied msg,
+    /// the returned Cmd is executed by the backend after the view is updated
+    fn update(&mut self, msg: MSG) -> Cmd<MSG>;
 
     /// returns a Node tree for rendering the view
     fn view(&self) -> Node<MSG>;
+
+    /// returns the global keyboard shortcuts of the app, the msg of the
+    /// shortcut is dispatched when its key chord is pressed anywhere in the
+    /// app, before the accelerators of the menu items
+    fn shortcuts(&self) -> Vec<Shortcut<MSG>> {
+        vec![]
+    }
+
+    /// returns the subscriptions of the app such as the timers, these are
+    /// diffed after each update so the ones which are removed are cancelled
+    fn subscriptions(&self) -> Vec<Sub<MSG>> {
+        vec![]
+    }
 }
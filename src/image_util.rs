@@ -1,9 +1,8 @@
 use image::ImageFormat;
 
-/// get the mime type of image
+/// get the mime type of image, None if the format is empty or unsupported
 pub(crate) fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
-    let img_format =
-        image::guess_format(bytes).expect("must have an image format");
+    let img_format = image::guess_format(bytes).ok()?;
     match img_format {
         ImageFormat::Png => Some("image/png"),
         ImageFormat::Jpeg => Some("image/jpeg"),
@@ -5,15 +5,23 @@
 #![deny(clippy::all)]
 
 pub use backend::Backend;
+pub use cmd::Cmd;
 pub use component::Component;
 pub use mt_dom;
+pub use sauron_native_macro::view;
 pub use sauron_widget::{
     attribute,
     attribute::{AttribKey, Value},
-    widget, Attribute, Element, Event, Node, Patch, Widget,
+    widget, Attribute, CustomWidget, Element, Event, Node, Patch, Widget,
 };
+pub use shortcut::Shortcut;
 pub use stretch;
+pub use sub::Sub;
 
 pub mod backend;
+pub mod cmd;
 mod component;
+#[cfg(any(feature = "with-gtk", feature = "with-static-html"))]
 pub(crate) mod image_util;
+pub mod shortcut;
+pub mod sub;
@@ -0,0 +1,55 @@
+//! global keyboard shortcuts of the app, which are delivered by the backends
+//! no matter which widget has focus.
+use crate::widget::event::{KeyCode, KeyEvent, Modifier};
+use crate::Component;
+
+/// A key chord which is mapped to a msg of the app, such as ctrl+s to save.
+/// Any key can be a shortcut such as escape or delete, though a key without
+/// ctrl or alt is then taken before it is typed into the text inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortcut<MSG> {
+    /// the key and the modifiers which are pressed
+    pub key_event: KeyEvent,
+    /// the msg which is dispatched when the key chord is pressed
+    pub msg: MSG,
+}
+
+impl<MSG> Shortcut<MSG> {
+    /// creates a shortcut of the key pressed together with the modifier,
+    /// the character keys are matched regardless of their case
+    pub fn new(modifier: Modifier, key_code: KeyCode, msg: MSG) -> Self {
+        let key_code = match key_code {
+            KeyCode::Char(ch) => KeyCode::Char(ch.to_ascii_lowercase()),
+            key_code => key_code,
+        };
+        Shortcut {
+            key_event: KeyEvent { key_code, modifier },
+            msg,
+        }
+    }
+
+    /// creates a shortcut from the accelerator text such as `Ctrl+S`,
+    /// returns None if the accelerator can not be parsed
+    pub fn from_accelerator(accelerator: &str, msg: MSG) -> Option<Self> {
+        KeyEvent::from_accelerator(accelerator).map(|key_event| {
+            Shortcut::new(key_event.modifier, key_event.key_code, msg)
+        })
+    }
+}
+
+/// the msg of the shortcut of the app which is pressed with the key event,
+/// the character key is lowercased since it is uppercase when shift is held
+pub(crate) fn find_shortcut<APP, MSG>(
+    app: &APP,
+    key_event: &KeyEvent,
+) -> Option<MSG>
+where
+    APP: Component<MSG>,
+{
+    let pressed =
+        Shortcut::new(key_event.modifier, key_event.key_code.clone(), ());
+    app.shortcuts()
+        .into_iter()
+        .find(|shortcut| shortcut.key_event == pressed.key_event)
+        .map(|shortcut| shortcut.msg)
+}
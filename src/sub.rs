@@ -0,0 +1,174 @@
+//! subscriptions of the app to the events which are not triggered by the
+//! widgets, such as a timer which refreshes a dashboard every few seconds.
+use std::{any::TypeId, cell::RefCell, rc::Rc, time::Duration};
+#[cfg(any(feature = "with-titik", feature = "with-remote"))]
+use std::{cell::Cell, time::Instant};
+
+/// A subscription which dispatches its msg every interval.
+/// The subscriptions of the app are diffed after each update, the ones which
+/// are no longer returned are cancelled and the new ones are started.
+pub struct Sub<MSG> {
+    interval: Duration,
+    /// the type of the closure, which identifies the subscription together
+    /// with the interval across the updates
+    type_id: TypeId,
+    msg_fn: Rc<dyn Fn() -> MSG>,
+}
+
+impl<MSG> Sub<MSG> {
+    /// creates a subscription which dispatches the msg returned by the
+    /// function every interval
+    pub fn every<F>(interval: Duration, f: F) -> Self
+    where
+        F: Fn() -> MSG + 'static,
+    {
+        Sub {
+            interval,
+            type_id: TypeId::of::<F>(),
+            msg_fn: Rc::new(f),
+        }
+    }
+
+    /// returns the interval of this subscription
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+impl<MSG> Clone for Sub<MSG> {
+    fn clone(&self) -> Self {
+        Sub {
+            interval: self.interval,
+            type_id: self.type_id,
+            msg_fn: Rc::clone(&self.msg_fn),
+        }
+    }
+}
+
+/// the interval of the subscription, a zero interval is due every
+/// millisecond so the backends don't loop forever
+pub(crate) fn interval_of(interval: Duration) -> Duration {
+    interval.max(Duration::from_millis(1))
+}
+
+/// the function of a running subscription, it is replaced by the function of
+/// the matching subscription in each update since it could capture a
+/// different state
+pub(crate) type SubFn<MSG> = Rc<RefCell<Rc<dyn Fn() -> MSG>>>;
+
+/// returns the msg of the running subscription
+pub(crate) fn emit_sub<MSG>(msg_fn: &SubFn<MSG>) -> MSG {
+    let msg_fn = Rc::clone(&msg_fn.borrow());
+    msg_fn()
+}
+
+/// a subscription which is started in the backend, the handle is used to
+/// cancel it
+pub(crate) struct RunningSub<MSG, H> {
+    pub(crate) interval: Duration,
+    type_id: TypeId,
+    pub(crate) msg_fn: SubFn<MSG>,
+    pub(crate) handle: H,
+}
+
+/// the running subscriptions of a backend
+pub(crate) struct Subscriptions<MSG, H> {
+    running: Vec<RunningSub<MSG, H>>,
+}
+
+impl<MSG, H> Subscriptions<MSG, H> {
+    pub(crate) fn new() -> Self {
+        Subscriptions { running: vec![] }
+    }
+
+    /// diff the subscriptions of the app with the running ones, the
+    /// subscriptions with the same interval and closure are kept running,
+    /// the new ones are started with `start` and the ones which are no longer
+    /// there are cancelled with `cancel`
+    pub(crate) fn update<S, C>(
+        &mut self,
+        subs: Vec<Sub<MSG>>,
+        mut start: S,
+        mut cancel: C,
+    ) where
+        S: FnMut(Duration, SubFn<MSG>) -> H,
+        C: FnMut(H),
+    {
+        let mut previous: Vec<Option<RunningSub<MSG, H>>> =
+            self.running.drain(..).map(Some).collect();
+        for sub in subs {
+            let matched = previous
+                .iter_mut()
+                .find(|running| {
+                    running.as_ref().is_some_and(|running| {
+                        running.interval == sub.interval
+                            && running.type_id == sub.type_id
+                    })
+                })
+                .and_then(Option::take);
+            let running = match matched {
+                Some(running) => {
+                    *running.msg_fn.borrow_mut() = sub.msg_fn;
+                    running
+                }
+                None => {
+                    let msg_fn = Rc::new(RefCell::new(sub.msg_fn));
+                    let handle = start(sub.interval, Rc::clone(&msg_fn));
+                    RunningSub {
+                        interval: sub.interval,
+                        type_id: sub.type_id,
+                        msg_fn,
+                        handle,
+                    }
+                }
+            };
+            self.running.push(running);
+        }
+        for running in previous.into_iter().flatten() {
+            cancel(running.handle);
+        }
+    }
+
+    /// returns the running subscriptions
+    pub(crate) fn running(&self) -> &[RunningSub<MSG, H>] {
+        &self.running
+    }
+}
+
+/// the subscriptions of the backends which wait for the time when they are
+/// due in their own event loop
+#[cfg(any(feature = "with-titik", feature = "with-remote"))]
+impl<MSG> Subscriptions<MSG, Cell<Instant>> {
+    /// diff the subscriptions of the app, the new subscriptions are due
+    /// after their interval
+    pub(crate) fn update_due(&mut self, subs: Vec<Sub<MSG>>) {
+        self.update(
+            subs,
+            |interval, _msg_fn| {
+                Cell::new(Instant::now() + interval_of(interval))
+            },
+            |_due| (),
+        );
+    }
+
+    /// returns the time when the next subscription is due
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        self.running
+            .iter()
+            .map(|running| running.handle.get())
+            .min()
+    }
+
+    /// returns the functions of the subscriptions which are due at this
+    /// time, these are then due again after their interval
+    pub(crate) fn take_due(&self, now: Instant) -> Vec<SubFn<MSG>> {
+        self.running
+            .iter()
+            .filter(|running| running.handle.get() <= now)
+            .map(|running| {
+                running.handle.set(now + interval_of(running.interval));
+                Rc::clone(&running.msg_fn)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,192 @@
+//! tests the mouse events of the canvas which are sent back to the app with
+//! the location local to the canvas, and the commands which are drawn from
+//! the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, draw::DrawCommand, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+/// the dots are painted where the mouse is pressed and dragged
+struct App {
+    dots: Vec<(i32, i32)>,
+    clicked: Option<(i32, i32)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Paint(i32, i32),
+    Click(i32, i32),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            dots: vec![],
+            clicked: None,
+        }
+    }
+
+    fn commands(&self) -> Vec<DrawCommand> {
+        self.dots
+            .iter()
+            .flat_map(|(x, y)| {
+                DrawCommand::fill_rect(*x as f64, *y as f64, 5.0, 5.0)
+            })
+            .collect()
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Paint(x, y) => self.dots.push((x, y)),
+            Msg::Click(x, y) => self.clicked = Some((x, y)),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                text_label(vec![value("paint")]),
+                canvas(vec![
+                    id("paper"),
+                    width(100.0),
+                    height(40.0),
+                    draw(self.commands()),
+                    on_mousedown(|event| Msg::Paint(event.x(), event.y())),
+                    on_mousemove(|event| Msg::Paint(event.x(), event.y())),
+                    on_click(|event| Msg::Click(event.x(), event.y())),
+                ]),
+            ],
+        )
+    }
+}
+
+fn paper() -> Selector {
+    Selector::Id("paper".to_string())
+}
+
+#[test]
+fn pressed_location_is_drawn() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &paper(),
+        AttribKey::MouseDown,
+        MouseEvent::pressed(15, 25)
+    ));
+    backend.fire(
+        &paper(),
+        AttribKey::MouseMove,
+        MouseEvent::mousemove(20, 25),
+    );
+    assert_eq!(backend.app().dots, [(15, 25), (20, 25)]);
+    assert_eq!(
+        backend.get_value(&paper(), AttribKey::Draw),
+        Some(Value::Draw(backend.app().commands()))
+    );
+}
+
+#[test]
+fn clicked_location_is_sent() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &paper(),
+        AttribKey::ClickEvent,
+        MouseEvent::click(7, 9)
+    ));
+    assert_eq!(backend.app().clicked, Some((7, 9)));
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_canvas {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{KeyModifiers, MouseButton, MouseEvent},
+        Event,
+    };
+
+    /// the node_idx of the canvas in the view
+    const CANVAS: usize = 2;
+
+    /// send the event to the canvas, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(CANVAS, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    /// the location of the canvas in the screen
+    fn origin(screen: &TitikScreen<Msg>) -> (u16, u16) {
+        let layout = screen.layout(CANVAS).expect("must have a layout");
+        (layout.location.x as u16, layout.location.y as u16)
+    }
+
+    /// the character in the canvas at this cell
+    fn char_at(screen: &TitikScreen<Msg>, column: u16, row: u16) -> char {
+        let (left, top) = origin(screen);
+        screen.lines()[(top + row) as usize]
+            .chars()
+            .nth((left + column) as usize)
+            .expect("must have the cell")
+    }
+
+    #[test]
+    fn mouse_events_have_the_location_in_the_canvas() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let (left, top) = origin(&screen);
+        // the canvas is below the label, the location is local to it
+        assert!(top > 0);
+        let (x, y) = (left + 3, top + 1);
+        let button = MouseButton::Left;
+        let mods = KeyModifiers::empty();
+        assert_eq!(
+            send(
+                &mut app,
+                &mut screen,
+                Event::Mouse(MouseEvent::Down(button, x, y, mods))
+            ),
+            [Msg::Paint(35, 30)]
+        );
+        assert_eq!(
+            send(
+                &mut app,
+                &mut screen,
+                Event::Mouse(MouseEvent::Up(button, x, y, mods))
+            ),
+            [Msg::Click(35, 30)]
+        );
+        assert_eq!(
+            send(
+                &mut app,
+                &mut screen,
+                Event::Mouse(MouseEvent::Drag(button, x + 1, y, mods))
+            ),
+            [Msg::Paint(45, 30)]
+        );
+    }
+
+    #[test]
+    fn commands_are_drawn_with_braille_dots() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let blank = '\u{2800}';
+        assert_eq!(char_at(&screen, 3, 1), blank);
+        app.update(Msg::Paint(35, 30));
+        screen.patch(app.view());
+        // the dot in the second column and third row of the braille cell
+        assert_eq!(char_at(&screen, 3, 1), '⠠');
+        assert_eq!(char_at(&screen, 4, 1), blank);
+    }
+}
@@ -0,0 +1,84 @@
+//! tests the conversion of the custom widgets with the static html renderer
+#![cfg(feature = "with-static-html")]
+use sauron_native::{
+    backend::{
+        render_to_html_string,
+        web_ui::{register_custom_widget, WebCustomWidget},
+    },
+    widget::{attribute::*, *},
+    Attribute, Node,
+};
+use std::any::Any;
+
+#[derive(Debug, Clone)]
+enum Msg {}
+
+#[derive(Debug)]
+struct Gauge {
+    percent: u32,
+}
+
+impl CustomWidget for Gauge {
+    fn name(&self) -> &str {
+        "Gauge"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// a custom widget which has no conversion registered
+#[derive(Debug)]
+struct TagEditor;
+
+impl CustomWidget for TagEditor {
+    fn name(&self) -> &str {
+        "TagEditor"
+    }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct GaugeHtml;
+
+impl WebCustomWidget<Msg> for GaugeHtml {
+    fn to_html(
+        &self,
+        custom_widget: &dyn CustomWidget,
+        _attrs: &[Attribute<Msg>],
+        _children: Vec<sauron::Node<Msg>>,
+    ) -> sauron::Node<Msg> {
+        let gauge: &Gauge = custom_widget
+            .as_any()
+            .downcast_ref()
+            .expect("must be a gauge");
+        sauron::html::meter(
+            vec![sauron::html::attributes::value(gauge.percent)],
+            vec![],
+        )
+    }
+}
+
+#[test]
+fn registered_custom_widget_is_converted_by_its_hook() {
+    register_custom_widget("Gauge", GaugeHtml);
+    let view: Node<Msg> = custom(Gauge { percent: 42 }, vec![], vec![]);
+    let html = render_to_html_string(&view);
+    assert_eq!(html, r#"<meter value="42" class="Gauge"></meter>"#);
+}
+
+#[test]
+fn unregistered_custom_widget_renders_the_placeholder() {
+    let view: Node<Msg> =
+        custom(TagEditor, vec![], vec![text_label(vec![value("rust")])]);
+    let html = render_to_html_string(&view);
+    assert!(html.starts_with(r#"<div class="TagEditor">"#));
+    assert!(html.contains("rust</label>"));
+}
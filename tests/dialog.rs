@@ -0,0 +1,184 @@
+//! tests the response of the dialog which is sent back to the app, and the
+//! dialog which is shown while it is in the view of the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node,
+};
+
+struct App {
+    is_confirming: bool,
+    is_deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    AskDelete,
+    Respond(String),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            is_confirming: false,
+            is_deleted: false,
+        }
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::AskDelete => self.is_confirming = true,
+            // the dialog is closed with any of the responses
+            Msg::Respond(response) => {
+                self.is_confirming = false;
+                self.is_deleted = response == DIALOG_OK;
+            }
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        let mut children =
+            vec![button(vec![label("Delete"), on_click(|_| Msg::AskDelete)])];
+        if self.is_confirming {
+            children.push(confirm(vec![
+                id("confirm"),
+                label("Delete file"),
+                value("Delete notes.md?"),
+                on_response(|input| Msg::Respond(input.value.to_string())),
+            ]));
+        }
+        column(vec![], children)
+    }
+}
+
+fn confirm_dialog() -> Selector {
+    Selector::Id("confirm".to_string())
+}
+
+fn button_of(label: &str) -> Selector {
+    Selector::Label(label.to_string())
+}
+
+#[test]
+fn dialog_is_shown_while_it_is_in_the_view() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(!backend.exists(&confirm_dialog()));
+    assert!(backend.click(&button_of("Delete")));
+    assert!(backend.exists(&confirm_dialog()));
+    assert!(backend.exists(&button_of(DIALOG_CANCEL)));
+}
+
+#[test]
+fn clicked_button_is_the_response() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.click(&button_of("Delete"));
+    assert!(backend.click(&button_of(DIALOG_OK)));
+    assert!(backend.app().is_deleted);
+    assert!(!backend.exists(&confirm_dialog()));
+}
+
+#[test]
+fn dismissed_dialog_has_an_empty_response() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.click(&button_of("Delete"));
+    assert!(backend.fire(
+        &confirm_dialog(),
+        AttribKey::ResponseEvent,
+        InputEvent::new("")
+    ));
+    assert!(!backend.app().is_deleted);
+    assert!(!backend.exists(&confirm_dialog()));
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_dialog {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the widgets in the view, the dialog has the message
+    /// and the row of the buttons
+    const DELETE: usize = 1;
+    const DIALOG: usize = 2;
+    const OK: usize = 5;
+
+    fn click() -> Event {
+        Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            0,
+            0,
+            KeyModifiers::empty(),
+        ))
+    }
+
+    /// send the event to the widget, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        node_idx: usize,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(node_idx, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    fn is_shown(screen: &TitikScreen<Msg>, text: &str) -> bool {
+        screen.lines().iter().any(|line| line.contains(text))
+    }
+
+    #[test]
+    fn dialog_is_shown_in_the_center() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 80, 24);
+        assert_eq!(
+            send(&mut app, &mut screen, DELETE, click()),
+            [Msg::AskDelete]
+        );
+        assert!(is_shown(&screen, "Delete file"));
+        assert!(is_shown(&screen, "Delete notes.md?"));
+        let layout = screen.layout(DIALOG).expect("must have a layout");
+        let center_x = layout.location.x + layout.size.width / 2.0;
+        let center_y = layout.location.y + layout.size.height / 2.0;
+        assert!((center_x - 40.0).abs() <= 1.0);
+        assert!((center_y - 12.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn clicked_button_closes_the_dialog() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 80, 24);
+        send(&mut app, &mut screen, DELETE, click());
+        assert_eq!(
+            send(&mut app, &mut screen, OK, click()),
+            [Msg::Respond(DIALOG_OK.to_string())]
+        );
+        assert!(app.is_deleted);
+        assert!(!is_shown(&screen, "Delete notes.md?"));
+    }
+
+    #[test]
+    fn escape_dismisses_the_dialog() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 80, 24);
+        send(&mut app, &mut screen, DELETE, click());
+        let escape = Event::Key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(
+            send(&mut app, &mut screen, DIALOG, escape),
+            [Msg::Respond(String::new())]
+        );
+        assert!(!app.is_deleted);
+        assert!(!is_shown(&screen, "Delete notes.md?"));
+    }
+}
@@ -0,0 +1,241 @@
+//! tests the change events of the dropdown and the list box which are sent
+//! back to the app, and the options which are shown as selected from the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{
+        attribute::{util::selected_values, *},
+        event::*,
+        *,
+    },
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    size: String,
+    toppings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    ChangeSize(String),
+    ChangeToppings(Vec<String>),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            size: "small".to_string(),
+            toppings: vec!["ham".to_string()],
+        }
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::ChangeSize(size) => self.size = size,
+            Msg::ChangeToppings(toppings) => self.toppings = toppings,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                dropdown(vec![
+                    id("size"),
+                    options(vec!["small", "medium", "large"]),
+                    value(self.size.clone()),
+                    on_change(|input| Msg::ChangeSize(input.value.to_string())),
+                ]),
+                list_box(vec![
+                    id("toppings"),
+                    options(vec!["cheese", "ham", "olives"]),
+                    multiple(true),
+                    height(3.0),
+                    value(self.toppings.clone()),
+                    on_change(|input| {
+                        Msg::ChangeToppings(selected_values(&input.value))
+                    }),
+                ]),
+            ],
+        )
+    }
+}
+
+fn size() -> Selector {
+    Selector::Id("size".to_string())
+}
+
+fn toppings() -> Selector {
+    Selector::Id("toppings".to_string())
+}
+
+/// the options which are shown as selected in the widget
+fn selected(
+    backend: &HeadlessBackend<App, Msg>,
+    selector: &Selector,
+) -> Vec<String> {
+    backend
+        .get_value(selector, AttribKey::Value)
+        .map(|v| selected_values(&v))
+        .unwrap_or_default()
+}
+
+#[test]
+fn picked_option_is_the_value_of_the_dropdown() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &size(),
+        AttribKey::ChangeEvent,
+        InputEvent::new("large")
+    ));
+    assert_eq!(backend.app().size, "large");
+    assert_eq!(selected(&backend, &size()), ["large"]);
+}
+
+#[test]
+fn checked_options_are_the_value_of_the_list_box() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &toppings(),
+        AttribKey::ChangeEvent,
+        InputEvent::new(vec!["cheese", "olives"])
+    ));
+    assert_eq!(backend.app().toppings, ["cheese", "olives"]);
+    assert_eq!(selected(&backend, &toppings()), ["cheese", "olives"]);
+}
+
+#[test]
+fn options_are_set() {
+    let backend = HeadlessBackend::new(App::new());
+    assert_eq!(
+        backend.get_value(&size(), AttribKey::Options),
+        Some(Value::from(vec!["small", "medium", "large"]))
+    );
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_dropdown {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the dropdown and the list box in the view
+    const DROPDOWN: usize = 1;
+    const LIST_BOX: usize = 2;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    fn click(x: u16, y: u16) -> Event {
+        Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            x,
+            y,
+            KeyModifiers::empty(),
+        ))
+    }
+
+    /// send the event to the widget, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        node_idx: usize,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(node_idx, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    fn is_shown(screen: &TitikScreen<Msg>, text: &str) -> bool {
+        screen.lines().iter().any(|line| line.contains(text))
+    }
+
+    #[test]
+    fn option_is_picked_from_the_open_list() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert!(!is_shown(&screen, "large"));
+        assert_eq!(
+            send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Enter)),
+            []
+        );
+        assert!(is_shown(&screen, "> small"));
+        assert!(is_shown(&screen, "large"));
+        send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Down));
+        send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Down));
+        assert_eq!(
+            send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Enter)),
+            [Msg::ChangeSize("large".to_string())]
+        );
+        assert_eq!(screen.lines()[0], "[large ▾]");
+        assert!(!is_shown(&screen, "small"));
+    }
+
+    #[test]
+    fn closed_dropdown_picks_the_next_option_with_the_keys() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(
+            send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Down)),
+            [Msg::ChangeSize("medium".to_string())]
+        );
+        assert_eq!(screen.lines()[0], "[medium ▾]");
+        // the first option is already picked
+        send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Up));
+        assert_eq!(send(&mut app, &mut screen, DROPDOWN, key(KeyCode::Up)), []);
+    }
+
+    #[test]
+    fn clicked_option_is_picked() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(send(&mut app, &mut screen, DROPDOWN, click(1, 0)), []);
+        assert_eq!(
+            send(&mut app, &mut screen, DROPDOWN, click(2, 2)),
+            [Msg::ChangeSize("medium".to_string())]
+        );
+        assert_eq!(app.size, "medium");
+    }
+
+    #[test]
+    fn options_are_checked_in_the_list_box() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert!(is_shown(&screen, "[x] ham"));
+        assert_eq!(
+            send(&mut app, &mut screen, LIST_BOX, key(KeyCode::Down)),
+            []
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, LIST_BOX, key(KeyCode::Char(' '))),
+            [Msg::ChangeToppings(vec![
+                "cheese".to_string(),
+                "ham".to_string()
+            ])]
+        );
+        assert!(is_shown(&screen, "[x] cheese"));
+        let top = screen
+            .layout(LIST_BOX)
+            .expect("must have a layout")
+            .location
+            .y;
+        assert_eq!(
+            send(&mut app, &mut screen, LIST_BOX, click(4, top as u16 + 1)),
+            [Msg::ChangeToppings(vec!["cheese".to_string()])]
+        );
+        assert!(is_shown(&screen, "[ ] ham"));
+    }
+}
@@ -0,0 +1,111 @@
+//! tests the routing of the native events into the callbacks of the widgets
+use sauron_native::{
+    backend::event_router::{EventRouter, NativeEvent},
+    widget::{attribute::*, event::*},
+    AttribKey, Attribute, Event,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Click,
+    Input(String),
+    Toggle(bool),
+}
+
+fn button_attrs() -> Vec<Attribute<Msg>> {
+    vec![label("+"), on_click(|_| Msg::Click)]
+}
+
+fn input_attrs() -> Vec<Attribute<Msg>> {
+    vec![
+        value("hello"),
+        on_input(|input| Msg::Input(input.value.to_string())),
+    ]
+}
+
+fn checkbox_attrs() -> Vec<Attribute<Msg>> {
+    vec![on_input(|input| Msg::Toggle(input.value.as_bool()))]
+}
+
+#[test]
+fn native_events_are_converted() {
+    let (key, event) = NativeEvent::Click.to_event();
+    assert_eq!(key, AttribKey::ClickEvent);
+    assert!(matches!(event, Event::MouseEvent(_)));
+
+    let (key, event) = NativeEvent::TextChanged("hi".to_string()).to_event();
+    assert_eq!(key, AttribKey::InputEvent);
+    match event {
+        Event::InputEvent(input) => assert_eq!(input.value.to_string(), "hi"),
+        event => panic!("expecting an input event: {:?}", event),
+    }
+
+    let (key, event) = NativeEvent::Toggled(true).to_event();
+    assert_eq!(key, AttribKey::InputEvent);
+    match event {
+        Event::InputEvent(input) => assert!(input.value.as_bool()),
+        event => panic!("expecting an input event: {:?}", event),
+    }
+}
+
+#[test]
+fn events_are_routed_to_the_callbacks_of_the_control() {
+    let mut router = EventRouter::new();
+    router.register(1, &button_attrs());
+    router.register(2, &input_attrs());
+    router.register(3, &checkbox_attrs());
+
+    assert_eq!(router.route(&1, &NativeEvent::Click), vec![Msg::Click]);
+    assert_eq!(
+        router.route(&2, &NativeEvent::TextChanged("world".to_string())),
+        vec![Msg::Input("world".to_string())]
+    );
+    assert_eq!(
+        router.route(&3, &NativeEvent::Toggled(true)),
+        vec![Msg::Toggle(true)]
+    );
+}
+
+#[test]
+fn events_without_matching_callbacks_emit_nothing() {
+    let mut router = EventRouter::new();
+    router.register(1, &button_attrs());
+    // a button has no input callback
+    assert!(router
+        .route(&1, &NativeEvent::TextChanged("x".to_string()))
+        .is_empty());
+    // there is no control with this id
+    assert!(router.route(&9, &NativeEvent::Click).is_empty());
+}
+
+#[test]
+fn controls_without_callbacks_are_not_registered() {
+    let mut router: EventRouter<u32, Msg> = EventRouter::new();
+    router.register(1, &[label("text")]);
+    assert!(!router.is_registered(&1));
+    router.register(2, &button_attrs());
+    assert!(router.is_registered(&2));
+}
+
+#[test]
+fn registering_again_replaces_the_callbacks() {
+    let mut router = EventRouter::new();
+    router.register(1, &button_attrs());
+    router.register(1, &checkbox_attrs());
+    assert!(router.route(&1, &NativeEvent::Click).is_empty());
+    assert_eq!(
+        router.route(&1, &NativeEvent::Toggled(false)),
+        vec![Msg::Toggle(false)]
+    );
+}
+
+#[test]
+fn clear_removes_all_the_callbacks() {
+    let mut router = EventRouter::new();
+    router.register(1, &button_attrs());
+    router.register(2, &input_attrs());
+    router.clear();
+    assert!(!router.is_registered(&1));
+    assert!(!router.is_registered(&2));
+    assert!(router.route(&1, &NativeEvent::Click).is_empty());
+}
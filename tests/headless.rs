@@ -0,0 +1,209 @@
+//! tests the behaviour of a component in the headless backend
+use sauron_native::{
+    backend::{headless_ui::Selector, Dispatch, HeadlessBackend},
+    mt_dom,
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Patch, Sub,
+};
+use std::time::Duration;
+
+struct App {
+    count: i32,
+    text: String,
+    items: Vec<String>,
+    ticks: u32,
+    is_ticking: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Msg {
+    Increment,
+    ChangeText(String),
+    AddItem,
+    ClearText,
+    Tick,
+    StopTicking,
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            count: 0,
+            text: String::new(),
+            items: vec![],
+            ticks: 0,
+            is_ticking: true,
+        }
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+            Msg::ChangeText(text) => self.text = text,
+            Msg::AddItem => {
+                self.items.push(self.text.clone());
+                return Cmd::message(Msg::ClearText);
+            }
+            Msg::ClearText => self.text = String::new(),
+            Msg::Tick => self.ticks += 1,
+            Msg::StopTicking => self.is_ticking = false,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                text_label(vec![id("count"), value(self.count.to_string())]),
+                button(vec![
+                    id("increment"),
+                    label("+"),
+                    on_click(|_| Msg::Increment),
+                ]),
+                text_input(vec![
+                    id("text"),
+                    value(self.text.clone()),
+                    autofocus(true),
+                    on_input(|input| Msg::ChangeText(input.value.to_string())),
+                ]),
+                button(vec![
+                    id("add"),
+                    label("Add"),
+                    on_click(|_| Msg::AddItem),
+                ]),
+                column(
+                    vec![id("items")],
+                    self.items
+                        .iter()
+                        .map(|item| mt_dom::text(item.clone()))
+                        .collect(),
+                ),
+            ],
+        )
+    }
+
+    fn subscriptions(&self) -> Vec<Sub<Msg>> {
+        if self.is_ticking {
+            vec![Sub::every(Duration::from_millis(100), || Msg::Tick)]
+        } else {
+            vec![]
+        }
+    }
+}
+
+fn id_of(id: &str) -> Selector {
+    Selector::Id(id.to_string())
+}
+
+/// the value of the widget as string, since a `Value::Str` is not equal
+/// to a `Value::String` of the same text
+fn value_of(backend: &HeadlessBackend<App, Msg>, id: &str) -> Option<String> {
+    backend
+        .get_value(&id_of(id), AttribKey::Value)
+        .map(|v| v.to_string())
+}
+
+#[test]
+fn click_updates_the_label() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.click(&id_of("increment")));
+    assert!(backend.click(&id_of("increment")));
+    assert_eq!(backend.app().count, 2);
+    assert_eq!(value_of(&backend, "count"), Some("2".to_string()));
+}
+
+#[test]
+fn clicking_a_widget_without_callbacks_is_not_fired() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(!backend.click(&id_of("count")));
+    assert!(!backend.click(&id_of("missing")));
+}
+
+#[test]
+fn the_cmd_of_the_update_is_executed() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.input(&id_of("text"), "buy milk"));
+    assert_eq!(value_of(&backend, "text"), Some("buy milk".to_string()));
+    assert!(backend.click(&id_of("add")));
+    assert_eq!(backend.app().items, vec!["buy milk".to_string()]);
+    // the text is cleared by the msg of the Cmd
+    assert_eq!(value_of(&backend, "text"), Some("".to_string()));
+}
+
+#[test]
+fn the_text_nodes_are_labels() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.input(&id_of("text"), "first");
+    backend.click(&id_of("add"));
+    backend.input(&id_of("text"), "second");
+    backend.click(&id_of("add"));
+    let root_widget = backend.root_widget();
+    let items = root_widget.find(&id_of("items")).expect("must have items");
+    let labels: Vec<Option<String>> = items
+        .get_children()
+        .iter()
+        .map(|child| {
+            assert_eq!(*child.widget(), Widget::Label);
+            child.get_value(AttribKey::Value).map(|v| v.to_string())
+        })
+        .collect();
+    assert_eq!(
+        labels,
+        vec![Some("first".to_string()), Some("second".to_string())]
+    );
+}
+
+#[test]
+fn the_patches_of_the_update() {
+    let mut app = App::new();
+    let old_view = app.view();
+    app.update(Msg::Increment);
+    let new_view = app.view();
+    let patches = mt_dom::diff_with_key(&old_view, &new_view, &AttribKey::Key);
+    assert_eq!(patches.len(), 1);
+    match &patches[0] {
+        Patch::AddAttributes(add) => {
+            assert_eq!(add.node_idx, 1);
+            assert_eq!(add.attrs.len(), 1);
+            assert_eq!(*add.attrs[0].name(), AttribKey::Value);
+        }
+        patch => panic!("expecting the value to be changed: {:?}", patch),
+    }
+
+    app.text = "item".to_string();
+    let old_view = app.view();
+    app.update(Msg::AddItem);
+    let new_view = app.view();
+    let patches = mt_dom::diff_with_key(&old_view, &new_view, &AttribKey::Key);
+    assert!(patches.iter().any(|patch| match patch {
+        Patch::AppendChildren(append) => append.children.len() == 1,
+        _ => false,
+    }));
+}
+
+#[test]
+fn autofocus_and_tab() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.is_focused(&id_of("text")));
+    assert!(backend.tab());
+    assert!(backend.is_focused(&id_of("add")));
+    assert!(backend.back_tab());
+    assert!(backend.is_focused(&id_of("text")));
+    assert!(backend.focus(&id_of("increment")));
+    assert!(!backend.is_focused(&id_of("text")));
+}
+
+#[test]
+fn subscriptions_are_ticked_until_removed() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.advance(Duration::from_millis(350));
+    assert_eq!(backend.app().ticks, 3);
+    backend.advance(Duration::from_millis(50));
+    assert_eq!(backend.app().ticks, 4);
+    backend.dispatch(Msg::StopTicking);
+    backend.advance(Duration::from_millis(1000));
+    assert_eq!(backend.app().ticks, 4);
+}
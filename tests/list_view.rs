@@ -0,0 +1,200 @@
+//! tests the scroll events of the list view which are sent back to the app,
+//! and the rows which are built at the scroll offset of the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+use std::ops::Range;
+
+struct App {
+    scroll_offset: f64,
+    visible: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Scroll(f64),
+    Visible(Range<usize>),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            scroll_offset: 0.0,
+            visible: 0..0,
+        }
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Scroll(scroll_offset) => self.scroll_offset = scroll_offset,
+            Msg::Visible(visible) => self.visible = visible,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![list_view(
+                vec![
+                    id("logs"),
+                    item_count(1000),
+                    row_height(1.0),
+                    height(5.0),
+                    overscan(2),
+                    scroll_offset(self.scroll_offset),
+                    on_scroll(|input| {
+                        Msg::Scroll(input.value.as_f64().expect("must be f64"))
+                    }),
+                    on_visible_range(Msg::Visible),
+                ],
+                |index| text_label(vec![value(format!("line {}", index))]),
+            )],
+        )
+    }
+}
+
+fn logs() -> Selector {
+    Selector::Id("logs".to_string())
+}
+
+/// the index of the items of the rows which are built
+fn built_rows(backend: &HeadlessBackend<App, Msg>) -> Vec<String> {
+    let root_widget = backend.root_widget();
+    root_widget
+        .find(&logs())
+        .expect("must have the list view")
+        .get_children()
+        .iter()
+        .map(|row| {
+            row.get_value(AttribKey::Key)
+                .map(|v| v.to_string())
+                .expect("must have a key")
+        })
+        .collect()
+}
+
+#[test]
+fn only_the_visible_rows_and_the_overscan_are_built() {
+    let backend = HeadlessBackend::new(App::new());
+    assert_eq!(built_rows(&backend), ["0", "1", "2", "3", "4", "5", "6"]);
+}
+
+#[test]
+fn rows_are_built_at_the_scroll_offset() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &logs(),
+        AttribKey::ScrollEvent,
+        InputEvent::new(500.0)
+    ));
+    assert_eq!(backend.app().scroll_offset, 500.0);
+    assert_eq!(
+        backend.get_value(&logs(), AttribKey::ScrollOffset),
+        Some(Value::from(500.0))
+    );
+    let rows: Vec<String> = (498..507).map(|i| i.to_string()).collect();
+    assert_eq!(built_rows(&backend), rows);
+}
+
+#[test]
+fn visible_range_is_sent_as_a_range() {
+    let backend = HeadlessBackend::new(App::new());
+    let range = vec![Value::from(10usize), Value::from(15usize)];
+    backend.fire(
+        &logs(),
+        AttribKey::VisibleRangeEvent,
+        InputEvent::new(range),
+    );
+    assert_eq!(backend.app().visible, 10..15);
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_list_view {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+        Event,
+    };
+
+    /// the node_idx of the list view in the view
+    const LIST_VIEW: usize = 1;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    /// send the event to the list view, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(LIST_VIEW, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    /// the lines of the rows which are shown
+    fn shown_lines(screen: &TitikScreen<Msg>) -> Vec<String> {
+        screen
+            .lines()
+            .into_iter()
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn page_down_scrolls_and_shows_the_next_rows() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(
+            shown_lines(&screen),
+            ["line 0", "line 1", "line 2", "line 3", "line 4"]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::PageDown)),
+            [Msg::Scroll(4.0), Msg::Visible(4..9)]
+        );
+        assert_eq!(
+            shown_lines(&screen),
+            ["line 4", "line 5", "line 6", "line 7", "line 8"]
+        );
+    }
+
+    #[test]
+    fn end_scrolls_to_the_last_rows() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        send(&mut app, &mut screen, key(KeyCode::End));
+        assert_eq!(app.scroll_offset, 995.0);
+        assert_eq!(app.visible, 995..1000);
+        assert_eq!(
+            shown_lines(&screen).last().map(|l| l.as_str()),
+            Some("line 999")
+        );
+        // scrolling past the end emits nothing
+        assert_eq!(send(&mut app, &mut screen, key(KeyCode::Down)), []);
+    }
+
+    #[test]
+    fn mouse_wheel_scrolls_by_a_row() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let wheel =
+            Event::Mouse(MouseEvent::ScrollDown(0, 0, KeyModifiers::empty()));
+        assert_eq!(
+            send(&mut app, &mut screen, wheel),
+            [Msg::Scroll(1.0), Msg::Visible(1..6)]
+        );
+        assert_eq!(shown_lines(&screen)[0], "line 1");
+    }
+}
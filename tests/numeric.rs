@@ -0,0 +1,211 @@
+//! tests the input events of the spin button and the slider which are sent
+//! back to the app, and the number which is shown from the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    copies: f64,
+    volume: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    ChangeCopies(f64),
+    ChangeVolume(f64),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            copies: 0.0,
+            volume: 0.0,
+        }
+    }
+}
+
+/// the number in the value of the input event
+fn number(input: InputEvent) -> f64 {
+    input.value.as_f64().expect("must be f64")
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::ChangeCopies(copies) => self.copies = copies,
+            Msg::ChangeVolume(volume) => self.volume = volume,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                spin_button(vec![
+                    id("copies"),
+                    min(0.0),
+                    max(10.0),
+                    step(2.0),
+                    value(self.copies),
+                    on_input(|input| Msg::ChangeCopies(number(input))),
+                ]),
+                slider(vec![
+                    id("volume"),
+                    min(0.0),
+                    max(100.0),
+                    step(5.0),
+                    value(self.volume),
+                    on_input(|input| Msg::ChangeVolume(number(input))),
+                ]),
+            ],
+        )
+    }
+}
+
+fn copies() -> Selector {
+    Selector::Id("copies".to_string())
+}
+
+fn volume() -> Selector {
+    Selector::Id("volume".to_string())
+}
+
+#[test]
+fn spin_button_number_is_set() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &copies(),
+        AttribKey::InputEvent,
+        InputEvent::new(6.0)
+    ));
+    assert_eq!(backend.app().copies, 6.0);
+    assert_eq!(
+        backend.get_value(&copies(), AttribKey::Value),
+        Some(Value::F64(6.0))
+    );
+}
+
+#[test]
+fn slider_number_is_set() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &volume(),
+        AttribKey::InputEvent,
+        InputEvent::new(35.0)
+    ));
+    assert_eq!(backend.app().volume, 35.0);
+    assert_eq!(
+        backend.get_value(&volume(), AttribKey::Value),
+        Some(Value::F64(35.0))
+    );
+    assert_eq!(
+        backend.get_value(&volume(), AttribKey::Max),
+        Some(Value::F64(100.0))
+    );
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_numeric {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the spin button and the slider in the view
+    const SPIN_BUTTON: usize = 1;
+    const SLIDER: usize = 2;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    /// send the event to the widget, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        node_idx: usize,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(node_idx, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    #[test]
+    fn spin_button_is_adjusted_by_the_step_within_the_range() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(screen.lines()[0], "[-] 0 [+]");
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, key(KeyCode::Up)),
+            [Msg::ChangeCopies(2.0)]
+        );
+        assert_eq!(screen.lines()[0], "[-] 2 [+]");
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, key(KeyCode::PageUp)),
+            [Msg::ChangeCopies(10.0)]
+        );
+        // the highest number is already set
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, key(KeyCode::Up)),
+            []
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, key(KeyCode::Home)),
+            [Msg::ChangeCopies(0.0)]
+        );
+    }
+
+    #[test]
+    fn spin_button_is_adjusted_by_clicking_the_ends() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let click = |x: u16| {
+            Event::Mouse(MouseEvent::Down(
+                MouseButton::Left,
+                x,
+                0,
+                KeyModifiers::empty(),
+            ))
+        };
+        let plus = screen.lines()[0].find("[+]").expect("must show the +");
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, click(plus as u16 + 1)),
+            [Msg::ChangeCopies(2.0)]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, SPIN_BUTTON, click(1)),
+            [Msg::ChangeCopies(0.0)]
+        );
+    }
+
+    #[test]
+    fn slider_is_shown_as_a_gauge() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(
+            send(&mut app, &mut screen, SLIDER, key(KeyCode::PageUp)),
+            [Msg::ChangeVolume(50.0)]
+        );
+        assert_eq!(screen.lines()[1], "[-] [##########----------] 50 [+]");
+        assert_eq!(
+            send(&mut app, &mut screen, SLIDER, key(KeyCode::Left)),
+            [Msg::ChangeVolume(45.0)]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, SLIDER, key(KeyCode::End)),
+            [Msg::ChangeVolume(100.0)]
+        );
+        assert_eq!(screen.lines()[1], "[-] [####################] 100 [+]");
+    }
+}
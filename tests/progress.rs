@@ -0,0 +1,162 @@
+//! tests the progress bar and the spinner which are shown from the progress
+//! of the app, and updated when the progress changes
+use sauron_native::{
+    backend::{headless_ui::Selector, Dispatch, HeadlessBackend},
+    widget::{attribute::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    /// the progress is not known until the size of the files is counted
+    fraction: Option<f64>,
+    is_copying: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Progress(f64),
+    Done,
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            fraction: None,
+            is_copying: true,
+        }
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Progress(fraction) => self.fraction = Some(fraction),
+            Msg::Done => self.is_copying = false,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                progress_bar(vec![
+                    id("copy"),
+                    label("copying"),
+                    indeterminate(self.fraction.is_none()),
+                    fraction(self.fraction.unwrap_or(0.0)),
+                ]),
+                spinner(vec![id("busy"), active(self.is_copying)]),
+            ],
+        )
+    }
+}
+
+fn copy() -> Selector {
+    Selector::Id("copy".to_string())
+}
+
+fn busy() -> Selector {
+    Selector::Id("busy".to_string())
+}
+
+#[test]
+fn progress_bar_shows_the_fraction_when_it_is_known() {
+    let backend = HeadlessBackend::new(App::new());
+    assert_eq!(
+        backend.get_value(&copy(), AttribKey::Indeterminate),
+        Some(Value::Bool(true))
+    );
+    backend.dispatch(Msg::Progress(0.5));
+    assert_eq!(
+        backend.get_value(&copy(), AttribKey::Indeterminate),
+        Some(Value::Bool(false))
+    );
+    assert_eq!(
+        backend.get_value(&copy(), AttribKey::Fraction),
+        Some(Value::F64(0.5))
+    );
+}
+
+#[test]
+fn spinner_is_stopped() {
+    let backend = HeadlessBackend::new(App::new());
+    assert_eq!(
+        backend.get_value(&busy(), AttribKey::Active),
+        Some(Value::Bool(true))
+    );
+    backend.dispatch(Msg::Done);
+    assert_eq!(
+        backend.get_value(&busy(), AttribKey::Active),
+        Some(Value::Bool(false))
+    );
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_progress {
+    use super::*;
+    use sauron_native::{backend::titik_ui::TitikScreen, mt_dom, Patch};
+
+    /// the node_idx of the progress bar and the spinner in the view
+    const PROGRESS_BAR: usize = 1;
+    const SPINNER: usize = 2;
+
+    /// the address of the widget, which is changed when the widget is
+    /// recreated
+    fn address(screen: &TitikScreen<Msg>, node_idx: usize) -> *const () {
+        screen
+            .widget(node_idx)
+            .map(|widget| widget as *const _ as *const ())
+            .expect("must have the widget")
+    }
+
+    /// update the app with the msg, then patch the screen with the new view
+    /// which must only add the changed attributes
+    fn update(app: &mut App, screen: &mut TitikScreen<Msg>, msg: Msg) {
+        let old_view = app.view();
+        app.update(msg);
+        let new_view = app.view();
+        let patches =
+            mt_dom::diff_with_key(&old_view, &new_view, &AttribKey::Key);
+        assert!(patches
+            .iter()
+            .all(|patch| matches!(patch, Patch::AddAttributes(_))));
+        screen.patch(new_view);
+    }
+
+    #[test]
+    fn progress_bar_is_updated_without_recreating_it() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let widget = address(&screen, PROGRESS_BAR);
+        let line = &screen.lines()[0];
+        assert!(line.contains('█') && line.contains('░'));
+        assert!(line.ends_with("copying"));
+
+        update(&mut app, &mut screen, Msg::Progress(0.25));
+        assert_eq!(
+            screen.lines()[0],
+            format!("{}{} 25% copying", "█".repeat(5), "░".repeat(15))
+        );
+        update(&mut app, &mut screen, Msg::Progress(1.0));
+        assert_eq!(
+            screen.lines()[0],
+            format!("{} 100% copying", "█".repeat(20))
+        );
+        assert_eq!(address(&screen, PROGRESS_BAR), widget);
+    }
+
+    #[test]
+    fn spinner_is_stopped_without_recreating_it() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let widget = address(&screen, SPINNER);
+        let frame = screen.lines()[1].clone();
+        assert!("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏".contains(frame.as_str()));
+        assert_eq!(frame.chars().count(), 1);
+
+        update(&mut app, &mut screen, Msg::Done);
+        assert_eq!(screen.lines()[1], "");
+        assert_eq!(address(&screen, SPINNER), widget);
+    }
+}
@@ -0,0 +1,264 @@
+//! tests the remote backend and renderer over a local socket pair
+#![cfg(all(unix, feature = "with-remote"))]
+use sauron_native::{
+    backend::{
+        remote_ui::{
+            wire, RemoteBackend, RemoteRenderer, RendererMsg, ToRenderer,
+        },
+        Dispatch,
+    },
+    widget::{
+        attribute::*,
+        event::*,
+        remote::{RemoteAttribute, RemoteNode, RemotePatch},
+        *,
+    },
+    AttribKey, Cmd, Component, Event, Node, Shortcut, Sub, Value,
+};
+use std::{
+    net::Shutdown,
+    os::unix::net::UnixStream,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Inc,
+    FocusInc,
+}
+
+struct Counter {
+    count: i32,
+}
+
+impl Component<Msg> for Counter {
+    fn title(&self) -> String {
+        "counter".to_string()
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Inc => self.count += 1,
+            Msg::FocusInc => return Cmd::focus("inc"),
+        }
+        Cmd::none()
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut<Msg>> {
+        Shortcut::from_accelerator("Ctrl+I", Msg::Inc)
+            .into_iter()
+            .collect()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![
+                button(vec![id("inc"), label("+"), on_click(|_| Msg::Inc)]),
+                text_label(vec![value(self.count.to_string())]),
+            ],
+        )
+    }
+}
+
+/// ticks until its subscription is no longer returned
+struct Ticker {
+    ticks: i32,
+}
+
+impl Component<Msg> for Ticker {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        if msg == Msg::Inc {
+            self.ticks += 1;
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        text_label(vec![value(self.ticks.to_string())])
+    }
+
+    fn subscriptions(&self) -> Vec<Sub<Msg>> {
+        if self.ticks < 3 {
+            vec![Sub::every(Duration::from_millis(1), || Msg::Inc)]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// the view as it is serialized, the handler ids are left out since they
+/// differ between the backend and a fresh conversion
+fn to_json(node: &RemoteNode) -> String {
+    let mut json = serde_json::to_value(node).unwrap();
+    strip_handlers(&mut json);
+    json.to_string()
+}
+
+fn strip_handlers(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Object(map) => {
+            map.remove("Handler");
+            map.values_mut().for_each(strip_handlers);
+        }
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(strip_handlers);
+        }
+        _ => (),
+    }
+}
+
+#[test]
+fn events_and_patches_round_trip() {
+    let (component_side, mut renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let component_reader = component_side.try_clone().unwrap();
+
+    let backend = RemoteBackend::new(Counter { count: 0 }, component_side)
+        .expect("must mount the app");
+    let renderer_writer = renderer_side.try_clone().unwrap();
+    let mut renderer = RemoteRenderer::new(&mut renderer_side, renderer_writer)
+        .expect("must receive the mount");
+    assert_eq!(renderer.title(), "counter");
+
+    // click the button in the renderer, then close the renderer side so the
+    // backend stops receiving after the click
+    let button_handler = renderer.root().handler_ids()[0].clone();
+    renderer.update(RendererMsg::Event(
+        button_handler,
+        Event::from(MouseEvent::click(0, 0)),
+    ));
+    renderer_side.shutdown(Shutdown::Write).unwrap();
+    backend
+        .run(component_reader)
+        .expect("must receive the events");
+    assert_eq!(backend.app().count, 1);
+
+    // the patches of the click are applied to the view of the renderer
+    let message = wire::read_frame(&mut renderer_side).expect("must patch");
+    renderer.update(RendererMsg::Received(message));
+    let expected = RemoteNode::from_node(&backend.app().view());
+    assert_eq!(to_json(renderer.root()), to_json(&expected));
+}
+
+#[test]
+fn shortcuts_round_trip() {
+    let (component_side, mut renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let component_reader = component_side.try_clone().unwrap();
+    let backend = RemoteBackend::new(Counter { count: 0 }, component_side)
+        .expect("must mount the app");
+    let renderer_writer = renderer_side.try_clone().unwrap();
+    let mut renderer = RemoteRenderer::new(&mut renderer_side, renderer_writer)
+        .expect("must receive the mount");
+
+    // press the shortcut in the renderer, which is sent back to the backend
+    let shortcuts = renderer.shortcuts();
+    assert_eq!(shortcuts.len(), 1);
+    assert_eq!(
+        shortcuts[0].key_event,
+        KeyEvent::from_accelerator("Ctrl+I").unwrap()
+    );
+    renderer.update(shortcuts[0].msg.clone());
+    renderer_side.shutdown(Shutdown::Write).unwrap();
+    backend
+        .run(component_reader)
+        .expect("must receive the shortcut");
+    assert_eq!(backend.app().count, 1);
+}
+
+#[test]
+fn focus_is_sent_to_the_renderer() {
+    let (component_side, mut renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let backend = RemoteBackend::new(Counter { count: 0 }, component_side)
+        .expect("must mount the app");
+    let renderer_writer = renderer_side.try_clone().unwrap();
+    let mut renderer = RemoteRenderer::new(&mut renderer_side, renderer_writer)
+        .expect("must receive the mount");
+
+    backend.dispatch(Msg::FocusInc);
+    let message = wire::read_frame(&mut renderer_side).expect("must focus");
+    assert_eq!(message, ToRenderer::Focus("inc".to_string()));
+    // the focus is left to the backend where the renderer is mounted
+    assert!(!renderer.update(RendererMsg::Received(message)).is_none());
+}
+
+#[test]
+fn subscriptions_tick_while_waiting_for_the_events() {
+    let (component_side, mut renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let component_reader = component_side.try_clone().unwrap();
+    let backend = RemoteBackend::new(Ticker { ticks: 0 }, component_side)
+        .expect("must mount the app");
+
+    // the renderer is closed once it receives the patches of the 3 ticks
+    let renderer = thread::spawn(move || {
+        let mount: ToRenderer = wire::read_frame(&mut renderer_side).unwrap();
+        assert!(matches!(mount, ToRenderer::Mount { .. }));
+        for _ in 0..3 {
+            let patch: ToRenderer =
+                wire::read_frame(&mut renderer_side).unwrap();
+            assert!(matches!(patch, ToRenderer::Patch(_)));
+        }
+    });
+    backend
+        .run(component_reader)
+        .expect("must stop when the renderer is closed");
+    renderer.join().unwrap();
+    assert_eq!(backend.app().ticks, 3);
+}
+
+#[test]
+fn closed_renderer_stops_the_backend() {
+    let (component_side, renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let component_reader = component_side.try_clone().unwrap();
+    let backend = RemoteBackend::new(Counter { count: 0 }, component_side)
+        .expect("must mount the app");
+    drop(renderer_side);
+    backend.run(component_reader).expect("must stop at the end");
+}
+
+#[test]
+fn spawned_renderer_polls_the_patches() {
+    let (mut component_side, renderer_side) =
+        UnixStream::pair().expect("must create a socket pair");
+    let counter = Counter { count: 0 };
+    wire::write_frame(
+        &mut component_side,
+        &ToRenderer::Mount {
+            title: counter.title(),
+            root: RemoteNode::from_node(&counter.view()),
+            shortcuts: vec![],
+        },
+    )
+    .unwrap();
+    let renderer_writer = renderer_side.try_clone().unwrap();
+    let mut renderer = RemoteRenderer::spawn(renderer_side, renderer_writer)
+        .expect("must receive the mount");
+    assert_eq!(renderer.subscriptions().len(), 1);
+
+    // set the value of the text label, which is the third node
+    let patch = RemotePatch::AddAttributes(
+        2,
+        vec![RemoteAttribute::Value(
+            AttribKey::Value,
+            vec![Value::from("1")],
+        )],
+    );
+    wire::write_frame(&mut component_side, &ToRenderer::Patch(vec![patch]))
+        .unwrap();
+    drop(component_side);
+
+    // the subscription is dropped once the component side is closed
+    let started = Instant::now();
+    while !renderer.subscriptions().is_empty() {
+        assert!(started.elapsed() < Duration::from_secs(5), "must be closed");
+        thread::sleep(Duration::from_millis(10));
+        renderer.update(RendererMsg::Poll);
+    }
+    let expected = RemoteNode::from_node(&Counter { count: 1 }.view());
+    assert_eq!(to_json(renderer.root()), to_json(&expected));
+}
@@ -0,0 +1,132 @@
+//! tests the serialization of the values, events and node trees
+#![cfg(feature = "with-remote")]
+use sauron_native::{
+    widget::{
+        attribute::*,
+        event::*,
+        remote::{emit_handler, HandlerId, RemoteNode},
+        *,
+    },
+    AttribKey, Event, Node, Value,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Inc,
+    Dec,
+    Input(String),
+}
+
+fn view() -> Node<Msg> {
+    column(
+        vec![],
+        vec![
+            button(vec![label("+"), on_click(|_| Msg::Inc)]),
+            row(
+                vec![],
+                vec![
+                    text_input(vec![on_input(|input| {
+                        Msg::Input(input.value.to_string())
+                    })]),
+                    button(vec![label("-"), on_click(|_| Msg::Dec)]),
+                ],
+            ),
+        ],
+    )
+}
+
+#[test]
+fn layout_is_deserialized_with_its_location_and_size() {
+    let json = r#"{"Layout":{"x":1.0,"y":2.0,"width":30.0,"height":40.0}}"#;
+    let value: Value = serde_json::from_str(json).expect("must deserialize");
+    match &value {
+        Value::Layout(layout) => {
+            assert_eq!(layout.location.x, 1.0);
+            assert_eq!(layout.location.y, 2.0);
+            assert_eq!(layout.size.width, 30.0);
+            assert_eq!(layout.size.height, 40.0);
+        }
+        value => panic!("expecting a layout: {:?}", value),
+    }
+    assert_eq!(serde_json::to_string(&value).expect("must serialize"), json);
+}
+
+#[test]
+fn static_str_is_deserialized_into_a_string() {
+    let json = serde_json::to_string(&Value::from("hello")).unwrap();
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value, Value::String("hello".to_string()));
+}
+
+#[test]
+fn mouse_event_round_trip() {
+    let event = Event::MouseEvent(MouseEvent::click(3, 4));
+    let json = serde_json::to_string(&event).unwrap();
+    let deserialized: Event = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, event);
+}
+
+#[test]
+fn unknown_mouse_event_type_is_an_error() {
+    let json = serde_json::to_string(&MouseEvent::click(3, 4))
+        .unwrap()
+        .replace("click", "scratch");
+    let err = serde_json::from_str::<MouseEvent>(&json)
+        .expect_err("must not deserialize an unknown type");
+    assert!(err.to_string().contains("unknown mouse event type"));
+}
+
+#[test]
+fn each_handler_has_its_own_id() {
+    let remote = RemoteNode::from_node(&view());
+    let ids: Vec<usize> = remote
+        .handler_ids()
+        .iter()
+        .map(|handler| handler.id)
+        .collect();
+    assert_eq!(ids, vec![0, 1, 2]);
+}
+
+#[test]
+fn handlers_are_emitted_by_their_id() {
+    let node = view();
+    let remote = RemoteNode::from_node(&node);
+    let handler_ids = remote.handler_ids();
+    assert_eq!(
+        emit_handler(
+            &node,
+            handler_ids[0],
+            Event::from(MouseEvent::click(0, 0))
+        ),
+        vec![Msg::Inc]
+    );
+    assert_eq!(
+        emit_handler(&node, handler_ids[1], InputEvent::new("hi").into()),
+        vec![Msg::Input("hi".to_string())]
+    );
+    assert_eq!(
+        emit_handler(
+            &node,
+            handler_ids[2],
+            Event::from(MouseEvent::click(0, 0))
+        ),
+        vec![Msg::Dec]
+    );
+    let unknown = HandlerId {
+        id: 3,
+        event: AttribKey::ClickEvent,
+    };
+    assert!(
+        emit_handler(&node, &unknown, MouseEvent::click(0, 0).into())
+            .is_empty()
+    );
+}
+
+#[test]
+fn node_round_trip() {
+    let remote = RemoteNode::from_node(&view());
+    let json = serde_json::to_string(&remote).unwrap();
+    let deserialized: RemoteNode = serde_json::from_str(&json).unwrap();
+    // the static str labels are deserialized into Strings
+    assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+}
@@ -0,0 +1,68 @@
+//! tests the html which is rendered from the widget tree without a browser
+#![cfg(feature = "with-static-html")]
+use sauron_native::{
+    backend::{
+        render_to_html_page, render_to_html_string,
+        render_to_html_string_with_size,
+    },
+    widget::{attribute::*, event::*, *},
+    Node,
+};
+
+#[derive(Debug, Clone)]
+enum Msg {
+    Click,
+}
+
+fn view() -> Node<Msg> {
+    column(
+        vec![],
+        vec![
+            button(vec![label("Save"), on_click(|_| Msg::Click)]),
+            text_label(vec![value("3 items")]),
+            checkbox(vec![label("done"), value(true)]),
+        ],
+    )
+}
+
+#[test]
+fn renders_the_widgets_with_their_classes() {
+    let html = render_to_html_string(&view());
+    assert!(html.starts_with(r#"<div class="Vbox""#));
+    assert!(html.contains(r#"<button class="Button" name="">Save</button>"#));
+    assert!(html.contains(r#"<label class="Label" style="">3 items</label>"#));
+    assert!(html.contains(r#"<input type="checkbox" checked="checked"/>"#));
+    assert!(html.contains("<label>done</label>"));
+}
+
+#[test]
+fn the_listeners_are_not_rendered() {
+    let html = render_to_html_string(&view());
+    assert!(!html.contains("click"));
+}
+
+#[test]
+fn the_default_size_is_the_page_size() {
+    assert_eq!(
+        render_to_html_string(&view()),
+        render_to_html_string_with_size(&view(), 1024.0, 768.0)
+    );
+}
+
+#[test]
+fn the_widgets_are_rendered_in_a_small_size() {
+    let html = render_to_html_string_with_size(&view(), 100.0, 50.0);
+    assert!(html.contains("Save</button>"));
+    assert!(html.contains("3 items"));
+}
+
+#[test]
+fn the_page_has_the_escaped_title_and_the_body() {
+    let page = render_to_html_page("Tom & Jerry <1>", &view());
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("<title>Tom &amp; Jerry &lt;1&gt;</title>"));
+    assert!(page.contains(&format!(
+        "<body>\n{}\n</body>",
+        render_to_html_string(&view())
+    )));
+}
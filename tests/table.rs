@@ -0,0 +1,207 @@
+//! tests the events of the table which are sent back to the app, and the
+//! rows, the selection and the sorted column which are shown from the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    files: Vec<(&'static str, u32)>,
+    selected: Option<usize>,
+    sort_column: Option<usize>,
+    is_descending: bool,
+    opened: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Select(usize),
+    Sort(usize),
+    Open(usize),
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            files: vec![("notes.md", 12), ("app.rs", 340), ("lib.rs", 7)],
+            selected: None,
+            sort_column: None,
+            is_descending: false,
+            opened: None,
+        }
+    }
+}
+
+/// the index in the value of the input event
+fn index(input: InputEvent) -> usize {
+    input.value.as_f64().expect("must be an index") as usize
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Select(row) => self.selected = Some(row),
+            // sorting the same column again reverses the order
+            Msg::Sort(column) => {
+                self.is_descending =
+                    self.sort_column == Some(column) && !self.is_descending;
+                self.sort_column = Some(column);
+                match column {
+                    0 => self.files.sort_by_key(|(name, _)| *name),
+                    _ => self.files.sort_by_key(|(_, size)| *size),
+                }
+                if self.is_descending {
+                    self.files.reverse();
+                }
+            }
+            Msg::Open(row) => self.opened = Some(self.files[row].0),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![table(vec![
+                id("files"),
+                columns(vec!["name", "size"]),
+                rows(self.files.iter().map(|(name, size)| {
+                    vec![Value::from(*name), Value::from(*size)]
+                })),
+                sortable(true),
+                sort_column(self.sort_column.map_or(-1.0, |c| c as f64)),
+                sort_descending(self.is_descending),
+                selected(self.selected.map_or(-1.0, |s| s as f64)),
+                on_select(|input| Msg::Select(index(input))),
+                on_sort(|input| Msg::Sort(index(input))),
+                on_row_activate(|input| Msg::Open(index(input))),
+            ])],
+        )
+    }
+}
+
+fn files() -> Selector {
+    Selector::Id("files".to_string())
+}
+
+fn get_index(backend: &HeadlessBackend<App, Msg>, key: AttribKey) -> f64 {
+    backend
+        .get_value(&files(), key)
+        .and_then(|v| v.as_f64())
+        .expect("must have the index")
+}
+
+#[test]
+fn selected_row_is_shown() {
+    let backend = HeadlessBackend::new(App::new());
+    assert_eq!(get_index(&backend, AttribKey::Selected), -1.0);
+    assert!(backend.fire(&files(), AttribKey::SelectEvent, InputEvent::new(1)));
+    assert_eq!(backend.app().selected, Some(1));
+    assert_eq!(get_index(&backend, AttribKey::Selected), 1.0);
+}
+
+#[test]
+fn sorted_column_is_shown() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.fire(&files(), AttribKey::SortEvent, InputEvent::new(1));
+    assert_eq!(get_index(&backend, AttribKey::SortColumn), 1.0);
+    let names: Vec<_> = backend.app().files.iter().map(|f| f.0).collect();
+    assert_eq!(names, ["lib.rs", "notes.md", "app.rs"]);
+
+    backend.fire(&files(), AttribKey::SortEvent, InputEvent::new(1));
+    assert_eq!(
+        backend.get_value(&files(), AttribKey::SortDescending),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(backend.app().files[0].0, "app.rs");
+}
+
+#[test]
+fn activated_row_is_opened() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.fire(&files(), AttribKey::RowActivateEvent, InputEvent::new(2));
+    assert_eq!(backend.app().opened, Some("lib.rs"));
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_table {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the table in the view
+    const TABLE: usize = 1;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    /// send the event to the table, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(TABLE, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    #[test]
+    fn rows_are_selected_and_activated_with_the_keys() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Down)),
+            [Msg::Select(0)]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Down)),
+            [Msg::Select(1)]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Enter)),
+            [Msg::Open(1)]
+        );
+        assert_eq!(app.opened, Some("app.rs"));
+    }
+
+    #[test]
+    fn focused_column_is_sorted_with_the_key() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(send(&mut app, &mut screen, key(KeyCode::Right)), []);
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Char('s'))),
+            [Msg::Sort(1)]
+        );
+        assert!(screen.lines().iter().any(|line| line.contains("size ▲")));
+        send(&mut app, &mut screen, key(KeyCode::Char('s')));
+        assert!(screen.lines().iter().any(|line| line.contains("size ▼")));
+    }
+
+    #[test]
+    fn clicked_header_is_sorted() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let layout = screen.layout(TABLE).expect("must have a layout");
+        let (x, y) = (layout.location.x as u16, layout.location.y as u16);
+        let click = Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            x + 2,
+            y,
+            KeyModifiers::empty(),
+        ));
+        assert_eq!(send(&mut app, &mut screen, click), [Msg::Sort(0)]);
+        assert!(screen.lines().iter().any(|line| line.contains("name ▲")));
+    }
+}
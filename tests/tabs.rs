@@ -0,0 +1,160 @@
+//! tests the tab change event which is sent back to the app, and the tab
+//! which is shown from the selected index of the app
+use sauron_native::{
+    backend::{headless_ui::Selector, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    selected: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    ChangeTab(usize),
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::ChangeTab(index) => self.selected = index,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![tabs(
+                vec![
+                    id("settings"),
+                    selected(self.selected),
+                    on_tab_change(|input| {
+                        let index = input.value.as_f64().expect("must be f64");
+                        Msg::ChangeTab(index as usize)
+                    }),
+                ],
+                vec![
+                    tab("General", text_label(vec![value("general page")])),
+                    tab("Network", text_label(vec![value("network page")])),
+                    tab("About", text_label(vec![value("about page")])),
+                ],
+            )],
+        )
+    }
+}
+
+fn settings() -> Selector {
+    Selector::Id("settings".to_string())
+}
+
+#[test]
+fn changed_tab_is_selected() {
+    let backend = HeadlessBackend::new(App { selected: 0 });
+    assert!(backend.fire(
+        &settings(),
+        AttribKey::TabChangeEvent,
+        InputEvent::new(2)
+    ));
+    assert_eq!(backend.app().selected, 2);
+    assert_eq!(
+        backend.get_value(&settings(), AttribKey::Selected),
+        Some(Value::from(2usize))
+    );
+}
+
+#[test]
+fn tabs_have_their_label() {
+    let backend = HeadlessBackend::new(App { selected: 0 });
+    let root_widget = backend.root_widget();
+    let labels: Vec<String> = root_widget
+        .find(&settings())
+        .expect("must have the tabs")
+        .get_children()
+        .iter()
+        .map(|tab| {
+            assert_eq!(*tab.widget(), Widget::Tab);
+            tab.get_value(AttribKey::Label)
+                .map(|v| v.to_string())
+                .expect("must have a label")
+        })
+        .collect();
+    assert_eq!(labels, ["General", "Network", "About"]);
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_tabs {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the tabs in the view
+    const TABS: usize = 1;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    /// send the event to the tabs, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(TABS, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    fn is_shown(screen: &TitikScreen<Msg>, text: &str) -> bool {
+        screen.lines().iter().any(|line| line.contains(text))
+    }
+
+    #[test]
+    fn tabs_are_changed_with_the_keys() {
+        let mut app = App { selected: 0 };
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert!(is_shown(&screen, "general page"));
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Right)),
+            [Msg::ChangeTab(1)]
+        );
+        assert!(is_shown(&screen, "network page"));
+        assert!(!is_shown(&screen, "general page"));
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::End)),
+            [Msg::ChangeTab(2)]
+        );
+        assert!(is_shown(&screen, "about page"));
+        // the last tab is already selected
+        assert_eq!(send(&mut app, &mut screen, key(KeyCode::Right)), []);
+    }
+
+    #[test]
+    fn clicked_tab_is_selected() {
+        let mut app = App { selected: 0 };
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let top = screen.layout(TABS).expect("must have a layout").location.y;
+        let tab_bar = &screen.lines()[top as usize];
+        let x = tab_bar[..tab_bar.find("About").expect("must show the label")]
+            .chars()
+            .count();
+        let click = Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            x as u16,
+            top as u16,
+            KeyModifiers::empty(),
+        ));
+        assert_eq!(send(&mut app, &mut screen, click), [Msg::ChangeTab(2)]);
+        assert!(is_shown(&screen, "about page"));
+    }
+}
@@ -0,0 +1,196 @@
+//! tests the patches which are applied to the titik widgets, patching the
+//! widgets of the old view must draw the same as the widgets of the new view
+#![cfg(feature = "with-titik")]
+use image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+use sauron_native::{
+    backend::titik_ui::TitikScreen,
+    mt_dom,
+    widget::{attribute::*, *},
+    AttribKey, Node, Patch,
+};
+use titik::Link;
+
+#[derive(Debug, Clone)]
+enum Msg {}
+
+/// the name of the patches from the old view to the new view
+fn patch_kinds(old: &Node<Msg>, new: &Node<Msg>) -> Vec<&'static str> {
+    mt_dom::diff_with_key(old, new, &AttribKey::Key)
+        .iter()
+        .map(|patch| match patch {
+            Patch::InsertNode(_) => "InsertNode",
+            Patch::AppendChildren(_) => "AppendChildren",
+            Patch::RemoveNode(_) => "RemoveNode",
+            Patch::ReplaceNode(_) => "ReplaceNode",
+            Patch::AddAttributes(_) => "AddAttributes",
+            Patch::RemoveAttributes(_) => "RemoveAttributes",
+            Patch::ChangeText(_) => "ChangeText",
+        })
+        .collect()
+}
+
+/// patch the screen of the old view into the new view, which must draw the
+/// same as the screen that is created from the new view
+fn patched(old: Node<Msg>, new: Node<Msg>) -> TitikScreen<Msg> {
+    let expected = TitikScreen::new(new.clone(), 40, 10);
+    let mut screen = TitikScreen::new(old, 40, 10);
+    screen.patch(new);
+    assert_eq!(screen.lines(), expected.lines());
+    assert_eq!(screen.buffer(), expected.buffer());
+    screen
+}
+
+fn png(color: [u8; 3]) -> Vec<u8> {
+    let image = RgbImage::from_pixel(100, 100, Rgb(color));
+    let mut bytes = vec![];
+    DynamicImage::ImageRgb8(image)
+        .write_to(&mut bytes, ImageOutputFormat::Png)
+        .expect("must encode the png");
+    bytes
+}
+
+#[test]
+fn checkbox_is_checked_and_unchecked() {
+    let view = |checked: bool| {
+        column(vec![], vec![checkbox(vec![label("done"), value(checked)])])
+    };
+    assert_eq!(patch_kinds(&view(false), &view(true)), ["AddAttributes"]);
+    let screen = patched(view(false), view(true));
+    let unchecked = TitikScreen::new(view(false), 40, 10);
+    assert_ne!(screen.lines(), unchecked.lines());
+    patched(view(true), view(false));
+}
+
+#[test]
+fn removed_checked_attribute_unchecks_the_checkbox() {
+    let old = column(vec![], vec![checkbox(vec![label("done"), value(true)])]);
+    let new = column(vec![], vec![checkbox(vec![label("done")])]);
+    assert_eq!(patch_kinds(&old, &new), ["RemoveAttributes"]);
+    patched(old, new);
+}
+
+#[test]
+fn text_input_value_is_set() {
+    let view = |text: &'static str| {
+        column(vec![], vec![text_input(vec![value(text)])])
+    };
+    assert_eq!(
+        patch_kinds(&view("buy"), &view("buy milk")),
+        ["AddAttributes"]
+    );
+    let screen = patched(view("buy"), view("buy milk"));
+    assert!(screen.lines().iter().any(|line| line.contains("buy milk")));
+}
+
+#[test]
+fn label_text_is_set() {
+    let view = |count: i32| {
+        column(vec![], vec![text_label(vec![value(count.to_string())])])
+    };
+    assert_eq!(patch_kinds(&view(1), &view(2)), ["AddAttributes"]);
+    let screen = patched(view(1), view(2));
+    assert_eq!(screen.lines()[0].trim(), "2");
+}
+
+#[test]
+fn text_node_is_changed() {
+    let view = |txt: &str| column(vec![], vec![mt_dom::text(txt)]);
+    assert_eq!(patch_kinds(&view("before"), &view("after")), ["ChangeText"]);
+    let screen = patched(view("before"), view("after"));
+    assert_eq!(screen.lines()[0].trim(), "after");
+}
+
+#[test]
+fn image_data_is_set() {
+    let view =
+        |color: [u8; 3]| column(vec![], vec![image(vec![data(png(color))])]);
+    let (red, blue) = ([255, 0, 0], [0, 0, 255]);
+    assert_eq!(patch_kinds(&view(red), &view(blue)), ["AddAttributes"]);
+    let screen = patched(view(red), view(blue));
+    assert_ne!(
+        screen.buffer(),
+        TitikScreen::new(view(red), 40, 10).buffer()
+    );
+}
+
+#[test]
+fn link_uri_is_set() {
+    let view = |address: &'static str| {
+        column(vec![], vec![link(vec![label("docs"), uri(address)])])
+    };
+    let (old, new) = ("https://old.example", "https://new.example");
+    assert_eq!(patch_kinds(&view(old), &view(new)), ["AddAttributes"]);
+    let screen = patched(view(old), view(new));
+    let link: &Link = screen
+        .widget(1)
+        .and_then(|widget| widget.as_any().downcast_ref())
+        .expect("must be a link");
+    assert!(format!("{:?}", link).contains(new));
+}
+
+#[test]
+fn node_is_replaced_with_another_widget() {
+    let old = column(vec![], vec![text_label(vec![value("Save")])]);
+    let new = column(vec![], vec![button(vec![label("Save")])]);
+    assert_eq!(patch_kinds(&old, &new), ["ReplaceNode"]);
+    patched(old, new);
+}
+
+/// the labels of the items with their key
+fn items(keys: &[&'static str]) -> Node<Msg> {
+    column(
+        vec![],
+        keys.iter()
+            .map(|k| text_label(vec![key(*k), value(*k)]))
+            .collect(),
+    )
+}
+
+fn item_lines(screen: &TitikScreen<Msg>) -> Vec<String> {
+    screen
+        .lines()
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[test]
+fn keyed_node_is_inserted() {
+    let (old, new) = (items(&["a", "c"]), items(&["a", "b", "c"]));
+    assert!(patch_kinds(&old, &new).contains(&"InsertNode"));
+    let screen = patched(old, new);
+    assert_eq!(item_lines(&screen), ["a", "b", "c"]);
+}
+
+#[test]
+fn keyed_node_is_removed() {
+    let (old, new) = (items(&["a", "b", "c"]), items(&["a", "c"]));
+    assert!(patch_kinds(&old, &new).contains(&"RemoveNode"));
+    let screen = patched(old, new);
+    assert_eq!(item_lines(&screen), ["a", "c"]);
+}
+
+#[test]
+fn node_is_appended() {
+    let (old, new) = (items(&["a"]), items(&["a", "b"]));
+    assert_eq!(patch_kinds(&old, &new), ["AppendChildren"]);
+    let screen = patched(old, new);
+    assert_eq!(item_lines(&screen), ["a", "b"]);
+}
+
+#[test]
+fn keyed_nodes_are_swapped() {
+    let (old, new) = (items(&["a", "b", "c"]), items(&["b", "a", "c"]));
+    assert_eq!(patch_kinds(&old, &new), ["InsertNode", "RemoveNode"]);
+    let screen = patched(old, new);
+    assert_eq!(item_lines(&screen), ["b", "a", "c"]);
+}
+
+#[test]
+fn keyed_node_is_moved_to_the_end() {
+    let (old, new) = (items(&["a", "b", "c"]), items(&["b", "c", "a"]));
+    assert_eq!(patch_kinds(&old, &new), ["AppendChildren", "RemoveNode"]);
+    let screen = patched(old, new);
+    assert_eq!(item_lines(&screen), ["b", "c", "a"]);
+}
@@ -0,0 +1,283 @@
+//! tests the events of the tree view which are sent back to the app, and the
+//! expanded and selected items which are shown from the app
+use sauron_native::{
+    backend::{headless_ui::Selector, Dispatch, HeadlessBackend},
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Node, Value,
+};
+
+struct App {
+    folders: Vec<(&'static str, Vec<&'static str>)>,
+    expanded: Vec<String>,
+    selected: Option<String>,
+    opened: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Expand(String),
+    Collapse(String),
+    Select(String),
+    Open(String),
+    /// sort the folders in the reverse order
+    Reverse,
+}
+
+impl App {
+    fn new() -> Self {
+        App {
+            folders: vec![
+                ("src", vec!["main.rs", "lib.rs"]),
+                ("docs", vec!["guide.md"]),
+            ],
+            expanded: vec![],
+            selected: None,
+            opened: None,
+        }
+    }
+
+    /// the item with its key, which is also the id in the events
+    fn item(&self, name: &'static str, files: Vec<Node<Msg>>) -> Node<Msg> {
+        tree_item(
+            vec![
+                key(name),
+                label(name),
+                expanded(self.expanded.iter().any(|e| e == name)),
+                selected(self.selected.as_deref() == Some(name)),
+            ],
+            files,
+        )
+    }
+}
+
+impl Component<Msg> for App {
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Expand(id) => self.expanded.push(id),
+            Msg::Collapse(id) => self.expanded.retain(|e| *e != id),
+            Msg::Select(id) => self.selected = Some(id),
+            Msg::Open(id) => self.opened = Some(id),
+            Msg::Reverse => self.folders.reverse(),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![],
+            vec![tree_view(
+                vec![
+                    id("files"),
+                    on_expand(|input| Msg::Expand(input.value.to_string())),
+                    on_collapse(|input| Msg::Collapse(input.value.to_string())),
+                    on_select(|input| Msg::Select(input.value.to_string())),
+                    on_row_activate(|input| Msg::Open(input.value.to_string())),
+                ],
+                self.folders
+                    .iter()
+                    .map(|(folder, files)| {
+                        let files = files
+                            .iter()
+                            .map(|file| self.item(file, vec![]))
+                            .collect();
+                        self.item(folder, files)
+                    })
+                    .collect(),
+            )],
+        )
+    }
+}
+
+fn files() -> Selector {
+    Selector::Id("files".to_string())
+}
+
+fn item_value(
+    backend: &HeadlessBackend<App, Msg>,
+    item: &str,
+    key: AttribKey,
+) -> Option<Value> {
+    backend.get_value(&Selector::Key(item.to_string()), key)
+}
+
+#[test]
+fn expanded_item_is_shown_as_expanded() {
+    let backend = HeadlessBackend::new(App::new());
+    assert!(backend.fire(
+        &files(),
+        AttribKey::ExpandEvent,
+        InputEvent::new("src")
+    ));
+    assert_eq!(backend.app().expanded, ["src"]);
+    assert_eq!(
+        item_value(&backend, "src", AttribKey::Expanded),
+        Some(Value::Bool(true))
+    );
+    backend.fire(&files(), AttribKey::CollapseEvent, InputEvent::new("src"));
+    assert_eq!(
+        item_value(&backend, "src", AttribKey::Expanded),
+        Some(Value::Bool(false))
+    );
+}
+
+#[test]
+fn expanded_item_stays_expanded_when_reordered() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.fire(&files(), AttribKey::ExpandEvent, InputEvent::new("docs"));
+    backend.dispatch(Msg::Reverse);
+    let root_widget = backend.root_widget();
+    let folders: Vec<_> = root_widget
+        .find(&files())
+        .expect("must have the tree view")
+        .get_children()
+        .iter()
+        .map(|item| {
+            (
+                item.get_value(AttribKey::Key).map(|v| v.to_string()),
+                item.get_value(AttribKey::Expanded).cloned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        folders,
+        [
+            (Some("docs".to_string()), Some(Value::Bool(true))),
+            (Some("src".to_string()), Some(Value::Bool(false))),
+        ]
+    );
+}
+
+#[test]
+fn selected_item_is_shown_as_selected() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.fire(&files(), AttribKey::SelectEvent, InputEvent::new("lib.rs"));
+    assert_eq!(backend.app().selected.as_deref(), Some("lib.rs"));
+    assert_eq!(
+        item_value(&backend, "lib.rs", AttribKey::Selected),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        item_value(&backend, "main.rs", AttribKey::Selected),
+        Some(Value::Bool(false))
+    );
+}
+
+#[test]
+fn activated_item_is_opened() {
+    let backend = HeadlessBackend::new(App::new());
+    backend.fire(
+        &files(),
+        AttribKey::RowActivateEvent,
+        InputEvent::new("guide.md"),
+    );
+    assert_eq!(backend.app().opened.as_deref(), Some("guide.md"));
+}
+
+#[cfg(feature = "with-titik")]
+mod titik_tree_view {
+    use super::*;
+    use sauron_native::backend::titik_ui::TitikScreen;
+    use titik::{
+        crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        },
+        Event,
+    };
+
+    /// the node_idx of the tree view in the view
+    const TREE_VIEW: usize = 1;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    /// send the event to the tree view, then update the app with the msgs
+    fn send(
+        app: &mut App,
+        screen: &mut TitikScreen<Msg>,
+        event: Event,
+    ) -> Vec<Msg> {
+        let msgs = screen.send_event(TREE_VIEW, event);
+        for msg in msgs.iter() {
+            app.update(msg.clone());
+        }
+        screen.patch(app.view());
+        msgs
+    }
+
+    /// the rows of the tree, without the indentation
+    fn rows(screen: &TitikScreen<Msg>) -> Vec<String> {
+        screen
+            .lines()
+            .into_iter()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn items_are_navigated_with_the_keys() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        assert_eq!(rows(&screen), ["▸ src", "▸ docs"]);
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Down)),
+            [Msg::Select("src".to_string())]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Right)),
+            [Msg::Expand("src".to_string())]
+        );
+        assert_eq!(rows(&screen), ["> ▾ src", "main.rs", "lib.rs", "▸ docs"]);
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Right)),
+            [Msg::Select("main.rs".to_string())]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Enter)),
+            [Msg::Open("main.rs".to_string())]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Left)),
+            [Msg::Select("src".to_string())]
+        );
+        assert_eq!(
+            send(&mut app, &mut screen, key(KeyCode::Left)),
+            [Msg::Collapse("src".to_string())]
+        );
+        assert_eq!(rows(&screen), ["> ▸ src", "▸ docs"]);
+        assert_eq!(app.opened.as_deref(), Some("main.rs"));
+    }
+
+    #[test]
+    fn clicked_arrow_expands_the_item() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        let click = Event::Mouse(MouseEvent::Down(
+            MouseButton::Left,
+            2,
+            1,
+            KeyModifiers::empty(),
+        ));
+        assert_eq!(
+            send(&mut app, &mut screen, click),
+            [Msg::Expand("docs".to_string())]
+        );
+        assert_eq!(rows(&screen), ["▸ src", "▾ docs", "guide.md"]);
+    }
+
+    #[test]
+    fn expanded_items_stay_expanded_when_reordered() {
+        let mut app = App::new();
+        let mut screen = TitikScreen::new(app.view(), 40, 10);
+        send(&mut app, &mut screen, key(KeyCode::Down));
+        send(&mut app, &mut screen, key(KeyCode::Right));
+        app.update(Msg::Reverse);
+        screen.patch(app.view());
+        assert_eq!(rows(&screen), ["▸ docs", "> ▾ src", "main.rs", "lib.rs"]);
+        assert_eq!(
+            screen.lines(),
+            TitikScreen::new(app.view(), 40, 10).lines()
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use sauron_native::{view, Node};
+
+fn main() {
+    let _: Node<()> = view! {
+        <button lable="+"/>
+    };
+}
@@ -0,0 +1,9 @@
+use sauron_native::{view, Node};
+
+fn main() {
+    let _: Node<()> = view! {
+        <colum>
+            <button label="+"/>
+        </colum>
+    };
+}
@@ -0,0 +1,338 @@
+//! tests the view macro and the widgets and attributes it recognizes
+use sauron_native::{
+    widget::{attribute::*, event::*, *},
+    AttribKey, Node, Widget,
+};
+
+#[allow(dead_code)]
+#[path = "../crates/sauron-native-macro/src/known.rs"]
+mod known;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Click,
+}
+
+/// the builder functions which creates this widget
+fn widget_builders(widget: &Widget) -> &'static [&'static str] {
+    match widget {
+        Widget::Vbox => &["column"],
+        Widget::Hbox => &["row"],
+        Widget::Vpane => &["vpane"],
+        Widget::Hpane => &["hpane"],
+        Widget::Button => &["button"],
+        Widget::Label => &["text_label"],
+        Widget::Paragraph => &["paragraph"],
+        Widget::TextInput => &["text_input"],
+        Widget::Checkbox => &["checkbox"],
+        Widget::Radio => &["radio"],
+        Widget::Image => &["image"],
+        Widget::Svg => &["svg"],
+        Widget::TextArea => &["textarea"],
+        Widget::Overlay => &["overlay"],
+        Widget::GroupBox => &["groupbox"],
+        Widget::HeaderBar => &["header_bar"],
+        Widget::MenuBar => &["menu_bar"],
+        Widget::Menu => &["menu"],
+        Widget::MenuItem => &["menu_item"],
+        Widget::CheckMenuItem => &["check_menu_item"],
+        Widget::RadioMenuItem => &["radio_menu_item"],
+        Widget::MenuSeparator => &["menu_separator"],
+        Widget::SearchInput => &["search_input"],
+        Widget::Link => &["link"],
+        Widget::Table => &["table"],
+        Widget::ListView => &["list_view"],
+        Widget::Tabs => &["tabs"],
+        Widget::Tab => &["tab"],
+        Widget::Dropdown => &["dropdown"],
+        Widget::ListBox => &["list_box"],
+        Widget::SpinButton => &["spin_button"],
+        Widget::Slider => &["slider"],
+        Widget::ProgressBar => &["progress_bar"],
+        Widget::Spinner => &["spinner"],
+        Widget::Canvas => &["canvas"],
+        Widget::TreeView => &["tree_view"],
+        Widget::TreeItem => &["tree_item"],
+        Widget::Dialog => &["dialog", "alert", "confirm"],
+        Widget::FileChooser => &["file_chooser"],
+        Widget::Custom(_) => &["custom"],
+    }
+}
+
+/// every widget, except the custom widget which needs an implementation
+fn all_widgets() -> Vec<Widget> {
+    vec![
+        Widget::Vbox,
+        Widget::Hbox,
+        Widget::Vpane,
+        Widget::Hpane,
+        Widget::Button,
+        Widget::Label,
+        Widget::Paragraph,
+        Widget::TextInput,
+        Widget::Checkbox,
+        Widget::Radio,
+        Widget::Image,
+        Widget::Svg,
+        Widget::TextArea,
+        Widget::Overlay,
+        Widget::GroupBox,
+        Widget::HeaderBar,
+        Widget::MenuBar,
+        Widget::Menu,
+        Widget::MenuItem,
+        Widget::CheckMenuItem,
+        Widget::RadioMenuItem,
+        Widget::MenuSeparator,
+        Widget::SearchInput,
+        Widget::Link,
+        Widget::Table,
+        Widget::ListView,
+        Widget::Tabs,
+        Widget::Tab,
+        Widget::Dropdown,
+        Widget::ListBox,
+        Widget::SpinButton,
+        Widget::Slider,
+        Widget::ProgressBar,
+        Widget::Spinner,
+        Widget::Canvas,
+        Widget::TreeView,
+        Widget::TreeItem,
+        Widget::Dialog,
+        Widget::FileChooser,
+    ]
+}
+
+/// the name of the attribute or event in the view macro which sets this
+/// key, None if the key is only set internally
+fn attribute_name(key: &AttribKey) -> Option<&'static str> {
+    match key {
+        AttribKey::Value => Some("value"),
+        AttribKey::Label => Some("label"),
+        AttribKey::Checked => Some("checked"),
+        AttribKey::Alignment => None,
+        AttribKey::Key => Some("key"),
+        AttribKey::Id => Some("id"),
+        AttribKey::Editable => Some("editable"),
+        AttribKey::Data => Some("data"),
+        AttribKey::SvgImage => Some("svg_image"),
+        AttribKey::Style => None,
+        AttribKey::Scrollable => Some("scrollable"),
+        AttribKey::Layout => None,
+        AttribKey::Width => Some("width"),
+        AttribKey::Height => Some("height"),
+        AttribKey::PositionType => None,
+        AttribKey::Resizable => Some("resizable"),
+        AttribKey::Monospace => Some("monospace"),
+        AttribKey::Selectable => Some("selectable"),
+        AttribKey::Preformatted => Some("preformatted"),
+        AttribKey::Name => Some("name"),
+        AttribKey::Uri => Some("uri"),
+        AttribKey::Placeholder => Some("placeholder"),
+        AttribKey::Columns => Some("columns"),
+        AttribKey::Rows => Some("rows"),
+        AttribKey::Sortable => Some("sortable"),
+        AttribKey::SortColumn => Some("sort_column"),
+        AttribKey::SortDescending => Some("sort_descending"),
+        AttribKey::Selected => Some("selected"),
+        AttribKey::ItemCount => Some("item_count"),
+        AttribKey::RowHeight => Some("row_height"),
+        AttribKey::ScrollOffset => Some("scroll_offset"),
+        AttribKey::Overscan => Some("overscan"),
+        AttribKey::Options => Some("options"),
+        AttribKey::Multiple => Some("multiple"),
+        AttribKey::Min => Some("min"),
+        AttribKey::Max => Some("max"),
+        AttribKey::Step => Some("step"),
+        AttribKey::Fraction => Some("fraction"),
+        AttribKey::Indeterminate => Some("indeterminate"),
+        AttribKey::Active => Some("active"),
+        AttribKey::Expanded => Some("expanded"),
+        AttribKey::Draw => Some("draw"),
+        AttribKey::FileMode => Some("file_mode"),
+        AttribKey::Filters => Some("filters"),
+        AttribKey::Directory => Some("directory"),
+        AttribKey::Accelerator => Some("accelerator"),
+        AttribKey::Autofocus => Some("autofocus"),
+        AttribKey::ClickEvent => Some("on_click"),
+        AttribKey::MouseDown => Some("on_mousedown"),
+        AttribKey::MouseUp => Some("on_mouseup"),
+        AttribKey::MouseMove => Some("on_mousemove"),
+        AttribKey::InputEvent => Some("on_input"),
+        AttribKey::KeyEvent => Some("on_keypress"),
+        AttribKey::DoubleClickEvent => Some("on_doubleclick"),
+        AttribKey::FocusEvent => Some("on_focus"),
+        AttribKey::BlurEvent => Some("on_blur"),
+        AttribKey::Activate => Some("on_enter"),
+        AttribKey::SelectEvent => Some("on_select"),
+        AttribKey::SortEvent => Some("on_sort"),
+        AttribKey::RowActivateEvent => Some("on_row_activate"),
+        AttribKey::ScrollEvent => Some("on_scroll"),
+        AttribKey::VisibleRangeEvent => Some("on_visible_range"),
+        AttribKey::TabChangeEvent => Some("on_tab_change"),
+        AttribKey::ChangeEvent => Some("on_change"),
+        AttribKey::ExpandEvent => Some("on_expand"),
+        AttribKey::CollapseEvent => Some("on_collapse"),
+        AttribKey::ResponseEvent => Some("on_response"),
+        AttribKey::FilesEvent => Some("on_files"),
+        AttribKey::ActivateEvent => Some("on_activate"),
+        AttribKey::For => Some("for"),
+    }
+}
+
+/// every attribute key
+fn all_keys() -> Vec<AttribKey> {
+    use AttribKey::*;
+    vec![
+        Value,
+        Label,
+        Checked,
+        Alignment,
+        Key,
+        Id,
+        Editable,
+        Data,
+        SvgImage,
+        Style,
+        Scrollable,
+        Layout,
+        Width,
+        Height,
+        PositionType,
+        Resizable,
+        Monospace,
+        Selectable,
+        Preformatted,
+        Name,
+        Uri,
+        Placeholder,
+        Columns,
+        Rows,
+        Sortable,
+        SortColumn,
+        SortDescending,
+        Selected,
+        ItemCount,
+        RowHeight,
+        ScrollOffset,
+        Overscan,
+        Options,
+        Multiple,
+        Min,
+        Max,
+        Step,
+        Fraction,
+        Indeterminate,
+        Active,
+        Expanded,
+        Draw,
+        FileMode,
+        Filters,
+        Directory,
+        Accelerator,
+        Autofocus,
+        ClickEvent,
+        MouseDown,
+        MouseUp,
+        MouseMove,
+        InputEvent,
+        KeyEvent,
+        DoubleClickEvent,
+        FocusEvent,
+        BlurEvent,
+        Activate,
+        SelectEvent,
+        SortEvent,
+        RowActivateEvent,
+        ScrollEvent,
+        VisibleRangeEvent,
+        TabChangeEvent,
+        ChangeEvent,
+        ExpandEvent,
+        CollapseEvent,
+        ResponseEvent,
+        FilesEvent,
+        ActivateEvent,
+        For,
+    ]
+}
+
+#[test]
+fn every_widget_is_known() {
+    for widget in all_widgets() {
+        for builder in widget_builders(&widget) {
+            assert!(
+                known::widget_kind(builder).is_some(),
+                "{:?} is created with `{}` which is unknown to the view macro",
+                widget,
+                builder
+            );
+        }
+    }
+    assert!(known::widget_kind("custom").is_some());
+}
+
+#[test]
+fn every_known_widget_exists() {
+    let builders: Vec<&str> = all_widgets()
+        .iter()
+        .flat_map(|widget| widget_builders(widget).iter().copied())
+        .chain(Some("custom"))
+        .collect();
+    let specials = ["paragraph", "custom", "list_view", "tab"];
+    for name in known::CONTAINERS
+        .iter()
+        .chain(known::LEAVES.iter())
+        .chain(specials.iter())
+    {
+        assert!(builders.contains(name), "`{}` is not a widget", name);
+    }
+}
+
+#[test]
+fn every_attribute_is_known() {
+    for key in all_keys() {
+        if let Some(name) = attribute_name(&key) {
+            assert!(
+                known::attribute_fn(name).is_some(),
+                "{:?} is set with `{}` which is unknown to the view macro",
+                key,
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn every_known_attribute_exists() {
+    let names: Vec<&str> =
+        all_keys().iter().filter_map(attribute_name).collect();
+    for name in known::ATTRIBUTES.iter().chain(known::EVENTS.iter()) {
+        assert!(names.contains(name), "`{}` is not an attribute", name);
+    }
+}
+
+#[test]
+fn view_expands_to_the_builders() {
+    let expanded: Node<Msg> = sauron_native::view! {
+        <column>
+            <button label="+" on_click=|_| Msg::Click/>
+            <text_input value="hello" for="name"/>
+        </column>
+    };
+    let expected: Node<Msg> = column(
+        vec![],
+        vec![
+            button(vec![label("+"), on_click(|_| Msg::Click)]),
+            text_input(vec![value("hello"), for_("name")]),
+        ],
+    );
+    assert_eq!(expanded, expected);
+}
+
+#[test]
+fn unknown_widgets_and_attributes_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}